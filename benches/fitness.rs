@@ -3,7 +3,7 @@ use genetic_algorithm::chromosome::Chromosome;
 use genetic_algorithm::fitness::placeholders::{
     CountTrue, CountTrueWithSleep, Countdown, CountdownNoisy, SumGenes,
 };
-use genetic_algorithm::fitness::Fitness;
+use genetic_algorithm::fitness::{Fitness, FitnessCache};
 use genetic_algorithm::genotype::{BinaryGenotype, Genotype, ListGenotype, RangeGenotype};
 use genetic_algorithm::population::Population;
 use rand::prelude::*;
@@ -114,5 +114,77 @@ pub fn multithreading_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, placeholders_benchmark, multithreading_benchmark);
+pub fn cache_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fitness-cache");
+
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(100)
+        .with_genes_hashing(true)
+        .build()
+        .unwrap();
+
+    // cheap fitness and a warm cache, so lock contention dominates
+    let chromosomes = (0..10_000)
+        .map(|_| {
+            let mut chromosome = Chromosome::new(genotype.random_genes_factory(&mut rng));
+            chromosome.set_genes_hash(Some(chromosome.calculate_hash()));
+            chromosome
+        })
+        .collect();
+    let population = Population::new(chromosomes, true);
+
+    let caches = [
+        ("none", None),
+        ("lru", Some(FitnessCache::try_new(100_000).unwrap())),
+        (
+            "sharded-lru",
+            Some(
+                FitnessCache::try_new_sharded(100_000, FitnessCache::DEFAULT_NUMBER_OF_SHARDS)
+                    .unwrap(),
+            ),
+        ),
+        ("unbounded", Some(FitnessCache::new_unbounded())),
+    ];
+
+    for (name, cache) in caches.iter() {
+        let fitness_thread_local = Some(ThreadLocal::new());
+        let mut fitness = CountTrue;
+        fitness.call_for_population(
+            &mut population.clone(),
+            &genotype,
+            fitness_thread_local.as_ref(),
+            cache.as_ref(),
+        );
+
+        for threaded in ["single-threaded", "multi-threaded"] {
+            let thread_local = if threaded == "multi-threaded" {
+                fitness_thread_local.as_ref()
+            } else {
+                None
+            };
+            group.bench_function(format!("{}-{}", name, threaded), |b| {
+                b.iter_batched(
+                    || population.clone(),
+                    |mut data| {
+                        fitness.call_for_population(
+                            &mut data,
+                            &genotype,
+                            thread_local,
+                            cache.as_ref(),
+                        );
+                    },
+                    BatchSize::LargeInput,
+                );
+            });
+        }
+    }
+}
+
+criterion_group!(
+    benches,
+    placeholders_benchmark,
+    multithreading_benchmark,
+    cache_benchmark
+);
 criterion_main!(benches);
@@ -140,5 +140,113 @@ pub fn mutation_benchmark(c: &mut Criterion) {
     }
 }
 
-criterion_group!(benches, mutation_benchmark);
+pub fn binary_vs_bit_benchmark(c: &mut Criterion) {
+    let mut rng = SmallRng::from_entropy();
+    let genes_sizes = vec![100, 100_000];
+
+    let mut group = c.benchmark_group("genotype-binary-vs-bit");
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    for genes_size in &genes_sizes {
+        let genotype = BinaryGenotype::builder()
+            .with_genes_size(*genes_size)
+            .build()
+            .unwrap();
+        let mut father = Chromosome::new(genotype.random_genes_factory(&mut rng));
+        let mut mother = Chromosome::new(genotype.random_genes_factory(&mut rng));
+
+        group.bench_function(BenchmarkId::new("binary-clone", genes_size), |b| {
+            b.iter(|| black_box(&father).clone())
+        });
+        group.bench_function(BenchmarkId::new("binary-hash", genes_size), |b| {
+            b.iter(|| black_box(&father).calculate_hash())
+        });
+        group.bench_function(
+            BenchmarkId::new("binary-mutate-multi-10", genes_size),
+            |b| {
+                b.iter(|| {
+                    genotype.mutate_chromosome_genes(10, false, black_box(&mut father), &mut rng)
+                })
+            },
+        );
+        group.bench_function(
+            BenchmarkId::new("binary-crossover-genes-uniform", genes_size),
+            |b| {
+                b.iter(|| {
+                    genotype.crossover_chromosome_genes(
+                        genes_size / 2,
+                        true,
+                        black_box(&mut father),
+                        black_box(&mut mother),
+                        &mut rng,
+                    )
+                })
+            },
+        );
+        group.bench_function(
+            BenchmarkId::new("binary-crossover-points-9", genes_size),
+            |b| {
+                b.iter(|| {
+                    genotype.crossover_chromosome_points(
+                        9,
+                        false,
+                        black_box(&mut father),
+                        black_box(&mut mother),
+                        &mut rng,
+                    )
+                })
+            },
+        );
+    }
+
+    for genes_size in &genes_sizes {
+        let genotype = BitGenotype::builder()
+            .with_genes_size(*genes_size)
+            .build()
+            .unwrap();
+        let mut father = Chromosome::new(genotype.random_genes_factory(&mut rng));
+        let mut mother = Chromosome::new(genotype.random_genes_factory(&mut rng));
+
+        group.bench_function(BenchmarkId::new("bit-clone", genes_size), |b| {
+            b.iter(|| black_box(&father).clone())
+        });
+        group.bench_function(BenchmarkId::new("bit-hash", genes_size), |b| {
+            b.iter(|| black_box(&father).calculate_hash())
+        });
+        group.bench_function(BenchmarkId::new("bit-mutate-multi-10", genes_size), |b| {
+            b.iter(|| genotype.mutate_chromosome_genes(10, false, black_box(&mut father), &mut rng))
+        });
+        group.bench_function(
+            BenchmarkId::new("bit-crossover-genes-uniform", genes_size),
+            |b| {
+                b.iter(|| {
+                    genotype.crossover_chromosome_genes(
+                        genes_size / 2,
+                        true,
+                        black_box(&mut father),
+                        black_box(&mut mother),
+                        &mut rng,
+                    )
+                })
+            },
+        );
+        group.bench_function(
+            BenchmarkId::new("bit-crossover-points-9", genes_size),
+            |b| {
+                b.iter(|| {
+                    genotype.crossover_chromosome_points(
+                        9,
+                        false,
+                        black_box(&mut father),
+                        black_box(&mut mother),
+                        &mut rng,
+                    )
+                })
+            },
+        );
+    }
+}
+
+criterion_group!(benches, mutation_benchmark, binary_vs_bit_benchmark);
 criterion_main!(benches);
@@ -52,6 +52,12 @@ fn main() {
             MutationType::StepScaled(vec![0.1, 0.01, 0.001]),  // continuous refinement
             MutationType::Discrete,                             // integer steps
         ])
+        .with_allele_scales(vec![
+            AlleleScale::Linear,
+            AlleleScale::Linear,
+            AlleleScale::Log, // steps in decades, 0.001 is as reachable as 0.1
+            AlleleScale::Linear,
+        ])
         .build()
         .unwrap();
 
@@ -80,6 +80,13 @@ pub trait RangeAllele:
     /// Floors to nearest integer (identity for integer types)
     fn floor(&self) -> Self;
 
+    /// Converts to f64, used for continuous arithmetic (particle velocities, non-linear
+    /// [AlleleScale](crate::genotype::AlleleScale), linear gene constraints and grammatical codons)
+    fn to_f64(&self) -> f64;
+
+    /// Converts from f64, rounding to the nearest value (saturating) for integer types
+    fn from_f64(value: f64) -> Self;
+
     /// Needed as f32 and f64 don't implement saturating_sub and saturating_add
     fn clamped_add(current_value: Self, delta: Self, max_value: Self) -> Self;
     fn clamped_sub(current_value: Self, delta: Self, min_value: Self) -> Self;
@@ -101,6 +108,12 @@ impl RangeAllele for f32 {
     fn floor(&self) -> Self {
         f32::floor(*self)
     }
+    fn to_f64(&self) -> f64 {
+        *self as f64
+    }
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
     // ignore f32::MAX, not realistic for use case
     fn clamped_add(current_value: Self, delta: Self, max_value: Self) -> Self {
         let new_value = current_value + delta;
@@ -133,6 +146,12 @@ impl RangeAllele for f64 {
     fn floor(&self) -> Self {
         f64::floor(*self)
     }
+    fn to_f64(&self) -> f64 {
+        *self
+    }
+    fn from_f64(value: f64) -> Self {
+        value
+    }
     // ignore f64::MAX, not realistic for use case
     fn clamped_add(current_value: Self, delta: Self, max_value: Self) -> Self {
         let new_value = current_value + delta;
@@ -165,6 +184,12 @@ impl RangeAllele for i8 {
     fn floor(&self) -> Self {
         *self
     }
+    fn to_f64(&self) -> f64 {
+        *self as f64
+    }
+    fn from_f64(value: f64) -> Self {
+        value.round() as i8
+    }
     fn clamped_add(current_value: Self, delta: Self, max_value: Self) -> Self {
         let new_value = current_value.saturating_add(delta);
         if new_value > max_value {
@@ -195,6 +220,12 @@ impl RangeAllele for i16 {
     fn floor(&self) -> Self {
         *self
     }
+    fn to_f64(&self) -> f64 {
+        *self as f64
+    }
+    fn from_f64(value: f64) -> Self {
+        value.round() as i16
+    }
     fn clamped_add(current_value: Self, delta: Self, max_value: Self) -> Self {
         let new_value = current_value.saturating_add(delta);
         if new_value > max_value {
@@ -225,6 +256,12 @@ impl RangeAllele for i32 {
     fn floor(&self) -> Self {
         *self
     }
+    fn to_f64(&self) -> f64 {
+        *self as f64
+    }
+    fn from_f64(value: f64) -> Self {
+        value.round() as i32
+    }
     fn clamped_add(current_value: Self, delta: Self, max_value: Self) -> Self {
         let new_value = current_value.saturating_add(delta);
         if new_value > max_value {
@@ -255,6 +292,12 @@ impl RangeAllele for u8 {
     fn floor(&self) -> Self {
         *self
     }
+    fn to_f64(&self) -> f64 {
+        *self as f64
+    }
+    fn from_f64(value: f64) -> Self {
+        value.round() as u8
+    }
     fn clamped_add(current_value: Self, delta: Self, max_value: Self) -> Self {
         let new_value = current_value.saturating_add(delta);
         if new_value > max_value {
@@ -285,6 +328,12 @@ impl RangeAllele for u16 {
     fn floor(&self) -> Self {
         *self
     }
+    fn to_f64(&self) -> f64 {
+        *self as f64
+    }
+    fn from_f64(value: f64) -> Self {
+        value.round() as u16
+    }
     fn clamped_add(current_value: Self, delta: Self, max_value: Self) -> Self {
         let new_value = current_value.saturating_add(delta);
         if new_value > max_value {
@@ -315,6 +364,12 @@ impl RangeAllele for u32 {
     fn floor(&self) -> Self {
         *self
     }
+    fn to_f64(&self) -> f64 {
+        *self as f64
+    }
+    fn from_f64(value: f64) -> Self {
+        value.round() as u32
+    }
     fn clamped_add(current_value: Self, delta: Self, max_value: Self) -> Self {
         let new_value = current_value.saturating_add(delta);
         if new_value > max_value {
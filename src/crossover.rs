@@ -81,8 +81,9 @@ pub type CrossoverAllele<C> = <<C as Crossover>::Genotype as Genotype>::Allele;
 ///     ) {
 ///         let now = Instant::now();
 ///         let existing_population_size = state.population.chromosomes.len();
+///         // The selection_rate fraction (or the full parent pair in the steady-state variant)
 ///         let selected_population_size =
-///             (existing_population_size as f32 * self.selection_rate).ceil() as usize;
+///             config.crossover_selection_size(existing_population_size, self.selection_rate);
 ///
 ///         // Note: increment_age is handled by the evolve loop immediately before this call
 ///         // Important!!! Append offspring as recycled clones from parents (will reset age and crossover later)
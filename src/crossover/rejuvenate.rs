@@ -22,14 +22,14 @@ impl<G: EvolveGenotype> Crossover for Rejuvenate<G> {
         &mut self,
         _genotype: &G,
         state: &mut EvolveState<G>,
-        _config: &EvolveConfig,
+        config: &EvolveConfig,
         _reporter: &mut SR,
         _rng: &mut R,
     ) {
         let now = Instant::now();
         let existing_population_size = state.population.chromosomes.len();
         let selected_population_size =
-            (existing_population_size as f32 * self.selection_rate).ceil() as usize;
+            config.crossover_selection_size(existing_population_size, self.selection_rate);
         let dropped_population_size = (existing_population_size - selected_population_size).max(0);
 
         state.population.truncate(selected_population_size);
@@ -29,7 +29,7 @@ impl<G: EvolveGenotype + SupportsGeneCrossover> Crossover for Uniform<G> {
         &mut self,
         genotype: &G,
         state: &mut EvolveState<G>,
-        _config: &EvolveConfig,
+        config: &EvolveConfig,
         _reporter: &mut SR,
         rng: &mut R,
     ) {
@@ -37,7 +37,7 @@ impl<G: EvolveGenotype + SupportsGeneCrossover> Crossover for Uniform<G> {
         let number_of_crossovers = genotype.genes_size() / 2;
         let existing_population_size = state.population.chromosomes.len();
         let selected_population_size =
-            (existing_population_size as f32 * self.selection_rate).ceil() as usize;
+            config.crossover_selection_size(existing_population_size, self.selection_rate);
         state
             .population
             .extend_from_within(selected_population_size);
@@ -7,3 +7,8 @@ pub struct TryFromStrategyBuilderError(pub &'static str);
 /// Contains a descriptive message about what went wrong (e.g. missing genes_size, missing allele_range).
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct TryFromGenotypeBuilderError(pub &'static str);
+
+/// Error returned when a BNF grammar definition can't be parsed.
+/// Contains a descriptive message about what went wrong (e.g. undefined non-terminal, missing `::=`).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TryFromGrammarError(pub &'static str);
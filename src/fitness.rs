@@ -10,7 +10,12 @@ pub mod cache;
 pub mod placeholders;
 pub mod prelude;
 
-pub use self::cache::Cache as FitnessCache;
+pub use self::cache::{
+    Cache as FitnessCache, CacheBackend as FitnessCacheBackend,
+    CacheBackendFile as FitnessCacheBackendFile, CacheBackendLru as FitnessCacheBackendLru,
+    CacheBackendSharded as FitnessCacheBackendSharded,
+    CacheBackendUnbounded as FitnessCacheBackendUnbounded, CacheKey as FitnessCacheKey,
+};
 
 use crate::chromosome::Chromosome;
 use crate::genotype::Genotype;
@@ -157,18 +162,19 @@ pub trait Fitness: Clone + Send + Sync + std::fmt::Debug {
         genotype: &Self::Genotype,
         cache: Option<&FitnessCache>,
     ) {
-        let value = match (cache, chromosome.genes_hash()) {
-            (Some(cache), Some(genes_hash)) => {
-                if let Some(value) = cache.read(genes_hash) {
+        let value = match cache.and_then(|cache| cache.key_for(chromosome).map(|key| (cache, key)))
+        {
+            Some((cache, key)) => {
+                if let Some(value) = cache.read_key(&key) {
                     Some(value)
                 } else if let Some(value) = self.calculate_for_chromosome(chromosome, genotype) {
-                    cache.write(genes_hash, value);
+                    cache.write_key(key, value);
                     Some(value)
                 } else {
                     None
                 }
             }
-            _ => self.calculate_for_chromosome(chromosome, genotype),
+            None => self.calculate_for_chromosome(chromosome, genotype),
         };
         chromosome.set_fitness_score(value);
     }
@@ -1,63 +1,484 @@
+//! The fitness cache, keyed by [GenesHash] (optionally including the genes themselves), with
+//! pluggable storage backends.
+//!
+//! The [Cache] is a cheap to clone handle (all state is `Arc`-wrapped), so it can be shared over
+//! repeated runs and multiple strategies. The storage is delegated to a [CacheBackend]:
+//! * [CacheBackendLru], bounded in-memory LRU cache
+//! * [CacheBackendUnbounded], unbounded in-memory map
+//! * [CacheBackendSharded], splits the keys over multiple backends to reduce lock contention
+//!   with `with_par_fitness(true)` (sharded LRU is used by `with_fitness_cache(size)`)
+//! * [CacheBackendFile], append-only log file, warmed at start-up and flushed at exit, so fitness
+//!   values survive the program and can be shared across processes (sequentially)
 use super::FitnessValue;
-use crate::chromosome::GenesHash;
+use crate::allele::Allele;
+use crate::chromosome::{Chromosome, GenesHash};
 use lru::LruCache;
 use nohash_hasher::NoHashHasher;
-use std::hash::BuildHasherDefault;
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+use std::io::{self, BufReader, BufWriter, Read, Write};
 use std::num::NonZeroUsize;
-use std::sync::{Arc, RwLock};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 
-type LruCacheBuildHasher = BuildHasherDefault<NoHashHasher<u64>>;
+type CacheBuildHasher = BuildHasherDefault<NoHashHasher<u64>>;
 
-#[derive(Debug, Clone)]
-pub struct Cache {
+/// The cache key. The genes_hash alone is fast, but hash collisions silently return the fitness of
+/// other genes. When the genes_bytes are included (see [Cache::with_genes_in_key]), the key is
+/// collision-safe at the cost of memory (and file size for [CacheBackendFile]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheKey {
+    pub genes_hash: GenesHash,
+    pub genes_bytes: Option<Box<[u8]>>,
+}
+
+impl CacheKey {
+    pub fn new(genes_hash: GenesHash) -> Self {
+        Self {
+            genes_hash,
+            genes_bytes: None,
+        }
+    }
+    /// Include the exact byte representation of the genes, as fed to the hasher by
+    /// [Allele::hash_slice]
+    pub fn new_with_genes<T: Allele>(genes_hash: GenesHash, genes: &[T]) -> Self {
+        let mut recorder = BytesRecorder::default();
+        T::hash_slice(genes, &mut recorder);
+        Self {
+            genes_hash,
+            genes_bytes: Some(recorder.0.into_boxed_slice()),
+        }
+    }
+}
+
+// Only the genes_hash is hashed (it already is a hash), equality also checks the genes_bytes
+impl Hash for CacheKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.genes_hash);
+    }
+}
+
+/// Records the bytes written to it, instead of hashing them
+#[derive(Default)]
+struct BytesRecorder(Vec<u8>);
+impl Hasher for BytesRecorder {
+    fn finish(&self) -> u64 {
+        0
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+}
+
+/// The storage of the [Cache]. Implementations must be thread-safe, as fitness is calculated in
+/// parallel when `with_par_fitness(true)` is set.
+pub trait CacheBackend: Send + Sync + std::fmt::Debug {
+    fn read(&self, key: &CacheKey) -> Option<FitnessValue>;
+    fn write(&self, key: CacheKey, value: FitnessValue);
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Persist pending writes, no-op for in-memory backends
+    fn flush(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Bounded in-memory LRU cache. Reads only take the read lock (peek), the hits are buffered and
+/// promoted in batches when the write lock is taken (on write or when the buffer is full). Hits are
+/// dropped from the buffer under contention, so the eviction order is approximately LRU.
+#[derive(Debug)]
+pub struct CacheBackendLru {
     pub cache_size: usize,
-    pub cache_state: Arc<RwLock<LruCache<GenesHash, FitnessValue, LruCacheBuildHasher>>>,
-    pub cache_hit_counter: Arc<RwLock<usize>>,
-    pub cache_miss_counter: Arc<RwLock<usize>>,
+    state: RwLock<LruCache<CacheKey, FitnessValue, CacheBuildHasher>>,
+    pending_promotions: Mutex<Vec<CacheKey>>,
 }
 
-impl Cache {
+impl CacheBackendLru {
+    const PROMOTION_BATCH_SIZE: usize = 64;
+
     pub fn try_new(cache_size: usize) -> Result<Self, &'static str> {
         if let Some(non_zero_cache_size) = NonZeroUsize::new(cache_size) {
-            let cache = LruCache::with_hasher(non_zero_cache_size, LruCacheBuildHasher::default());
-            let cache_state = Arc::new(RwLock::new(cache));
-            let cache_hit_counter = Arc::new(RwLock::new(0));
-            let cache_miss_counter = Arc::new(RwLock::new(0));
+            let state = LruCache::with_hasher(non_zero_cache_size, CacheBuildHasher::default());
             Ok(Self {
                 cache_size,
-                cache_state,
-                cache_hit_counter,
-                cache_miss_counter,
+                state: RwLock::new(state),
+                pending_promotions: Mutex::new(Vec::with_capacity(Self::PROMOTION_BATCH_SIZE)),
             })
         } else {
             Err("cache_size must be greater than 0")
         }
     }
 
+    fn promote_pending(
+        state: &mut LruCache<CacheKey, FitnessValue, CacheBuildHasher>,
+        pending_promotions: &mut Vec<CacheKey>,
+    ) {
+        pending_promotions.drain(..).for_each(|key| {
+            state.promote(&key);
+        });
+    }
+}
+
+impl CacheBackend for CacheBackendLru {
+    fn read(&self, key: &CacheKey) -> Option<FitnessValue> {
+        let value = self.state.read().unwrap().peek(key).cloned();
+        if value.is_some() {
+            if let Ok(mut pending_promotions) = self.pending_promotions.try_lock() {
+                pending_promotions.push(key.clone());
+                if pending_promotions.len() >= Self::PROMOTION_BATCH_SIZE {
+                    if let Ok(mut state) = self.state.try_write() {
+                        Self::promote_pending(&mut state, &mut pending_promotions);
+                    }
+                }
+            }
+        }
+        value
+    }
+    fn write(&self, key: CacheKey, value: FitnessValue) {
+        let mut state = self.state.write().unwrap();
+        if let Ok(mut pending_promotions) = self.pending_promotions.try_lock() {
+            Self::promote_pending(&mut state, &mut pending_promotions);
+        }
+        state.put(key, value);
+    }
+    fn len(&self) -> usize {
+        self.state.read().unwrap().len()
+    }
+}
+
+/// Splits the keys over multiple independent backends (by genes_hash), so parallel fitness
+/// calculation (`with_par_fitness(true)`) doesn't serialize on a single lock.
+#[derive(Debug)]
+pub struct CacheBackendSharded<B: CacheBackend> {
+    pub shards: Vec<B>,
+}
+
+impl<B: CacheBackend> CacheBackendSharded<B> {
+    pub fn try_new(shards: Vec<B>) -> Result<Self, &'static str> {
+        if shards.is_empty() {
+            Err("number_of_shards must be greater than 0")
+        } else {
+            Ok(Self { shards })
+        }
+    }
+
+    fn shard(&self, key: &CacheKey) -> &B {
+        // genes_hash is an FxHash, which has weak low bits, so mix before taking the high bits
+        let mixed = key.genes_hash.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 32;
+        &self.shards[mixed as usize % self.shards.len()]
+    }
+}
+
+impl CacheBackendSharded<CacheBackendLru> {
+    /// Each shard holds at least this many entries, which caps the number_of_shards for small caches
+    pub const MIN_SHARD_SIZE: usize = 16;
+
+    /// The cache_size is divided over the shards (rounded up). The number_of_shards is capped so
+    /// each shard holds at least [Self::MIN_SHARD_SIZE] entries.
+    pub fn try_new_lru(cache_size: usize, number_of_shards: usize) -> Result<Self, &'static str> {
+        if cache_size == 0 {
+            return Err("cache_size must be greater than 0");
+        }
+        if number_of_shards == 0 {
+            return Err("number_of_shards must be greater than 0");
+        }
+        let number_of_shards = number_of_shards
+            .min(cache_size / Self::MIN_SHARD_SIZE)
+            .max(1);
+        let shard_size = (cache_size + number_of_shards - 1) / number_of_shards;
+        (0..number_of_shards)
+            .map(|_| CacheBackendLru::try_new(shard_size))
+            .collect::<Result<Vec<_>, _>>()
+            .and_then(Self::try_new)
+    }
+}
+
+impl CacheBackendSharded<CacheBackendUnbounded> {
+    pub fn try_new_unbounded(number_of_shards: usize) -> Result<Self, &'static str> {
+        Self::try_new(
+            (0..number_of_shards)
+                .map(|_| CacheBackendUnbounded::new())
+                .collect(),
+        )
+    }
+}
+
+impl<B: CacheBackend> CacheBackend for CacheBackendSharded<B> {
+    fn read(&self, key: &CacheKey) -> Option<FitnessValue> {
+        self.shard(key).read(key)
+    }
+    fn write(&self, key: CacheKey, value: FitnessValue) {
+        self.shard(&key).write(key, value)
+    }
+    fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.len()).sum()
+    }
+    fn flush(&self) -> io::Result<()> {
+        self.shards.iter().try_for_each(|shard| shard.flush())
+    }
+}
+
+/// Unbounded in-memory map. Memory grows with every distinct chromosome evaluated, so only use
+/// for expensive fitness functions with a limited number of evaluations.
+#[derive(Debug, Default)]
+pub struct CacheBackendUnbounded {
+    state: RwLock<HashMap<CacheKey, FitnessValue, CacheBuildHasher>>,
+}
+
+impl CacheBackendUnbounded {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CacheBackend for CacheBackendUnbounded {
+    fn read(&self, key: &CacheKey) -> Option<FitnessValue> {
+        self.state.read().unwrap().get(key).cloned()
+    }
+    fn write(&self, key: CacheKey, value: FitnessValue) {
+        self.state.write().unwrap().insert(key, value);
+    }
+    fn len(&self) -> usize {
+        self.state.read().unwrap().len()
+    }
+}
+
+/// File-backed store, as an append-only log of records:
+/// `genes_hash (u64 LE), genes_bytes length (u32 LE, u32::MAX for none), genes_bytes, value (i64 LE)`.
+///
+/// The existing log is read into an unbounded in-memory map when opened (warm start), a truncated
+/// trailing record (e.g. from a crash during writing) is removed. New values are appended through
+/// a buffer, which is written on [flush](CacheBackend::flush) and on drop (when the last [Cache]
+/// handle goes out of scope). The last record wins for duplicate keys.
+///
+/// The file is not locked, so share it across processes sequentially (e.g. repeated runs of a
+/// program), not concurrently.
+#[derive(Debug)]
+pub struct CacheBackendFile {
+    pub path: PathBuf,
+    state: RwLock<HashMap<CacheKey, FitnessValue, CacheBuildHasher>>,
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl CacheBackendFile {
+    /// Opens (or creates) the log file and warms the cache with its contents
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&path)?;
+
+        let mut state = HashMap::default();
+        let mut valid_length: u64 = 0;
+        let mut reader = BufReader::new(&file);
+        while let Some((key, value)) = Self::read_record(&mut reader)? {
+            valid_length += Self::record_length(&key);
+            state.insert(key, value);
+        }
+        // drop a truncated trailing record, so new records are appended after the last valid one
+        if file.metadata()?.len() > valid_length {
+            file.set_len(valid_length)?;
+        }
+
+        Ok(Self {
+            path,
+            state: RwLock::new(state),
+            writer: Mutex::new(BufWriter::new(file)),
+        })
+    }
+
+    fn read_record<R: Read>(reader: &mut R) -> io::Result<Option<(CacheKey, FitnessValue)>> {
+        let mut u64_buffer = [0u8; 8];
+        let mut u32_buffer = [0u8; 4];
+        if !Self::read_exact_or_eof(reader, &mut u64_buffer)? {
+            return Ok(None);
+        }
+        let genes_hash = u64::from_le_bytes(u64_buffer);
+        if !Self::read_exact_or_eof(reader, &mut u32_buffer)? {
+            return Ok(None);
+        }
+        let genes_bytes = match u32::from_le_bytes(u32_buffer) {
+            u32::MAX => None,
+            length => {
+                let mut bytes = vec![0u8; length as usize];
+                if !Self::read_exact_or_eof(reader, &mut bytes)? {
+                    return Ok(None);
+                }
+                Some(bytes.into_boxed_slice())
+            }
+        };
+        if !Self::read_exact_or_eof(reader, &mut u64_buffer)? {
+            return Ok(None);
+        }
+        let value = i64::from_le_bytes(u64_buffer) as FitnessValue;
+        Ok(Some((
+            CacheKey {
+                genes_hash,
+                genes_bytes,
+            },
+            value,
+        )))
+    }
+
+    fn record_length(key: &CacheKey) -> u64 {
+        (8 + 4 + key.genes_bytes.as_ref().map_or(0, |bytes| bytes.len()) + 8) as u64
+    }
+
+    fn read_exact_or_eof<R: Read>(reader: &mut R, buffer: &mut [u8]) -> io::Result<bool> {
+        match reader.read_exact(buffer) {
+            Ok(()) => Ok(true),
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn write_record<W: Write>(
+        writer: &mut W,
+        key: &CacheKey,
+        value: FitnessValue,
+    ) -> io::Result<()> {
+        writer.write_all(&key.genes_hash.to_le_bytes())?;
+        match &key.genes_bytes {
+            Some(bytes) => {
+                writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+                writer.write_all(bytes)?;
+            }
+            None => writer.write_all(&u32::MAX.to_le_bytes())?,
+        }
+        writer.write_all(&(value as i64).to_le_bytes())
+    }
+}
+
+impl CacheBackend for CacheBackendFile {
+    fn read(&self, key: &CacheKey) -> Option<FitnessValue> {
+        self.state.read().unwrap().get(key).cloned()
+    }
+    /// Write errors are not propagated (fitness calculation can't fail), they resurface on flush
+    fn write(&self, key: CacheKey, value: FitnessValue) {
+        let mut writer = self.writer.lock().unwrap();
+        let _ = Self::write_record(&mut *writer, &key, value);
+        self.state.write().unwrap().insert(key, value);
+    }
+    fn len(&self) -> usize {
+        self.state.read().unwrap().len()
+    }
+    fn flush(&self) -> io::Result<()> {
+        self.writer.lock().unwrap().flush()
+    }
+}
+
+impl Drop for CacheBackendFile {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Cache {
+    pub backend: Arc<dyn CacheBackend>,
+    pub genes_in_key: bool,
+    pub cache_hit_counter: Arc<AtomicUsize>,
+    pub cache_miss_counter: Arc<AtomicUsize>,
+}
+
+impl Cache {
+    /// Used by the strategy builders' `with_fitness_cache(size)`
+    pub const DEFAULT_NUMBER_OF_SHARDS: usize = 16;
+
+    /// Bounded in-memory LRU cache, see [CacheBackendLru]
+    pub fn try_new(cache_size: usize) -> Result<Self, &'static str> {
+        CacheBackendLru::try_new(cache_size).map(Self::new_with_backend)
+    }
+    /// Sharded bounded in-memory LRU cache, see [CacheBackendSharded::try_new_lru]. Used by the
+    /// strategy builders' `with_fitness_cache(size)`, as it scales with `with_par_fitness(true)`.
+    pub fn try_new_sharded(
+        cache_size: usize,
+        number_of_shards: usize,
+    ) -> Result<Self, &'static str> {
+        CacheBackendSharded::try_new_lru(cache_size, number_of_shards).map(Self::new_with_backend)
+    }
+    /// Unbounded in-memory cache, see [CacheBackendUnbounded]
+    pub fn new_unbounded() -> Self {
+        Self::new_with_backend(CacheBackendUnbounded::new())
+    }
+    /// File-backed cache, see [CacheBackendFile]
+    pub fn try_new_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        CacheBackendFile::open(path).map(Self::new_with_backend)
+    }
+    pub fn new_with_backend<B: CacheBackend + 'static>(backend: B) -> Self {
+        Self {
+            backend: Arc::new(backend),
+            genes_in_key: false,
+            cache_hit_counter: Arc::new(AtomicUsize::new(0)),
+            cache_miss_counter: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+    /// Include the genes themselves in the key, making it collision-safe. Costs memory and an
+    /// additional pass over the genes per lookup.
+    pub fn with_genes_in_key(mut self, genes_in_key: bool) -> Self {
+        self.genes_in_key = genes_in_key;
+        self
+    }
+
+    /// The key for the chromosome, only available when genes_hash is stored on the chromosome
+    pub fn key_for<T: Allele>(&self, chromosome: &Chromosome<T>) -> Option<CacheKey> {
+        chromosome.genes_hash().map(|genes_hash| {
+            if self.genes_in_key {
+                CacheKey::new_with_genes(genes_hash, &chromosome.genes)
+            } else {
+                CacheKey::new(genes_hash)
+            }
+        })
+    }
+
     pub fn read(&self, genes_hash: GenesHash) -> Option<FitnessValue> {
-        let value = self
-            .cache_state
-            .read()
-            .map(|c| c.peek(&genes_hash).cloned())
-            .unwrap();
+        self.read_key(&CacheKey::new(genes_hash))
+    }
+
+    pub fn write(&self, genes_hash: GenesHash, value: FitnessValue) {
+        self.write_key(CacheKey::new(genes_hash), value)
+    }
+
+    pub fn read_key(&self, key: &CacheKey) -> Option<FitnessValue> {
+        let value = self.backend.read(key);
 
         if value.is_some() {
-            *self.cache_hit_counter.write().unwrap() += 1
+            self.cache_hit_counter.fetch_add(1, Ordering::Relaxed);
         } else {
-            *self.cache_miss_counter.write().unwrap() += 1
+            self.cache_miss_counter.fetch_add(1, Ordering::Relaxed);
         }
 
         value
     }
 
-    pub fn write(&self, genes_hash: GenesHash, value: FitnessValue) {
-        self.cache_state.write().unwrap().put(genes_hash, value);
+    pub fn write_key(&self, key: CacheKey, value: FitnessValue) {
+        self.backend.write(key, value);
+    }
+
+    pub fn len(&self) -> usize {
+        self.backend.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.backend.is_empty()
+    }
+
+    /// Persist pending writes of the backend. Also happens on drop of the last handle for
+    /// [CacheBackendFile], but errors are only reported here.
+    pub fn flush(&self) -> io::Result<()> {
+        self.backend.flush()
     }
 
     /// hit_miss_stats() -> (hits, misses, ratio)
     pub fn hit_miss_stats(&self) -> (usize, usize, f32) {
-        let cache_hits = *self.cache_hit_counter.read().unwrap();
-        let cache_misses = *self.cache_miss_counter.read().unwrap();
+        let cache_hits = self.cache_hit_counter.load(Ordering::Relaxed);
+        let cache_misses = self.cache_miss_counter.load(Ordering::Relaxed);
 
         if cache_misses == 0 {
             (cache_hits, 0, 0.0)
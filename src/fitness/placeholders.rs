@@ -1,9 +1,20 @@
 //! placeholders for testing and bootstrapping, not really used in practice
-use crate::fitness::{Fitness, FitnessChromosome, FitnessValue};
-use crate::genotype::{BinaryGenotype, Genotype};
+//!
+//! Also contains a suite of standard benchmark functions with known optima (see [Benchmark]), to
+//! evaluate strategy configurations and drive regression tests:
+//! * continuous, for [RangeGenotype](crate::genotype::RangeGenotype) and
+//!   [MultiRangeGenotype](crate::genotype::MultiRangeGenotype): [Sphere], [Rastrigin],
+//!   [Rosenbrock], [Ackley], [Griewank], [Schwefel]
+//! * combinatorial, for [BinaryGenotype]: [OneMax], [LeadingOnes], [Trap], [NkLandscape]
+//! * permutation, for [UniqueGenotype]: [TravelingSalesman] (TSPLIB format) and
+//!   [QuadraticAssignment] (QAPLIB format)
+use crate::fitness::{Fitness, FitnessChromosome, FitnessOrdering, FitnessValue};
+use crate::genotype::{BinaryGenotype, Genotype, UniqueGenotype};
+use itertools::Itertools;
 use rand::distributions::{Distribution, Uniform};
 use rand::rngs::SmallRng;
 use rand::SeedableRng;
+use std::f64::consts::{E, PI};
 use std::marker::PhantomData;
 use std::ops::Range;
 use std::{thread, time};
@@ -191,3 +202,718 @@ impl<G: Genotype> Fitness for CountdownNoisy<G> {
         }
     }
 }
+
+/// A benchmark fitness function with a known optimum, so a strategy configuration can be evaluated
+/// against it (e.g. `with_target_fitness_score(fitness.optimal_fitness_score(&genotype).unwrap())`)
+pub trait Benchmark: Fitness {
+    fn fitness_ordering(&self) -> FitnessOrdering;
+    /// The fitness score of the global optimum, None if unknown
+    fn optimal_fitness_score(&self, genotype: &Self::Genotype) -> Option<FitnessValue>;
+}
+
+macro_rules! continuous_benchmark {
+    ($(#[$meta:meta])* $name:ident, $function:ident) => {
+        $(#[$meta])*
+        ///
+        /// Minimize, the optimal fitness score is 0. Float scores are converted to [FitnessValue]
+        /// with the precision (default 1.0), just like [SumGenes].
+        #[derive(Clone, Debug)]
+        pub struct $name<G: Genotype> {
+            precision: f64,
+            buffer: Vec<f64>,
+            _phantom: PhantomData<G>,
+        }
+        impl<G: Genotype> $name<G> {
+            pub fn new() -> Self {
+                Self::default()
+            }
+            pub fn new_with_precision(precision: f64) -> Self {
+                Self {
+                    precision,
+                    ..Default::default()
+                }
+            }
+        }
+        impl<G: Genotype> Default for $name<G> {
+            fn default() -> Self {
+                Self {
+                    precision: 1.0_f64,
+                    buffer: vec![],
+                    _phantom: PhantomData,
+                }
+            }
+        }
+        impl<G: Genotype> Fitness for $name<G>
+        where
+            G::Allele: Into<f64>,
+        {
+            type Genotype = G;
+            fn calculate_for_chromosome(
+                &mut self,
+                chromosome: &FitnessChromosome<Self>,
+                _genotype: &Self::Genotype,
+            ) -> Option<FitnessValue> {
+                self.buffer.clear();
+                self.buffer
+                    .extend(chromosome.genes.iter().map(|&e| e.into()));
+                Some(($function(&self.buffer) / self.precision) as FitnessValue)
+            }
+        }
+        impl<G: Genotype> Benchmark for $name<G>
+        where
+            G::Allele: Into<f64>,
+        {
+            fn fitness_ordering(&self) -> FitnessOrdering {
+                FitnessOrdering::Minimize
+            }
+            fn optimal_fitness_score(&self, _genotype: &Self::Genotype) -> Option<FitnessValue> {
+                Some(0)
+            }
+        }
+    };
+}
+
+continuous_benchmark!(
+    /// Sphere: `sum(x^2)`, unimodal. Domain [-5.12, 5.12], optimum at x = 0
+    Sphere,
+    sphere
+);
+continuous_benchmark!(
+    /// Rastrigin: `10n + sum(x^2 - 10cos(2πx))`, highly multimodal with a regular grid of local
+    /// optima. Domain [-5.12, 5.12], optimum at x = 0
+    Rastrigin,
+    rastrigin
+);
+continuous_benchmark!(
+    /// Rosenbrock: `sum(100(x[i+1] - x[i]^2)^2 + (1 - x[i])^2)`, optimum in a narrow curved
+    /// valley. Domain [-5, 10], optimum at x = 1
+    Rosenbrock,
+    rosenbrock
+);
+continuous_benchmark!(
+    /// Ackley: nearly flat outer region with many local optima and a deep hole in the center.
+    /// Domain [-32.768, 32.768], optimum at x = 0
+    Ackley,
+    ackley
+);
+continuous_benchmark!(
+    /// Griewank: `1 + sum(x^2)/4000 - prod(cos(x[i]/sqrt(i+1)))`, many widespread regularly
+    /// distributed local optima. Domain [-600, 600], optimum at x = 0
+    Griewank,
+    griewank
+);
+continuous_benchmark!(
+    /// Schwefel: `418.9829n - sum(x sin(sqrt(|x|)))`, deceptive as the second best local optimum
+    /// is far away from the global optimum. Domain [-500, 500], optimum at x = 420.9687
+    Schwefel,
+    schwefel
+);
+
+fn sphere(x: &[f64]) -> f64 {
+    x.iter().map(|v| v * v).sum()
+}
+fn rastrigin(x: &[f64]) -> f64 {
+    10.0 * x.len() as f64
+        + x.iter()
+            .map(|v| v * v - 10.0 * (2.0 * PI * v).cos())
+            .sum::<f64>()
+}
+fn rosenbrock(x: &[f64]) -> f64 {
+    x.windows(2)
+        .map(|w| 100.0 * (w[1] - w[0] * w[0]).powi(2) + (1.0 - w[0]).powi(2))
+        .sum()
+}
+fn ackley(x: &[f64]) -> f64 {
+    if x.is_empty() {
+        return 0.0;
+    }
+    let n = x.len() as f64;
+    let sum_squares: f64 = x.iter().map(|v| v * v).sum();
+    let sum_cosines: f64 = x.iter().map(|v| (2.0 * PI * v).cos()).sum();
+    (-20.0 * (-0.2 * (sum_squares / n).sqrt()).exp() - (sum_cosines / n).exp() + 20.0 + E).max(0.0)
+}
+fn griewank(x: &[f64]) -> f64 {
+    let sum: f64 = x.iter().map(|v| v * v / 4000.0).sum();
+    let product: f64 = x
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (v / ((i + 1) as f64).sqrt()).cos())
+        .product();
+    1.0 + sum - product
+}
+fn schwefel(x: &[f64]) -> f64 {
+    418.9829 * x.len() as f64 - x.iter().map(|v| v * v.abs().sqrt().sin()).sum::<f64>()
+}
+
+/// OneMax: the number of true genes. Maximize, the optimal fitness score is genes_size
+#[derive(Clone, Debug)]
+pub struct OneMax;
+impl Fitness for OneMax {
+    type Genotype = BinaryGenotype;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &FitnessChromosome<Self>,
+        _genotype: &Self::Genotype,
+    ) -> Option<FitnessValue> {
+        Some(chromosome.genes.iter().filter(|&value| *value).count() as FitnessValue)
+    }
+}
+impl Benchmark for OneMax {
+    fn fitness_ordering(&self) -> FitnessOrdering {
+        FitnessOrdering::Maximize
+    }
+    fn optimal_fitness_score(&self, genotype: &Self::Genotype) -> Option<FitnessValue> {
+        Some(genotype.genes_size() as FitnessValue)
+    }
+}
+
+/// LeadingOnes: the number of consecutive true genes from the start. Maximize, the optimal
+/// fitness score is genes_size
+#[derive(Clone, Debug)]
+pub struct LeadingOnes;
+impl Fitness for LeadingOnes {
+    type Genotype = BinaryGenotype;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &FitnessChromosome<Self>,
+        _genotype: &Self::Genotype,
+    ) -> Option<FitnessValue> {
+        Some(chromosome.genes.iter().take_while(|&value| *value).count() as FitnessValue)
+    }
+}
+impl Benchmark for LeadingOnes {
+    fn fitness_ordering(&self) -> FitnessOrdering {
+        FitnessOrdering::Maximize
+    }
+    fn optimal_fitness_score(&self, genotype: &Self::Genotype) -> Option<FitnessValue> {
+        Some(genotype.genes_size() as FitnessValue)
+    }
+}
+
+/// Concatenated deceptive trap: the genes are split in consecutive blocks of block_size (the last
+/// block may be smaller). A block with all genes true scores block_size, otherwise it scores
+/// `block_size - 1 - ones`, which leads hill climbers to the all false local optimum.
+/// Maximize, the optimal fitness score is genes_size
+#[derive(Clone, Debug)]
+pub struct Trap {
+    pub block_size: usize,
+}
+impl Trap {
+    pub fn new(block_size: usize) -> Self {
+        Self {
+            block_size: block_size.max(1),
+        }
+    }
+}
+impl Fitness for Trap {
+    type Genotype = BinaryGenotype;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &FitnessChromosome<Self>,
+        _genotype: &Self::Genotype,
+    ) -> Option<FitnessValue> {
+        let score: usize = chromosome
+            .genes
+            .chunks(self.block_size)
+            .map(|block| {
+                let ones = block.iter().filter(|&value| *value).count();
+                if ones == block.len() {
+                    block.len()
+                } else {
+                    block.len() - 1 - ones
+                }
+            })
+            .sum();
+        Some(score as FitnessValue)
+    }
+}
+impl Benchmark for Trap {
+    fn fitness_ordering(&self) -> FitnessOrdering {
+        FitnessOrdering::Maximize
+    }
+    fn optimal_fitness_score(&self, genotype: &Self::Genotype) -> Option<FitnessValue> {
+        Some(genotype.genes_size() as FitnessValue)
+    }
+}
+
+/// NK-landscape: each gene contributes a value depending on itself and its k cyclic successors,
+/// looked up in a random table (seeded, so reproducible). The ruggedness increases with k.
+/// Contributions are integers in `0..=NkLandscape::MAX_CONTRIBUTION`, the fitness score is
+/// their sum. Maximize, the optimal fitness score is determined by brute force for genes_size up
+/// to [NkLandscape::BRUTE_FORCE_MAX_GENES_SIZE] (unknown otherwise)
+#[derive(Clone, Debug)]
+pub struct NkLandscape {
+    pub genes_size: usize,
+    pub k: usize,
+    pub contributions: Vec<Vec<u32>>,
+}
+impl NkLandscape {
+    pub const MAX_CONTRIBUTION: u32 = 1_000_000;
+    pub const BRUTE_FORCE_MAX_GENES_SIZE: usize = 20;
+
+    /// k is capped at genes_size - 1
+    pub fn new(genes_size: usize, k: usize, seed: u64) -> Self {
+        let k = k.min(genes_size.saturating_sub(1));
+        let mut rng = SmallRng::seed_from_u64(seed);
+        let sampler = Uniform::from(0..=Self::MAX_CONTRIBUTION);
+        let contributions = (0..genes_size)
+            .map(|_| {
+                (0..(1 << (k + 1)))
+                    .map(|_| sampler.sample(&mut rng))
+                    .collect()
+            })
+            .collect();
+        Self {
+            genes_size,
+            k,
+            contributions,
+        }
+    }
+    fn score(&self, genes: &[bool]) -> FitnessValue {
+        let genes_size = genes.len().min(self.genes_size);
+        (0..genes_size)
+            .map(|i| {
+                let index = (0..=self.k).fold(0_usize, |index, offset| {
+                    (index << 1) | genes[(i + offset) % genes_size] as usize
+                });
+                self.contributions[i][index] as FitnessValue
+            })
+            .sum()
+    }
+}
+impl Fitness for NkLandscape {
+    type Genotype = BinaryGenotype;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &FitnessChromosome<Self>,
+        _genotype: &Self::Genotype,
+    ) -> Option<FitnessValue> {
+        Some(self.score(&chromosome.genes))
+    }
+}
+impl Benchmark for NkLandscape {
+    fn fitness_ordering(&self) -> FitnessOrdering {
+        FitnessOrdering::Maximize
+    }
+    fn optimal_fitness_score(&self, _genotype: &Self::Genotype) -> Option<FitnessValue> {
+        if self.genes_size > Self::BRUTE_FORCE_MAX_GENES_SIZE {
+            return None;
+        }
+        (0..self.genes_size)
+            .map(|_| [false, true])
+            .multi_cartesian_product()
+            .map(|genes| self.score(&genes))
+            .max()
+    }
+}
+
+/// Traveling salesman problem: the length of the closed tour visiting the cities in the order of
+/// the genes (indices into the distances matrix). Use with a [UniqueGenotype] with allele_list
+/// `(0..dimension).collect()`. Minimize, the optimal fitness score is the optimal tour length when
+/// known (see [TravelingSalesman::with_optimal_tour_length]).
+///
+/// Load instances in TSPLIB format with [TravelingSalesman::from_tsplib] (node coordinates with
+/// EUC_2D, CEIL_2D, MAN_2D, ATT or GEO distances, or EXPLICIT edge weights in any of the matrix
+/// formats), and optimal tours with [TravelingSalesman::parse_tsplib_tour].
+#[derive(Clone, Debug)]
+pub struct TravelingSalesman {
+    pub name: String,
+    pub distances: Vec<Vec<FitnessValue>>,
+    pub optimal_tour_length: Option<FitnessValue>,
+}
+impl TravelingSalesman {
+    pub fn new(distances: Vec<Vec<FitnessValue>>) -> Self {
+        Self {
+            name: String::new(),
+            distances,
+            optimal_tour_length: None,
+        }
+    }
+    pub fn with_optimal_tour_length(mut self, optimal_tour_length: FitnessValue) -> Self {
+        self.optimal_tour_length = Some(optimal_tour_length);
+        self
+    }
+    pub fn dimension(&self) -> usize {
+        self.distances.len()
+    }
+    pub fn tour_length(&self, tour: &[usize]) -> FitnessValue {
+        if tour.is_empty() {
+            return 0;
+        }
+        tour.iter()
+            .zip(tour.iter().cycle().skip(1))
+            .map(|(&from, &to)| self.distances[from][to])
+            .sum()
+    }
+
+    pub fn from_tsplib(content: &str) -> Result<Self, &'static str> {
+        let tsplib = Tsplib::parse(content)?;
+        let dimension = tsplib.dimension.ok_or("TSPLIB DIMENSION is missing")?;
+        if dimension == 0 {
+            return Err("TSPLIB DIMENSION must be positive");
+        }
+        let edge_weight_type = tsplib
+            .header("EDGE_WEIGHT_TYPE")
+            .ok_or("TSPLIB EDGE_WEIGHT_TYPE is missing")?;
+
+        let distances = if edge_weight_type == "EXPLICIT" {
+            let weights = tsplib
+                .section("EDGE_WEIGHT_SECTION")
+                .ok_or("TSPLIB EDGE_WEIGHT_SECTION is missing")?;
+            let format = tsplib.header("EDGE_WEIGHT_FORMAT").unwrap_or("FULL_MATRIX");
+            Self::explicit_distances(dimension, format, &weights)?
+        } else {
+            let coordinates = tsplib
+                .section("NODE_COORD_SECTION")
+                .ok_or("TSPLIB NODE_COORD_SECTION is missing")?;
+            if dimension
+                .checked_mul(3)
+                .map_or(true, |size| coordinates.len() < size)
+            {
+                return Err("TSPLIB NODE_COORD_SECTION has less nodes than DIMENSION");
+            }
+            let nodes: Vec<(f64, f64)> = coordinates
+                .chunks(3)
+                .take(dimension)
+                .map(|node| (node[1], node[2]))
+                .collect();
+            let distance: fn((f64, f64), (f64, f64)) -> FitnessValue = match edge_weight_type {
+                "EUC_2D" => |a, b| nint((a.0 - b.0).hypot(a.1 - b.1)),
+                "CEIL_2D" => |a, b| (a.0 - b.0).hypot(a.1 - b.1).ceil() as FitnessValue,
+                "MAN_2D" => |a, b| nint((a.0 - b.0).abs() + (a.1 - b.1).abs()),
+                "ATT" => |a, b| {
+                    let r = (((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)) / 10.0).sqrt();
+                    let t = nint(r);
+                    if (t as f64) < r {
+                        t + 1
+                    } else {
+                        t
+                    }
+                },
+                "GEO" => |a, b| {
+                    let (latitude_a, longitude_a) = (geo_radians(a.0), geo_radians(a.1));
+                    let (latitude_b, longitude_b) = (geo_radians(b.0), geo_radians(b.1));
+                    let q1 = (longitude_a - longitude_b).cos();
+                    let q2 = (latitude_a - latitude_b).cos();
+                    let q3 = (latitude_a + latitude_b).cos();
+                    (6378.388 * (0.5 * ((1.0 + q1) * q2 - (1.0 - q1) * q3)).acos() + 1.0)
+                        as FitnessValue
+                },
+                _ => return Err("TSPLIB EDGE_WEIGHT_TYPE is not supported"),
+            };
+            nodes
+                .iter()
+                .enumerate()
+                .map(|(i, &a)| {
+                    nodes
+                        .iter()
+                        .enumerate()
+                        .map(|(j, &b)| if i == j { 0 } else { distance(a, b) })
+                        .collect()
+                })
+                .collect()
+        };
+
+        Ok(Self {
+            name: tsplib.header("NAME").unwrap_or_default().to_string(),
+            distances,
+            optimal_tour_length: None,
+        })
+    }
+
+    /// Parses the TOUR_SECTION of a TSPLIB tour file (e.g. `.opt.tour`) into 0-based indices
+    pub fn parse_tsplib_tour(content: &str) -> Result<Vec<usize>, &'static str> {
+        let tsplib = Tsplib::parse(content)?;
+        let nodes = tsplib
+            .section("TOUR_SECTION")
+            .ok_or("TSPLIB TOUR_SECTION is missing")?;
+        nodes
+            .into_iter()
+            .take_while(|&node| node >= 0.0)
+            .map(|node| {
+                if node >= 1.0 {
+                    Ok(node as usize - 1)
+                } else {
+                    Err("TSPLIB TOUR_SECTION nodes are 1-based")
+                }
+            })
+            .collect()
+    }
+
+    fn explicit_distances(
+        dimension: usize,
+        format: &str,
+        weights: &[f64],
+    ) -> Result<Vec<Vec<FitnessValue>>, &'static str> {
+        // check the number of weights before allocating the cells
+        let full_size = dimension.checked_mul(dimension);
+        let cells_size = match format {
+            "FULL_MATRIX" => full_size,
+            "UPPER_ROW" | "LOWER_COL" | "LOWER_ROW" | "UPPER_COL" => {
+                full_size.map(|size| (size - dimension) / 2)
+            }
+            "UPPER_DIAG_ROW" | "LOWER_DIAG_COL" | "LOWER_DIAG_ROW" | "UPPER_DIAG_COL" => full_size
+                .and_then(|size| size.checked_add(dimension))
+                .map(|size| size / 2),
+            _ => return Err("TSPLIB EDGE_WEIGHT_FORMAT is not supported"),
+        };
+        if cells_size.map_or(true, |size| weights.len() < size) {
+            return Err("TSPLIB EDGE_WEIGHT_SECTION has less weights than EDGE_WEIGHT_FORMAT");
+        }
+        // symmetric formats map to the cells (i, j) in order, column formats are the transposed
+        // row formats
+        let cells: Vec<(usize, usize)> = match format {
+            "FULL_MATRIX" => (0..dimension)
+                .flat_map(|i| (0..dimension).map(move |j| (i, j)))
+                .collect(),
+            "UPPER_ROW" | "LOWER_COL" => (0..dimension)
+                .flat_map(|i| (i + 1..dimension).map(move |j| (i, j)))
+                .collect(),
+            "LOWER_ROW" | "UPPER_COL" => (0..dimension)
+                .flat_map(|i| (0..i).map(move |j| (i, j)))
+                .collect(),
+            "UPPER_DIAG_ROW" | "LOWER_DIAG_COL" => (0..dimension)
+                .flat_map(|i| (i..dimension).map(move |j| (i, j)))
+                .collect(),
+            "LOWER_DIAG_ROW" | "UPPER_DIAG_COL" => (0..dimension)
+                .flat_map(|i| (0..=i).map(move |j| (i, j)))
+                .collect(),
+            _ => return Err("TSPLIB EDGE_WEIGHT_FORMAT is not supported"),
+        };
+        let mut distances = vec![vec![0; dimension]; dimension];
+        cells
+            .into_iter()
+            .zip(weights.iter())
+            .for_each(|((i, j), &weight)| {
+                distances[i][j] = weight.round() as FitnessValue;
+                if format != "FULL_MATRIX" {
+                    distances[j][i] = weight.round() as FitnessValue;
+                }
+            });
+        Ok(distances)
+    }
+}
+impl Fitness for TravelingSalesman {
+    type Genotype = UniqueGenotype<usize>;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &FitnessChromosome<Self>,
+        _genotype: &Self::Genotype,
+    ) -> Option<FitnessValue> {
+        Some(self.tour_length(&chromosome.genes))
+    }
+}
+impl Benchmark for TravelingSalesman {
+    fn fitness_ordering(&self) -> FitnessOrdering {
+        FitnessOrdering::Minimize
+    }
+    fn optimal_fitness_score(&self, _genotype: &Self::Genotype) -> Option<FitnessValue> {
+        self.optimal_tour_length
+    }
+}
+
+/// Quadratic assignment problem: facility i is assigned to location genes\[i\], the cost is
+/// `sum(flows[i][j] * distances[genes[i]][genes[j]])`. Use with a [UniqueGenotype] with
+/// allele_list `(0..dimension).collect()`. Minimize, the optimal fitness score is the optimal cost
+/// when known (see [QuadraticAssignment::with_optimal_cost]).
+///
+/// Load instances in QAPLIB format with [QuadraticAssignment::from_qaplib] and solutions with
+/// [QuadraticAssignment::parse_qaplib_solution].
+#[derive(Clone, Debug)]
+pub struct QuadraticAssignment {
+    pub flows: Vec<Vec<FitnessValue>>,
+    pub distances: Vec<Vec<FitnessValue>>,
+    pub optimal_cost: Option<FitnessValue>,
+}
+impl QuadraticAssignment {
+    pub fn new(flows: Vec<Vec<FitnessValue>>, distances: Vec<Vec<FitnessValue>>) -> Self {
+        Self {
+            flows,
+            distances,
+            optimal_cost: None,
+        }
+    }
+    pub fn with_optimal_cost(mut self, optimal_cost: FitnessValue) -> Self {
+        self.optimal_cost = Some(optimal_cost);
+        self
+    }
+    pub fn dimension(&self) -> usize {
+        self.flows.len()
+    }
+    pub fn cost(&self, assignment: &[usize]) -> FitnessValue {
+        assignment
+            .iter()
+            .enumerate()
+            .map(|(i, &location_i)| {
+                assignment
+                    .iter()
+                    .enumerate()
+                    .map(|(j, &location_j)| {
+                        self.flows[i][j] * self.distances[location_i][location_j]
+                    })
+                    .sum::<FitnessValue>()
+            })
+            .sum()
+    }
+
+    /// QAPLIB format: the dimension n followed by the n x n matrices A (flows) and B (distances)
+    pub fn from_qaplib(content: &str) -> Result<Self, &'static str> {
+        let numbers = parse_numbers(content).ok_or("QAPLIB contains a non-numeric value")?;
+        let dimension = *numbers.first().ok_or("QAPLIB dimension is missing")?;
+        if dimension <= 0 {
+            return Err("QAPLIB dimension must be positive");
+        }
+        let dimension = dimension as usize;
+        let matrix_size = dimension
+            .checked_mul(dimension)
+            .ok_or("QAPLIB has less values than two dimension x dimension matrices")?;
+        if matrix_size
+            .checked_mul(2)
+            .and_then(|size| size.checked_add(1))
+            .map_or(true, |size| numbers.len() < size)
+        {
+            return Err("QAPLIB has less values than two dimension x dimension matrices");
+        }
+        let matrix = |offset: usize| -> Vec<Vec<FitnessValue>> {
+            numbers[offset..offset + matrix_size]
+                .chunks(dimension)
+                .map(|row| row.iter().map(|&v| v as FitnessValue).collect())
+                .collect()
+        };
+        Ok(Self::new(matrix(1), matrix(1 + matrix_size)))
+    }
+
+    /// QAPLIB solution format: the dimension n, the optimal cost and the 1-based assignment.
+    /// Returns the cost and the 0-based assignment
+    pub fn parse_qaplib_solution(
+        content: &str,
+    ) -> Result<(FitnessValue, Vec<usize>), &'static str> {
+        let numbers = parse_numbers(content).ok_or("QAPLIB contains a non-numeric value")?;
+        if numbers.len() < 2 {
+            return Err("QAPLIB solution dimension and cost are missing");
+        }
+        if numbers[0] <= 0 {
+            return Err("QAPLIB solution dimension must be positive");
+        }
+        let dimension = numbers[0] as usize;
+        if dimension
+            .checked_add(2)
+            .map_or(true, |size| numbers.len() < size)
+        {
+            return Err("QAPLIB solution has less locations than dimension");
+        }
+        let assignment = numbers[2..2 + dimension]
+            .iter()
+            .map(|&location| {
+                if location >= 1 {
+                    Ok(location as usize - 1)
+                } else {
+                    Err("QAPLIB solution locations are 1-based")
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((numbers[1] as FitnessValue, assignment))
+    }
+}
+impl Fitness for QuadraticAssignment {
+    type Genotype = UniqueGenotype<usize>;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &FitnessChromosome<Self>,
+        _genotype: &Self::Genotype,
+    ) -> Option<FitnessValue> {
+        Some(self.cost(&chromosome.genes))
+    }
+}
+impl Benchmark for QuadraticAssignment {
+    fn fitness_ordering(&self) -> FitnessOrdering {
+        FitnessOrdering::Minimize
+    }
+    fn optimal_fitness_score(&self, _genotype: &Self::Genotype) -> Option<FitnessValue> {
+        self.optimal_cost
+    }
+}
+
+/// TSPLIB rounding to the nearest integer
+fn nint(value: f64) -> FitnessValue {
+    (value + 0.5) as FitnessValue
+}
+
+/// TSPLIB GEO coordinates are DDD.MM (degrees and minutes). The truncated PI is part of the
+/// TSPLIB specification, the published optimal tour lengths depend on it
+#[allow(clippy::approx_constant)]
+fn geo_radians(value: f64) -> f64 {
+    let degrees = value.trunc();
+    let minutes = value - degrees;
+    3.141592 * (degrees + 5.0 * minutes / 3.0) / 180.0
+}
+
+fn parse_numbers(content: &str) -> Option<Vec<i64>> {
+    content
+        .split_whitespace()
+        .map(|token| token.parse().ok())
+        .collect()
+}
+
+/// The specification part (`KEY : VALUE` lines) and the numeric data sections of a TSPLIB file
+struct Tsplib<'a> {
+    headers: Vec<(&'a str, &'a str)>,
+    sections: Vec<(&'a str, Vec<f64>)>,
+    dimension: Option<usize>,
+}
+impl<'a> Tsplib<'a> {
+    fn parse(content: &'a str) -> Result<Self, &'static str> {
+        let mut headers = vec![];
+        let mut sections: Vec<(&str, Vec<f64>)> = vec![];
+        for line in content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+        {
+            if line.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                if line == "EOF" {
+                    break;
+                } else if let Some((key, value)) = line.split_once(':') {
+                    headers.push((key.trim(), value.trim()));
+                } else {
+                    sections.push((line, vec![]));
+                }
+            } else if let Some((_, values)) = sections.last_mut() {
+                for token in line.split_whitespace() {
+                    values.push(
+                        token
+                            .parse()
+                            .map_err(|_| "TSPLIB section contains a non-numeric value")?,
+                    );
+                }
+            } else {
+                return Err("TSPLIB data found outside of a section");
+            }
+        }
+        let dimension = match headers.iter().find(|(key, _)| *key == "DIMENSION") {
+            Some((_, value)) => Some(
+                value
+                    .parse()
+                    .map_err(|_| "TSPLIB DIMENSION is not a number")?,
+            ),
+            None => None,
+        };
+        Ok(Self {
+            headers,
+            sections,
+            dimension,
+        })
+    }
+    fn header(&self, key: &str) -> Option<&'a str> {
+        self.headers
+            .iter()
+            .find(|(header_key, _)| *header_key == key)
+            .map(|(_, value)| *value)
+    }
+    fn section(&self, key: &str) -> Option<Vec<f64>> {
+        self.sections
+            .iter()
+            .find(|(section_key, _)| *section_key == key)
+            .map(|(_, values)| values.clone())
+    }
+}
@@ -2,12 +2,12 @@
 pub use crate::chromosome::{Chromosome, GenesHash};
 #[doc(no_inline)]
 pub use crate::fitness::{
-    fitness_value, Fitness, FitnessChromosome, FitnessGenes, FitnessGenotype, FitnessOrdering,
-    FitnessPopulation, FitnessValue,
+    fitness_value, Fitness, FitnessCache, FitnessChromosome, FitnessGenes, FitnessGenotype,
+    FitnessOrdering, FitnessPopulation, FitnessValue,
 };
 #[doc(no_inline)]
 pub use crate::genotype::{
-    Allele, BinaryGenotype, Genotype, GenotypeBuilder, ListGenotype, MultiListGenotype,
-    MultiRangeGenotype, MultiUniqueGenotype, RangeAllele, RangeGenotype,
-    TryFromGenotypeBuilderError, UniqueGenotype,
+    Allele, BinaryGenotype, BitGenotype, Genotype, GenotypeBuilder, Grammar, GrammaticalGenotype,
+    GridGenotype, ListGenotype, MultiListGenotype, MultiRangeGenotype, MultiUniqueGenotype,
+    RangeAllele, RangeGenotype, TryFromGenotypeBuilderError, UniqueGenotype,
 };
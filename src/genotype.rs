@@ -1,25 +1,50 @@
 //! The search space for the algorithm.
+mod allele_scale;
 mod binary;
+mod bit;
 mod builder;
+mod composite;
+mod gene_constraint;
+mod grammar;
+mod grammatical;
+mod grid;
 mod list;
 mod multi_list;
 mod multi_range;
 mod multi_unique;
 mod mutation_type;
+mod partial_permutation;
 mod range;
+mod subset;
+mod tree;
 mod unique;
+mod variable_list;
 
+pub use self::allele_scale::AlleleScale;
 pub use self::binary::Binary as BinaryGenotype;
+pub use self::bit::Bit as BitGenotype;
 pub use self::builder::{
     Builder as GenotypeBuilder, TryFromBuilderError as TryFromGenotypeBuilderError,
 };
+pub use self::composite::{Composite as CompositeGenotype, CompositeAllele, CompositeSegment};
+pub use self::gene_constraint::{GeneConstraint, LinearBound};
+pub use self::grammar::{
+    Grammar, GrammarDerivation, GrammarDerivationNode, GrammarMappingError, GrammarRule,
+    GrammarSymbol, TryFromGrammarError,
+};
+pub use self::grammatical::Grammatical as GrammaticalGenotype;
+pub use self::grid::{Grid as GridGenotype, GridCells};
 pub use self::list::List as ListGenotype;
 pub use self::multi_list::MultiList as MultiListGenotype;
 pub use self::multi_range::MultiRange as MultiRangeGenotype;
 pub use self::multi_unique::MultiUnique as MultiUniqueGenotype;
 pub use self::mutation_type::MutationType;
+pub use self::partial_permutation::PartialPermutation as PartialPermutationGenotype;
 pub use self::range::Range as RangeGenotype;
+pub use self::subset::Subset as SubsetGenotype;
+pub use self::tree::Tree as TreeGenotype;
 pub use self::unique::Unique as UniqueGenotype;
+pub use self::variable_list::VariableList as VariableListGenotype;
 
 pub use crate::allele::{Allele, RangeAllele};
 use crate::chromosome::{Chromosome, Genes};
@@ -159,7 +184,8 @@ pub trait Genotype:
 pub trait EvolveGenotype: Genotype {}
 
 /// Genotype that supports gene-index-based crossover (swap individual genes).
-/// Not implemented by [UniqueGenotype] or [MultiUniqueGenotype] (would break uniqueness).
+/// Not implemented by [UniqueGenotype], [MultiUniqueGenotype] or [PartialPermutationGenotype] (would
+/// break uniqueness).
 pub trait SupportsGeneCrossover: Genotype {
     fn crossover_chromosome_genes<R: Rng>(
         &self,
@@ -172,7 +198,8 @@ pub trait SupportsGeneCrossover: Genotype {
 }
 
 /// Genotype that supports point-based crossover (swap sections at crossover points).
-/// Not implemented by [UniqueGenotype]. Implemented by [MultiUniqueGenotype].
+/// Not implemented by [UniqueGenotype], [PartialPermutationGenotype] or [SubsetGenotype]. Implemented
+/// by [MultiUniqueGenotype]. [GridGenotype] swaps rectangular 2D blocks instead of sections.
 pub trait SupportsPointCrossover: Genotype {
     fn crossover_chromosome_points<R: Rng>(
         &self,
@@ -224,3 +251,58 @@ pub trait PermutateGenotype: Genotype {
         false
     }
 }
+
+/// Genotype suitable for [Estimate](crate::strategy::estimate::Estimate).
+/// Only genotypes with a finite allele list per gene can be modelled by per-gene allele probability
+/// vectors. All methods have defaults, so unsupported genotypes remain eligible for the superset
+/// [StrategyBuilder](crate::strategy::StrategyBuilder) (runtime check via allows_estimation()).
+pub trait EstimateGenotype: Genotype {
+    /// number of alleles per gene, the size of the per-gene probability vectors
+    fn allele_sizes(&self) -> Vec<usize> {
+        vec![]
+    }
+    /// index of the allele in the allele list of the gene, None if not in the allele list
+    fn allele_index(&self, _gene_index: usize, _allele: &Self::Allele) -> Option<usize> {
+        None
+    }
+    /// set the gene to the allele with the given index in the allele list of the gene
+    /// (does not reset the chromosome metadata)
+    fn set_gene_by_allele_index(
+        &self,
+        _chromosome: &mut Chromosome<Self::Allele>,
+        _gene_index: usize,
+        _allele_index: usize,
+    ) {
+    }
+    /// only genotypes with a finite allele list per gene implement estimation
+    fn allows_estimation(&self) -> bool {
+        false
+    }
+}
+
+/// Genotype suitable for [ParticleSwarm](crate::strategy::particle_swarm::ParticleSwarm).
+/// Only genotypes with a continuous (numeric) allele range per gene can be moved through by
+/// particles. All methods have defaults, so unsupported genotypes remain eligible for the superset
+/// [StrategyBuilder](crate::strategy::StrategyBuilder) (runtime check via allows_particle_swarm()).
+pub trait ParticleSwarmGenotype: Genotype {
+    /// the (start, end) bounds of the allele range per gene, the bounds of the particle positions
+    fn position_bounds(&self) -> Vec<(f64, f64)> {
+        vec![]
+    }
+    /// the genes as particle position
+    fn genes_to_position(&self, _genes: &Genes<Self::Allele>) -> Vec<f64> {
+        vec![]
+    }
+    /// set the genes from the (clamped) particle position, rounding discrete genes
+    /// (does not reset the chromosome metadata)
+    fn set_genes_from_position(
+        &self,
+        _chromosome: &mut Chromosome<Self::Allele>,
+        _position: &[f64],
+    ) {
+    }
+    /// only genotypes with a numeric allele range per gene implement particle swarms
+    fn allows_particle_swarm(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,265 @@
+use super::MutationType;
+use crate::allele::RangeAllele;
+use rand::prelude::*;
+use std::ops::RangeInclusive;
+
+/// The scale on which the genes of (Multi)RangeGenotype are searched. Not to be confused with the
+/// scale index of the scaled mutation types, which controls the mutation phases.
+///
+/// For non-linear scales, the allele values are transformed to the scale and all operations are
+/// performed on the scaled value: random initialization samples uniformly on the scale, the
+/// bandwidths and steps of all [MutationType]s are in scaled units, HillClimb neighbours move on
+/// the scale and the Permutate grids (Step/StepScaled) are evenly spaced on the scale. The results
+/// are transformed back and clamped to the allele range.
+///
+/// * `Linear` (default): the allele values themselves, uniform sampling
+/// * `Log`: the log10 of the allele values, so uniform per decade. Requires a positive allele
+///   range. A `Step(0.5)` multiplies or divides the value by √10. Typical for learning rates and
+///   regularization strengths.
+/// * `Custom`: a user provided strictly increasing transform and its inverse (e.g. log2 or sqrt)
+///
+/// ** Note: ** For integer alleles the transformed back values are rounded, so steps below the
+/// integer resolution do not move the gene and grids may contain duplicate values.
+///
+/// # Example:
+/// ```
+/// use genetic_algorithm::genotype::{AlleleScale, Genotype, MultiRangeGenotype, MutationType};
+///
+/// let genotype = MultiRangeGenotype::<f32>::builder()
+///     .with_allele_ranges(vec![
+///         0.001..=1.0, // learning rate
+///         1.0..=1024.0, // batch size
+///     ])
+///     .with_allele_scales(vec![
+///         AlleleScale::Log,
+///         AlleleScale::Custom { to_scale: f64::log2, from_scale: f64::exp2 },
+///     ])
+///     .with_mutation_types(vec![
+///         MutationType::Range(0.5), // half a decade
+///         MutationType::Step(1.0), // double or halve
+///     ])
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub enum AlleleScale {
+    #[default]
+    Linear,
+    Log,
+    Custom {
+        to_scale: fn(f64) -> f64,
+        from_scale: fn(f64) -> f64,
+    },
+}
+
+impl AlleleScale {
+    pub fn is_linear(&self) -> bool {
+        matches!(self, Self::Linear)
+    }
+    pub fn to_scale(&self, value: f64) -> f64 {
+        match self {
+            Self::Linear => value,
+            Self::Log => value.log10(),
+            Self::Custom { to_scale, .. } => to_scale(value),
+        }
+    }
+    pub fn from_scale(&self, scaled_value: f64) -> f64 {
+        match self {
+            Self::Linear => scaled_value,
+            Self::Log => 10f64.powf(scaled_value),
+            Self::Custom { from_scale, .. } => from_scale(scaled_value),
+        }
+    }
+
+    /// the scaled bounds must be finite and increasing, also for the floored start and exclusive
+    /// end of discrete sampling (e.g. a Log scale on 0.5..=10.0 floors the start to 0)
+    pub(crate) fn is_valid_for<T: RangeAllele>(
+        &self,
+        allele_range: &RangeInclusive<T>,
+        mutation_type: &MutationType<T>,
+    ) -> bool {
+        let (low, high) = self.scaled_bounds(allele_range);
+        let discrete_low = self.to_scale(allele_range.start().floor().to_f64());
+        let discrete_high = self.to_scale(allele_range.end().floor().to_f64() + 1.0);
+        low.is_finite()
+            && high.is_finite()
+            && low <= high
+            && (!matches!(mutation_type, MutationType::Discrete)
+                || (discrete_low.is_finite()
+                    && discrete_high.is_finite()
+                    && discrete_low < discrete_high))
+    }
+
+    pub(crate) fn scaled_bounds<T: RangeAllele>(
+        &self,
+        allele_range: &RangeInclusive<T>,
+    ) -> (f64, f64) {
+        (
+            self.to_scale(allele_range.start().to_f64()),
+            self.to_scale(allele_range.end().to_f64()),
+        )
+    }
+    pub(crate) fn scaled_value<T: RangeAllele>(
+        &self,
+        value: T,
+        allele_range: &RangeInclusive<T>,
+    ) -> f64 {
+        let (low, high) = self.scaled_bounds(allele_range);
+        self.to_scale(value.to_f64()).clamp(low, high)
+    }
+    pub(crate) fn unscaled_value<T: RangeAllele>(
+        &self,
+        scaled_value: f64,
+        allele_range: &RangeInclusive<T>,
+    ) -> T {
+        let value = T::from_f64(self.from_scale(scaled_value));
+        if value < *allele_range.start() {
+            *allele_range.start()
+        } else if value > *allele_range.end() {
+            *allele_range.end()
+        } else {
+            value
+        }
+    }
+
+    pub(crate) fn sample_gene_random<T: RangeAllele, R: Rng>(
+        &self,
+        allele_range: &RangeInclusive<T>,
+        mutation_type: &MutationType<T>,
+        rng: &mut R,
+    ) -> T {
+        if matches!(mutation_type, MutationType::Discrete) {
+            // [start, end+1) on the scale for uniform floor() sampling
+            let start = allele_range.start().floor().to_f64();
+            let end = allele_range.end().floor().to_f64();
+            let scaled_value = rng.gen_range(self.to_scale(start)..self.to_scale(end + 1.0));
+            T::from_f64(self.from_scale(scaled_value).floor().clamp(start, end))
+        } else {
+            let (low, high) = self.scaled_bounds(allele_range);
+            self.unscaled_value(rng.gen_range(low..=high), allele_range)
+        }
+    }
+
+    pub(crate) fn mutate_gene<T: RangeAllele, R: Rng>(
+        &self,
+        value: T,
+        allele_range: &RangeInclusive<T>,
+        mutation_type: &MutationType<T>,
+        current_scale_index: usize,
+        rng: &mut R,
+    ) -> T {
+        let (low, high) = self.scaled_bounds(allele_range);
+        let current_value = self.scaled_value(value, allele_range);
+        let scaled_value = match mutation_type {
+            MutationType::Random => rng.gen_range(low..=high),
+            MutationType::Discrete => {
+                return self.sample_gene_random(allele_range, mutation_type, rng)
+            }
+            MutationType::Range(bandwidth) => {
+                // post-clamp
+                let delta = rng.gen_range(0.0..=bandwidth.to_f64());
+                if rng.gen() {
+                    current_value + delta
+                } else {
+                    current_value - delta
+                }
+            }
+            MutationType::RangeScaled(bandwidths) => {
+                let bandwidth = bandwidths[current_scale_index].to_f64();
+                if current_scale_index >= bandwidths.len().saturating_sub(1) {
+                    // post-clamp
+                    let delta = rng.gen_range(0.0..=bandwidth);
+                    if rng.gen() {
+                        current_value + delta
+                    } else {
+                        current_value - delta
+                    }
+                } else {
+                    // pre-clamp
+                    rng.gen_range(
+                        (current_value - bandwidth).max(low)
+                            ..=(current_value + bandwidth).min(high),
+                    )
+                }
+            }
+            MutationType::Step(step) => {
+                if rng.gen() {
+                    current_value + step.to_f64()
+                } else {
+                    current_value - step.to_f64()
+                }
+            }
+            MutationType::StepScaled(steps) => {
+                let step = steps[current_scale_index].to_f64();
+                if rng.gen() {
+                    current_value + step
+                } else {
+                    current_value - step
+                }
+            }
+        };
+        self.unscaled_value(scaled_value.clamp(low, high), allele_range)
+    }
+
+    /// the down and up neighbouring values on the scale, excluding unchanged values. Not for
+    /// Discrete, which has all integer values as neighbours regardless of the scale
+    pub(crate) fn neighbouring_values<T: RangeAllele, R: Rng>(
+        &self,
+        value: T,
+        allele_range: &RangeInclusive<T>,
+        mutation_type: &MutationType<T>,
+        current_scale_index: usize,
+        rng: &mut R,
+    ) -> Vec<T> {
+        let (low, high) = self.scaled_bounds(allele_range);
+        let current_value = self.scaled_value(value, allele_range);
+        let mut delta = |towards: f64| match mutation_type {
+            MutationType::Random => rng.gen_range(0.0..=(towards - current_value).abs()),
+            MutationType::Range(bandwidth) => rng.gen_range(0.0..=bandwidth.to_f64()),
+            MutationType::RangeScaled(bandwidths) => {
+                rng.gen_range(0.0..=bandwidths[current_scale_index].to_f64())
+            }
+            MutationType::Step(step) => step.to_f64(),
+            MutationType::StepScaled(steps) => steps[current_scale_index].to_f64(),
+            MutationType::Discrete => 0.0,
+        };
+        let mut scaled_values = vec![];
+        if low < current_value {
+            scaled_values.push((current_value - delta(low)).max(low));
+        }
+        if current_value < high {
+            scaled_values.push((current_value + delta(high)).min(high));
+        }
+        scaled_values
+            .into_iter()
+            .map(|scaled_value| self.unscaled_value(scaled_value, allele_range))
+            .filter(|new_value| *new_value != value)
+            .collect()
+    }
+
+    /// evenly spaced values on the scale between the scaled start and end (inclusive)
+    pub(crate) fn permutable_gene_values<T: RangeAllele>(
+        &self,
+        allele_range: &RangeInclusive<T>,
+        scaled_start: f64,
+        scaled_end: f64,
+        step: f64,
+    ) -> Vec<T> {
+        Self::scaled_grid(scaled_start, scaled_end, step)
+            .map(|scaled_value| self.unscaled_value(scaled_value, allele_range))
+            .collect()
+    }
+    pub(crate) fn scaled_grid(
+        scaled_start: f64,
+        scaled_end: f64,
+        step: f64,
+    ) -> impl Iterator<Item = f64> {
+        std::iter::successors(Some(scaled_start), move |value| {
+            if *value < scaled_end {
+                Some((*value + step).min(scaled_end))
+            } else {
+                None
+            }
+        })
+    }
+}
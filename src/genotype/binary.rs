@@ -1,7 +1,7 @@
 use super::builder::{Builder, TryFromBuilderError};
 use super::{
-    EvolveGenotype, Genotype, HillClimbGenotype, MutationType, PermutateGenotype,
-    SupportsGeneCrossover, SupportsPointCrossover,
+    EstimateGenotype, EvolveGenotype, Genotype, HillClimbGenotype, MutationType,
+    ParticleSwarmGenotype, PermutateGenotype, SupportsGeneCrossover, SupportsPointCrossover,
 };
 use crate::chromosome::{Chromosome, Genes};
 use crate::population::Population;
@@ -43,6 +43,10 @@ impl TryFrom<Builder<Self>> for Binary {
             Err(TryFromBuilderError(
                 "BinaryGenotype requires a genes_size > 0",
             ))
+        } else if !builder.gene_constraints.is_empty() {
+            Err(TryFromBuilderError(
+                "BinaryGenotype does not support gene_constraints",
+            ))
         } else {
             let genes_size = builder.genes_size.unwrap();
             Ok(Self {
@@ -268,6 +272,28 @@ impl PermutateGenotype for Binary {
     }
 }
 
+impl EstimateGenotype for Binary {
+    fn allele_sizes(&self) -> Vec<usize> {
+        vec![2; self.genes_size]
+    }
+    fn allele_index(&self, _gene_index: usize, allele: &bool) -> Option<usize> {
+        Some(*allele as usize)
+    }
+    fn set_gene_by_allele_index(
+        &self,
+        chromosome: &mut Chromosome<bool>,
+        gene_index: usize,
+        allele_index: usize,
+    ) {
+        chromosome.genes[gene_index] = allele_index == 1;
+    }
+    fn allows_estimation(&self) -> bool {
+        true
+    }
+}
+
+impl ParticleSwarmGenotype for Binary {}
+
 impl fmt::Display for Binary {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "genotype:")?;
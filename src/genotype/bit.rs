@@ -0,0 +1,449 @@
+use super::builder::{Builder, TryFromBuilderError};
+use super::{
+    EstimateGenotype, EvolveGenotype, Genotype, HillClimbGenotype, MutationType,
+    ParticleSwarmGenotype, PermutateGenotype, SupportsGeneCrossover, SupportsPointCrossover,
+};
+use crate::chromosome::{Chromosome, Genes};
+use crate::population::Population;
+use itertools::Itertools;
+use num::BigUint;
+use rand::distributions::Uniform;
+use rand::prelude::*;
+use std::fmt;
+
+const BITS_PER_WORD: usize = u64::BITS as usize;
+
+/// Genes are booleans packed in u64 words, a memory efficient alternative to
+/// [BinaryGenotype](crate::genotype::BinaryGenotype) for large genes_size (e.g. feature
+/// selection). The genes_size is the number of bits, the genes hold genes_size / 64 words
+/// (rounded up). The unused bits of the last word are always zero. Copying, hashing and
+/// crossover are performed per word (crossover with word-level masks).
+///
+/// On random initialization, each bit has a 50% probability of becoming true or false. Each bit
+/// has an equal probability of mutating. If a bit mutates, its value is flipped. The HillClimb
+/// neighbours are all single bit flips. Permutate enumerates all 2^genes_size bit combinations.
+///
+/// Use the helper methods to access the bits in the fitness function, like
+/// [count_ones](Self::count_ones), [count_ones_masked](Self::count_ones_masked),
+/// [ones](Self::ones) and [get](Self::get). Use [genes_from_bools](Self::genes_from_bools) to build
+/// seed genes, the builder rejects seed genes which are not words_size long or have unused bits
+/// set in the last word.
+///
+/// # Example:
+/// ```
+/// use genetic_algorithm::genotype::{BitGenotype, Genotype};
+///
+/// let genotype = BitGenotype::builder()
+///     .with_genes_size(100_000)
+///     .with_genes_hashing(true) // optional, defaults to true
+///     .with_chromosome_recycling(true) // optional, defaults to true
+///     .build()
+///     .unwrap();
+///
+/// let genes = genotype.genes_from_bools(&[true, false, true]);
+/// assert_eq!(genotype.count_ones(&genes), 2);
+/// assert_eq!(genotype.ones(&genes).collect::<Vec<_>>(), vec![0, 2]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Bit {
+    pub genes_size: usize,
+    pub words_size: usize,
+    gene_index_sampler: Uniform<usize>,
+    pub seed_genes_list: Vec<Vec<u64>>,
+    pub genes_hashing: bool,
+    pub chromosome_recycling: bool,
+}
+
+impl TryFrom<Builder<Self>> for Bit {
+    type Error = TryFromBuilderError;
+
+    fn try_from(builder: Builder<Self>) -> Result<Self, Self::Error> {
+        if !builder.genes_size.is_some_and(|x| x > 0) {
+            Err(TryFromBuilderError("BitGenotype requires a genes_size > 0"))
+        } else if !builder.gene_constraints.is_empty() {
+            Err(TryFromBuilderError(
+                "BitGenotype does not support gene_constraints",
+            ))
+        } else {
+            let genes_size = builder.genes_size.unwrap();
+            let genotype = Self {
+                genes_size,
+                words_size: (genes_size + BITS_PER_WORD - 1) / BITS_PER_WORD,
+                gene_index_sampler: Uniform::from(0..genes_size),
+                seed_genes_list: builder.seed_genes_list,
+                genes_hashing: builder.genes_hashing,
+                chromosome_recycling: builder.chromosome_recycling,
+            };
+            if genotype
+                .seed_genes_list
+                .iter()
+                .all(|genes| genotype.is_valid_genes(genes))
+            {
+                Ok(genotype)
+            } else {
+                Err(TryFromBuilderError(
+                    "BitGenotype seed genes must have words_size words, with the unused bits of the last word zeroed",
+                ))
+            }
+        }
+    }
+}
+
+impl Bit {
+    fn mutation_type(&self) -> &MutationType<u64> {
+        &MutationType::Random
+    }
+    /// the mask of the used bits of the last word
+    fn last_word_mask(&self) -> u64 {
+        match self.genes_size % BITS_PER_WORD {
+            0 => u64::MAX,
+            used_bits => (1u64 << used_bits) - 1,
+        }
+    }
+    /// words_size words, with the unused bits of the last word zeroed
+    fn is_valid_genes(&self, genes: &[u64]) -> bool {
+        genes.len() == self.words_size
+            && genes
+                .last()
+                .is_some_and(|word| word & !self.last_word_mask() == 0)
+    }
+    /// the mask of the bits in start..end within the word
+    fn range_mask(word_index: usize, start: usize, end: usize) -> u64 {
+        let word_start = word_index * BITS_PER_WORD;
+        let low = start.saturating_sub(word_start).min(BITS_PER_WORD);
+        let high = end.saturating_sub(word_start).min(BITS_PER_WORD);
+        let below = |bits: usize| {
+            if bits == BITS_PER_WORD {
+                u64::MAX
+            } else {
+                (1u64 << bits) - 1
+            }
+        };
+        below(high) & !below(low)
+    }
+    /// swap the bits in start..end between the genes, per word
+    fn swap_range(&self, father: &mut [u64], mother: &mut [u64], start: usize, end: usize) {
+        if start >= end {
+            return;
+        }
+        for word_index in (start / BITS_PER_WORD)..=((end - 1) / BITS_PER_WORD) {
+            let diff = (father[word_index] ^ mother[word_index])
+                & Self::range_mask(word_index, start, end);
+            father[word_index] ^= diff;
+            mother[word_index] ^= diff;
+        }
+    }
+
+    /// the value of the bit at the index
+    pub fn get(&self, genes: &[u64], index: usize) -> bool {
+        genes[index / BITS_PER_WORD] & (1u64 << (index % BITS_PER_WORD)) != 0
+    }
+    /// set the value of the bit at the index
+    pub fn set(&self, genes: &mut [u64], index: usize, value: bool) {
+        let bit = 1u64 << (index % BITS_PER_WORD);
+        if value {
+            genes[index / BITS_PER_WORD] |= bit;
+        } else {
+            genes[index / BITS_PER_WORD] &= !bit;
+        }
+    }
+    /// flip the value of the bit at the index
+    pub fn flip(&self, genes: &mut [u64], index: usize) {
+        genes[index / BITS_PER_WORD] ^= 1u64 << (index % BITS_PER_WORD);
+    }
+    /// the number of true bits (popcount)
+    pub fn count_ones(&self, genes: &[u64]) -> usize {
+        genes.iter().map(|word| word.count_ones() as usize).sum()
+    }
+    /// the number of true bits which are also true in the mask (popcount of the intersection),
+    /// e.g. for the number of selected features in a group
+    pub fn count_ones_masked(&self, genes: &[u64], mask: &[u64]) -> usize {
+        genes
+            .iter()
+            .zip(mask.iter())
+            .map(|(word, mask_word)| (word & mask_word).count_ones() as usize)
+            .sum()
+    }
+    /// the indices of the true bits, in increasing order
+    pub fn ones<'a>(&self, genes: &'a [u64]) -> impl Iterator<Item = usize> + 'a {
+        genes.iter().enumerate().flat_map(|(word_index, word)| {
+            let mut word = *word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    None
+                } else {
+                    let bit_index = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    Some(word_index * BITS_PER_WORD + bit_index)
+                }
+            })
+        })
+    }
+    /// pack the booleans into genes (missing booleans are false, excess booleans are ignored)
+    pub fn genes_from_bools(&self, bools: &[bool]) -> Genes<u64> {
+        let mut genes = vec![0; self.words_size];
+        bools
+            .iter()
+            .take(self.genes_size)
+            .enumerate()
+            .filter(|(_, value)| **value)
+            .for_each(|(index, _)| self.set(&mut genes, index, true));
+        genes
+    }
+    /// unpack the genes into genes_size booleans
+    pub fn genes_to_bools(&self, genes: &[u64]) -> Vec<bool> {
+        (0..self.genes_size)
+            .map(|index| self.get(genes, index))
+            .collect()
+    }
+}
+
+impl Genotype for Bit {
+    type Allele = u64;
+
+    fn genes_size(&self) -> usize {
+        self.genes_size
+    }
+    fn sample_gene_index<R: Rng>(&self, rng: &mut R) -> usize {
+        self.gene_index_sampler.sample(rng)
+    }
+    fn sample_gene_indices<R: Rng>(
+        &self,
+        count: usize,
+        allow_duplicates: bool,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        if allow_duplicates {
+            rng.sample_iter(self.gene_index_sampler)
+                .take(count)
+                .collect()
+        } else {
+            rand::seq::index::sample(rng, self.genes_size, count.min(self.genes_size)).into_vec()
+        }
+    }
+
+    fn mutate_chromosome_genes<R: Rng>(
+        &self,
+        number_of_mutations: usize,
+        allow_duplicates: bool,
+        chromosome: &mut Chromosome<Self::Allele>,
+        rng: &mut R,
+    ) {
+        if allow_duplicates {
+            rng.sample_iter(self.gene_index_sampler)
+                .take(number_of_mutations)
+                .for_each(|index| self.flip(&mut chromosome.genes, index));
+        } else {
+            rand::seq::index::sample(
+                rng,
+                self.genes_size,
+                number_of_mutations.min(self.genes_size),
+            )
+            .iter()
+            .for_each(|index| self.flip(&mut chromosome.genes, index));
+        }
+        chromosome.reset_metadata(self.genes_hashing);
+    }
+    /// The seed genes are resized to words_size (padding with zeros) and the unused bits of the
+    /// last word are cleared
+    fn set_seed_genes_list(&mut self, seed_genes_list: Vec<Genes<Self::Allele>>) {
+        let last_word_mask = self.last_word_mask();
+        self.seed_genes_list = seed_genes_list
+            .into_iter()
+            .map(|mut genes| {
+                genes.resize(self.words_size, 0);
+                *genes.last_mut().unwrap() &= last_word_mask;
+                genes
+            })
+            .collect();
+    }
+    fn seed_genes_list(&self) -> &Vec<Genes<Self::Allele>> {
+        &self.seed_genes_list
+    }
+    fn set_genes_hashing(&mut self, genes_hashing: bool) {
+        self.genes_hashing = genes_hashing;
+    }
+    fn random_genes_factory<R: Rng>(&self, rng: &mut R) -> Vec<u64> {
+        if self.seed_genes_list.is_empty() {
+            let mut genes: Vec<u64> = (0..self.words_size).map(|_| rng.gen()).collect();
+            *genes.last_mut().unwrap() &= self.last_word_mask();
+            genes
+        } else {
+            self.seed_genes_list.choose(rng).unwrap().clone()
+        }
+    }
+    fn genes_capacity(&self) -> usize {
+        self.words_size
+    }
+    fn genes_hashing(&self) -> bool {
+        self.genes_hashing
+    }
+    fn chromosome_recycling(&self) -> bool {
+        self.chromosome_recycling
+    }
+}
+
+impl EvolveGenotype for Bit {}
+impl SupportsGeneCrossover for Bit {
+    /// the crossover bits are collected in a mask per word, then swapped per word
+    fn crossover_chromosome_genes<R: Rng>(
+        &self,
+        number_of_crossovers: usize,
+        allow_duplicates: bool,
+        father: &mut Chromosome<Self::Allele>,
+        mother: &mut Chromosome<Self::Allele>,
+        rng: &mut R,
+    ) {
+        let mut mask = vec![0u64; self.words_size];
+        if allow_duplicates {
+            rng.sample_iter(self.gene_index_sampler)
+                .take(number_of_crossovers)
+                .for_each(|index| self.flip(&mut mask, index));
+        } else {
+            rand::seq::index::sample(
+                rng,
+                self.genes_size(),
+                number_of_crossovers.min(self.genes_size()),
+            )
+            .iter()
+            .for_each(|index| self.flip(&mut mask, index));
+        }
+        father
+            .genes
+            .iter_mut()
+            .zip(mother.genes.iter_mut())
+            .zip(mask)
+            .for_each(|((father_word, mother_word), mask_word)| {
+                let diff = (*father_word ^ *mother_word) & mask_word;
+                *father_word ^= diff;
+                *mother_word ^= diff;
+            });
+        mother.reset_metadata(self.genes_hashing);
+        father.reset_metadata(self.genes_hashing);
+    }
+}
+impl SupportsPointCrossover for Bit {
+    fn crossover_chromosome_points<R: Rng>(
+        &self,
+        number_of_crossovers: usize,
+        allow_duplicates: bool,
+        father: &mut Chromosome<Self::Allele>,
+        mother: &mut Chromosome<Self::Allele>,
+        rng: &mut R,
+    ) {
+        if allow_duplicates {
+            rng.sample_iter(self.gene_index_sampler)
+                .take(number_of_crossovers)
+                .for_each(|index| {
+                    self.swap_range(&mut father.genes, &mut mother.genes, index, self.genes_size);
+                });
+        } else {
+            rand::seq::index::sample(
+                rng,
+                self.genes_size(),
+                number_of_crossovers.min(self.genes_size()),
+            )
+            .iter()
+            .sorted_unstable()
+            .chunks(2)
+            .into_iter()
+            .for_each(|mut chunk| match (chunk.next(), chunk.next()) {
+                (Some(start_index), Some(end_index)) => {
+                    self.swap_range(&mut father.genes, &mut mother.genes, start_index, end_index);
+                }
+                (Some(start_index), _) => {
+                    self.swap_range(
+                        &mut father.genes,
+                        &mut mother.genes,
+                        start_index,
+                        self.genes_size,
+                    );
+                }
+                _ => (),
+            });
+        }
+        mother.reset_metadata(self.genes_hashing);
+        father.reset_metadata(self.genes_hashing);
+    }
+}
+impl HillClimbGenotype for Bit {
+    fn fill_neighbouring_population<R: Rng>(
+        &self,
+        chromosome: &Chromosome<Self::Allele>,
+        population: &mut Population<Self::Allele>,
+        _rng: &mut R,
+    ) {
+        (0..self.genes_size).for_each(|index| {
+            let mut new_chromosome = population.new_chromosome(chromosome);
+            self.flip(&mut new_chromosome.genes, index);
+            new_chromosome.reset_metadata(self.genes_hashing);
+            population.chromosomes.push(new_chromosome);
+        });
+    }
+
+    fn neighbouring_population_size(&self) -> BigUint {
+        BigUint::from(self.genes_size)
+    }
+}
+
+impl PermutateGenotype for Bit {
+    fn chromosome_permutations_into_iter<'a>(
+        &'a self,
+        _chromosome: Option<&Chromosome<Self::Allele>>,
+    ) -> Box<dyn Iterator<Item = Chromosome<Self::Allele>> + Send + 'a> {
+        if self.seed_genes_list.is_empty() {
+            Box::new(
+                (0..self.genes_size())
+                    .map(|_| vec![true, false])
+                    .multi_cartesian_product()
+                    .map(|bools| Chromosome::new(self.genes_from_bools(&bools))),
+            )
+        } else {
+            Box::new(
+                self.seed_genes_list
+                    .clone()
+                    .into_iter()
+                    .map(Chromosome::new),
+            )
+        }
+    }
+    fn chromosome_permutations_size(&self) -> BigUint {
+        if self.seed_genes_list.is_empty() {
+            BigUint::from(2u8).pow(self.genes_size() as u32)
+        } else {
+            self.seed_genes_list.len().into()
+        }
+    }
+    fn allows_permutation(&self) -> bool {
+        true
+    }
+}
+
+impl EstimateGenotype for Bit {}
+
+impl ParticleSwarmGenotype for Bit {}
+
+impl fmt::Display for Bit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "genotype:")?;
+        writeln!(f, "  genes_size: {}", self.genes_size)?;
+        writeln!(f, "  words_size: {}", self.words_size)?;
+        writeln!(f, "  mutation_type: {:?}", self.mutation_type())?;
+        writeln!(
+            f,
+            "  chromosome_permutations_size: {}",
+            self.chromosome_permutations_size_report()
+        )?;
+        writeln!(
+            f,
+            "  neighbouring_population_size: {}",
+            self.neighbouring_population_size_report()
+        )?;
+        writeln!(
+            f,
+            "  expected_number_of_sampled_index_duplicates: {}",
+            self.expected_number_of_sampled_index_duplicates_report()
+        )?;
+        writeln!(f, "  seed_genes: {:?}", self.seed_genes_list.len())
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Genotype, MutationType};
+use super::{AlleleScale, GeneConstraint, Genotype, Grammar, MutationType};
 use crate::chromosome::Genes;
 pub use crate::errors::TryFromGenotypeBuilderError as TryFromBuilderError;
 use std::ops::RangeInclusive;
@@ -27,12 +27,25 @@ use std::ops::RangeInclusive;
 #[derive(Clone, Debug)]
 pub struct Builder<G: Genotype> {
     pub genes_size: Option<usize>,
+    pub genes_size_range: Option<RangeInclusive<usize>>,
     pub allele_list: Option<Vec<G::Allele>>,
     pub allele_lists: Option<Vec<Vec<G::Allele>>>,
     pub allele_range: Option<RangeInclusive<G::Allele>>,
     pub allele_ranges: Option<Vec<RangeInclusive<G::Allele>>>,
     pub mutation_type: Option<MutationType<G::Allele>>,
     pub mutation_types: Option<Vec<MutationType<G::Allele>>>,
+    pub allele_scale: Option<AlleleScale>,
+    pub allele_scales: Option<Vec<AlleleScale>>,
+    pub allele_list_weights: Option<Vec<f64>>,
+    pub allele_lists_weights: Option<Vec<Vec<f64>>>,
+    pub ordinal_mutation: Option<bool>,
+    pub ordinal_mutations: Option<Vec<bool>>,
+    pub gene_constraints: Vec<GeneConstraint<G::Allele>>,
+    pub function_set: Option<Vec<(G::Allele, usize)>>,
+    pub terminal_set: Option<Vec<G::Allele>>,
+    pub max_depth: Option<usize>,
+    pub init_depth_range: Option<RangeInclusive<usize>>,
+    pub grammar: Option<Grammar>,
     pub seed_genes_list: Vec<Genes<G::Allele>>,
     pub genes_hashing: bool,
     pub chromosome_recycling: bool,
@@ -43,14 +56,22 @@ impl<G: Genotype> Builder<G> {
         Self::default()
     }
 
-    /// Set the number of genes per chromosome. Required for Binary, List, Unique, Range.
+    /// Set the number of genes per chromosome. Required for Binary, List, Unique, Range. Used as
+    /// maximum number of nodes for Tree.
     /// Automatically derived from allele_lists/allele_ranges length for Multi* genotypes.
     pub fn with_genes_size(mut self, genes_size: usize) -> Self {
         self.genes_size = Some(genes_size);
         self
     }
 
-    /// Set a shared allele list for all genes. Used by List and Unique genotypes.
+    /// Set the allowed number of genes per chromosome (min..=max). Required for VariableList,
+    /// where the length of the genes is part of the solution.
+    pub fn with_genes_size_range(mut self, genes_size_range: RangeInclusive<usize>) -> Self {
+        self.genes_size_range = Some(genes_size_range);
+        self
+    }
+
+    /// Set a shared allele list for all genes. Used by List, VariableList and Unique genotypes.
     pub fn with_allele_list(mut self, allele_list: Vec<G::Allele>) -> Self {
         self.allele_list = Some(allele_list);
         self
@@ -93,6 +114,90 @@ impl<G: Genotype> Builder<G> {
         self
     }
 
+    /// Set a shared allele scale for all genes. Used by RangeGenotype.
+    /// See [AlleleScale] for options (Linear, Log, Custom).
+    pub fn with_allele_scale(mut self, allele_scale: AlleleScale) -> Self {
+        self.allele_scale = Some(allele_scale);
+        self
+    }
+
+    /// Set per-gene allele scales. Used by MultiRangeGenotype.
+    /// Length must match genes_size. See [AlleleScale] for options.
+    pub fn with_allele_scales(mut self, allele_scales: Vec<AlleleScale>) -> Self {
+        self.allele_scales = Some(allele_scales);
+        self
+    }
+
+    /// Set the relative weights of the shared allele list values. Used by ListGenotype.
+    /// Length must match the allele_list length. Weights must be non-negative.
+    pub fn with_allele_list_weights(mut self, allele_list_weights: Vec<f64>) -> Self {
+        self.allele_list_weights = Some(allele_list_weights);
+        self
+    }
+
+    /// Set the relative weights of the per-gene allele list values. Used by MultiListGenotype.
+    /// Lengths must match the allele_lists lengths. Weights must be non-negative.
+    pub fn with_allele_lists_weights(mut self, allele_lists_weights: Vec<Vec<f64>>) -> Self {
+        self.allele_lists_weights = Some(allele_lists_weights);
+        self
+    }
+
+    /// Mutate to a neighbouring entry in the shared allele list, instead of a random one. Used by
+    /// ListGenotype. Default: false.
+    pub fn with_ordinal_mutation(mut self, ordinal_mutation: bool) -> Self {
+        self.ordinal_mutation = Some(ordinal_mutation);
+        self
+    }
+
+    /// Mutate to a neighbouring entry in the per-gene allele lists, instead of a random one. Used
+    /// by MultiListGenotype. Length must match genes_size.
+    pub fn with_ordinal_mutations(mut self, ordinal_mutations: Vec<bool>) -> Self {
+        self.ordinal_mutations = Some(ordinal_mutations);
+        self
+    }
+
+    /// Set constraints between genes (conditional activation, forbidden value pairs, linear
+    /// constraints). Used by MultiList and MultiRange genotypes (Linear only by MultiRange).
+    /// See [GeneConstraint] for options.
+    pub fn with_gene_constraints(
+        mut self,
+        gene_constraints: Vec<GeneConstraint<G::Allele>>,
+    ) -> Self {
+        self.gene_constraints = gene_constraints;
+        self
+    }
+
+    /// Set the function set as (node, arity) pairs. Used by TreeGenotype.
+    /// Example: `vec![(Node::Add, 2), (Node::Neg, 1)]`.
+    pub fn with_function_set(mut self, function_set: Vec<(G::Allele, usize)>) -> Self {
+        self.function_set = Some(function_set);
+        self
+    }
+
+    /// Set the terminal set (nodes with arity 0). Used by TreeGenotype.
+    pub fn with_terminal_set(mut self, terminal_set: Vec<G::Allele>) -> Self {
+        self.terminal_set = Some(terminal_set);
+        self
+    }
+
+    /// Set the maximum tree depth (a single terminal has depth 0). Used by TreeGenotype.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Set the tree depths to ramp over on random initialization. Used by TreeGenotype.
+    pub fn with_init_depth_range(mut self, init_depth_range: RangeInclusive<usize>) -> Self {
+        self.init_depth_range = Some(init_depth_range);
+        self
+    }
+
+    /// Set the BNF grammar mapping the codons to the phenotype. Used by GrammaticalGenotype.
+    pub fn with_grammar(mut self, grammar: Grammar) -> Self {
+        self.grammar = Some(grammar);
+        self
+    }
+
     #[deprecated(since = "0.23.0", note = "use `with_mutation_type` instead")]
     pub fn with_allele_mutation_range(
         mut self,
@@ -187,12 +292,25 @@ impl<G: Genotype> Default for Builder<G> {
     fn default() -> Self {
         Self {
             genes_size: None,
+            genes_size_range: None,
             allele_list: None,
             allele_lists: None,
             allele_range: None,
             allele_ranges: None,
             mutation_type: None,
             mutation_types: None,
+            allele_scale: None,
+            allele_scales: None,
+            allele_list_weights: None,
+            allele_lists_weights: None,
+            ordinal_mutation: None,
+            ordinal_mutations: None,
+            gene_constraints: vec![],
+            function_set: None,
+            terminal_set: None,
+            max_depth: None,
+            init_depth_range: None,
+            grammar: None,
             seed_genes_list: vec![],
             genes_hashing: true,
             chromosome_recycling: true,
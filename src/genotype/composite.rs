@@ -0,0 +1,607 @@
+use super::builder::{Builder, TryFromBuilderError};
+use super::{
+    Allele, BinaryGenotype, BitGenotype, EstimateGenotype, EvolveGenotype, Genotype,
+    GrammaticalGenotype, GridCells, GridGenotype, HillClimbGenotype, ListGenotype,
+    MultiListGenotype, MultiRangeGenotype, MultiUniqueGenotype, PartialPermutationGenotype,
+    ParticleSwarmGenotype, PermutateGenotype, RangeAllele, RangeGenotype, SubsetGenotype,
+    SupportsGeneCrossover, SupportsPointCrossover, TreeGenotype, UniqueGenotype,
+    VariableListGenotype,
+};
+use crate::chromosome::{Chromosome, Genes};
+use crate::population::Population;
+use num::BigUint;
+use rand::distributions::Uniform;
+use rand::prelude::*;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// A gene of a [Composite] genotype, tagged with the segment it belongs to. The genes of the
+/// first segment always precede the genes of the second segment.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CompositeAllele<A, B> {
+    First(A),
+    Second(B),
+}
+
+impl<A: Copy, B: Copy> CompositeAllele<A, B> {
+    pub fn first(&self) -> Option<A> {
+        match self {
+            Self::First(allele) => Some(*allele),
+            Self::Second(_) => None,
+        }
+    }
+    pub fn second(&self) -> Option<B> {
+        match self {
+            Self::First(_) => None,
+            Self::Second(allele) => Some(*allele),
+        }
+    }
+}
+
+impl<A: Allele, B: Allele> Allele for CompositeAllele<A, B> {
+    fn hash_slice(slice: &[Self], hasher: &mut impl Hasher) {
+        slice.iter().for_each(|allele| match allele {
+            Self::First(allele) => {
+                0u8.hash(hasher);
+                A::hash_slice(std::slice::from_ref(allele), hasher);
+            }
+            Self::Second(allele) => {
+                1u8.hash(hasher);
+                B::hash_slice(std::slice::from_ref(allele), hasher);
+            }
+        });
+    }
+}
+
+/// Genes are the concatenation of the genes of two sub-genotypes (segments), which can have
+/// different allele types. Nest composites in the second segment to combine more than two
+/// segments. Random initialization, mutation, crossover, neighbours and permutations are
+/// delegated to each segment genotype, so every segment keeps its own constraints (e.g.
+/// uniqueness of a [UniqueGenotype] segment).
+///
+/// Mutations are distributed over the segments proportional to their genes_size. Crossovers are
+/// distributed likewise, through the [CompositeSegment] trait: segments which support gene or point
+/// crossover delegate to it, other segments (e.g. [UniqueGenotype]) are swapped as a whole between
+/// the parents. The neighbours for [HillClimb](crate::strategy::hill_climb::HillClimb) are the
+/// neighbours of each segment with the other segment unchanged. The permutations for
+/// [Permutate](crate::strategy::permutate::Permutate) are the cartesian product of the
+/// permutations of the segments.
+///
+/// The composite is constructed from its segments with `CompositeGenotype::new(first, second)`,
+/// not with the genotype builder. The seed genes lists of the segments are used in random
+/// initialization per segment. Seed genes for the composite as a whole can be set with
+/// `with_seed_genes_list()`, see `join_genes()`. Genes hashing and chromosome recycling default to
+/// the setting of the segments (enabled only if enabled on both).
+///
+/// The fitness gets typed access to the segments with `split_genes()`.
+///
+/// # Example:
+/// ```
+/// use genetic_algorithm::genotype::{
+///     BinaryGenotype, CompositeGenotype, Genotype, RangeGenotype, UniqueGenotype,
+/// };
+///
+/// let genotype = CompositeGenotype::new(
+///     UniqueGenotype::builder()
+///         .with_allele_list((0..5).collect())
+///         .build()
+///         .unwrap(),
+///     CompositeGenotype::new(
+///         BinaryGenotype::builder().with_genes_size(5).build().unwrap(),
+///         RangeGenotype::builder()
+///             .with_genes_size(2)
+///             .with_allele_range(0.0..=1.0)
+///             .build()
+///             .unwrap(),
+///     ),
+/// );
+/// assert_eq!(genotype.genes_size(), 12);
+///
+/// let mut rng = rand::thread_rng();
+/// let genes = genotype.random_genes_factory(&mut rng);
+/// let (order, rest) = genotype.split_genes(&genes);
+/// let (mask, weights) = genotype.second.split_genes(&rest);
+/// assert_eq!((order.len(), mask.len(), weights.len()), (5, 5, 2));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Composite<A: Genotype, B: Genotype> {
+    pub first: A,
+    pub second: B,
+    pub seed_genes_list: Vec<Genes<CompositeAllele<A::Allele, B::Allele>>>,
+    pub genes_hashing: bool,
+    pub chromosome_recycling: bool,
+}
+
+impl<A: Genotype, B: Genotype> TryFrom<Builder<Self>> for Composite<A, B> {
+    type Error = TryFromBuilderError;
+
+    fn try_from(_builder: Builder<Self>) -> Result<Self, Self::Error> {
+        Err(TryFromBuilderError(
+            "CompositeGenotype is constructed from its segments with CompositeGenotype::new(first, second)",
+        ))
+    }
+}
+
+impl<A: Genotype, B: Genotype> Composite<A, B> {
+    pub fn new(first: A, second: B) -> Self {
+        Self {
+            genes_hashing: first.genes_hashing() && second.genes_hashing(),
+            chromosome_recycling: first.chromosome_recycling() && second.chromosome_recycling(),
+            first,
+            second,
+            seed_genes_list: vec![],
+        }
+    }
+    pub fn with_seed_genes_list(
+        mut self,
+        seed_genes_list: Vec<Genes<CompositeAllele<A::Allele, B::Allele>>>,
+    ) -> Self {
+        self.seed_genes_list = seed_genes_list;
+        self
+    }
+    pub fn with_genes_hashing(mut self, genes_hashing: bool) -> Self {
+        self.genes_hashing = genes_hashing;
+        self
+    }
+    pub fn with_chromosome_recycling(mut self, chromosome_recycling: bool) -> Self {
+        self.chromosome_recycling = chromosome_recycling;
+        self
+    }
+
+    /// Split the genes into the typed genes of the first and second segment
+    pub fn split_genes(
+        &self,
+        genes: &[CompositeAllele<A::Allele, B::Allele>],
+    ) -> (Genes<A::Allele>, Genes<B::Allele>) {
+        let split_index = Self::split_index(genes);
+        (
+            genes[..split_index]
+                .iter()
+                .filter_map(|allele| allele.first())
+                .collect(),
+            genes[split_index..]
+                .iter()
+                .filter_map(|allele| allele.second())
+                .collect(),
+        )
+    }
+    /// Join the typed genes of the first and second segment into composite genes
+    pub fn join_genes(
+        &self,
+        first: &[A::Allele],
+        second: &[B::Allele],
+    ) -> Genes<CompositeAllele<A::Allele, B::Allele>> {
+        first
+            .iter()
+            .map(|allele| CompositeAllele::First(*allele))
+            .chain(second.iter().map(|allele| CompositeAllele::Second(*allele)))
+            .collect()
+    }
+
+    fn split_index(genes: &[CompositeAllele<A::Allele, B::Allele>]) -> usize {
+        genes.partition_point(|allele| matches!(allele, CompositeAllele::First(_)))
+    }
+    fn split_chromosome(
+        &self,
+        chromosome: &Chromosome<CompositeAllele<A::Allele, B::Allele>>,
+    ) -> (Chromosome<A::Allele>, Chromosome<B::Allele>) {
+        let (first, second) = self.split_genes(&chromosome.genes);
+        (Chromosome::new(first), Chromosome::new(second))
+    }
+    /// overwrite the genes in place, keeping the allocation for chromosome recycling
+    fn set_genes(
+        &self,
+        chromosome: &mut Chromosome<CompositeAllele<A::Allele, B::Allele>>,
+        first: &[A::Allele],
+        second: &[B::Allele],
+    ) {
+        chromosome.genes.clear();
+        chromosome
+            .genes
+            .extend(first.iter().map(|allele| CompositeAllele::First(*allele)));
+        chromosome
+            .genes
+            .extend(second.iter().map(|allele| CompositeAllele::Second(*allele)));
+    }
+    /// distribute the count over the segments proportional to their genes_size
+    fn split_count<R: Rng>(&self, count: usize, rng: &mut R) -> (usize, usize) {
+        let first_genes_size = self.first.genes_size();
+        let genes_size = self.genes_size();
+        let first_count = (0..count)
+            .filter(|_| rng.gen_range(0..genes_size) < first_genes_size)
+            .count();
+        (first_count, count - first_count)
+    }
+}
+
+impl<A: Genotype, B: Genotype> Genotype for Composite<A, B> {
+    type Allele = CompositeAllele<A::Allele, B::Allele>;
+
+    fn genes_size(&self) -> usize {
+        self.first.genes_size() + self.second.genes_size()
+    }
+    fn sample_gene_index<R: Rng>(&self, rng: &mut R) -> usize {
+        rng.gen_range(0..self.genes_size())
+    }
+    fn sample_gene_indices<R: Rng>(
+        &self,
+        count: usize,
+        allow_duplicates: bool,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        if allow_duplicates {
+            rng.sample_iter(Uniform::from(0..self.genes_size()))
+                .take(count)
+                .collect()
+        } else {
+            rand::seq::index::sample(rng, self.genes_size(), count.min(self.genes_size()))
+                .into_vec()
+        }
+    }
+
+    fn mutate_chromosome_genes<R: Rng>(
+        &self,
+        number_of_mutations: usize,
+        allow_duplicates: bool,
+        chromosome: &mut Chromosome<Self::Allele>,
+        rng: &mut R,
+    ) {
+        let (first_count, second_count) = self.split_count(number_of_mutations, rng);
+        let (mut first, mut second) = self.split_chromosome(chromosome);
+        if first_count > 0 {
+            self.first
+                .mutate_chromosome_genes(first_count, allow_duplicates, &mut first, rng);
+        }
+        if second_count > 0 {
+            self.second
+                .mutate_chromosome_genes(second_count, allow_duplicates, &mut second, rng);
+        }
+        self.set_genes(chromosome, &first.genes, &second.genes);
+        chromosome.reset_metadata(self.genes_hashing);
+    }
+    fn set_seed_genes_list(&mut self, seed_genes_list: Vec<Genes<Self::Allele>>) {
+        self.seed_genes_list = seed_genes_list;
+    }
+    fn seed_genes_list(&self) -> &Vec<Genes<Self::Allele>> {
+        &self.seed_genes_list
+    }
+    fn set_genes_hashing(&mut self, genes_hashing: bool) {
+        self.genes_hashing = genes_hashing;
+    }
+    fn random_genes_factory<R: Rng>(&self, rng: &mut R) -> Genes<Self::Allele> {
+        if self.seed_genes_list.is_empty() {
+            let first = self.first.random_genes_factory(rng);
+            let second = self.second.random_genes_factory(rng);
+            self.join_genes(&first, &second)
+        } else {
+            self.seed_genes_list.choose(rng).unwrap().clone()
+        }
+    }
+    fn genes_capacity(&self) -> usize {
+        self.first.genes_capacity() + self.second.genes_capacity()
+    }
+    fn genes_hashing(&self) -> bool {
+        self.genes_hashing
+    }
+    fn chromosome_recycling(&self) -> bool {
+        self.chromosome_recycling
+    }
+    fn max_scale_index(&self) -> Option<usize> {
+        self.first
+            .max_scale_index()
+            .max(self.second.max_scale_index())
+    }
+    fn current_scale_index(&self) -> Option<usize> {
+        self.first
+            .current_scale_index()
+            .max(self.second.current_scale_index())
+    }
+    fn reset_scale_index(&mut self) {
+        self.first.reset_scale_index();
+        self.second.reset_scale_index();
+    }
+    fn increment_scale_index(&mut self) -> bool {
+        let first_incremented = self.first.increment_scale_index();
+        let second_incremented = self.second.increment_scale_index();
+        first_incremented || second_incremented
+    }
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+    }
+}
+
+/// A genotype usable as segment of a [Composite] genotype in crossovers. Segments which support
+/// gene or point crossover delegate to it. The default implementations swap the whole segment
+/// between the parents, which is always valid (e.g. for [UniqueGenotype]).
+pub trait CompositeSegment: Genotype {
+    fn crossover_segment_genes<R: Rng>(
+        &self,
+        _number_of_crossovers: usize,
+        _allow_duplicates: bool,
+        father: &mut Chromosome<Self::Allele>,
+        mother: &mut Chromosome<Self::Allele>,
+        _rng: &mut R,
+    ) {
+        std::mem::swap(&mut father.genes, &mut mother.genes);
+    }
+    fn crossover_segment_points<R: Rng>(
+        &self,
+        _number_of_crossovers: usize,
+        _allow_duplicates: bool,
+        father: &mut Chromosome<Self::Allele>,
+        mother: &mut Chromosome<Self::Allele>,
+        _rng: &mut R,
+    ) {
+        std::mem::swap(&mut father.genes, &mut mother.genes);
+    }
+}
+
+macro_rules! impl_composite_segment_crossover {
+    (genes) => {
+        fn crossover_segment_genes<R: Rng>(
+            &self,
+            number_of_crossovers: usize,
+            allow_duplicates: bool,
+            father: &mut Chromosome<Self::Allele>,
+            mother: &mut Chromosome<Self::Allele>,
+            rng: &mut R,
+        ) {
+            self.crossover_chromosome_genes(
+                number_of_crossovers,
+                allow_duplicates,
+                father,
+                mother,
+                rng,
+            );
+        }
+    };
+    (points) => {
+        fn crossover_segment_points<R: Rng>(
+            &self,
+            number_of_crossovers: usize,
+            allow_duplicates: bool,
+            father: &mut Chromosome<Self::Allele>,
+            mother: &mut Chromosome<Self::Allele>,
+            rng: &mut R,
+        ) {
+            self.crossover_chromosome_points(
+                number_of_crossovers,
+                allow_duplicates,
+                father,
+                mother,
+                rng,
+            );
+        }
+    };
+}
+
+impl CompositeSegment for BinaryGenotype {
+    impl_composite_segment_crossover!(genes);
+    impl_composite_segment_crossover!(points);
+}
+impl CompositeSegment for BitGenotype {
+    impl_composite_segment_crossover!(genes);
+    impl_composite_segment_crossover!(points);
+}
+impl<T: Allele + PartialEq + Hash> CompositeSegment for ListGenotype<T> {
+    impl_composite_segment_crossover!(genes);
+    impl_composite_segment_crossover!(points);
+}
+impl<T: Allele + PartialEq + Hash> CompositeSegment for MultiListGenotype<T> {
+    impl_composite_segment_crossover!(genes);
+    impl_composite_segment_crossover!(points);
+}
+impl<T: Allele + PartialEq + Hash> CompositeSegment for VariableListGenotype<T> {
+    impl_composite_segment_crossover!(genes);
+    impl_composite_segment_crossover!(points);
+}
+impl<T: RangeAllele> CompositeSegment for RangeGenotype<T>
+where
+    Uniform<T>: Send + Sync,
+{
+    impl_composite_segment_crossover!(genes);
+    impl_composite_segment_crossover!(points);
+}
+impl<T: RangeAllele> CompositeSegment for MultiRangeGenotype<T>
+where
+    Uniform<T>: Send + Sync,
+{
+    impl_composite_segment_crossover!(genes);
+    impl_composite_segment_crossover!(points);
+}
+impl<T: Allele + PartialEq + Hash> CompositeSegment for SubsetGenotype<T> {
+    impl_composite_segment_crossover!(genes);
+}
+impl<T: Allele + PartialEq + Hash> CompositeSegment for PartialPermutationGenotype<T> {}
+impl<T: Allele + Hash> CompositeSegment for UniqueGenotype<T> {}
+impl<T: Allele + Hash> CompositeSegment for MultiUniqueGenotype<T> {
+    impl_composite_segment_crossover!(points);
+}
+impl<T: Allele + PartialEq + Hash> CompositeSegment for TreeGenotype<T> {
+    impl_composite_segment_crossover!(points);
+}
+impl<T: RangeAllele> CompositeSegment for GrammaticalGenotype<T>
+where
+    Uniform<T>: Send + Sync,
+{
+    impl_composite_segment_crossover!(genes);
+    impl_composite_segment_crossover!(points);
+}
+impl<G: GridCells> CompositeSegment for GridGenotype<G> {
+    impl_composite_segment_crossover!(genes);
+    impl_composite_segment_crossover!(points);
+}
+impl<A: CompositeSegment, B: CompositeSegment> CompositeSegment for Composite<A, B> {
+    impl_composite_segment_crossover!(genes);
+    impl_composite_segment_crossover!(points);
+}
+
+impl<A: EvolveGenotype, B: EvolveGenotype> EvolveGenotype for Composite<A, B> {}
+impl<A: CompositeSegment, B: CompositeSegment> SupportsGeneCrossover for Composite<A, B> {
+    fn crossover_chromosome_genes<R: Rng>(
+        &self,
+        number_of_crossovers: usize,
+        allow_duplicates: bool,
+        father: &mut Chromosome<Self::Allele>,
+        mother: &mut Chromosome<Self::Allele>,
+        rng: &mut R,
+    ) {
+        let (first_count, second_count) = self.split_count(number_of_crossovers, rng);
+        let (mut father_first, mut father_second) = self.split_chromosome(father);
+        let (mut mother_first, mut mother_second) = self.split_chromosome(mother);
+        if first_count > 0 {
+            self.first.crossover_segment_genes(
+                first_count,
+                allow_duplicates,
+                &mut father_first,
+                &mut mother_first,
+                rng,
+            );
+        }
+        if second_count > 0 {
+            self.second.crossover_segment_genes(
+                second_count,
+                allow_duplicates,
+                &mut father_second,
+                &mut mother_second,
+                rng,
+            );
+        }
+        self.set_genes(father, &father_first.genes, &father_second.genes);
+        self.set_genes(mother, &mother_first.genes, &mother_second.genes);
+        mother.reset_metadata(self.genes_hashing);
+        father.reset_metadata(self.genes_hashing);
+    }
+}
+impl<A: CompositeSegment, B: CompositeSegment> SupportsPointCrossover for Composite<A, B> {
+    fn crossover_chromosome_points<R: Rng>(
+        &self,
+        number_of_crossovers: usize,
+        allow_duplicates: bool,
+        father: &mut Chromosome<Self::Allele>,
+        mother: &mut Chromosome<Self::Allele>,
+        rng: &mut R,
+    ) {
+        let (first_count, second_count) = self.split_count(number_of_crossovers, rng);
+        let (mut father_first, mut father_second) = self.split_chromosome(father);
+        let (mut mother_first, mut mother_second) = self.split_chromosome(mother);
+        if first_count > 0 {
+            self.first.crossover_segment_points(
+                first_count,
+                allow_duplicates,
+                &mut father_first,
+                &mut mother_first,
+                rng,
+            );
+        }
+        if second_count > 0 {
+            self.second.crossover_segment_points(
+                second_count,
+                allow_duplicates,
+                &mut father_second,
+                &mut mother_second,
+                rng,
+            );
+        }
+        self.set_genes(father, &father_first.genes, &father_second.genes);
+        self.set_genes(mother, &mother_first.genes, &mother_second.genes);
+        mother.reset_metadata(self.genes_hashing);
+        father.reset_metadata(self.genes_hashing);
+    }
+}
+
+impl<A: HillClimbGenotype, B: HillClimbGenotype> HillClimbGenotype for Composite<A, B> {
+    fn fill_neighbouring_population<R: Rng>(
+        &self,
+        chromosome: &Chromosome<Self::Allele>,
+        population: &mut Population<Self::Allele>,
+        rng: &mut R,
+    ) {
+        let (first, second) = self.split_chromosome(chromosome);
+
+        let mut first_population = Population::new_empty(false);
+        self.first
+            .fill_neighbouring_population(&first, &mut first_population, rng);
+        first_population.chromosomes.iter().for_each(|neighbour| {
+            let mut new_chromosome = population.new_chromosome(chromosome);
+            self.set_genes(&mut new_chromosome, &neighbour.genes, &second.genes);
+            new_chromosome.reset_metadata(self.genes_hashing);
+            population.chromosomes.push(new_chromosome);
+        });
+
+        let mut second_population = Population::new_empty(false);
+        self.second
+            .fill_neighbouring_population(&second, &mut second_population, rng);
+        second_population.chromosomes.iter().for_each(|neighbour| {
+            let mut new_chromosome = population.new_chromosome(chromosome);
+            self.set_genes(&mut new_chromosome, &first.genes, &neighbour.genes);
+            new_chromosome.reset_metadata(self.genes_hashing);
+            population.chromosomes.push(new_chromosome);
+        });
+    }
+
+    fn neighbouring_population_size(&self) -> BigUint {
+        self.first.neighbouring_population_size() + self.second.neighbouring_population_size()
+    }
+}
+
+impl<A: PermutateGenotype, B: PermutateGenotype> PermutateGenotype for Composite<A, B> {
+    fn chromosome_permutations_into_iter<'a>(
+        &'a self,
+        chromosome: Option<&Chromosome<Self::Allele>>,
+    ) -> Box<dyn Iterator<Item = Chromosome<Self::Allele>> + Send + 'a> {
+        if self.seed_genes_list.is_empty() {
+            let (first, second) = chromosome
+                .map(|chromosome| self.split_chromosome(chromosome))
+                .unzip();
+            Box::new(
+                self.first
+                    .chromosome_permutations_into_iter(first.as_ref())
+                    .flat_map(move |first| {
+                        self.second
+                            .chromosome_permutations_into_iter(second.as_ref())
+                            .map(move |second| {
+                                Chromosome::new(self.join_genes(&first.genes, &second.genes))
+                            })
+                    }),
+            )
+        } else {
+            Box::new(
+                self.seed_genes_list
+                    .clone()
+                    .into_iter()
+                    .map(Chromosome::new),
+            )
+        }
+    }
+    fn chromosome_permutations_size(&self) -> BigUint {
+        if self.seed_genes_list.is_empty() {
+            self.first.chromosome_permutations_size() * self.second.chromosome_permutations_size()
+        } else {
+            self.seed_genes_list.len().into()
+        }
+    }
+    fn allows_permutation(&self) -> bool {
+        self.first.allows_permutation() && self.second.allows_permutation()
+    }
+}
+
+impl<A: Genotype, B: Genotype> EstimateGenotype for Composite<A, B> {}
+
+impl<A: Genotype, B: Genotype> ParticleSwarmGenotype for Composite<A, B> {}
+
+impl<A: Genotype, B: Genotype> fmt::Display for Composite<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "genotype:")?;
+        writeln!(f, "  genes_size: {}", self.genes_size())?;
+        writeln!(f, "  first_genes_size: {}", self.first.genes_size())?;
+        writeln!(f, "  second_genes_size: {}", self.second.genes_size())?;
+        writeln!(
+            f,
+            "  expected_number_of_sampled_index_duplicates: {}",
+            self.expected_number_of_sampled_index_duplicates_report()
+        )?;
+        writeln!(f, "  seed_genes: {:?}", self.seed_genes_list.len())
+    }
+}
@@ -0,0 +1,412 @@
+use crate::allele::{Allele, RangeAllele};
+use crate::chromosome::Chromosome;
+use crate::population::Population;
+use rand::prelude::*;
+use std::ops::RangeInclusive;
+
+/// Maximum number of repair passes (and random genes retries) before giving up on feasibility
+pub(crate) const MAX_REPAIR_ATTEMPTS: usize = 100;
+/// Maximum number of projection sweeps over the linear constraints per repair pass
+const MAX_PROJECTION_ITERATIONS: usize = 50;
+
+/// The bound on the weighted sum of the genes in a [GeneConstraint::Linear]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LinearBound {
+    Equal(f64),
+    AtMost(f64),
+    AtLeast(f64),
+}
+
+/// A declarative constraint between genes, set with `with_gene_constraints()` on the builder of
+/// the [MultiListGenotype](crate::genotype::MultiListGenotype) and
+/// [MultiRangeGenotype](crate::genotype::MultiRangeGenotype).
+///
+/// * `ActiveIf`: the gene is only meaningful when the condition gene has one of the
+///   condition_values. Otherwise the gene is inactive and fixed to the inactive_value, so
+///   irrelevant values don't spread out over the population as spurious diversity. Multiple
+///   ActiveIf constraints on the same gene must all hold for the gene to be active.
+/// * `ForbiddenPair`: the two genes can't have these two values at the same time
+/// * `Linear`: the weighted sum of the genes (`Σ coefficient * gene`) must satisfy the
+///   [LinearBound] within the tolerance (e.g. weights summing to 1.0). Only for numeric genes
+///   (MultiRangeGenotype)
+///
+/// The constraints are enforced on random initialization, mutation, crossover, HillClimb
+/// neighbours and Permutate enumeration, so that infeasible chromosomes are never generated:
+/// * Random genes and mutated/crossed-over genes are repaired: inactive genes are set to their
+///   inactive_value, one of the genes of a forbidden pair is resampled and the active genes of a
+///   linear constraint are projected on the bound (clamped to their allele_range, Discrete genes
+///   are resampled instead). If no feasible genes are reached, the mutation or crossover is
+///   rejected and the original genes are kept. Random initialization retries with new random genes
+///   and falls back to the feasible genes found by the builder, which returns an error for
+///   (practically) infeasible gene_constraints.
+/// * HillClimb neighbours are repaired for inactivation and otherwise dropped when infeasible.
+///   The reported neighbouring_population_size is therefore an upper bound.
+/// * Permutate only yields the feasible permutations (inactive genes must have their
+///   inactive_value, so take care this value is in the permutable values). The reported
+///   chromosome_permutations_size is therefore an upper bound.
+///
+/// ** Note: ** Seed genes are taken as given and are not repaired. The Estimate and
+/// ParticleSwarm strategies can't enforce the constraints, so their builders reject genotypes with
+/// gene_constraints. The other genotypes don't support gene_constraints and their builders return
+/// an error when they are set.
+///
+/// # Example:
+/// ```
+/// use genetic_algorithm::genotype::{GeneConstraint, Genotype, MultiRangeGenotype, MutationType};
+///
+/// let genotype = MultiRangeGenotype::<f64>::builder()
+///     .with_allele_ranges(vec![
+///         0.0..=1.0, // use regularization (boolean)
+///         0.0..=1.0, // regularization strength, only used with regularization
+///         0.0..=1.0, // weight a
+///         0.0..=1.0, // weight b
+///         0.0..=1.0, // weight c
+///     ])
+///     .with_mutation_types(vec![
+///         MutationType::Discrete,
+///         MutationType::Range(0.1),
+///         MutationType::Range(0.1),
+///         MutationType::Range(0.1),
+///         MutationType::Range(0.1),
+///     ])
+///     .with_gene_constraints(vec![
+///         GeneConstraint::ActiveIf {
+///             gene_index: 1,
+///             condition_gene_index: 0,
+///             condition_values: vec![1.0],
+///             inactive_value: 0.0,
+///         },
+///         GeneConstraint::ForbiddenPair {
+///             gene_indices: (0, 2),
+///             values: (1.0, 0.0),
+///         },
+///         GeneConstraint::sum_to(vec![2, 3, 4], 1.0),
+///     ])
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub enum GeneConstraint<T: Allele> {
+    ActiveIf {
+        gene_index: usize,
+        condition_gene_index: usize,
+        condition_values: Vec<T>,
+        inactive_value: T,
+    },
+    ForbiddenPair {
+        gene_indices: (usize, usize),
+        values: (T, T),
+    },
+    Linear {
+        coefficients: Vec<(usize, f64)>,
+        bound: LinearBound,
+        tolerance: f64,
+    },
+}
+
+impl<T: Allele> GeneConstraint<T> {
+    /// The default tolerance of the linear constraints, loose enough for f32 genes
+    pub const DEFAULT_TOLERANCE: f64 = 1e-5;
+
+    /// A linear constraint with the default tolerance
+    pub fn linear(coefficients: Vec<(usize, f64)>, bound: LinearBound) -> Self {
+        Self::Linear {
+            coefficients,
+            bound,
+            tolerance: Self::DEFAULT_TOLERANCE,
+        }
+    }
+    /// A linear constraint for the plain sum of the genes with the default tolerance
+    pub fn sum_to(gene_indices: Vec<usize>, value: f64) -> Self {
+        Self::linear(
+            gene_indices.into_iter().map(|index| (index, 1.0)).collect(),
+            LinearBound::Equal(value),
+        )
+    }
+    pub fn is_linear(&self) -> bool {
+        matches!(self, Self::Linear { .. })
+    }
+
+    /// distinct gene indices within the genes_size, finite linear values
+    pub(crate) fn is_valid_for(&self, genes_size: usize) -> bool {
+        match self {
+            Self::ActiveIf {
+                gene_index,
+                condition_gene_index,
+                ..
+            } => {
+                *gene_index < genes_size
+                    && *condition_gene_index < genes_size
+                    && gene_index != condition_gene_index
+            }
+            Self::ForbiddenPair {
+                gene_indices: (first, second),
+                ..
+            } => *first < genes_size && *second < genes_size && first != second,
+            Self::Linear {
+                coefficients,
+                bound,
+                tolerance,
+            } => {
+                let bound_value = match bound {
+                    LinearBound::Equal(value)
+                    | LinearBound::AtMost(value)
+                    | LinearBound::AtLeast(value) => *value,
+                };
+                !coefficients.is_empty()
+                    && coefficients
+                        .iter()
+                        .all(|(index, coefficient)| *index < genes_size && coefficient.is_finite())
+                    && bound_value.is_finite()
+                    && tolerance.is_finite()
+                    && *tolerance >= 0.0
+            }
+        }
+    }
+}
+
+/// Sets the inactive genes to their inactive_value. Repeated for chained activations, where the
+/// condition gene is itself deactivated
+pub(crate) fn deactivate_genes<T: Allele + PartialEq>(
+    constraints: &[GeneConstraint<T>],
+    genes: &mut [T],
+) {
+    for _ in 0..=constraints.len() {
+        let mut changed = false;
+        constraints.iter().for_each(|constraint| {
+            if let GeneConstraint::ActiveIf {
+                gene_index,
+                condition_gene_index,
+                condition_values,
+                inactive_value,
+            } = constraint
+            {
+                if !condition_values.contains(&genes[*condition_gene_index])
+                    && genes[*gene_index] != *inactive_value
+                {
+                    genes[*gene_index] = *inactive_value;
+                    changed = true;
+                }
+            }
+        });
+        if !changed {
+            return;
+        }
+    }
+}
+
+/// The genes which are inactive, and thus fixed to their inactive_value
+pub(crate) fn inactive_gene_mask<T: Allele + PartialEq>(
+    constraints: &[GeneConstraint<T>],
+    genes: &[T],
+) -> Vec<bool> {
+    let mut mask = vec![false; genes.len()];
+    constraints.iter().for_each(|constraint| {
+        if let GeneConstraint::ActiveIf {
+            gene_index,
+            condition_gene_index,
+            condition_values,
+            ..
+        } = constraint
+        {
+            if !condition_values.contains(&genes[*condition_gene_index]) {
+                mask[*gene_index] = true;
+            }
+        }
+    });
+    mask
+}
+
+fn violated_forbidden_pair<T: Allele + PartialEq>(
+    constraints: &[GeneConstraint<T>],
+    genes: &[T],
+) -> Option<(usize, usize)> {
+    constraints.iter().find_map(|constraint| match constraint {
+        GeneConstraint::ForbiddenPair {
+            gene_indices: (first, second),
+            values: (first_value, second_value),
+        } if genes[*first] == *first_value && genes[*second] == *second_value => {
+            Some((*first, *second))
+        }
+        _ => None,
+    })
+}
+
+/// Checks the ActiveIf and ForbiddenPair constraints, the Linear constraints are ignored
+pub(crate) fn is_feasible<T: Allele + PartialEq>(
+    constraints: &[GeneConstraint<T>],
+    genes: &[T],
+) -> bool {
+    constraints.iter().all(|constraint| match constraint {
+        GeneConstraint::ActiveIf {
+            gene_index,
+            condition_gene_index,
+            condition_values,
+            inactive_value,
+        } => {
+            condition_values.contains(&genes[*condition_gene_index])
+                || genes[*gene_index] == *inactive_value
+        }
+        GeneConstraint::ForbiddenPair {
+            gene_indices: (first, second),
+            values: (first_value, second_value),
+        } => genes[*first] != *first_value || genes[*second] != *second_value,
+        GeneConstraint::Linear { .. } => true,
+    })
+}
+
+/// The required change of the weighted sum to satisfy the bound, zero if within tolerance
+fn linear_delta<T: RangeAllele>(
+    coefficients: &[(usize, f64)],
+    bound: &LinearBound,
+    tolerance: f64,
+    genes: &[T],
+) -> f64 {
+    let sum: f64 = coefficients
+        .iter()
+        .map(|(index, coefficient)| coefficient * genes[*index].to_f64())
+        .sum();
+    let delta = match bound {
+        LinearBound::Equal(value) => value - sum,
+        LinearBound::AtMost(value) => (value - sum).min(0.0),
+        LinearBound::AtLeast(value) => (value - sum).max(0.0),
+    };
+    if delta.abs() <= tolerance {
+        0.0
+    } else {
+        delta
+    }
+}
+
+/// Checks the Linear constraints only
+pub(crate) fn is_linear_feasible<T: RangeAllele>(
+    constraints: &[GeneConstraint<T>],
+    genes: &[T],
+) -> bool {
+    constraints.iter().all(|constraint| match constraint {
+        GeneConstraint::Linear {
+            coefficients,
+            bound,
+            tolerance,
+        } => linear_delta(coefficients, bound, *tolerance, genes) == 0.0,
+        _ => true,
+    })
+}
+
+/// Alternating projection of the free genes on the violated linear constraints, clamped to the
+/// allele_ranges. Genes at the bound in the direction of the required change don't take part.
+pub(crate) fn project_linear<T: RangeAllele>(
+    constraints: &[GeneConstraint<T>],
+    genes: &mut [T],
+    allele_ranges: &[RangeInclusive<T>],
+    is_free: impl Fn(usize) -> bool,
+) {
+    for _ in 0..MAX_PROJECTION_ITERATIONS {
+        let mut satisfied = true;
+        constraints.iter().for_each(|constraint| {
+            if let GeneConstraint::Linear {
+                coefficients,
+                bound,
+                tolerance,
+            } = constraint
+            {
+                let delta = linear_delta(coefficients, bound, *tolerance, genes);
+                if delta == 0.0 {
+                    return;
+                }
+                satisfied = false;
+                let movable: Vec<(usize, f64)> = coefficients
+                    .iter()
+                    .copied()
+                    .filter(|(index, coefficient)| {
+                        let allele_range = &allele_ranges[*index];
+                        let direction = delta * coefficient;
+                        is_free(*index)
+                            && direction != 0.0
+                            && (direction < 0.0 || genes[*index] < *allele_range.end())
+                            && (direction > 0.0 || genes[*index] > *allele_range.start())
+                    })
+                    .collect();
+                let norm: f64 = movable
+                    .iter()
+                    .map(|(_, coefficient)| coefficient * coefficient)
+                    .sum();
+                if norm == 0.0 {
+                    return;
+                }
+                movable.iter().for_each(|(index, coefficient)| {
+                    let allele_range = &allele_ranges[*index];
+                    let value = (genes[*index].to_f64() + delta * coefficient / norm)
+                        .clamp(allele_range.start().to_f64(), allele_range.end().to_f64());
+                    genes[*index] = T::from_f64(value);
+                });
+            }
+        });
+        if satisfied {
+            return;
+        }
+    }
+}
+
+/// Repair the genes towards feasibility: deactivate the inactive genes, resample one of the genes
+/// of a violated forbidden pair and project on the linear constraints. If the projection does not
+/// reach feasibility (e.g. due to rounding or fixed genes), a random gene of a linear constraint is
+/// resampled and the repair is retried. Returns false if no feasible genes are reached within
+/// MAX_REPAIR_ATTEMPTS.
+pub(crate) fn repair_genes<T: Allele + PartialEq, R: Rng>(
+    constraints: &[GeneConstraint<T>],
+    genes: &mut [T],
+    rng: &mut R,
+    mut resample_gene: impl FnMut(usize, &mut R) -> T,
+    mut project_linear: impl FnMut(&mut [T]),
+    is_linear_feasible: impl Fn(&[T]) -> bool,
+) -> bool {
+    let linear_gene_indices: Vec<usize> = constraints
+        .iter()
+        .filter_map(|constraint| match constraint {
+            GeneConstraint::Linear { coefficients, .. } => Some(coefficients),
+            _ => None,
+        })
+        .flat_map(|coefficients| coefficients.iter().map(|(index, _)| *index))
+        .collect();
+
+    for _ in 0..MAX_REPAIR_ATTEMPTS {
+        deactivate_genes(constraints, genes);
+        if let Some((first, second)) = violated_forbidden_pair(constraints, genes) {
+            let index = if rng.gen() { first } else { second };
+            genes[index] = resample_gene(index, rng);
+            continue;
+        }
+        project_linear(genes);
+        deactivate_genes(constraints, genes);
+        if is_feasible(constraints, genes) && is_linear_feasible(genes) {
+            return true;
+        }
+        if let Some(index) = linear_gene_indices.choose(rng) {
+            genes[*index] = resample_gene(*index, rng);
+        }
+    }
+    false
+}
+
+/// Deactivate the inactive genes of the neighbours added from start_index onwards and drop the
+/// neighbours which are infeasible or became equal to the source chromosome
+pub(crate) fn retain_feasible_neighbours<T: Allele + PartialEq>(
+    constraints: &[GeneConstraint<T>],
+    chromosome: &Chromosome<T>,
+    population: &mut Population<T>,
+    start_index: usize,
+    genes_hashing: bool,
+    is_feasible: impl Fn(&[T]) -> bool,
+) {
+    let neighbours = population.chromosomes.split_off(start_index);
+    for mut neighbour in neighbours {
+        deactivate_genes(constraints, &mut neighbour.genes);
+        if neighbour.genes != chromosome.genes && is_feasible(&neighbour.genes) {
+            neighbour.reset_metadata(genes_hashing);
+            population.chromosomes.push(neighbour);
+        } else {
+            population.drop_chromosome(neighbour);
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use crate::allele::RangeAllele;
+pub use crate::errors::TryFromGrammarError;
+use std::collections::HashMap;
+use std::fmt;
+
+pub const DEFAULT_MAX_WRAPS: usize = 2;
+pub const DEFAULT_MAX_DEPTH: usize = 64;
+
+/// A symbol in a production of a [GrammarRule]. Non-terminals refer to the index of their rule in
+/// the [Grammar].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GrammarSymbol {
+    Terminal(String),
+    NonTerminal(usize),
+}
+
+/// A rule of a [Grammar], the non-terminal name (without the angle brackets) and its alternative
+/// productions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GrammarRule {
+    pub name: String,
+    pub productions: Vec<Vec<GrammarSymbol>>,
+}
+
+/// A context-free grammar in Backus-Naur form, used to map integer codons to a derivation (the
+/// phenotype) in grammatical evolution. See
+/// [GrammaticalGenotype](crate::genotype::GrammaticalGenotype).
+///
+/// Parse with `Grammar::from_bnf()`. Each rule has the form `<name> ::= production | production`,
+/// a rule may continue on the next lines (starting with `|`). The first rule is the start symbol.
+/// Within a production:
+/// * `<name>` is a non-terminal, which must be defined by a rule
+/// * `"text"` or `'text'` is a quoted terminal, kept as is (including whitespace, `""` for an
+///   empty production)
+/// * other whitespace separated text is an unquoted terminal
+///
+/// Lines starting with `#` are comments. The phenotype string is the concatenation of the
+/// terminals, without separators (so quote terminals which need whitespace).
+///
+/// The mapping is the standard depth-first, leftmost derivation: each non-terminal with multiple
+/// productions consumes the next codon and chooses production `codon % number_of_productions`.
+/// Non-terminals with a single production don't consume a codon. When the codons run out, the
+/// reading wraps around to the first codon, up to `max_wraps` times (defaults to 2). An incomplete
+/// derivation after the wraps, or a derivation deeper than `max_depth` (defaults to 64) is an
+/// invalid mapping, reported as [GrammarMappingError].
+///
+/// # Example:
+/// ```
+/// use genetic_algorithm::genotype::Grammar;
+///
+/// let grammar = Grammar::from_bnf(
+///     r#"
+///     <expr> ::= <expr> <op> <expr> | "(" <expr> ")" | <var>
+///     <op>   ::= + | - | *
+///     <var>  ::= x | 1.0
+///     "#,
+/// )
+/// .unwrap()
+/// .with_max_wraps(1); // optional, defaults to 2
+///
+/// // <expr> -> <var> (2 % 3), <var> -> x (0 % 2)
+/// let derivation = grammar.map(&[2u8, 0]).unwrap();
+/// assert_eq!(derivation.phenotype(), "x");
+///
+/// // <expr> <op> <expr> (0), <var> (2), x (0), + (3 % 3), <var> (5 % 3), 1.0 (1)
+/// let derivation = grammar.map(&[0u8, 2, 0, 3, 5, 1]).unwrap();
+/// assert_eq!(derivation.phenotype(), "x+1.0");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Grammar {
+    pub rules: Vec<GrammarRule>,
+    pub max_wraps: usize,
+    pub max_depth: usize,
+}
+
+impl Grammar {
+    pub fn from_bnf(bnf: &str) -> Result<Self, TryFromGrammarError> {
+        // (name, productions text) per rule, joining the continuation lines
+        let mut definitions: Vec<(String, String)> = vec![];
+        for line in bnf.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some((name, productions)) = line.split_once("::=") {
+                let name = Self::parse_non_terminal_name(name.trim()).ok_or(
+                    TryFromGrammarError("Grammar rule names must have the form <name>"),
+                )?;
+                definitions.push((name, productions.to_string()));
+            } else if line.starts_with('|') {
+                let (_, productions) = definitions.last_mut().ok_or(TryFromGrammarError(
+                    "Grammar continuation line (starting with |) without a preceding rule",
+                ))?;
+                productions.push(' ');
+                productions.push_str(line);
+            } else {
+                return Err(TryFromGrammarError(
+                    "Grammar lines must have the form <name> ::= productions, or continue the previous rule with |",
+                ));
+            }
+        }
+        if definitions.is_empty() {
+            return Err(TryFromGrammarError("Grammar requires at least one rule"));
+        }
+
+        let mut rule_indices: HashMap<String, usize> = HashMap::new();
+        for (index, (name, _)) in definitions.iter().enumerate() {
+            if rule_indices.insert(name.clone(), index).is_some() {
+                return Err(TryFromGrammarError(
+                    "Grammar rules must be defined only once (use | for alternatives)",
+                ));
+            }
+        }
+        let rules = definitions
+            .into_iter()
+            .map(|(name, productions)| {
+                Ok(GrammarRule {
+                    name,
+                    productions: Self::parse_productions(&productions, &rule_indices)?,
+                })
+            })
+            .collect::<Result<Vec<_>, TryFromGrammarError>>()?;
+
+        Ok(Self {
+            rules,
+            max_wraps: DEFAULT_MAX_WRAPS,
+            max_depth: DEFAULT_MAX_DEPTH,
+        })
+    }
+    pub fn with_max_wraps(mut self, max_wraps: usize) -> Self {
+        self.max_wraps = max_wraps;
+        self
+    }
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    fn parse_non_terminal_name(text: &str) -> Option<String> {
+        text.strip_prefix('<')
+            .and_then(|text| text.strip_suffix('>'))
+            .filter(|name| !name.is_empty() && !name.contains(['<', '>']))
+            .map(str::to_string)
+    }
+    fn parse_productions(
+        text: &str,
+        rule_indices: &HashMap<String, usize>,
+    ) -> Result<Vec<Vec<GrammarSymbol>>, TryFromGrammarError> {
+        let mut productions = vec![];
+        let mut production = vec![];
+        let mut chars = text.chars().peekable();
+        while let Some(char) = chars.next() {
+            match char {
+                '|' => {
+                    productions.push(std::mem::take(&mut production));
+                }
+                '"' | '\'' => {
+                    let terminal: String = chars.by_ref().take_while(|c| *c != char).collect();
+                    production.push(GrammarSymbol::Terminal(terminal));
+                }
+                '<' => {
+                    let name: String = chars.by_ref().take_while(|c| *c != '>').collect();
+                    let rule_index = rule_indices.get(&name).ok_or(TryFromGrammarError(
+                        "Grammar refers to an undefined non-terminal",
+                    ))?;
+                    production.push(GrammarSymbol::NonTerminal(*rule_index));
+                }
+                _ if char.is_whitespace() => {}
+                _ => {
+                    let mut terminal = char.to_string();
+                    while let Some(next) = chars
+                        .next_if(|c| !c.is_whitespace() && !matches!(c, '|' | '<' | '"' | '\''))
+                    {
+                        terminal.push(next);
+                    }
+                    production.push(GrammarSymbol::Terminal(terminal));
+                }
+            }
+        }
+        productions.push(production);
+        if productions.iter().any(|production| production.is_empty()) {
+            return Err(TryFromGrammarError(
+                "Grammar productions must not be empty (use \"\" for an empty production)",
+            ));
+        }
+        Ok(productions)
+    }
+
+    /// Map the codons to a derivation of the start symbol (the first rule)
+    pub fn map<T: RangeAllele>(
+        &self,
+        codons: &[T],
+    ) -> Result<GrammarDerivation, GrammarMappingError> {
+        if codons.is_empty() {
+            return Err(GrammarMappingError::NoCodons);
+        }
+        let mut codon_index = 0;
+        let root = self.expand(0, 0, codons, &mut codon_index)?;
+        Ok(GrammarDerivation {
+            root,
+            codons_used: codon_index,
+            wraps: codon_index.saturating_sub(1) / codons.len(),
+        })
+    }
+    fn expand<T: RangeAllele>(
+        &self,
+        rule_index: usize,
+        depth: usize,
+        codons: &[T],
+        codon_index: &mut usize,
+    ) -> Result<GrammarDerivationNode, GrammarMappingError> {
+        if depth > self.max_depth {
+            return Err(GrammarMappingError::MaxDepthExceeded);
+        }
+        let productions = &self.rules[rule_index].productions;
+        let production_index = if productions.len() > 1 {
+            if *codon_index >= codons.len() * (self.max_wraps + 1) {
+                return Err(GrammarMappingError::MaxWrapsExceeded);
+            }
+            let codon = codons[*codon_index % codons.len()].to_f64().max(0.0) as usize;
+            *codon_index += 1;
+            codon % productions.len()
+        } else {
+            0
+        };
+        let children = productions[production_index]
+            .iter()
+            .map(|symbol| match symbol {
+                GrammarSymbol::Terminal(terminal) => {
+                    Ok(GrammarDerivationNode::Terminal(terminal.clone()))
+                }
+                GrammarSymbol::NonTerminal(child_rule_index) => {
+                    self.expand(*child_rule_index, depth + 1, codons, codon_index)
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(GrammarDerivationNode::NonTerminal {
+            rule_index,
+            production_index,
+            children,
+        })
+    }
+}
+
+/// The reason a mapping of codons to a derivation is invalid. The fitness typically returns
+/// `None` for invalid mappings, so they are taken last in selection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GrammarMappingError {
+    NoCodons,
+    MaxWrapsExceeded,
+    MaxDepthExceeded,
+}
+
+impl fmt::Display for GrammarMappingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoCodons => write!(f, "no codons to map"),
+            Self::MaxWrapsExceeded => write!(f, "derivation incomplete after max_wraps"),
+            Self::MaxDepthExceeded => write!(f, "derivation deeper than max_depth"),
+        }
+    }
+}
+
+/// A node of the derivation tree (the AST), a terminal or an expanded non-terminal with the chosen
+/// production of its rule.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GrammarDerivationNode {
+    Terminal(String),
+    NonTerminal {
+        rule_index: usize,
+        production_index: usize,
+        children: Vec<GrammarDerivationNode>,
+    },
+}
+
+impl GrammarDerivationNode {
+    fn push_terminals(&self, phenotype: &mut String) {
+        match self {
+            Self::Terminal(terminal) => phenotype.push_str(terminal),
+            Self::NonTerminal { children, .. } => children
+                .iter()
+                .for_each(|child| child.push_terminals(phenotype)),
+        }
+    }
+}
+
+/// The result of a valid mapping, the derivation tree and the number of codons used (including
+/// the wrapped reads).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GrammarDerivation {
+    pub root: GrammarDerivationNode,
+    pub codons_used: usize,
+    pub wraps: usize,
+}
+
+impl GrammarDerivation {
+    /// The concatenated terminals
+    pub fn phenotype(&self) -> String {
+        let mut phenotype = String::new();
+        self.root.push_terminals(&mut phenotype);
+        phenotype
+    }
+}
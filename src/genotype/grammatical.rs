@@ -0,0 +1,343 @@
+use super::builder::{Builder, TryFromBuilderError};
+use super::grammar::{Grammar, GrammarDerivation, GrammarMappingError};
+use super::{
+    EstimateGenotype, EvolveGenotype, Genotype, HillClimbGenotype, ParticleSwarmGenotype,
+    PermutateGenotype, RangeAllele, RangeGenotype, SupportsGeneCrossover, SupportsPointCrossover,
+};
+use crate::chromosome::{Chromosome, Genes};
+use crate::population::Population;
+use num::BigUint;
+use rand::distributions::Uniform;
+use rand::prelude::*;
+use std::fmt;
+
+/// Grammatical evolution: the genes are integer codons, which are mapped to a phenotype by a BNF
+/// [Grammar] (see there for the grammar syntax and the mapping). The codons are managed by a
+/// [RangeGenotype] (typically `u8` or `u16` over the full allele range), which handles the random
+/// initialization, mutation, crossover, neighbours and permutations. So all the Evolve plugins work
+/// unchanged on the codon vector, only the fitness maps the codons to the phenotype with `map()`
+/// (the derivation tree and the used codons) or `phenotype()` (the concatenated terminals).
+///
+/// Invalid mappings (incomplete after the wraps, or too deep) are explicit: `map()` returns a
+/// [GrammarMappingError] and `phenotype()` returns `None`, which the fitness can pass on to mark the
+/// chromosome as invalid (taken last in selection).
+///
+/// The fitness receives the genotype next to the chromosome, so it derives the phenotype from the
+/// codons with `genotype.phenotype(&chromosome.genes)` (see example below).
+///
+/// The genotype builder takes the grammar with `with_grammar()`, and passes genes_size (the
+/// number of codons), allele_range, mutation_type, allele_scale and the shared options on to the
+/// codons genotype. Alternatively construct it from a prebuilt codons genotype with
+/// `GrammaticalGenotype::new(grammar, codons)`.
+///
+/// # Example:
+/// ```
+/// use genetic_algorithm::genotype::{Genotype, Grammar, GrammaticalGenotype};
+///
+/// let grammar = Grammar::from_bnf(
+///     r#"
+///     <expr> ::= <expr> <op> <expr> | <var>
+///     <op>   ::= + | *
+///     <var>  ::= x | y
+///     "#,
+/// )
+/// .unwrap();
+///
+/// let genotype = GrammaticalGenotype::builder()
+///     .with_grammar(grammar)
+///     .with_genes_size(20)
+///     .with_allele_range(0..=u8::MAX)
+///     .build()
+///     .unwrap();
+///
+/// // <expr> <op> <expr>, <var>, y, *, <var>, x
+/// assert_eq!(genotype.phenotype(&[0, 1, 1, 1, 1, 0]), Some("y*x".to_string()));
+///
+/// let mut rng = rand::thread_rng();
+/// let codons = genotype.random_genes_factory(&mut rng);
+/// if let Some(phenotype) = genotype.phenotype(&codons) {
+///     assert!(phenotype.ends_with(['x', 'y']));
+/// }
+/// ```
+///
+/// # Example fitness:
+/// ```
+/// use genetic_algorithm::fitness::prelude::*;
+///
+/// // evaluate the phenotype, an expression over x and y, in (x, y) = (2, 3) and aim for 10
+/// #[derive(Clone, Debug)]
+/// struct ExpressionTarget;
+/// impl Fitness for ExpressionTarget {
+///     type Genotype = GrammaticalGenotype<u8>;
+///     fn calculate_for_chromosome(
+///         &mut self,
+///         chromosome: &FitnessChromosome<Self>,
+///         genotype: &FitnessGenotype<Self>,
+///     ) -> Option<FitnessValue> {
+///         // invalid mappings have no fitness
+///         let phenotype = genotype.phenotype(&chromosome.genes)?;
+///         // + binds weaker than *, so evaluate the sum of products
+///         let value: isize = phenotype
+///             .split('+')
+///             .map(|product| {
+///                 product
+///                     .split('*')
+///                     .map(|var| if var == "x" { 2 } else { 3 })
+///                     .product::<isize>()
+///             })
+///             .sum();
+///         Some(-(value - 10).abs())
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Grammatical<T: RangeAllele = u8>
+where
+    Uniform<T>: Send + Sync,
+{
+    pub grammar: Grammar,
+    pub codons: RangeGenotype<T>,
+}
+
+impl<T: RangeAllele> TryFrom<Builder<Self>> for Grammatical<T>
+where
+    Uniform<T>: Send + Sync,
+{
+    type Error = TryFromBuilderError;
+
+    fn try_from(builder: Builder<Self>) -> Result<Self, Self::Error> {
+        if builder.grammar.is_none() {
+            Err(TryFromBuilderError(
+                "GrammaticalGenotype requires a grammar",
+            ))
+        } else if !builder.genes_size.is_some_and(|x| x > 0) {
+            Err(TryFromBuilderError(
+                "GrammaticalGenotype requires a genes_size > 0",
+            ))
+        } else if builder.allele_range.is_none() {
+            Err(TryFromBuilderError(
+                "GrammaticalGenotype requires a allele_range",
+            ))
+        } else if !builder.gene_constraints.is_empty() {
+            Err(TryFromBuilderError(
+                "GrammaticalGenotype does not support gene_constraints",
+            ))
+        } else {
+            let codons = RangeGenotype::<T>::try_from(Builder {
+                genes_size: builder.genes_size,
+                allele_range: builder.allele_range,
+                mutation_type: builder.mutation_type,
+                allele_scale: builder.allele_scale,
+                seed_genes_list: builder.seed_genes_list,
+                genes_hashing: builder.genes_hashing,
+                chromosome_recycling: builder.chromosome_recycling,
+                ..Default::default()
+            })?;
+            Ok(Self::new(builder.grammar.unwrap(), codons))
+        }
+    }
+}
+
+impl<T: RangeAllele> Grammatical<T>
+where
+    Uniform<T>: Send + Sync,
+{
+    pub fn new(grammar: Grammar, codons: RangeGenotype<T>) -> Self {
+        Self { grammar, codons }
+    }
+    /// Map the codons to a derivation of the grammar
+    pub fn map(&self, codons: &[T]) -> Result<GrammarDerivation, GrammarMappingError> {
+        self.grammar.map(codons)
+    }
+    /// The phenotype string of the codons, None for invalid mappings
+    pub fn phenotype(&self, codons: &[T]) -> Option<String> {
+        self.map(codons)
+            .ok()
+            .map(|derivation| derivation.phenotype())
+    }
+}
+
+impl<T: RangeAllele> Genotype for Grammatical<T>
+where
+    Uniform<T>: Send + Sync,
+{
+    type Allele = T;
+
+    fn genes_size(&self) -> usize {
+        self.codons.genes_size()
+    }
+    fn sample_gene_index<R: Rng>(&self, rng: &mut R) -> usize {
+        self.codons.sample_gene_index(rng)
+    }
+    fn sample_gene_indices<R: Rng>(
+        &self,
+        count: usize,
+        allow_duplicates: bool,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        self.codons
+            .sample_gene_indices(count, allow_duplicates, rng)
+    }
+    fn mutate_chromosome_genes<R: Rng>(
+        &self,
+        number_of_mutations: usize,
+        allow_duplicates: bool,
+        chromosome: &mut Chromosome<Self::Allele>,
+        rng: &mut R,
+    ) {
+        self.codons
+            .mutate_chromosome_genes(number_of_mutations, allow_duplicates, chromosome, rng);
+    }
+    fn set_seed_genes_list(&mut self, seed_genes_list: Vec<Genes<Self::Allele>>) {
+        self.codons.set_seed_genes_list(seed_genes_list);
+    }
+    fn seed_genes_list(&self) -> &Vec<Genes<Self::Allele>> {
+        self.codons.seed_genes_list()
+    }
+    fn set_genes_hashing(&mut self, genes_hashing: bool) {
+        self.codons.set_genes_hashing(genes_hashing);
+    }
+    fn random_genes_factory<R: Rng>(&self, rng: &mut R) -> Genes<Self::Allele> {
+        self.codons.random_genes_factory(rng)
+    }
+    fn genes_capacity(&self) -> usize {
+        self.codons.genes_capacity()
+    }
+    fn genes_hashing(&self) -> bool {
+        self.codons.genes_hashing()
+    }
+    fn chromosome_recycling(&self) -> bool {
+        self.codons.chromosome_recycling()
+    }
+    fn max_scale_index(&self) -> Option<usize> {
+        self.codons.max_scale_index()
+    }
+    fn current_scale_index(&self) -> Option<usize> {
+        self.codons.current_scale_index()
+    }
+    fn reset_scale_index(&mut self) {
+        self.codons.reset_scale_index();
+    }
+    fn increment_scale_index(&mut self) -> bool {
+        self.codons.increment_scale_index()
+    }
+    fn reset(&mut self) {
+        self.codons.reset();
+    }
+}
+
+impl<T: RangeAllele> EvolveGenotype for Grammatical<T> where Uniform<T>: Send + Sync {}
+impl<T: RangeAllele> SupportsGeneCrossover for Grammatical<T>
+where
+    Uniform<T>: Send + Sync,
+{
+    fn crossover_chromosome_genes<R: Rng>(
+        &self,
+        number_of_crossovers: usize,
+        allow_duplicates: bool,
+        father: &mut Chromosome<Self::Allele>,
+        mother: &mut Chromosome<Self::Allele>,
+        rng: &mut R,
+    ) {
+        self.codons.crossover_chromosome_genes(
+            number_of_crossovers,
+            allow_duplicates,
+            father,
+            mother,
+            rng,
+        );
+    }
+}
+impl<T: RangeAllele> SupportsPointCrossover for Grammatical<T>
+where
+    Uniform<T>: Send + Sync,
+{
+    fn crossover_chromosome_points<R: Rng>(
+        &self,
+        number_of_crossovers: usize,
+        allow_duplicates: bool,
+        father: &mut Chromosome<Self::Allele>,
+        mother: &mut Chromosome<Self::Allele>,
+        rng: &mut R,
+    ) {
+        self.codons.crossover_chromosome_points(
+            number_of_crossovers,
+            allow_duplicates,
+            father,
+            mother,
+            rng,
+        );
+    }
+}
+
+impl<T: RangeAllele> HillClimbGenotype for Grammatical<T>
+where
+    Uniform<T>: Send + Sync,
+{
+    fn fill_neighbouring_population<R: Rng>(
+        &self,
+        chromosome: &Chromosome<Self::Allele>,
+        population: &mut Population<Self::Allele>,
+        rng: &mut R,
+    ) {
+        self.codons
+            .fill_neighbouring_population(chromosome, population, rng);
+    }
+    fn neighbouring_population_size(&self) -> BigUint {
+        self.codons.neighbouring_population_size()
+    }
+}
+
+impl<T: RangeAllele> PermutateGenotype for Grammatical<T>
+where
+    Uniform<T>: Send + Sync,
+{
+    fn chromosome_permutations_into_iter<'a>(
+        &'a self,
+        chromosome: Option<&Chromosome<Self::Allele>>,
+    ) -> Box<dyn Iterator<Item = Chromosome<Self::Allele>> + Send + 'a> {
+        self.codons.chromosome_permutations_into_iter(chromosome)
+    }
+    fn chromosome_permutations_size(&self) -> BigUint {
+        self.codons.chromosome_permutations_size()
+    }
+    fn chromosome_permutations_size_report(&self) -> String {
+        self.codons.chromosome_permutations_size_report()
+    }
+    fn allows_permutation(&self) -> bool {
+        self.codons.allows_permutation()
+    }
+}
+
+impl<T: RangeAllele> EstimateGenotype for Grammatical<T> where Uniform<T>: Send + Sync {}
+
+impl<T: RangeAllele> ParticleSwarmGenotype for Grammatical<T> where Uniform<T>: Send + Sync {}
+
+impl<T: RangeAllele> fmt::Display for Grammatical<T>
+where
+    Uniform<T>: Send + Sync,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "genotype:")?;
+        writeln!(f, "  genes_size: {}", self.genes_size())?;
+        writeln!(f, "  grammar_rules: {}", self.grammar.rules.len())?;
+        writeln!(f, "  max_wraps: {}", self.grammar.max_wraps)?;
+        writeln!(f, "  max_depth: {}", self.grammar.max_depth)?;
+        writeln!(
+            f,
+            "  chromosome_permutations_size: {}",
+            self.chromosome_permutations_size_report()
+        )?;
+        writeln!(
+            f,
+            "  neighbouring_population_size: {}",
+            self.neighbouring_population_size_report()
+        )?;
+        writeln!(
+            f,
+            "  expected_number_of_sampled_index_duplicates: {}",
+            self.expected_number_of_sampled_index_duplicates_report()
+        )?;
+        writeln!(f, "  seed_genes: {:?}", self.seed_genes_list().len())
+    }
+}
@@ -0,0 +1,459 @@
+use super::builder::{Builder, TryFromBuilderError};
+use super::{
+    BinaryGenotype, EstimateGenotype, EvolveGenotype, Genotype, HillClimbGenotype, ListGenotype,
+    ParticleSwarmGenotype, PermutateGenotype, RangeAllele, RangeGenotype, SupportsGeneCrossover,
+    SupportsPointCrossover,
+};
+use crate::allele::Allele;
+use crate::chromosome::{Chromosome, Genes};
+use crate::population::Population;
+use num::BigUint;
+use rand::distributions::Uniform;
+use rand::prelude::*;
+use std::fmt;
+use std::hash::Hash;
+
+/// A genotype usable as the cells of a [Grid] genotype. The genes have to be positionally
+/// independent values (each gene can take any value, regardless of its position), so the grid can
+/// move them around between the cells: [BinaryGenotype], [ListGenotype] and [RangeGenotype].
+pub trait GridCells: SupportsGeneCrossover + HillClimbGenotype + PermutateGenotype {
+    /// Whether two cells have the same value, swapping them is not a move
+    fn same_cell_value(a: &Self::Allele, b: &Self::Allele) -> bool;
+}
+
+impl GridCells for BinaryGenotype {
+    fn same_cell_value(a: &bool, b: &bool) -> bool {
+        a == b
+    }
+}
+impl<T: Allele + PartialEq + Hash> GridCells for ListGenotype<T> {
+    fn same_cell_value(a: &T, b: &T) -> bool {
+        a == b
+    }
+}
+impl<T: RangeAllele> GridCells for RangeGenotype<T>
+where
+    Uniform<T>: Send + Sync,
+{
+    fn same_cell_value(a: &T, b: &T) -> bool {
+        a == b
+    }
+}
+
+/// Genes form a 2D grid of rows × columns cells, stored row-major in a flat vector (cell (row,
+/// column) is gene `row * columns + column`). The cell values are managed by a cells genotype
+/// ([BinaryGenotype], [ListGenotype] or [RangeGenotype], see [GridCells]) with a genes_size of
+/// rows × columns, which is used for random initialization, the value mutations, the value
+/// neighbours and the permutations. On top of that the grid adds 2D-aware operators:
+///
+/// * Mutation: each mutation is, with the swap_probability (default 0.1), a swap of two random
+///   rows or two random columns. Otherwise it is a cell value mutation of the cells genotype.
+/// * Gene crossover: delegated to the cells genotype (uniform per cell)
+/// * Point crossover: 2D block crossover, each crossover swaps a random rectangular block of cells
+///   between the parents (instead of the 1D tails of the flat vector)
+/// * HillClimb neighbours: the value neighbours of the cells genotype, plus the swaps of all
+///   horizontally and vertically adjacent cells with different values
+///
+/// The grid is constructed from its cells genotype with `GridGenotype::new(rows, columns, cells)`,
+/// not with the genotype builder (which returns an error). The construction fails if the
+/// genes_size of the cells genotype is not rows × columns. Genes hashing and chromosome recycling
+/// default to the setting of the cells genotype.
+///
+/// The fitness gets access to the genes by (row, column) with `get()`, `row()`, `column()` and
+/// `adjacent_indices()` (the 4-neighbourhood).
+///
+/// # Example:
+/// ```
+/// use genetic_algorithm::genotype::{Genotype, GridGenotype, ListGenotype};
+///
+/// // seating plan of 4 rows of 6 seats, with 3 groups of guests
+/// let genotype = GridGenotype::new(
+///     4,
+///     6,
+///     ListGenotype::builder()
+///         .with_genes_size(24)
+///         .with_allele_list(vec![0, 1, 2])
+///         .build()
+///         .unwrap(),
+/// )
+/// .unwrap()
+/// .with_swap_probability(0.2); // optional, defaults to 0.1
+///
+/// let mut rng = rand::thread_rng();
+/// let genes = genotype.random_genes_factory(&mut rng);
+/// let group = genotype.get(&genes, 3, 5);
+/// let same_group_neighbours = genotype
+///     .adjacent_indices(genotype.index(3, 5))
+///     .into_iter()
+///     .filter(|index| genes[*index] == group)
+///     .count();
+/// assert!(same_group_neighbours <= 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Grid<G: GridCells> {
+    pub cells: G,
+    pub rows: usize,
+    pub columns: usize,
+    pub swap_probability: f64,
+    pub seed_genes_list: Vec<Genes<G::Allele>>,
+    pub genes_hashing: bool,
+    pub chromosome_recycling: bool,
+}
+
+impl<G: GridCells> TryFrom<Builder<Self>> for Grid<G> {
+    type Error = TryFromBuilderError;
+
+    fn try_from(_builder: Builder<Self>) -> Result<Self, Self::Error> {
+        Err(TryFromBuilderError(
+            "GridGenotype is constructed from its cells genotype with GridGenotype::new(rows, columns, cells)",
+        ))
+    }
+}
+
+impl<G: GridCells> Grid<G> {
+    /// Returns an error if the genes_size of the cells genotype is not rows × columns
+    pub fn new(rows: usize, columns: usize, cells: G) -> Result<Self, TryFromBuilderError> {
+        if rows == 0 || columns == 0 {
+            Err(TryFromBuilderError(
+                "GridGenotype requires rows > 0 and columns > 0",
+            ))
+        } else if cells.genes_size() != rows * columns {
+            Err(TryFromBuilderError(
+                "GridGenotype requires a cells genotype with genes_size of rows * columns",
+            ))
+        } else {
+            Ok(Self {
+                genes_hashing: cells.genes_hashing(),
+                chromosome_recycling: cells.chromosome_recycling(),
+                cells,
+                rows,
+                columns,
+                swap_probability: 0.1,
+                seed_genes_list: vec![],
+            })
+        }
+    }
+    pub fn with_swap_probability(mut self, swap_probability: f64) -> Self {
+        self.swap_probability = swap_probability.clamp(0.0, 1.0);
+        self
+    }
+    pub fn with_seed_genes_list(mut self, seed_genes_list: Vec<Genes<G::Allele>>) -> Self {
+        self.seed_genes_list = seed_genes_list;
+        self
+    }
+    pub fn with_genes_hashing(mut self, genes_hashing: bool) -> Self {
+        self.genes_hashing = genes_hashing;
+        self
+    }
+    pub fn with_chromosome_recycling(mut self, chromosome_recycling: bool) -> Self {
+        self.chromosome_recycling = chromosome_recycling;
+        self
+    }
+
+    /// The gene index of the cell (row, column)
+    pub fn index(&self, row: usize, column: usize) -> usize {
+        row * self.columns + column
+    }
+    /// The (row, column) of the gene index
+    pub fn position(&self, index: usize) -> (usize, usize) {
+        (index / self.columns, index % self.columns)
+    }
+    pub fn get(&self, genes: &[G::Allele], row: usize, column: usize) -> G::Allele {
+        genes[self.index(row, column)]
+    }
+    pub fn set(&self, genes: &mut [G::Allele], row: usize, column: usize, value: G::Allele) {
+        genes[self.index(row, column)] = value;
+    }
+    pub fn row<'a>(&self, genes: &'a [G::Allele], row: usize) -> &'a [G::Allele] {
+        &genes[row * self.columns..(row + 1) * self.columns]
+    }
+    pub fn column<'a>(
+        &self,
+        genes: &'a [G::Allele],
+        column: usize,
+    ) -> impl Iterator<Item = G::Allele> + 'a {
+        genes.iter().skip(column).step_by(self.columns).copied()
+    }
+    /// The gene indices of the horizontally and vertically adjacent cells (up, left, right, down)
+    pub fn adjacent_indices(&self, index: usize) -> Vec<usize> {
+        let (row, column) = self.position(index);
+        let mut indices = Vec::with_capacity(4);
+        if row > 0 {
+            indices.push(index - self.columns);
+        }
+        if column > 0 {
+            indices.push(index - 1);
+        }
+        if column + 1 < self.columns {
+            indices.push(index + 1);
+        }
+        if row + 1 < self.rows {
+            indices.push(index + self.columns);
+        }
+        indices
+    }
+    pub fn swap_rows(&self, genes: &mut [G::Allele], row_a: usize, row_b: usize) {
+        if row_a != row_b {
+            let (low, high) = (row_a.min(row_b), row_a.max(row_b));
+            let (front, back) = genes.split_at_mut(high * self.columns);
+            front[low * self.columns..(low + 1) * self.columns]
+                .swap_with_slice(&mut back[..self.columns]);
+        }
+    }
+    pub fn swap_columns(&self, genes: &mut [G::Allele], column_a: usize, column_b: usize) {
+        if column_a != column_b {
+            (0..self.rows).for_each(|row| {
+                genes.swap(self.index(row, column_a), self.index(row, column_b));
+            });
+        }
+    }
+
+    /// swap two random rows or columns, if there are at least two of them
+    fn swap_random_lines<R: Rng>(&self, genes: &mut [G::Allele], rng: &mut R) {
+        let swap_rows = match (self.rows > 1, self.columns > 1) {
+            (true, true) => rng.gen(),
+            (true, false) => true,
+            (false, true) => false,
+            (false, false) => return,
+        };
+        if swap_rows {
+            let lines = rand::seq::index::sample(rng, self.rows, 2);
+            self.swap_rows(genes, lines.index(0), lines.index(1));
+        } else {
+            let lines = rand::seq::index::sample(rng, self.columns, 2);
+            self.swap_columns(genes, lines.index(0), lines.index(1));
+        }
+    }
+    /// a random (inclusive) span within the size
+    fn sample_span<R: Rng>(size: usize, rng: &mut R) -> (usize, usize) {
+        let a = rng.gen_range(0..size);
+        let b = rng.gen_range(0..size);
+        (a.min(b), a.max(b))
+    }
+}
+
+impl<G: GridCells> Genotype for Grid<G> {
+    type Allele = G::Allele;
+
+    fn genes_size(&self) -> usize {
+        self.rows * self.columns
+    }
+    fn sample_gene_index<R: Rng>(&self, rng: &mut R) -> usize {
+        self.cells.sample_gene_index(rng)
+    }
+    fn sample_gene_indices<R: Rng>(
+        &self,
+        count: usize,
+        allow_duplicates: bool,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        self.cells.sample_gene_indices(count, allow_duplicates, rng)
+    }
+
+    fn mutate_chromosome_genes<R: Rng>(
+        &self,
+        number_of_mutations: usize,
+        allow_duplicates: bool,
+        chromosome: &mut Chromosome<Self::Allele>,
+        rng: &mut R,
+    ) {
+        let mut cell_mutations = 0;
+        for _ in 0..number_of_mutations {
+            if rng.gen_bool(self.swap_probability) {
+                self.swap_random_lines(&mut chromosome.genes, rng);
+            } else {
+                cell_mutations += 1;
+            }
+        }
+        if cell_mutations > 0 {
+            self.cells
+                .mutate_chromosome_genes(cell_mutations, allow_duplicates, chromosome, rng);
+        }
+        chromosome.reset_metadata(self.genes_hashing);
+    }
+    fn set_seed_genes_list(&mut self, seed_genes_list: Vec<Genes<Self::Allele>>) {
+        self.seed_genes_list = seed_genes_list;
+    }
+    fn seed_genes_list(&self) -> &Vec<Genes<Self::Allele>> {
+        &self.seed_genes_list
+    }
+    fn set_genes_hashing(&mut self, genes_hashing: bool) {
+        self.genes_hashing = genes_hashing;
+    }
+    fn random_genes_factory<R: Rng>(&self, rng: &mut R) -> Genes<Self::Allele> {
+        if self.seed_genes_list.is_empty() {
+            self.cells.random_genes_factory(rng)
+        } else {
+            self.seed_genes_list.choose(rng).unwrap().clone()
+        }
+    }
+    fn genes_capacity(&self) -> usize {
+        self.genes_size()
+    }
+    fn genes_hashing(&self) -> bool {
+        self.genes_hashing
+    }
+    fn chromosome_recycling(&self) -> bool {
+        self.chromosome_recycling
+    }
+    fn max_scale_index(&self) -> Option<usize> {
+        self.cells.max_scale_index()
+    }
+    fn current_scale_index(&self) -> Option<usize> {
+        self.cells.current_scale_index()
+    }
+    fn reset_scale_index(&mut self) {
+        self.cells.reset_scale_index();
+    }
+    fn increment_scale_index(&mut self) -> bool {
+        self.cells.increment_scale_index()
+    }
+    fn reset(&mut self) {
+        self.cells.reset();
+    }
+}
+
+impl<G: GridCells> EvolveGenotype for Grid<G> {}
+impl<G: GridCells> SupportsGeneCrossover for Grid<G> {
+    fn crossover_chromosome_genes<R: Rng>(
+        &self,
+        number_of_crossovers: usize,
+        allow_duplicates: bool,
+        father: &mut Chromosome<Self::Allele>,
+        mother: &mut Chromosome<Self::Allele>,
+        rng: &mut R,
+    ) {
+        self.cells.crossover_chromosome_genes(
+            number_of_crossovers,
+            allow_duplicates,
+            father,
+            mother,
+            rng,
+        );
+        mother.reset_metadata(self.genes_hashing);
+        father.reset_metadata(self.genes_hashing);
+    }
+}
+impl<G: GridCells> SupportsPointCrossover for Grid<G> {
+    // 2D block crossover, allow_duplicates is not relevant as blocks are sampled independently
+    fn crossover_chromosome_points<R: Rng>(
+        &self,
+        number_of_crossovers: usize,
+        _allow_duplicates: bool,
+        father: &mut Chromosome<Self::Allele>,
+        mother: &mut Chromosome<Self::Allele>,
+        rng: &mut R,
+    ) {
+        for _ in 0..number_of_crossovers {
+            let (row_start, row_end) = Self::sample_span(self.rows, rng);
+            let (column_start, column_end) = Self::sample_span(self.columns, rng);
+            for row in row_start..=row_end {
+                let start_index = self.index(row, column_start);
+                let end_index = self.index(row, column_end) + 1;
+                father.genes[start_index..end_index]
+                    .swap_with_slice(&mut mother.genes[start_index..end_index]);
+            }
+        }
+        mother.reset_metadata(self.genes_hashing);
+        father.reset_metadata(self.genes_hashing);
+    }
+}
+
+impl<G: GridCells> HillClimbGenotype for Grid<G> {
+    fn fill_neighbouring_population<R: Rng>(
+        &self,
+        chromosome: &Chromosome<Self::Allele>,
+        population: &mut Population<Self::Allele>,
+        rng: &mut R,
+    ) {
+        let start_index = population.chromosomes.len();
+        self.cells
+            .fill_neighbouring_population(chromosome, population, rng);
+        population.chromosomes[start_index..]
+            .iter_mut()
+            .for_each(|neighbour| neighbour.reset_metadata(self.genes_hashing));
+
+        for index in 0..self.genes_size() {
+            let (row, column) = self.position(index);
+            let mut adjacent_indices = Vec::with_capacity(2);
+            if column + 1 < self.columns {
+                adjacent_indices.push(index + 1);
+            }
+            if row + 1 < self.rows {
+                adjacent_indices.push(index + self.columns);
+            }
+            for adjacent_index in adjacent_indices {
+                if !G::same_cell_value(&chromosome.genes[index], &chromosome.genes[adjacent_index])
+                {
+                    let mut new_chromosome = population.new_chromosome(chromosome);
+                    new_chromosome.genes.swap(index, adjacent_index);
+                    new_chromosome.reset_metadata(self.genes_hashing);
+                    population.chromosomes.push(new_chromosome);
+                }
+            }
+        }
+    }
+
+    fn neighbouring_population_size(&self) -> BigUint {
+        self.cells.neighbouring_population_size()
+            + BigUint::from(self.rows * (self.columns - 1) + (self.rows - 1) * self.columns)
+    }
+}
+
+impl<G: GridCells> PermutateGenotype for Grid<G> {
+    fn chromosome_permutations_into_iter<'a>(
+        &'a self,
+        chromosome: Option<&Chromosome<Self::Allele>>,
+    ) -> Box<dyn Iterator<Item = Chromosome<Self::Allele>> + Send + 'a> {
+        if self.seed_genes_list.is_empty() {
+            self.cells.chromosome_permutations_into_iter(chromosome)
+        } else {
+            Box::new(
+                self.seed_genes_list
+                    .clone()
+                    .into_iter()
+                    .map(Chromosome::new),
+            )
+        }
+    }
+    fn chromosome_permutations_size(&self) -> BigUint {
+        if self.seed_genes_list.is_empty() {
+            self.cells.chromosome_permutations_size()
+        } else {
+            self.seed_genes_list.len().into()
+        }
+    }
+    fn allows_permutation(&self) -> bool {
+        self.cells.allows_permutation()
+    }
+}
+
+impl<G: GridCells> EstimateGenotype for Grid<G> {}
+
+impl<G: GridCells> ParticleSwarmGenotype for Grid<G> {}
+
+impl<G: GridCells> fmt::Display for Grid<G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "genotype:")?;
+        writeln!(f, "  genes_size: {}", self.genes_size())?;
+        writeln!(f, "  rows: {}", self.rows)?;
+        writeln!(f, "  columns: {}", self.columns)?;
+        writeln!(f, "  swap_probability: {}", self.swap_probability)?;
+        writeln!(
+            f,
+            "  chromosome_permutations_size: {}",
+            self.chromosome_permutations_size_report()
+        )?;
+        writeln!(
+            f,
+            "  neighbouring_population_size: {}",
+            self.neighbouring_population_size_report()
+        )?;
+        writeln!(
+            f,
+            "  expected_number_of_sampled_index_duplicates: {}",
+            self.expected_number_of_sampled_index_duplicates_report()
+        )?;
+        writeln!(f, "  seed_genes: {:?}", self.seed_genes_list.len())
+    }
+}
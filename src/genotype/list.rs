@@ -1,14 +1,14 @@
 use super::builder::{Builder, TryFromBuilderError};
 use super::{
-    EvolveGenotype, Genotype, HillClimbGenotype, MutationType, PermutateGenotype,
-    SupportsGeneCrossover, SupportsPointCrossover,
+    EstimateGenotype, EvolveGenotype, Genotype, HillClimbGenotype, MutationType,
+    ParticleSwarmGenotype, PermutateGenotype, SupportsGeneCrossover, SupportsPointCrossover,
 };
 use crate::allele::Allele;
 use crate::chromosome::{Chromosome, Genes};
 use crate::population::Population;
 use itertools::Itertools;
 use num::BigUint;
-use rand::distributions::{Distribution, Uniform};
+use rand::distributions::{Distribution, Uniform, WeightedIndex};
 use rand::prelude::*;
 use std::fmt;
 use std::hash::Hash;
@@ -23,6 +23,17 @@ pub type DefaultAllele = usize;
 /// assigned again, not mutating as a result). Duplicate allele values are allowed. Defaults to
 /// usize as item.
 ///
+/// Optionally, the allele_list values can be given relative weights with
+/// `with_allele_list_weights()`, which are then used instead of the uniform probability for random
+/// initialization and mutation (e.g. to favour a priori plausible values). A zero weight excludes
+/// the value from random sampling, but it remains reachable by HillClimb neighbours, ordinal
+/// mutation and Permutate.
+///
+/// Optionally, the allele_list can be taken as ordered with `with_ordinal_mutation(true)`. A
+/// mutating gene then moves to a neighbouring entry in the allele_list (one up or one down, always
+/// changing), instead of jumping anywhere in the list. The HillClimb neighbours are restricted
+/// accordingly. Genes with values outside the allele_list (e.g. seed genes) mutate randomly.
+///
 /// # Example (usize, default):
 /// ```
 /// use genetic_algorithm::genotype::{Genotype, ListGenotype};
@@ -36,6 +47,19 @@ pub type DefaultAllele = usize;
 ///     .unwrap();
 /// ```
 ///
+/// # Example (weighted and ordinal):
+/// ```
+/// use genetic_algorithm::genotype::{Genotype, ListGenotype};
+///
+/// let genotype = ListGenotype::builder()
+///     .with_genes_size(10)
+///     .with_allele_list(vec![1, 2, 4, 8, 16, 32])
+///     .with_allele_list_weights(vec![1.0, 4.0, 8.0, 4.0, 1.0, 0.5]) // optional
+///     .with_ordinal_mutation(true) // optional, defaults to false
+///     .build()
+///     .unwrap();
+/// ```
+///
 /// # Example (struct, manual impl Allele)
 /// ```
 /// use genetic_algorithm::genotype::{Allele, Genotype, ListGenotype};
@@ -88,6 +112,9 @@ pub struct List<T: Allele + PartialEq + Hash = DefaultAllele> {
     pub allele_list: Vec<T>,
     gene_index_sampler: Uniform<usize>,
     allele_index_sampler: Uniform<usize>,
+    pub allele_list_weights: Option<Vec<f64>>,
+    allele_weighted_index_sampler: Option<WeightedIndex<f64>>,
+    pub ordinal_mutation: bool,
     pub seed_genes_list: Vec<Vec<T>>,
     pub genes_hashing: bool,
     pub chromosome_recycling: bool,
@@ -113,13 +140,36 @@ impl<T: Allele + PartialEq + Hash> TryFrom<Builder<Self>> for List<T> {
             Err(TryFromBuilderError(
                 "ListGenotype requires non-empty allele_list",
             ))
+        } else if builder
+            .allele_list_weights
+            .as_ref()
+            .is_some_and(|w| w.len() != builder.allele_list.as_ref().unwrap().len())
+        {
+            Err(TryFromBuilderError(
+                "ListGenotype allele_list_weights length must match allele_list length",
+            ))
+        } else if !builder.gene_constraints.is_empty() {
+            Err(TryFromBuilderError(
+                "ListGenotype does not support gene_constraints",
+            ))
         } else {
             let allele_list = builder.allele_list.unwrap();
+            let allele_weighted_index_sampler = match builder.allele_list_weights.as_ref() {
+                Some(weights) => Some(WeightedIndex::new(weights).map_err(|_| {
+                    TryFromBuilderError(
+                        "ListGenotype allele_list_weights must be non-negative with a positive sum",
+                    )
+                })?),
+                None => None,
+            };
             Ok(Self {
                 genes_size: builder.genes_size.unwrap(),
                 allele_list: allele_list.clone(),
                 gene_index_sampler: Uniform::from(0..builder.genes_size.unwrap()),
                 allele_index_sampler: Uniform::from(0..allele_list.len()),
+                allele_list_weights: builder.allele_list_weights,
+                allele_weighted_index_sampler,
+                ordinal_mutation: builder.ordinal_mutation.unwrap_or(false),
                 seed_genes_list: builder.seed_genes_list,
                 genes_hashing: builder.genes_hashing,
                 chromosome_recycling: builder.chromosome_recycling,
@@ -133,10 +183,50 @@ impl<T: Allele + PartialEq + Hash> List<T> {
         &MutationType::Random
     }
     pub fn sample_gene_random<R: Rng>(&self, rng: &mut R) -> T {
-        self.allele_list[self.allele_index_sampler.sample(rng)]
+        match &self.allele_weighted_index_sampler {
+            Some(sampler) => self.allele_list[sampler.sample(rng)],
+            None => self.allele_list[self.allele_index_sampler.sample(rng)],
+        }
+    }
+    pub fn mutate_gene<R: Rng>(&self, value: T, rng: &mut R) -> T {
+        if self.ordinal_mutation {
+            if let Some(index) = self.allele_list.iter().position(|a| *a == value) {
+                return self.allele_list
+                    [ordinal_neighbour_index(index, self.allele_list.len(), rng)];
+            }
+        }
+        self.sample_gene_random(rng)
+    }
+    fn ordinal_neighbour_indices(&self, value: T) -> Vec<usize> {
+        match self.allele_list.iter().position(|a| *a == value) {
+            Some(index) => ordinal_neighbour_indices(index, self.allele_list.len()),
+            None => (0..self.allele_list.len()).collect(),
+        }
     }
 }
 
+/// one up or one down, always changing if possible
+pub(crate) fn ordinal_neighbour_index<R: Rng>(index: usize, size: usize, rng: &mut R) -> usize {
+    if size < 2 {
+        index
+    } else if index == 0 {
+        1
+    } else if index >= size - 1 {
+        size - 2
+    } else if rng.gen() {
+        index + 1
+    } else {
+        index - 1
+    }
+}
+pub(crate) fn ordinal_neighbour_indices(index: usize, size: usize) -> Vec<usize> {
+    [index.checked_sub(1), Some(index + 1)]
+        .into_iter()
+        .flatten()
+        .filter(|i| *i < size)
+        .collect()
+}
+
 impl<T: Allele + PartialEq + Hash> Genotype for List<T> {
     type Allele = T;
 
@@ -171,7 +261,7 @@ impl<T: Allele + PartialEq + Hash> Genotype for List<T> {
         if allow_duplicates {
             for _ in 0..number_of_mutations {
                 let index = self.gene_index_sampler.sample(rng);
-                chromosome.genes[index] = self.sample_gene_random(rng);
+                chromosome.genes[index] = self.mutate_gene(chromosome.genes[index], rng);
             }
         } else {
             rand::seq::index::sample(
@@ -181,7 +271,7 @@ impl<T: Allele + PartialEq + Hash> Genotype for List<T> {
             )
             .iter()
             .for_each(|index| {
-                chromosome.genes[index] = self.sample_gene_random(rng);
+                chromosome.genes[index] = self.mutate_gene(chromosome.genes[index], rng);
             });
         }
         chromosome.reset_metadata(self.genes_hashing);
@@ -299,7 +389,15 @@ impl<T: Allele + PartialEq + Hash> HillClimbGenotype for List<T> {
         _rng: &mut R,
     ) {
         for index in 0..self.genes_size() {
-            for allele_value in self.allele_list.clone() {
+            let allele_values = if self.ordinal_mutation {
+                self.ordinal_neighbour_indices(chromosome.genes[index])
+                    .into_iter()
+                    .map(|allele_index| self.allele_list[allele_index])
+                    .collect()
+            } else {
+                self.allele_list.clone()
+            };
+            for allele_value in allele_values {
                 if chromosome.genes[index] != allele_value {
                     let mut new_chromosome = population.new_chromosome(chromosome);
                     new_chromosome.genes[index] = allele_value;
@@ -311,7 +409,11 @@ impl<T: Allele + PartialEq + Hash> HillClimbGenotype for List<T> {
     }
 
     fn neighbouring_population_size(&self) -> BigUint {
-        BigUint::from((self.allele_list.len() - 1) * self.genes_size)
+        if self.ordinal_mutation {
+            BigUint::from((self.allele_list.len() - 1).min(2) * self.genes_size)
+        } else {
+            BigUint::from((self.allele_list.len() - 1) * self.genes_size)
+        }
     }
 }
 
@@ -349,11 +451,35 @@ impl<T: Allele + PartialEq + Hash> PermutateGenotype for List<T> {
     }
 }
 
+impl<T: Allele + PartialEq + Hash> EstimateGenotype for List<T> {
+    fn allele_sizes(&self) -> Vec<usize> {
+        vec![self.allele_list.len(); self.genes_size]
+    }
+    fn allele_index(&self, _gene_index: usize, allele: &T) -> Option<usize> {
+        self.allele_list.iter().position(|a| a == allele)
+    }
+    fn set_gene_by_allele_index(
+        &self,
+        chromosome: &mut Chromosome<T>,
+        gene_index: usize,
+        allele_index: usize,
+    ) {
+        chromosome.genes[gene_index] = self.allele_list[allele_index];
+    }
+    fn allows_estimation(&self) -> bool {
+        true
+    }
+}
+
+impl<T: Allele + PartialEq + Hash> ParticleSwarmGenotype for List<T> {}
+
 impl<T: Allele + PartialEq + Hash> fmt::Display for List<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "genotype:")?;
         writeln!(f, "  genes_size: {}", self.genes_size)?;
         writeln!(f, "  mutation_type: {:?}", self.mutation_type())?;
+        writeln!(f, "  ordinal_mutation: {}", self.ordinal_mutation)?;
+        writeln!(f, "  weighted: {}", self.allele_list_weights.is_some())?;
         writeln!(
             f,
             "  chromosome_permutations_size: {}",
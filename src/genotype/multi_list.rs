@@ -1,7 +1,9 @@
 use super::builder::{Builder, TryFromBuilderError};
+use super::gene_constraint;
+use super::list::{ordinal_neighbour_index, ordinal_neighbour_indices};
 use super::{
-    EvolveGenotype, Genotype, HillClimbGenotype, MutationType, PermutateGenotype,
-    SupportsGeneCrossover, SupportsPointCrossover,
+    EstimateGenotype, EvolveGenotype, GeneConstraint, Genotype, HillClimbGenotype, MutationType,
+    ParticleSwarmGenotype, PermutateGenotype, SupportsGeneCrossover, SupportsPointCrossover,
 };
 use crate::allele::Allele;
 use crate::chromosome::{Chromosome, Genes};
@@ -10,6 +12,7 @@ use itertools::Itertools;
 use num::BigUint;
 use rand::distributions::{Distribution, Uniform, WeightedIndex};
 use rand::prelude::*;
+use rand::rngs::SmallRng;
 use std::fmt;
 use std::hash::Hash;
 
@@ -25,6 +28,17 @@ pub type DefaultAllele = usize;
 /// which could therefore be assigned again, not mutating as a result). Duplicate allele values are
 /// allowed. Defaults to usize as item.
 ///
+/// Optionally, the values of each allele_list can be given relative weights with
+/// `with_allele_lists_weights()`, which are then used instead of the uniform probability for
+/// random initialization and mutation. Optionally, individual allele_lists can be taken as ordered
+/// with `with_ordinal_mutations()`, moving a mutating gene to a neighbouring entry in its
+/// allele_list (also restricting the HillClimb neighbours). See
+/// [ListGenotype](crate::genotype::ListGenotype) for details.
+///
+/// Optionally, constraints between genes (conditional activation and forbidden value pairs) can
+/// be set with `with_gene_constraints()`, which are enforced on random initialization, mutation,
+/// crossover, HillClimb neighbours and Permutate enumeration. See [GeneConstraint] for details.
+///
 /// This genotype is also used in the [meta analysis](https://github.com/basvanwesting/genetic-algorithm-meta.git), to hold the indices of the
 /// different [Evolve](crate::strategy::evolve::Evolve) configuration values (defined outside of the genotype).
 ///
@@ -61,6 +75,13 @@ pub type DefaultAllele = usize;
 ///        (0..number_of_laps.len()).collect(),
 ///        (0..rain_probabilities.len()).collect(),
 ///     ])
+///     .with_allele_lists_weights(vec![ // optional, favour the experienced drivers
+///        vec![1.0; cars.len()],
+///        vec![3.0, 2.0, 1.0],
+///        vec![1.0; number_of_laps.len()],
+///        vec![1.0; rain_probabilities.len()],
+///     ])
+///     .with_ordinal_mutations(vec![false, false, true, true]) // optional, laps and rain are ordered
 ///     .with_genes_hashing(true) // optional, defaults to true
 ///     .with_chromosome_recycling(true) // optional, defaults to true
 ///     .build()
@@ -98,6 +119,12 @@ pub struct MultiList<T: Allele + PartialEq + Hash = DefaultAllele> {
     gene_index_sampler: Uniform<usize>,
     gene_weighted_index_sampler: WeightedIndex<usize>,
     allele_index_samplers: Vec<Uniform<usize>>,
+    pub allele_lists_weights: Option<Vec<Vec<f64>>>,
+    allele_weighted_index_samplers: Option<Vec<WeightedIndex<f64>>>,
+    pub ordinal_mutations: Vec<bool>,
+    pub gene_constraints: Vec<GeneConstraint<T>>,
+    // feasible genes found by the builder, the fallback when repairing random genes fails
+    feasible_genes: Vec<T>,
     pub seed_genes_list: Vec<Vec<T>>,
     pub genes_hashing: bool,
     pub chromosome_recycling: bool,
@@ -130,7 +157,58 @@ impl<T: Allele + PartialEq + Hash> TryFrom<Builder<Self>> for MultiList<T> {
                 ));
             }
             let allele_list_sizes: Vec<usize> = allele_lists.iter().map(|v| v.len()).collect();
-            Ok(Self {
+            if builder.allele_lists_weights.as_ref().is_some_and(|w| {
+                w.len() != genes_size
+                    || w.iter()
+                        .zip(allele_list_sizes.iter())
+                        .any(|(weights, size)| weights.len() != *size)
+            }) {
+                return Err(TryFromBuilderError(
+                    "MultiListGenotype allele_lists_weights lengths must match allele_lists lengths",
+                ));
+            }
+            if builder
+                .ordinal_mutations
+                .as_ref()
+                .is_some_and(|o| o.len() != genes_size)
+            {
+                return Err(TryFromBuilderError(
+                    "MultiListGenotype ordinal_mutations length must match allele_lists length",
+                ));
+            }
+            if builder
+                .gene_constraints
+                .iter()
+                .any(|gene_constraint| gene_constraint.is_linear())
+            {
+                return Err(TryFromBuilderError(
+                    "MultiListGenotype does not support Linear gene_constraints",
+                ));
+            }
+            if !builder
+                .gene_constraints
+                .iter()
+                .all(|gene_constraint| gene_constraint.is_valid_for(genes_size))
+            {
+                return Err(TryFromBuilderError(
+                    "MultiListGenotype gene_constraints must refer to distinct gene indices within genes_size",
+                ));
+            }
+            let allele_weighted_index_samplers = match builder.allele_lists_weights.as_ref() {
+                Some(allele_lists_weights) => Some(
+                    allele_lists_weights
+                        .iter()
+                        .map(WeightedIndex::new)
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|_| {
+                            TryFromBuilderError(
+                                "MultiListGenotype allele_lists_weights must be non-negative with a positive sum per gene",
+                            )
+                        })?,
+                ),
+                None => None,
+            };
+            let mut genotype = Self {
                 genes_size,
                 allele_list_sizes: allele_list_sizes.clone(),
                 allele_lists: allele_lists.clone(),
@@ -140,10 +218,26 @@ impl<T: Allele + PartialEq + Hash> TryFrom<Builder<Self>> for MultiList<T> {
                     .iter()
                     .map(|allele_value_size| Uniform::from(0..*allele_value_size))
                     .collect(),
+                allele_lists_weights: builder.allele_lists_weights,
+                allele_weighted_index_samplers,
+                ordinal_mutations: builder
+                    .ordinal_mutations
+                    .unwrap_or_else(|| vec![false; genes_size]),
+                gene_constraints: builder.gene_constraints,
+                feasible_genes: vec![],
                 seed_genes_list: builder.seed_genes_list,
                 genes_hashing: builder.genes_hashing,
                 chromosome_recycling: builder.chromosome_recycling,
-            })
+            };
+            // fail early on infeasible gene_constraints, keep the found genes as fallback
+            if !genotype.gene_constraints.is_empty() {
+                genotype.feasible_genes = genotype
+                    .random_feasible_genes(&mut SmallRng::seed_from_u64(0))
+                    .ok_or(TryFromBuilderError(
+                        "MultiListGenotype could not generate random genes satisfying the gene_constraints",
+                    ))?;
+            }
+            Ok(genotype)
         }
     }
 }
@@ -153,7 +247,67 @@ impl<T: Allele + PartialEq + Hash> MultiList<T> {
         &MutationType::Random
     }
     pub fn sample_gene_random<R: Rng>(&self, index: usize, rng: &mut R) -> T {
-        self.allele_lists[index][self.allele_index_samplers[index].sample(rng)]
+        match &self.allele_weighted_index_samplers {
+            Some(samplers) => self.allele_lists[index][samplers[index].sample(rng)],
+            None => self.allele_lists[index][self.allele_index_samplers[index].sample(rng)],
+        }
+    }
+    pub fn mutate_gene<R: Rng>(&self, index: usize, value: T, rng: &mut R) -> T {
+        if self.ordinal_mutations[index] {
+            let allele_list = &self.allele_lists[index];
+            if let Some(allele_index) = allele_list.iter().position(|a| *a == value) {
+                return allele_list[ordinal_neighbour_index(allele_index, allele_list.len(), rng)];
+            }
+        }
+        self.sample_gene_random(index, rng)
+    }
+    /// Checks the gene_constraints
+    pub fn is_feasible(&self, genes: &[T]) -> bool {
+        gene_constraint::is_feasible(&self.gene_constraints, genes)
+    }
+    /// Repairs the genes towards the gene_constraints, returns false if not feasible
+    pub fn repair_genes<R: Rng>(&self, genes: &mut [T], rng: &mut R) -> bool {
+        gene_constraint::repair_genes(
+            &self.gene_constraints,
+            genes,
+            rng,
+            |index, rng| self.sample_gene_random(index, rng),
+            |_| {},
+            |_| true,
+        )
+    }
+    /// keep the original genes if the repair fails
+    fn repair_or_restore_genes<R: Rng>(
+        &self,
+        genes: &mut [T],
+        original_genes: Option<Vec<T>>,
+        rng: &mut R,
+    ) {
+        if let Some(original_genes) = original_genes {
+            if !self.repair_genes(genes, rng) {
+                genes.copy_from_slice(&original_genes);
+            }
+        }
+    }
+    fn original_genes(&self, chromosome: &Chromosome<T>) -> Option<Vec<T>> {
+        (!self.gene_constraints.is_empty()).then(|| chromosome.genes.clone())
+    }
+    /// random genes repaired towards the gene_constraints, retrying with new random genes. None
+    /// if no feasible genes are found after MAX_REPAIR_ATTEMPTS
+    fn random_feasible_genes<R: Rng>(&self, rng: &mut R) -> Option<Vec<T>> {
+        (0..gene_constraint::MAX_REPAIR_ATTEMPTS).find_map(|_| {
+            let mut genes: Vec<T> = (0..self.genes_size)
+                .map(|index| self.sample_gene_random(index, rng))
+                .collect();
+            self.repair_genes(&mut genes, rng).then_some(genes)
+        })
+    }
+    fn ordinal_neighbour_indices(&self, index: usize, value: T) -> Vec<usize> {
+        let allele_list = &self.allele_lists[index];
+        match allele_list.iter().position(|a| *a == value) {
+            Some(allele_index) => ordinal_neighbour_indices(allele_index, allele_list.len()),
+            None => (0..allele_list.len()).collect(),
+        }
     }
 }
 
@@ -195,10 +349,11 @@ impl<T: Allele + PartialEq + Hash> Genotype for MultiList<T> {
         chromosome: &mut Chromosome<Self::Allele>,
         rng: &mut R,
     ) {
+        let original_genes = self.original_genes(chromosome);
         if allow_duplicates {
             for _ in 0..number_of_mutations {
                 let index = self.gene_weighted_index_sampler.sample(rng);
-                chromosome.genes[index] = self.sample_gene_random(index, rng);
+                chromosome.genes[index] = self.mutate_gene(index, chromosome.genes[index], rng);
             }
         } else {
             rand::seq::index::sample_weighted(
@@ -210,9 +365,10 @@ impl<T: Allele + PartialEq + Hash> Genotype for MultiList<T> {
             .unwrap()
             .iter()
             .for_each(|index| {
-                chromosome.genes[index] = self.sample_gene_random(index, rng);
+                chromosome.genes[index] = self.mutate_gene(index, chromosome.genes[index], rng);
             });
         }
+        self.repair_or_restore_genes(&mut chromosome.genes, original_genes, rng);
         chromosome.reset_metadata(self.genes_hashing);
     }
     fn set_seed_genes_list(&mut self, seed_genes_list: Vec<Genes<Self::Allele>>) {
@@ -226,9 +382,14 @@ impl<T: Allele + PartialEq + Hash> Genotype for MultiList<T> {
     }
     fn random_genes_factory<R: Rng>(&self, rng: &mut R) -> Vec<T> {
         if self.seed_genes_list.is_empty() {
-            (0..self.genes_size)
-                .map(|index| self.sample_gene_random(index, rng))
-                .collect()
+            if self.gene_constraints.is_empty() {
+                return (0..self.genes_size)
+                    .map(|index| self.sample_gene_random(index, rng))
+                    .collect();
+            }
+            // rarely satisfiable gene_constraints, fall back to the feasible genes of the builder
+            self.random_feasible_genes(rng)
+                .unwrap_or_else(|| self.feasible_genes.clone())
         } else {
             self.seed_genes_list.choose(rng).unwrap().clone()
         }
@@ -254,6 +415,8 @@ impl<T: Allele + PartialEq + Hash> SupportsGeneCrossover for MultiList<T> {
         mother: &mut Chromosome<Self::Allele>,
         rng: &mut R,
     ) {
+        let original_father_genes = self.original_genes(father);
+        let original_mother_genes = self.original_genes(mother);
         if allow_duplicates {
             rng.sample_iter(self.gene_index_sampler)
                 .take(number_of_crossovers)
@@ -271,6 +434,8 @@ impl<T: Allele + PartialEq + Hash> SupportsGeneCrossover for MultiList<T> {
                 std::mem::swap(&mut father.genes[index], &mut mother.genes[index]);
             });
         }
+        self.repair_or_restore_genes(&mut father.genes, original_father_genes, rng);
+        self.repair_or_restore_genes(&mut mother.genes, original_mother_genes, rng);
         mother.reset_metadata(self.genes_hashing);
         father.reset_metadata(self.genes_hashing);
     }
@@ -284,6 +449,8 @@ impl<T: Allele + PartialEq + Hash> SupportsPointCrossover for MultiList<T> {
         mother: &mut Chromosome<Self::Allele>,
         rng: &mut R,
     ) {
+        let original_father_genes = self.original_genes(father);
+        let original_mother_genes = self.original_genes(mother);
         if allow_duplicates {
             rng.sample_iter(self.gene_index_sampler)
                 .take(number_of_crossovers)
@@ -316,6 +483,8 @@ impl<T: Allele + PartialEq + Hash> SupportsPointCrossover for MultiList<T> {
                 _ => (),
             });
         }
+        self.repair_or_restore_genes(&mut father.genes, original_father_genes, rng);
+        self.repair_or_restore_genes(&mut mother.genes, original_mother_genes, rng);
         mother.reset_metadata(self.genes_hashing);
         father.reset_metadata(self.genes_hashing);
     }
@@ -327,8 +496,17 @@ impl<T: Allele + PartialEq + Hash> HillClimbGenotype for MultiList<T> {
         population: &mut Population<Self::Allele>,
         _rng: &mut R,
     ) {
+        let start_index = population.chromosomes.len();
         for index in 0..self.genes_size() {
-            for allele_value in self.allele_lists[index].clone() {
+            let allele_values = if self.ordinal_mutations[index] {
+                self.ordinal_neighbour_indices(index, chromosome.genes[index])
+                    .into_iter()
+                    .map(|allele_index| self.allele_lists[index][allele_index])
+                    .collect()
+            } else {
+                self.allele_lists[index].clone()
+            };
+            for allele_value in allele_values {
                 if chromosome.genes[index] != allele_value {
                     let mut new_chromosome = population.new_chromosome(chromosome);
                     new_chromosome.genes[index] = allele_value;
@@ -337,10 +515,32 @@ impl<T: Allele + PartialEq + Hash> HillClimbGenotype for MultiList<T> {
                 }
             }
         }
+        if !self.gene_constraints.is_empty() {
+            gene_constraint::retain_feasible_neighbours(
+                &self.gene_constraints,
+                chromosome,
+                population,
+                start_index,
+                self.genes_hashing,
+                |genes| self.is_feasible(genes),
+            );
+        }
     }
 
     fn neighbouring_population_size(&self) -> BigUint {
-        BigUint::from(self.allele_list_sizes.iter().map(|v| *v - 1).sum::<usize>())
+        BigUint::from(
+            self.allele_list_sizes
+                .iter()
+                .zip(self.ordinal_mutations.iter())
+                .map(|(size, ordinal)| {
+                    if *ordinal {
+                        (*size - 1).min(2)
+                    } else {
+                        *size - 1
+                    }
+                })
+                .sum::<usize>(),
+        )
     }
 }
 
@@ -355,6 +555,7 @@ impl<T: Allele + PartialEq + Hash> PermutateGenotype for MultiList<T> {
                     .clone()
                     .into_iter()
                     .multi_cartesian_product()
+                    .filter(|genes| self.is_feasible(genes))
                     .map(Chromosome::new),
             )
         } else {
@@ -367,6 +568,8 @@ impl<T: Allele + PartialEq + Hash> PermutateGenotype for MultiList<T> {
         }
     }
 
+    /// An upper bound with gene_constraints, as the permutations only include the feasible
+    /// chromosomes
     fn chromosome_permutations_size(&self) -> BigUint {
         if self.seed_genes_list.is_empty() {
             self.allele_list_sizes
@@ -382,11 +585,40 @@ impl<T: Allele + PartialEq + Hash> PermutateGenotype for MultiList<T> {
     }
 }
 
+impl<T: Allele + PartialEq + Hash> EstimateGenotype for MultiList<T> {
+    fn allele_sizes(&self) -> Vec<usize> {
+        self.allele_list_sizes.clone()
+    }
+    fn allele_index(&self, gene_index: usize, allele: &T) -> Option<usize> {
+        self.allele_lists[gene_index]
+            .iter()
+            .position(|a| a == allele)
+    }
+    fn set_gene_by_allele_index(
+        &self,
+        chromosome: &mut Chromosome<T>,
+        gene_index: usize,
+        allele_index: usize,
+    ) {
+        chromosome.genes[gene_index] = self.allele_lists[gene_index][allele_index];
+    }
+    /// the probability model samples each gene independently, which can't enforce the
+    /// gene_constraints
+    fn allows_estimation(&self) -> bool {
+        self.gene_constraints.is_empty()
+    }
+}
+
+impl<T: Allele + PartialEq + Hash> ParticleSwarmGenotype for MultiList<T> {}
+
 impl<T: Allele + PartialEq + Hash> fmt::Display for MultiList<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "genotype:")?;
         writeln!(f, "  genes_size: {}", self.genes_size)?;
         writeln!(f, "  mutation_type: {:?}", self.mutation_type())?;
+        writeln!(f, "  ordinal_mutations: {:?}", self.ordinal_mutations)?;
+        writeln!(f, "  weighted: {}", self.allele_lists_weights.is_some())?;
+        writeln!(f, "  gene_constraints: {}", self.gene_constraints.len())?;
         writeln!(
             f,
             "  chromosome_permutations_size: {}",
@@ -1,7 +1,9 @@
 use super::builder::{Builder, TryFromBuilderError};
+use super::gene_constraint;
 use super::{
-    EvolveGenotype, Genotype, HillClimbGenotype, MutationType, PermutateGenotype,
-    SupportsGeneCrossover, SupportsPointCrossover,
+    AlleleScale, EstimateGenotype, EvolveGenotype, GeneConstraint, Genotype, HillClimbGenotype,
+    MutationType, ParticleSwarmGenotype, PermutateGenotype, SupportsGeneCrossover,
+    SupportsPointCrossover,
 };
 use crate::allele::RangeAllele;
 use crate::chromosome::{Chromosome, Genes};
@@ -10,6 +12,7 @@ use itertools::Itertools;
 use num::BigUint;
 use rand::distributions::{Distribution, Uniform};
 use rand::prelude::*;
+use rand::rngs::SmallRng;
 use std::fmt;
 use std::ops::RangeInclusive;
 
@@ -26,6 +29,17 @@ pub type DefaultAllele = f32;
 /// # Mutation types
 /// See [MutationType]
 ///
+/// # Allele scales
+/// Optionally search genes on a non-linear scale (e.g. log scale for a learning rate) with
+/// `.with_allele_scales(vec![...])`, which applies to random initialization, mutation, neighbours
+/// and permutation grids. See [AlleleScale]
+///
+/// # Gene constraints
+/// Optionally set constraints between genes with `.with_gene_constraints(vec![...])`, like
+/// conditional activation, forbidden value pairs and linear constraints (e.g. weights summing to
+/// 1.0), which are enforced on random initialization, mutation, crossover, HillClimb neighbours
+/// and Permutate enumeration. See [GeneConstraint]
+///
 /// # Permutation
 ///
 /// Supports Permutation for scaled and discrete mutations only. This approach implements a
@@ -152,10 +166,14 @@ where
     pub genes_size: usize,
     pub allele_ranges: Vec<RangeInclusive<T>>,
     pub mutation_types: Vec<MutationType<T>>,
+    pub allele_scales: Vec<AlleleScale>,
     gene_index_sampler: Uniform<usize>,
     allele_samplers: Vec<Uniform<T>>,
     // post-clamped sampler, always positive to support unsigned
     allele_bandwidth_samplers: Vec<Option<Uniform<T>>>,
+    pub gene_constraints: Vec<GeneConstraint<T>>,
+    // feasible genes found by the builder, the fallback when repairing random genes fails
+    feasible_genes: Vec<T>,
     pub current_scale_index: usize,
     pub seed_genes_list: Vec<Vec<T>>,
     pub genes_hashing: bool,
@@ -193,6 +211,34 @@ where
             let mutation_types = builder
                 .mutation_types
                 .unwrap_or(vec![MutationType::Random; genes_size]);
+            let allele_scales = builder
+                .allele_scales
+                .unwrap_or(vec![AlleleScale::Linear; genes_size]);
+            if allele_scales.len() != genes_size {
+                return Err(TryFromBuilderError(
+                    "MultiRangeGenotype allele_scales length must match allele_ranges length",
+                ));
+            }
+            if !allele_scales
+                .iter()
+                .zip(allele_ranges.iter().zip(mutation_types.iter()))
+                .all(|(allele_scale, (allele_range, mutation_type))| {
+                    allele_scale.is_valid_for(allele_range, mutation_type)
+                })
+            {
+                return Err(TryFromBuilderError(
+                    "MultiRangeGenotype allele_scales must be finite and increasing over the allele_ranges (Log requires a positive allele_range)",
+                ));
+            }
+            if !builder
+                .gene_constraints
+                .iter()
+                .all(|gene_constraint| gene_constraint.is_valid_for(genes_size))
+            {
+                return Err(TryFromBuilderError(
+                    "MultiRangeGenotype gene_constraints must refer to distinct gene indices within genes_size (and have finite linear values)",
+                ));
+            }
             let allele_samplers = allele_ranges
                 .iter()
                 .zip(&mutation_types)
@@ -231,18 +277,30 @@ where
                 })
                 .collect();
 
-            Ok(Self {
+            let mut genotype = Self {
                 genes_size,
                 allele_ranges: allele_ranges.clone(),
                 mutation_types: mutation_types.clone(),
+                allele_scales,
                 gene_index_sampler: Uniform::from(0..genes_size),
                 allele_samplers,
                 allele_bandwidth_samplers,
+                gene_constraints: builder.gene_constraints,
+                feasible_genes: vec![],
                 current_scale_index: 0,
                 seed_genes_list: builder.seed_genes_list,
                 genes_hashing: builder.genes_hashing,
                 chromosome_recycling: builder.chromosome_recycling,
-            })
+            };
+            // fail early on infeasible gene_constraints, keep the found genes as fallback
+            if !genotype.gene_constraints.is_empty() {
+                genotype.feasible_genes = genotype
+                    .random_feasible_genes(&mut SmallRng::seed_from_u64(0))
+                    .ok_or(TryFromBuilderError(
+                        "MultiRangeGenotype could not generate random genes satisfying the gene_constraints",
+                    ))?;
+            }
+            Ok(genotype)
         }
     }
 }
@@ -255,14 +313,85 @@ where
         &self.mutation_types
     }
     pub fn sample_gene_random<R: Rng>(&self, index: usize, rng: &mut R) -> T {
+        if !self.allele_scales[index].is_linear() {
+            return self.allele_scales[index].sample_gene_random(
+                &self.allele_ranges[index],
+                &self.mutation_types[index],
+                rng,
+            );
+        }
         match self.mutation_types[index] {
             MutationType::Discrete => self.allele_samplers[index].sample(rng).floor(),
             _ => self.allele_samplers[index].sample(rng),
         }
     }
+    /// Checks the gene_constraints
+    pub fn is_feasible(&self, genes: &[T]) -> bool {
+        gene_constraint::is_feasible(&self.gene_constraints, genes)
+            && gene_constraint::is_linear_feasible(&self.gene_constraints, genes)
+    }
+    /// Repairs the genes towards the gene_constraints, returns false if not feasible. The linear
+    /// constraints are projected on the active, non-Discrete genes
+    pub fn repair_genes<R: Rng>(&self, genes: &mut [T], rng: &mut R) -> bool {
+        gene_constraint::repair_genes(
+            &self.gene_constraints,
+            genes,
+            rng,
+            |index, rng| self.sample_gene_random(index, rng),
+            |genes| {
+                let inactive = gene_constraint::inactive_gene_mask(&self.gene_constraints, genes);
+                gene_constraint::project_linear(
+                    &self.gene_constraints,
+                    genes,
+                    &self.allele_ranges,
+                    |index| {
+                        !inactive[index]
+                            && !matches!(self.mutation_types[index], MutationType::Discrete)
+                    },
+                );
+            },
+            |genes| gene_constraint::is_linear_feasible(&self.gene_constraints, genes),
+        )
+    }
+    /// keep the original genes if the repair fails
+    fn repair_or_restore_genes<R: Rng>(
+        &self,
+        genes: &mut [T],
+        original_genes: Option<Vec<T>>,
+        rng: &mut R,
+    ) {
+        if let Some(original_genes) = original_genes {
+            if !self.repair_genes(genes, rng) {
+                genes.copy_from_slice(&original_genes);
+            }
+        }
+    }
+    fn original_genes(&self, chromosome: &Chromosome<T>) -> Option<Vec<T>> {
+        (!self.gene_constraints.is_empty()).then(|| chromosome.genes.clone())
+    }
+    /// random genes repaired towards the gene_constraints, retrying with new random genes. None
+    /// if no feasible genes are found after MAX_REPAIR_ATTEMPTS
+    fn random_feasible_genes<R: Rng>(&self, rng: &mut R) -> Option<Vec<T>> {
+        (0..gene_constraint::MAX_REPAIR_ATTEMPTS).find_map(|_| {
+            let mut genes: Vec<T> = (0..self.genes_size)
+                .map(|index| self.sample_gene_random(index, rng))
+                .collect();
+            self.repair_genes(&mut genes, rng).then_some(genes)
+        })
+    }
     // all delta's are positive, because we support unsigned integers as RangeAllele
     // quite the overhead to make this work, but I think it is worth it
     pub fn mutate_gene<R: Rng>(&self, chromosome: &mut Chromosome<T>, index: usize, rng: &mut R) {
+        if !self.allele_scales[index].is_linear() {
+            chromosome.genes[index] = self.allele_scales[index].mutate_gene(
+                chromosome.genes[index],
+                &self.allele_ranges[index],
+                &self.mutation_types[index],
+                self.current_scale_index,
+                rng,
+            );
+            return;
+        }
         match &self.mutation_types[index] {
             MutationType::Random => {
                 chromosome.genes[index] = self.allele_samplers[index].sample(rng);
@@ -389,6 +518,7 @@ where
         chromosome: &mut Chromosome<Self::Allele>,
         rng: &mut R,
     ) {
+        let original_genes = self.original_genes(chromosome);
         if allow_duplicates {
             for _ in 0..number_of_mutations {
                 let index = self.gene_index_sampler.sample(rng);
@@ -405,6 +535,7 @@ where
                 self.mutate_gene(chromosome, index, rng);
             });
         }
+        self.repair_or_restore_genes(&mut chromosome.genes, original_genes, rng);
         chromosome.reset_metadata(self.genes_hashing);
     }
     fn set_seed_genes_list(&mut self, seed_genes_list: Vec<Genes<Self::Allele>>) {
@@ -454,9 +585,14 @@ where
 
     fn random_genes_factory<R: Rng>(&self, rng: &mut R) -> Vec<T> {
         if self.seed_genes_list.is_empty() {
-            (0..self.genes_size)
-                .map(|index| self.sample_gene_random(index, rng))
-                .collect()
+            if self.gene_constraints.is_empty() {
+                return (0..self.genes_size)
+                    .map(|index| self.sample_gene_random(index, rng))
+                    .collect();
+            }
+            // rarely satisfiable gene_constraints, fall back to the feasible genes of the builder
+            self.random_feasible_genes(rng)
+                .unwrap_or_else(|| self.feasible_genes.clone())
         } else {
             self.seed_genes_list.choose(rng).unwrap().clone()
         }
@@ -485,6 +621,8 @@ where
         mother: &mut Chromosome<Self::Allele>,
         rng: &mut R,
     ) {
+        let original_father_genes = self.original_genes(father);
+        let original_mother_genes = self.original_genes(mother);
         if allow_duplicates {
             rng.sample_iter(self.gene_index_sampler)
                 .take(number_of_crossovers)
@@ -502,6 +640,8 @@ where
                 std::mem::swap(&mut father.genes[index], &mut mother.genes[index]);
             });
         }
+        self.repair_or_restore_genes(&mut father.genes, original_father_genes, rng);
+        self.repair_or_restore_genes(&mut mother.genes, original_mother_genes, rng);
         mother.reset_metadata(self.genes_hashing);
         father.reset_metadata(self.genes_hashing);
     }
@@ -518,6 +658,8 @@ where
         mother: &mut Chromosome<Self::Allele>,
         rng: &mut R,
     ) {
+        let original_father_genes = self.original_genes(father);
+        let original_mother_genes = self.original_genes(mother);
         if allow_duplicates {
             rng.sample_iter(self.gene_index_sampler)
                 .take(number_of_crossovers)
@@ -550,6 +692,8 @@ where
                 _ => (),
             });
         }
+        self.repair_or_restore_genes(&mut father.genes, original_father_genes, rng);
+        self.repair_or_restore_genes(&mut mother.genes, original_mother_genes, rng);
         mother.reset_metadata(self.genes_hashing);
         father.reset_metadata(self.genes_hashing);
     }
@@ -564,8 +708,15 @@ where
         population: &mut Population<Self::Allele>,
         rng: &mut R,
     ) {
+        let start_index = population.chromosomes.len();
         self.mutation_types.iter().enumerate().for_each(
             |(index, mutation_type)| match mutation_type {
+                MutationType::Discrete => {
+                    self.fill_neighbouring_population_discrete(index, chromosome, population)
+                }
+                _ if !self.allele_scales[index].is_linear() => {
+                    self.fill_neighbouring_population_scaled(index, chromosome, population, rng)
+                }
                 MutationType::Random => {
                     self.fill_neighbouring_population_random(index, chromosome, population, rng)
                 }
@@ -596,11 +747,18 @@ where
                         )
                     }
                 }
-                MutationType::Discrete => {
-                    self.fill_neighbouring_population_discrete(index, chromosome, population)
-                }
             },
         );
+        if !self.gene_constraints.is_empty() {
+            gene_constraint::retain_feasible_neighbours(
+                &self.gene_constraints,
+                chromosome,
+                population,
+                start_index,
+                self.genes_hashing,
+                |genes| self.is_feasible(genes),
+            );
+        }
     }
 
     fn neighbouring_population_size(&self) -> BigUint {
@@ -612,6 +770,29 @@ impl<T: RangeAllele> MultiRange<T>
 where
     Uniform<T>: Send + Sync,
 {
+    fn fill_neighbouring_population_scaled<R: Rng>(
+        &self,
+        index: usize,
+        chromosome: &Chromosome<T>,
+        population: &mut Population<T>,
+        rng: &mut R,
+    ) {
+        self.allele_scales[index]
+            .neighbouring_values(
+                chromosome.genes[index],
+                &self.allele_ranges[index],
+                &self.mutation_types[index],
+                self.current_scale_index,
+                rng,
+            )
+            .into_iter()
+            .for_each(|value| {
+                let mut new_chromosome = population.new_chromosome(chromosome);
+                new_chromosome.genes[index] = value;
+                new_chromosome.reset_metadata(self.genes_hashing);
+                population.chromosomes.push(new_chromosome);
+            });
+    }
     fn fill_neighbouring_population_step(
         &self,
         index: usize,
@@ -778,6 +959,7 @@ where
                         }
                     })
                     .multi_cartesian_product()
+                    .filter(|genes| self.is_feasible(genes))
                     .map(Chromosome::new),
             )
         } else {
@@ -790,6 +972,8 @@ where
         }
     }
 
+    /// An upper bound with gene_constraints, as the permutations only include the feasible
+    /// chromosomes
     fn chromosome_permutations_size(&self) -> BigUint {
         self.chromosome_permutations_size_per_scale().iter().sum()
     }
@@ -826,6 +1010,16 @@ where
     Uniform<T>: Send + Sync,
 {
     pub fn permutable_gene_values_step(&self, index: usize, step: T) -> Vec<T> {
+        let allele_scale = &self.allele_scales[index];
+        if !allele_scale.is_linear() {
+            let (low, high) = allele_scale.scaled_bounds(&self.allele_ranges[index]);
+            return allele_scale.permutable_gene_values(
+                &self.allele_ranges[index],
+                low,
+                high,
+                step.to_f64(),
+            );
+        }
         let allele_range_start = *self.allele_ranges[index].start();
         let allele_range_end = *self.allele_ranges[index].end();
         std::iter::successors(Some(allele_range_start), |value| {
@@ -848,6 +1042,30 @@ where
         chromosome: Option<&Chromosome<T>>,
         steps: &[T],
     ) -> Vec<T> {
+        let allele_scale = &self.allele_scales[index];
+        if !allele_scale.is_linear() {
+            let allele_range = &self.allele_ranges[index];
+            let (low, high) = allele_scale.scaled_bounds(allele_range);
+            let (scaled_start, scaled_end) =
+                match (chromosome, self.current_scale_index.checked_sub(1)) {
+                    (Some(chromosome), Some(previous_scale_index)) => {
+                        let working_step = steps[previous_scale_index].to_f64();
+                        let current_value =
+                            allele_scale.scaled_value(chromosome.genes[index], allele_range);
+                        (
+                            (current_value - working_step).max(low),
+                            (current_value + working_step).min(high),
+                        )
+                    }
+                    _ => (low, high),
+                };
+            return allele_scale.permutable_gene_values(
+                allele_range,
+                scaled_start,
+                scaled_end,
+                steps[self.current_scale_index].to_f64(),
+            );
+        }
         let allele_range_start = *self.allele_ranges[index].start();
         let allele_range_end = *self.allele_ranges[index].end();
         let (allele_value_start, allele_value_end) = if let Some(chromosome) = chromosome {
@@ -924,6 +1142,21 @@ where
             .iter()
             .enumerate()
             .map(|(index, mutation_type)| match mutation_type {
+                MutationType::Step(step) if !self.allele_scales[index].is_linear() => {
+                    let (low, high) =
+                        self.allele_scales[index].scaled_bounds(&self.allele_ranges[index]);
+                    AlleleScale::scaled_grid(low, high, step.to_f64()).count()
+                }
+                MutationType::StepScaled(steps) if !self.allele_scales[index].is_linear() => {
+                    let (scaled_start, scaled_end) =
+                        if let Some(previous_scale_index) = scale_index.checked_sub(1) {
+                            (0.0, 2.0 * steps[previous_scale_index].to_f64())
+                        } else {
+                            self.allele_scales[index].scaled_bounds(&self.allele_ranges[index])
+                        };
+                    AlleleScale::scaled_grid(scaled_start, scaled_end, steps[scale_index].to_f64())
+                        .count()
+                }
                 MutationType::Step(step) => {
                     let allele_value_start = *self.allele_ranges[index].start();
                     let allele_value_end = *self.allele_ranges[index].end();
@@ -998,6 +1231,57 @@ where
     }
 }
 
+impl<T: RangeAllele> EstimateGenotype for MultiRange<T> where Uniform<T>: Send + Sync {}
+
+impl<T: RangeAllele> ParticleSwarmGenotype for MultiRange<T>
+where
+    Uniform<T>: Send + Sync,
+{
+    fn position_bounds(&self) -> Vec<(f64, f64)> {
+        self.allele_ranges
+            .iter()
+            .zip(self.mutation_types.iter())
+            .zip(self.allele_scales.iter())
+            .map(|((allele_range, mutation_type), allele_scale)| {
+                let (start, end) = match mutation_type {
+                    MutationType::Discrete => (
+                        allele_range.start().floor().to_f64(),
+                        allele_range.end().floor().to_f64(),
+                    ),
+                    _ => (allele_range.start().to_f64(), allele_range.end().to_f64()),
+                };
+                (allele_scale.to_scale(start), allele_scale.to_scale(end))
+            })
+            .collect()
+    }
+    fn genes_to_position(&self, genes: &Genes<Self::Allele>) -> Vec<f64> {
+        genes
+            .iter()
+            .zip(self.allele_scales.iter())
+            .map(|(gene, allele_scale)| allele_scale.to_scale(gene.to_f64()))
+            .collect()
+    }
+    fn set_genes_from_position(&self, chromosome: &mut Chromosome<Self::Allele>, position: &[f64]) {
+        chromosome
+            .genes
+            .iter_mut()
+            .zip(position.iter())
+            .zip(self.mutation_types.iter().zip(self.allele_scales.iter()))
+            .for_each(|((gene, value), (mutation_type, allele_scale))| {
+                let value = allele_scale.from_scale(*value);
+                *gene = match mutation_type {
+                    MutationType::Discrete => T::from_f64(value.round()),
+                    _ => T::from_f64(value),
+                }
+            });
+    }
+    /// the particles move freely through the position space, which can't enforce the
+    /// gene_constraints
+    fn allows_particle_swarm(&self) -> bool {
+        self.gene_constraints.is_empty()
+    }
+}
+
 impl<T: RangeAllele> Clone for MultiRange<T>
 where
     Uniform<T>: Send + Sync,
@@ -1047,9 +1331,12 @@ where
             genes_size: self.genes_size,
             allele_ranges: self.allele_ranges.clone(),
             mutation_types: self.mutation_types.clone(),
+            allele_scales: self.allele_scales.clone(),
             gene_index_sampler: self.gene_index_sampler,
             allele_samplers,
             allele_bandwidth_samplers,
+            gene_constraints: self.gene_constraints.clone(),
+            feasible_genes: self.feasible_genes.clone(),
             current_scale_index: self.current_scale_index,
             seed_genes_list: self.seed_genes_list.clone(),
             genes_hashing: self.genes_hashing,
@@ -1067,6 +1354,8 @@ where
             .field("genes_size", &self.genes_size)
             .field("allele_ranges", &self.allele_ranges)
             .field("mutation_types", &self.mutation_types)
+            .field("allele_scales", &self.allele_scales)
+            .field("gene_constraints", &self.gene_constraints)
             .field("seed_genes_list", &self.seed_genes_list)
             .finish()
     }
@@ -1080,6 +1369,8 @@ where
         writeln!(f, "genotype:")?;
         writeln!(f, "  genes_size: {}", self.genes_size)?;
         writeln!(f, "  mutation_types: {:?}", self.mutation_types())?;
+        writeln!(f, "  allele_scales: {:?}", self.allele_scales)?;
+        writeln!(f, "  gene_constraints: {}", self.gene_constraints.len())?;
 
         writeln!(
             f,
@@ -1,7 +1,7 @@
 use super::builder::{Builder, TryFromBuilderError};
 use super::{
-    EvolveGenotype, Genotype, HillClimbGenotype, MutationType, PermutateGenotype,
-    SupportsPointCrossover,
+    EstimateGenotype, EvolveGenotype, Genotype, HillClimbGenotype, MutationType,
+    ParticleSwarmGenotype, PermutateGenotype, SupportsPointCrossover,
 };
 use crate::allele::Allele;
 use crate::chromosome::{Chromosome, Genes};
@@ -104,6 +104,10 @@ impl<T: Allele + Hash> TryFrom<Builder<Self>> for MultiUnique<T> {
             Err(TryFromBuilderError(
                 "MultiUniqueGenotype requires non-empty allele_lists",
             ))
+        } else if !builder.gene_constraints.is_empty() {
+            Err(TryFromBuilderError(
+                "MultiUniqueGenotype does not support gene_constraints",
+            ))
         } else {
             let allele_lists = builder.allele_lists.unwrap();
             let allele_list_sizes: Vec<usize> = allele_lists.iter().map(|v| v.len()).collect();
@@ -428,6 +432,10 @@ impl<T: Allele + Hash> PermutateGenotype for MultiUnique<T> {
     }
 }
 
+impl<T: Allele + Hash> EstimateGenotype for MultiUnique<T> {}
+
+impl<T: Allele + Hash> ParticleSwarmGenotype for MultiUnique<T> {}
+
 impl<T: Allele + Hash> fmt::Display for MultiUnique<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "genotype:")?;
@@ -0,0 +1,340 @@
+use super::builder::{Builder, TryFromBuilderError};
+use super::{
+    EstimateGenotype, EvolveGenotype, Genotype, HillClimbGenotype, MutationType,
+    ParticleSwarmGenotype, PermutateGenotype,
+};
+use crate::allele::Allele;
+use crate::chromosome::{Chromosome, Genes};
+use crate::population::Population;
+use itertools::Itertools;
+use num::BigUint;
+use rand::distributions::{Distribution, Uniform};
+use rand::prelude::*;
+use std::fmt;
+use std::hash::Hash;
+
+pub type DefaultAllele = usize;
+
+/// Genes are an ordered selection of genes_size (k) distinct values out of the allele_list (n
+/// values), for routing with optional stops or sequencing a subset of jobs. Like
+/// [UniqueGenotype](crate::genotype::UniqueGenotype) (which is the case k = n), the values don't
+/// need to be unique, they are treated as positionally unique in the allele_list. Defaults to
+/// usize as item.
+///
+/// On random initialization, k values are sampled without replacement in random order. Each gene
+/// has an equal probability of mutating. If a gene mutates, it is either swapped with another gene
+/// (changing the order) or exchanged with an unused value (changing the selection), with equal
+/// probability when both are possible. The HillClimb neighbours are all gene swaps and all
+/// exchanges with unused values, k * (k - 1) / 2 + k * (n - k) in total. Permutate enumerates all
+/// n! / (n - k)! arrangements.
+///
+/// # Panics
+///
+/// Does not support gene or point crossover. Will panic when tried, but
+/// [EvolveBuilder](crate::strategy::evolve::EvolveBuilder) shouldn't allow this.
+/// Use [CrossoverClone](crate::crossover::CrossoverClone) or
+/// [CrossoverRejuvenate](crate::crossover::CrossoverRejuvenate) instead.
+///
+/// # Example (usize, default):
+/// ```
+/// use genetic_algorithm::genotype::{Genotype, PartialPermutationGenotype};
+///
+/// let genotype = PartialPermutationGenotype::builder()
+///     .with_allele_list((0..100).collect()) // n
+///     .with_genes_size(10) // k
+///     .with_genes_hashing(true) // optional, defaults to true
+///     .with_chromosome_recycling(true) // optional, defaults to true
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct PartialPermutation<T: Allele + PartialEq + Hash = DefaultAllele> {
+    pub genes_size: usize,
+    pub allele_list: Vec<T>,
+    gene_index_sampler: Uniform<usize>,
+    pub seed_genes_list: Vec<Vec<T>>,
+    pub genes_hashing: bool,
+    pub chromosome_recycling: bool,
+}
+
+impl<T: Allele + PartialEq + Hash> TryFrom<Builder<Self>> for PartialPermutation<T> {
+    type Error = TryFromBuilderError;
+
+    fn try_from(builder: Builder<Self>) -> Result<Self, Self::Error> {
+        if builder.allele_list.is_none() {
+            if builder.allele_lists.is_some() {
+                Err(TryFromBuilderError(
+                    "PartialPermutationGenotype requires with_allele_list (singular), not with_allele_lists",
+                ))
+            } else {
+                Err(TryFromBuilderError(
+                    "PartialPermutationGenotype requires allele_list",
+                ))
+            }
+        } else if builder.allele_list.as_ref().map(|o| o.is_empty()).unwrap() {
+            Err(TryFromBuilderError(
+                "PartialPermutationGenotype requires non-empty allele_list",
+            ))
+        } else if !builder.genes_size.is_some_and(|x| x > 0) {
+            Err(TryFromBuilderError(
+                "PartialPermutationGenotype requires a genes_size > 0",
+            ))
+        } else if builder.genes_size.unwrap() > builder.allele_list.as_ref().unwrap().len() {
+            Err(TryFromBuilderError(
+                "PartialPermutationGenotype requires a genes_size <= allele_list length",
+            ))
+        } else if !builder.gene_constraints.is_empty() {
+            Err(TryFromBuilderError(
+                "PartialPermutationGenotype does not support gene_constraints",
+            ))
+        } else {
+            let genes_size = builder.genes_size.unwrap();
+            Ok(Self {
+                genes_size,
+                allele_list: builder.allele_list.unwrap(),
+                gene_index_sampler: Uniform::from(0..genes_size),
+                seed_genes_list: builder.seed_genes_list,
+                genes_hashing: builder.genes_hashing,
+                chromosome_recycling: builder.chromosome_recycling,
+            })
+        }
+    }
+}
+
+impl<T: Allele + PartialEq + Hash> PartialPermutation<T> {
+    fn mutation_type(&self) -> &MutationType<T> {
+        &MutationType::Random
+    }
+    /// the allele_list positions of the genes and the unused allele_list positions. Each gene is
+    /// matched to the first unused position with its value (genes not in the allele_list take the
+    /// first unused position)
+    fn allele_positions(&self, genes: &[T]) -> (Vec<usize>, Vec<usize>) {
+        let mut used = vec![false; self.allele_list.len()];
+        let mut positions: Vec<Option<usize>> = genes
+            .iter()
+            .map(|gene| {
+                let position = (0..self.allele_list.len())
+                    .find(|i| !used[*i] && self.allele_list[*i] == *gene)?;
+                used[position] = true;
+                Some(position)
+            })
+            .collect();
+        positions.iter_mut().filter(|p| p.is_none()).for_each(|p| {
+            let position = used.iter().position(|u| !u).unwrap();
+            used[position] = true;
+            *p = Some(position);
+        });
+        let unused = (0..self.allele_list.len()).filter(|i| !used[*i]).collect();
+        (positions.into_iter().flatten().collect(), unused)
+    }
+    /// swap with another gene or exchange with an unused value
+    fn mutate_position<R: Rng>(
+        &self,
+        index: usize,
+        positions: &mut [usize],
+        unused: &mut [usize],
+        rng: &mut R,
+    ) {
+        let exchange = match (unused.is_empty(), positions.len() < 2) {
+            (true, true) => return,
+            (true, false) => false,
+            (false, true) => true,
+            (false, false) => rng.gen(),
+        };
+        if exchange {
+            let unused_index = rng.gen_range(0..unused.len());
+            std::mem::swap(&mut positions[index], &mut unused[unused_index]);
+        } else {
+            let other_index = rng.gen_range(0..positions.len() - 1);
+            let other_index = if other_index >= index {
+                other_index + 1
+            } else {
+                other_index
+            };
+            positions.swap(index, other_index);
+        }
+    }
+}
+
+impl<T: Allele + PartialEq + Hash> Genotype for PartialPermutation<T> {
+    type Allele = T;
+
+    fn genes_size(&self) -> usize {
+        self.genes_size
+    }
+    fn sample_gene_index<R: Rng>(&self, rng: &mut R) -> usize {
+        self.gene_index_sampler.sample(rng)
+    }
+    fn sample_gene_indices<R: Rng>(
+        &self,
+        count: usize,
+        allow_duplicates: bool,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        if allow_duplicates {
+            rng.sample_iter(self.gene_index_sampler)
+                .take(count)
+                .collect()
+        } else {
+            rand::seq::index::sample(rng, self.genes_size, count.min(self.genes_size)).into_vec()
+        }
+    }
+
+    fn mutate_chromosome_genes<R: Rng>(
+        &self,
+        number_of_mutations: usize,
+        allow_duplicates: bool,
+        chromosome: &mut Chromosome<Self::Allele>,
+        rng: &mut R,
+    ) {
+        let (mut positions, mut unused) = self.allele_positions(&chromosome.genes);
+        if allow_duplicates {
+            for _ in 0..number_of_mutations {
+                let index = self.gene_index_sampler.sample(rng);
+                self.mutate_position(index, &mut positions, &mut unused, rng);
+            }
+        } else {
+            rand::seq::index::sample(
+                rng,
+                self.genes_size,
+                number_of_mutations.min(self.genes_size),
+            )
+            .iter()
+            .for_each(|index| self.mutate_position(index, &mut positions, &mut unused, rng));
+        }
+        chromosome
+            .genes
+            .iter_mut()
+            .zip(positions)
+            .for_each(|(gene, position)| *gene = self.allele_list[position]);
+        chromosome.reset_metadata(self.genes_hashing);
+    }
+    fn set_seed_genes_list(&mut self, seed_genes_list: Vec<Genes<Self::Allele>>) {
+        self.seed_genes_list = seed_genes_list;
+    }
+    fn seed_genes_list(&self) -> &Vec<Genes<Self::Allele>> {
+        &self.seed_genes_list
+    }
+    fn set_genes_hashing(&mut self, genes_hashing: bool) {
+        self.genes_hashing = genes_hashing;
+    }
+    fn random_genes_factory<R: Rng>(&self, rng: &mut R) -> Vec<T> {
+        if self.seed_genes_list.is_empty() {
+            rand::seq::index::sample(rng, self.allele_list.len(), self.genes_size)
+                .into_iter()
+                .map(|index| self.allele_list[index])
+                .collect()
+        } else {
+            self.seed_genes_list.choose(rng).unwrap().clone()
+        }
+    }
+    fn genes_capacity(&self) -> usize {
+        self.genes_size
+    }
+    fn genes_hashing(&self) -> bool {
+        self.genes_hashing
+    }
+    fn chromosome_recycling(&self) -> bool {
+        self.chromosome_recycling
+    }
+}
+
+impl<T: Allele + PartialEq + Hash> EvolveGenotype for PartialPermutation<T> {}
+impl<T: Allele + PartialEq + Hash> HillClimbGenotype for PartialPermutation<T> {
+    fn fill_neighbouring_population<R: Rng>(
+        &self,
+        chromosome: &Chromosome<Self::Allele>,
+        population: &mut Population<Self::Allele>,
+        _rng: &mut R,
+    ) {
+        (0..self.genes_size())
+            .tuple_combinations()
+            .for_each(|(first, second)| {
+                let mut new_chromosome = population.new_chromosome(chromosome);
+                new_chromosome.genes.swap(first, second);
+                new_chromosome.reset_metadata(self.genes_hashing);
+                population.chromosomes.push(new_chromosome);
+            });
+        let (_positions, unused) = self.allele_positions(&chromosome.genes);
+        for index in 0..self.genes_size() {
+            for position in unused.iter() {
+                let mut new_chromosome = population.new_chromosome(chromosome);
+                new_chromosome.genes[index] = self.allele_list[*position];
+                new_chromosome.reset_metadata(self.genes_hashing);
+                population.chromosomes.push(new_chromosome);
+            }
+        }
+    }
+
+    fn neighbouring_population_size(&self) -> BigUint {
+        let k = self.genes_size;
+        let n = self.allele_list.len();
+        BigUint::from(k * (k - 1) / 2 + k * (n - k))
+    }
+}
+
+impl<T: Allele + PartialEq + Hash> PermutateGenotype for PartialPermutation<T> {
+    fn chromosome_permutations_into_iter<'a>(
+        &'a self,
+        _chromosome: Option<&Chromosome<Self::Allele>>,
+    ) -> Box<dyn Iterator<Item = Chromosome<Self::Allele>> + Send + 'a> {
+        if self.seed_genes_list.is_empty() {
+            Box::new(
+                self.allele_list
+                    .clone()
+                    .into_iter()
+                    .permutations(self.genes_size())
+                    .map(Chromosome::new),
+            )
+        } else {
+            Box::new(
+                self.seed_genes_list
+                    .clone()
+                    .into_iter()
+                    .map(Chromosome::new),
+            )
+        }
+    }
+
+    /// n! / (n - k)!, the product of (n - k + 1)..=n
+    fn chromosome_permutations_size(&self) -> BigUint {
+        if self.seed_genes_list.is_empty() {
+            let n = self.allele_list.len();
+            ((n - self.genes_size + 1)..=n).map(BigUint::from).product()
+        } else {
+            self.seed_genes_list.len().into()
+        }
+    }
+    fn allows_permutation(&self) -> bool {
+        true
+    }
+}
+
+impl<T: Allele + PartialEq + Hash> EstimateGenotype for PartialPermutation<T> {}
+
+impl<T: Allele + PartialEq + Hash> ParticleSwarmGenotype for PartialPermutation<T> {}
+
+impl<T: Allele + PartialEq + Hash> fmt::Display for PartialPermutation<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "genotype:")?;
+        writeln!(f, "  genes_size: {}", self.genes_size)?;
+        writeln!(f, "  allele_list_size: {}", self.allele_list.len())?;
+        writeln!(f, "  mutation_type: {:?}", self.mutation_type())?;
+        writeln!(
+            f,
+            "  chromosome_permutations_size: {}",
+            self.chromosome_permutations_size_report()
+        )?;
+        writeln!(
+            f,
+            "  neighbouring_population_size: {}",
+            self.neighbouring_population_size_report()
+        )?;
+        writeln!(
+            f,
+            "  expected_number_of_sampled_index_duplicates: {}",
+            self.expected_number_of_sampled_index_duplicates_report()
+        )?;
+        writeln!(f, "  seed_genes: {:?}", self.seed_genes_list.len())
+    }
+}
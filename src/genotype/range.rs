@@ -1,7 +1,7 @@
 use super::builder::{Builder, TryFromBuilderError};
 use super::{
-    EvolveGenotype, Genotype, HillClimbGenotype, MutationType, PermutateGenotype,
-    SupportsGeneCrossover, SupportsPointCrossover,
+    AlleleScale, EstimateGenotype, EvolveGenotype, Genotype, HillClimbGenotype, MutationType,
+    ParticleSwarmGenotype, PermutateGenotype, SupportsGeneCrossover, SupportsPointCrossover,
 };
 use crate::allele::RangeAllele;
 use crate::chromosome::{Chromosome, Genes};
@@ -23,6 +23,10 @@ pub type DefaultAllele = f32;
 /// # Mutation types
 /// See [MutationType]
 ///
+/// # Allele scale
+/// Optionally search on a non-linear scale (e.g. log scale for a learning rate), which applies to
+/// random initialization, mutation, neighbours and permutation grids. See [AlleleScale]
+///
 /// # Permutation
 ///
 /// Supports Permutation for scaled and discrete mutations only. This approach implements a
@@ -44,7 +48,7 @@ pub type DefaultAllele = f32;
 ///
 /// # Example (f32, default):
 /// ```
-/// use genetic_algorithm::genotype::{Genotype, RangeGenotype, MutationType};
+/// use genetic_algorithm::genotype::{AlleleScale, Genotype, RangeGenotype, MutationType};
 ///
 /// let genotype = RangeGenotype::builder()
 ///     .with_genes_size(100)
@@ -54,6 +58,7 @@ pub type DefaultAllele = f32;
 ///     .with_mutation_type(MutationType::Range(0.1)) // optional, restricts mutations to a smaller relative range bandwidth: [-0.1..=0.1] uniformly sampled
 ///     .with_mutation_type(MutationType::StepScaled(vec![0.1, 0.01, 0.001])) // optional, restricts mutations to relative step up or down of each scale
 ///     .with_mutation_type(MutationType::RangeScaled(vec![1.0, 1.0, 0.1, 0.1, 0.01])) // optional, optional, restricts mutations to relative bandwidth up or down of each scale
+///     .with_allele_scale(AlleleScale::Linear) // optional, defaults to Linear
 ///     .with_genes_hashing(true) // optional, defaults to true
 ///     .with_chromosome_recycling(true) // optional, defaults to true
 ///     .build()
@@ -84,6 +89,7 @@ where
     pub genes_size: usize,
     pub allele_range: RangeInclusive<T>,
     pub mutation_type: MutationType<T>,
+    pub allele_scale: AlleleScale,
     gene_index_sampler: Uniform<usize>,
     allele_sampler: Uniform<T>,
     // post-clamped sampler, always positive to support unsigned
@@ -107,10 +113,20 @@ where
             ))
         } else if builder.allele_range.is_none() {
             Err(TryFromBuilderError("RangeGenotype requires a allele_range"))
+        } else if !builder.gene_constraints.is_empty() {
+            Err(TryFromBuilderError(
+                "RangeGenotype does not support gene_constraints",
+            ))
         } else {
             let genes_size = builder.genes_size.unwrap();
             let allele_range = builder.allele_range.unwrap();
             let mutation_type = builder.mutation_type.unwrap_or(MutationType::Random);
+            let allele_scale = builder.allele_scale.unwrap_or_default();
+            if !allele_scale.is_valid_for(&allele_range, &mutation_type) {
+                return Err(TryFromBuilderError(
+                    "RangeGenotype allele_scale must be finite and increasing over the allele_range (Log requires a positive allele_range)",
+                ));
+            }
             let allele_sampler = match mutation_type {
                 MutationType::Discrete => {
                     // [start, end+1) for uniform floor() sampling
@@ -144,6 +160,7 @@ where
                 genes_size,
                 allele_range: allele_range.clone(),
                 mutation_type,
+                allele_scale,
                 gene_index_sampler: Uniform::from(0..genes_size),
                 allele_sampler,
                 allele_bandwidth_sampler,
@@ -164,6 +181,13 @@ where
         &self.mutation_type
     }
     pub fn sample_gene_random<R: Rng>(&self, rng: &mut R) -> T {
+        if !self.allele_scale.is_linear() {
+            return self.allele_scale.sample_gene_random(
+                &self.allele_range,
+                &self.mutation_type,
+                rng,
+            );
+        }
         match self.mutation_type {
             MutationType::Discrete => self.allele_sampler.sample(rng).floor(),
             _ => self.allele_sampler.sample(rng),
@@ -173,6 +197,16 @@ where
     // all delta's are positive, because we support unsigned integers as RangeAllele
     // quite the overhead to make this work, but I think it is worth it
     pub fn mutate_gene<R: Rng>(&self, chromosome: &mut Chromosome<T>, index: usize, rng: &mut R) {
+        if !self.allele_scale.is_linear() {
+            chromosome.genes[index] = self.allele_scale.mutate_gene(
+                chromosome.genes[index],
+                &self.allele_range,
+                &self.mutation_type,
+                self.current_scale_index,
+                rng,
+            );
+            return;
+        }
         match &self.mutation_type {
             MutationType::Random => {
                 chromosome.genes[index] = self.allele_sampler.sample(rng);
@@ -459,6 +493,9 @@ where
         population: &mut Population<Self::Allele>,
         rng: &mut R,
     ) {
+        if !self.allele_scale.is_linear() && self.mutation_type != MutationType::Discrete {
+            return self.fill_neighbouring_population_scaled(chromosome, population, rng);
+        }
         match &self.mutation_type {
             MutationType::Random => {
                 self.fill_neighbouring_population_random(chromosome, population, rng)
@@ -501,6 +538,30 @@ impl<T: RangeAllele> Range<T>
 where
     Uniform<T>: Send + Sync,
 {
+    fn fill_neighbouring_population_scaled<R: Rng>(
+        &self,
+        chromosome: &Chromosome<T>,
+        population: &mut Population<T>,
+        rng: &mut R,
+    ) {
+        (0..self.genes_size).for_each(|index| {
+            self.allele_scale
+                .neighbouring_values(
+                    chromosome.genes[index],
+                    &self.allele_range,
+                    &self.mutation_type,
+                    self.current_scale_index,
+                    rng,
+                )
+                .into_iter()
+                .for_each(|value| {
+                    let mut new_chromosome = population.new_chromosome(chromosome);
+                    new_chromosome.genes[index] = value;
+                    new_chromosome.reset_metadata(self.genes_hashing);
+                    population.chromosomes.push(new_chromosome);
+                });
+        });
+    }
     fn fill_neighbouring_population_step(
         &self,
         chromosome: &Chromosome<T>,
@@ -721,6 +782,15 @@ where
     Uniform<T>: Send + Sync,
 {
     pub fn permutable_gene_values_step(&self, step: T) -> Vec<T> {
+        if !self.allele_scale.is_linear() {
+            let (low, high) = self.allele_scale.scaled_bounds(&self.allele_range);
+            return self.allele_scale.permutable_gene_values(
+                &self.allele_range,
+                low,
+                high,
+                step.to_f64(),
+            );
+        }
         let allele_range_start = *self.allele_range.start();
         let allele_range_end = *self.allele_range.end();
         std::iter::successors(Some(allele_range_start), |value| {
@@ -743,6 +813,29 @@ where
         chromosome: Option<&Chromosome<T>>,
         steps: &[T],
     ) -> Vec<T> {
+        if !self.allele_scale.is_linear() {
+            let (low, high) = self.allele_scale.scaled_bounds(&self.allele_range);
+            let (scaled_start, scaled_end) =
+                match (chromosome, self.current_scale_index.checked_sub(1)) {
+                    (Some(chromosome), Some(previous_scale_index)) => {
+                        let working_step = steps[previous_scale_index].to_f64();
+                        let current_value = self
+                            .allele_scale
+                            .scaled_value(chromosome.genes[index], &self.allele_range);
+                        (
+                            (current_value - working_step).max(low),
+                            (current_value + working_step).min(high),
+                        )
+                    }
+                    _ => (low, high),
+                };
+            return self.allele_scale.permutable_gene_values(
+                &self.allele_range,
+                scaled_start,
+                scaled_end,
+                steps[self.current_scale_index].to_f64(),
+            );
+        }
         let allele_range_start = *self.allele_range.start();
         let allele_range_end = *self.allele_range.end();
         let (allele_value_start, allele_value_end) = if let Some(chromosome) = chromosome {
@@ -813,6 +906,20 @@ where
     pub fn chromosome_permutations_size_for_scale_index(&self, scale_index: usize) -> BigUint {
         BigUint::from(
             match &self.mutation_type {
+                MutationType::Step(step) if !self.allele_scale.is_linear() => {
+                    let (low, high) = self.allele_scale.scaled_bounds(&self.allele_range);
+                    AlleleScale::scaled_grid(low, high, step.to_f64()).count()
+                }
+                MutationType::StepScaled(steps) if !self.allele_scale.is_linear() => {
+                    let (scaled_start, scaled_end) =
+                        if let Some(previous_scale_index) = scale_index.checked_sub(1) {
+                            (0.0, 2.0 * steps[previous_scale_index].to_f64())
+                        } else {
+                            self.allele_scale.scaled_bounds(&self.allele_range)
+                        };
+                    AlleleScale::scaled_grid(scaled_start, scaled_end, steps[scale_index].to_f64())
+                        .count()
+                }
                 MutationType::Step(step) => {
                     let allele_range_start = *self.allele_range.start();
                     let allele_range_end = *self.allele_range.end();
@@ -884,6 +991,64 @@ where
     }
 }
 
+impl<T: RangeAllele> EstimateGenotype for Range<T> where Uniform<T>: Send + Sync {}
+
+impl<T: RangeAllele> ParticleSwarmGenotype for Range<T>
+where
+    Uniform<T>: Send + Sync,
+{
+    fn position_bounds(&self) -> Vec<(f64, f64)> {
+        let bounds = match self.mutation_type {
+            _ if !self.allele_scale.is_linear() => {
+                let (start, end) = match self.mutation_type {
+                    MutationType::Discrete => (
+                        self.allele_range.start().floor(),
+                        self.allele_range.end().floor(),
+                    ),
+                    _ => (*self.allele_range.start(), *self.allele_range.end()),
+                };
+                (
+                    self.allele_scale.to_scale(start.to_f64()),
+                    self.allele_scale.to_scale(end.to_f64()),
+                )
+            }
+            MutationType::Discrete => (
+                self.allele_range.start().floor().to_f64(),
+                self.allele_range.end().floor().to_f64(),
+            ),
+            _ => (
+                self.allele_range.start().to_f64(),
+                self.allele_range.end().to_f64(),
+            ),
+        };
+        vec![bounds; self.genes_size]
+    }
+    fn genes_to_position(&self, genes: &Genes<Self::Allele>) -> Vec<f64> {
+        genes
+            .iter()
+            .map(|gene| self.allele_scale.to_scale(gene.to_f64()))
+            .collect()
+    }
+    fn set_genes_from_position(&self, chromosome: &mut Chromosome<Self::Allele>, position: &[f64]) {
+        let discrete = matches!(self.mutation_type, MutationType::Discrete);
+        chromosome
+            .genes
+            .iter_mut()
+            .zip(position.iter())
+            .for_each(|(gene, value)| {
+                let value = self.allele_scale.from_scale(*value);
+                *gene = if discrete {
+                    T::from_f64(value.round())
+                } else {
+                    T::from_f64(value)
+                }
+            });
+    }
+    fn allows_particle_swarm(&self) -> bool {
+        true
+    }
+}
+
 impl<T: RangeAllele> Clone for Range<T>
 where
     Uniform<T>: Send + Sync,
@@ -925,6 +1090,7 @@ where
             genes_size: self.genes_size,
             allele_range: self.allele_range.clone(),
             mutation_type: self.mutation_type.clone(),
+            allele_scale: self.allele_scale,
             gene_index_sampler: self.gene_index_sampler,
             allele_sampler,
             allele_bandwidth_sampler,
@@ -945,6 +1111,7 @@ where
             .field("genes_size", &self.genes_size)
             .field("allele_range", &self.allele_range)
             .field("mutation_type", &self.mutation_type)
+            .field("allele_scale", &self.allele_scale)
             .field("seed_genes_list", &self.seed_genes_list)
             .finish()
     }
@@ -958,6 +1125,7 @@ where
         writeln!(f, "genotype:")?;
         writeln!(f, "  genes_size: {}", self.genes_size)?;
         writeln!(f, "  mutation_type: {:?}", self.mutation_type())?;
+        writeln!(f, "  allele_scale: {:?}", self.allele_scale)?;
 
         writeln!(
             f,
@@ -0,0 +1,403 @@
+use super::builder::{Builder, TryFromBuilderError};
+use super::{
+    EstimateGenotype, EvolveGenotype, Genotype, HillClimbGenotype, MutationType,
+    ParticleSwarmGenotype, PermutateGenotype, SupportsGeneCrossover,
+};
+use crate::allele::Allele;
+use crate::chromosome::{Chromosome, Genes};
+use crate::population::Population;
+use itertools::Itertools;
+use num::BigUint;
+use rand::distributions::{Distribution, Uniform};
+use rand::prelude::*;
+use std::fmt;
+use std::hash::Hash;
+
+pub type DefaultAllele = usize;
+
+/// Genes are a selection of exactly genes_size (k) values out of the allele_list (n values), for
+/// "choose exactly k of n" problems (e.g. portfolio, facility location, team selection). The genes
+/// are kept in allele_list order, so each selection has a single representation (relevant for
+/// genes hashing and the fitness cache). Like [UniqueGenotype](crate::genotype::UniqueGenotype),
+/// the values don't need to be unique, they are treated as positionally unique in the
+/// allele_list. Defaults to usize as item.
+///
+/// On random initialization, k values are sampled without replacement. If a gene mutates, a
+/// selected value is swapped with an unselected value, preserving the selection size. Gene
+/// crossover exchanges values between the parents which are only selected by one of them (the
+/// values selected by both are kept), also preserving the selection size. The HillClimb
+/// neighbours are all the in/out swaps, k * (n - k) in total. Permutate enumerates all C(n, k)
+/// selections.
+///
+/// # Panics
+///
+/// Does not support point crossover. Will panic when tried, but
+/// [EvolveBuilder](crate::strategy::evolve::EvolveBuilder) shouldn't allow this.
+///
+/// # Example (usize, default):
+/// ```
+/// use genetic_algorithm::genotype::{Genotype, SubsetGenotype};
+///
+/// let genotype = SubsetGenotype::builder()
+///     .with_allele_list((0..100).collect()) // n
+///     .with_genes_size(10) // k
+///     .with_genes_hashing(true) // optional, defaults to true
+///     .with_chromosome_recycling(true) // optional, defaults to true
+///     .build()
+///     .unwrap();
+/// ```
+///
+/// # Example (struct)
+/// ```
+/// use genetic_algorithm::genotype::{Allele, Genotype, SubsetGenotype};
+///
+/// #[derive(Clone, Copy, PartialEq, Hash, Debug)]
+/// struct Item(pub u16, pub u16);
+/// genetic_algorithm::impl_allele!(Item);
+///
+/// let genotype = SubsetGenotype::builder()
+///     .with_allele_list(vec![
+///         Item(23, 505),
+///         Item(26, 352),
+///         Item(20, 458),
+///     ])
+///     .with_genes_size(2)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Subset<T: Allele + PartialEq + Hash = DefaultAllele> {
+    pub genes_size: usize,
+    pub allele_list: Vec<T>,
+    gene_index_sampler: Uniform<usize>,
+    pub seed_genes_list: Vec<Vec<T>>,
+    pub genes_hashing: bool,
+    pub chromosome_recycling: bool,
+}
+
+impl<T: Allele + PartialEq + Hash> TryFrom<Builder<Self>> for Subset<T> {
+    type Error = TryFromBuilderError;
+
+    fn try_from(builder: Builder<Self>) -> Result<Self, Self::Error> {
+        if builder.allele_list.is_none() {
+            if builder.allele_lists.is_some() {
+                Err(TryFromBuilderError(
+                    "SubsetGenotype requires with_allele_list (singular), not with_allele_lists",
+                ))
+            } else {
+                Err(TryFromBuilderError("SubsetGenotype requires allele_list"))
+            }
+        } else if builder.allele_list.as_ref().map(|o| o.is_empty()).unwrap() {
+            Err(TryFromBuilderError(
+                "SubsetGenotype requires non-empty allele_list",
+            ))
+        } else if !builder.genes_size.is_some_and(|x| x > 0) {
+            Err(TryFromBuilderError(
+                "SubsetGenotype requires a genes_size > 0",
+            ))
+        } else if builder.genes_size.unwrap() > builder.allele_list.as_ref().unwrap().len() {
+            Err(TryFromBuilderError(
+                "SubsetGenotype requires a genes_size <= allele_list length",
+            ))
+        } else if !builder.gene_constraints.is_empty() {
+            Err(TryFromBuilderError(
+                "SubsetGenotype does not support gene_constraints",
+            ))
+        } else {
+            let genes_size = builder.genes_size.unwrap();
+            Ok(Self {
+                genes_size,
+                allele_list: builder.allele_list.unwrap(),
+                gene_index_sampler: Uniform::from(0..genes_size),
+                seed_genes_list: builder.seed_genes_list,
+                genes_hashing: builder.genes_hashing,
+                chromosome_recycling: builder.chromosome_recycling,
+            })
+        }
+    }
+}
+
+impl<T: Allele + PartialEq + Hash> Subset<T> {
+    fn mutation_type(&self) -> &MutationType<T> {
+        &MutationType::Random
+    }
+    /// the allele_list positions selected by the genes. Genes in allele_list order are matched in
+    /// a single pass, others (e.g. manual seed genes) are matched to the first unselected position
+    pub fn selection_mask(&self, genes: &[T]) -> Vec<bool> {
+        let mut mask = vec![false; self.allele_list.len()];
+        let mut genes_iter = genes.iter().peekable();
+        for (index, allele) in self.allele_list.iter().enumerate() {
+            if genes_iter.peek().is_some_and(|gene| *gene == allele) {
+                mask[index] = true;
+                genes_iter.next();
+            }
+        }
+        for gene in genes_iter {
+            if let Some(index) =
+                (0..self.allele_list.len()).find(|i| !mask[*i] && self.allele_list[*i] == *gene)
+            {
+                mask[index] = true;
+            }
+        }
+        mask
+    }
+    /// the selected and unselected allele_list positions
+    fn selection_indices(&self, genes: &[T]) -> (Vec<usize>, Vec<usize>) {
+        self.selection_mask(genes)
+            .into_iter()
+            .enumerate()
+            .partition_map(|(index, selected)| {
+                if selected {
+                    itertools::Either::Left(index)
+                } else {
+                    itertools::Either::Right(index)
+                }
+            })
+    }
+    /// set the genes to the selected allele_list positions, in allele_list order
+    fn set_genes(&self, genes: &mut Genes<T>, selected_indices: &[usize]) {
+        let mut mask = vec![false; self.allele_list.len()];
+        selected_indices
+            .iter()
+            .for_each(|index| mask[*index] = true);
+        genes.clear();
+        genes.extend(
+            self.allele_list
+                .iter()
+                .zip(mask)
+                .filter_map(|(allele, selected)| selected.then_some(*allele)),
+        );
+    }
+    /// exchange count values between both sides, the values stay unique over both sides
+    fn exchange<R: Rng>(
+        count: usize,
+        allow_duplicates: bool,
+        left: &mut [usize],
+        right: &mut [usize],
+        rng: &mut R,
+    ) {
+        if left.is_empty() || right.is_empty() {
+            return;
+        }
+        if allow_duplicates {
+            for _ in 0..count {
+                let left_index = rng.gen_range(0..left.len());
+                let right_index = rng.gen_range(0..right.len());
+                std::mem::swap(&mut left[left_index], &mut right[right_index]);
+            }
+        } else {
+            let count = count.min(left.len()).min(right.len());
+            let left_indices = rand::seq::index::sample(rng, left.len(), count);
+            let right_indices = rand::seq::index::sample(rng, right.len(), count);
+            left_indices
+                .iter()
+                .zip(right_indices.iter())
+                .for_each(|(left_index, right_index)| {
+                    std::mem::swap(&mut left[left_index], &mut right[right_index]);
+                });
+        }
+    }
+}
+
+impl<T: Allele + PartialEq + Hash> Genotype for Subset<T> {
+    type Allele = T;
+
+    fn genes_size(&self) -> usize {
+        self.genes_size
+    }
+    fn sample_gene_index<R: Rng>(&self, rng: &mut R) -> usize {
+        self.gene_index_sampler.sample(rng)
+    }
+    fn sample_gene_indices<R: Rng>(
+        &self,
+        count: usize,
+        allow_duplicates: bool,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        if allow_duplicates {
+            rng.sample_iter(self.gene_index_sampler)
+                .take(count)
+                .collect()
+        } else {
+            rand::seq::index::sample(rng, self.genes_size, count.min(self.genes_size)).into_vec()
+        }
+    }
+
+    fn mutate_chromosome_genes<R: Rng>(
+        &self,
+        number_of_mutations: usize,
+        allow_duplicates: bool,
+        chromosome: &mut Chromosome<Self::Allele>,
+        rng: &mut R,
+    ) {
+        let (mut selected, mut unselected) = self.selection_indices(&chromosome.genes);
+        Self::exchange(
+            number_of_mutations,
+            allow_duplicates,
+            &mut selected,
+            &mut unselected,
+            rng,
+        );
+        self.set_genes(&mut chromosome.genes, &selected);
+        chromosome.reset_metadata(self.genes_hashing);
+    }
+    fn set_seed_genes_list(&mut self, seed_genes_list: Vec<Genes<Self::Allele>>) {
+        self.seed_genes_list = seed_genes_list;
+    }
+    fn seed_genes_list(&self) -> &Vec<Genes<Self::Allele>> {
+        &self.seed_genes_list
+    }
+    fn set_genes_hashing(&mut self, genes_hashing: bool) {
+        self.genes_hashing = genes_hashing;
+    }
+    fn random_genes_factory<R: Rng>(&self, rng: &mut R) -> Vec<T> {
+        if self.seed_genes_list.is_empty() {
+            rand::seq::index::sample(rng, self.allele_list.len(), self.genes_size)
+                .into_iter()
+                .sorted_unstable()
+                .map(|index| self.allele_list[index])
+                .collect()
+        } else {
+            self.seed_genes_list.choose(rng).unwrap().clone()
+        }
+    }
+    fn genes_capacity(&self) -> usize {
+        self.genes_size
+    }
+    fn genes_hashing(&self) -> bool {
+        self.genes_hashing
+    }
+    fn chromosome_recycling(&self) -> bool {
+        self.chromosome_recycling
+    }
+}
+
+impl<T: Allele + PartialEq + Hash> EvolveGenotype for Subset<T> {}
+impl<T: Allele + PartialEq + Hash> SupportsGeneCrossover for Subset<T> {
+    fn crossover_chromosome_genes<R: Rng>(
+        &self,
+        number_of_crossovers: usize,
+        allow_duplicates: bool,
+        father: &mut Chromosome<Self::Allele>,
+        mother: &mut Chromosome<Self::Allele>,
+        rng: &mut R,
+    ) {
+        let father_mask = self.selection_mask(&father.genes);
+        let mother_mask = self.selection_mask(&mother.genes);
+        let mut shared = vec![];
+        let mut father_only = vec![];
+        let mut mother_only = vec![];
+        for (index, selection) in father_mask.into_iter().zip(mother_mask).enumerate() {
+            match selection {
+                (true, true) => shared.push(index),
+                (true, false) => father_only.push(index),
+                (false, true) => mother_only.push(index),
+                (false, false) => (),
+            }
+        }
+        Self::exchange(
+            number_of_crossovers,
+            allow_duplicates,
+            &mut father_only,
+            &mut mother_only,
+            rng,
+        );
+        father_only.extend_from_slice(&shared);
+        mother_only.extend_from_slice(&shared);
+        self.set_genes(&mut father.genes, &father_only);
+        self.set_genes(&mut mother.genes, &mother_only);
+        mother.reset_metadata(self.genes_hashing);
+        father.reset_metadata(self.genes_hashing);
+    }
+}
+impl<T: Allele + PartialEq + Hash> HillClimbGenotype for Subset<T> {
+    fn fill_neighbouring_population<R: Rng>(
+        &self,
+        chromosome: &Chromosome<Self::Allele>,
+        population: &mut Population<Self::Allele>,
+        _rng: &mut R,
+    ) {
+        let (selected, unselected) = self.selection_indices(&chromosome.genes);
+        for selected_index in 0..selected.len() {
+            for unselected_index in unselected.iter() {
+                let mut new_selected = selected.clone();
+                new_selected[selected_index] = *unselected_index;
+                let mut new_chromosome = population.new_chromosome(chromosome);
+                self.set_genes(&mut new_chromosome.genes, &new_selected);
+                new_chromosome.reset_metadata(self.genes_hashing);
+                population.chromosomes.push(new_chromosome);
+            }
+        }
+    }
+
+    fn neighbouring_population_size(&self) -> BigUint {
+        BigUint::from(self.genes_size * (self.allele_list.len() - self.genes_size))
+    }
+}
+
+impl<T: Allele + PartialEq + Hash> PermutateGenotype for Subset<T> {
+    fn chromosome_permutations_into_iter<'a>(
+        &'a self,
+        _chromosome: Option<&Chromosome<Self::Allele>>,
+    ) -> Box<dyn Iterator<Item = Chromosome<Self::Allele>> + Send + 'a> {
+        if self.seed_genes_list.is_empty() {
+            Box::new(
+                self.allele_list
+                    .clone()
+                    .into_iter()
+                    .combinations(self.genes_size)
+                    .map(Chromosome::new),
+            )
+        } else {
+            Box::new(
+                self.seed_genes_list
+                    .clone()
+                    .into_iter()
+                    .map(Chromosome::new),
+            )
+        }
+    }
+
+    /// C(n, k), computed incrementally as C(n, i + 1) = C(n, i) * (n - i) / (i + 1), which is
+    /// exact in each step
+    fn chromosome_permutations_size(&self) -> BigUint {
+        if self.seed_genes_list.is_empty() {
+            let n = self.allele_list.len();
+            (0..self.genes_size).fold(BigUint::from(1u8), |acc, i| acc * (n - i) / (i + 1))
+        } else {
+            self.seed_genes_list.len().into()
+        }
+    }
+    fn allows_permutation(&self) -> bool {
+        true
+    }
+}
+
+impl<T: Allele + PartialEq + Hash> EstimateGenotype for Subset<T> {}
+
+impl<T: Allele + PartialEq + Hash> ParticleSwarmGenotype for Subset<T> {}
+
+impl<T: Allele + PartialEq + Hash> fmt::Display for Subset<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "genotype:")?;
+        writeln!(f, "  genes_size: {}", self.genes_size)?;
+        writeln!(f, "  allele_list_size: {}", self.allele_list.len())?;
+        writeln!(f, "  mutation_type: {:?}", self.mutation_type())?;
+        writeln!(
+            f,
+            "  chromosome_permutations_size: {}",
+            self.chromosome_permutations_size_report()
+        )?;
+        writeln!(
+            f,
+            "  neighbouring_population_size: {}",
+            self.neighbouring_population_size_report()
+        )?;
+        writeln!(
+            f,
+            "  expected_number_of_sampled_index_duplicates: {}",
+            self.expected_number_of_sampled_index_duplicates_report()
+        )?;
+        writeln!(f, "  seed_genes: {:?}", self.seed_genes_list.len())
+    }
+}
@@ -0,0 +1,473 @@
+use super::builder::{Builder, TryFromBuilderError};
+use super::{EvolveGenotype, Genotype, HillClimbGenotype, SupportsPointCrossover};
+use crate::allele::Allele;
+use crate::chromosome::{Chromosome, Genes};
+use crate::population::Population;
+use itertools::Itertools;
+use num::BigUint;
+use rand::distributions::{Distribution, Uniform};
+use rand::prelude::*;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::RangeInclusive;
+
+pub const DEFAULT_MAX_DEPTH: usize = 17;
+pub const DEFAULT_INIT_DEPTH_RANGE: RangeInclusive<usize> = 2..=6;
+
+#[derive(Clone, Copy, Debug)]
+enum TreeMutation {
+    Subtree,
+    Point,
+    Hoist,
+}
+
+/// Genes are a tree of nodes, flattened in prefix (Polish) notation, for genetic programming
+/// (symbolic regression, small decision trees, etc.). Each node is either a function with a fixed
+/// arity (taken from the function_set) or a terminal with arity zero (taken from the
+/// terminal_set). The arity of each node determines how many of the following subtrees are its
+/// children, so the genes are still a plain vector of nodes. Use
+/// [arity](Tree::arity), [subtree_end](Tree::subtree_end) and [depth](Tree::depth) to walk the
+/// genes in the fitness calculation.
+///
+/// On random initialization, ramped half-and-half is used: the depth is sampled uniformly from
+/// the init_depth_range and the tree is either full (functions only until the depth is reached)
+/// or grown (functions and terminals mixed), with equal probability.
+///
+/// Each mutation is one of the following, with equal probability:
+/// * Subtree: replace a random subtree with a newly grown one
+/// * Point: replace a random node with another node of the same arity
+/// * Hoist: replace the whole tree with one of its random subtrees (shrinks the tree)
+///
+/// Point crossover is a subtree crossover: a random subtree is swapped between the parents
+/// (multiple crossover points repeat this). The allow_duplicates flag is ignored. Gene crossover
+/// is not supported, as it would break the tree structure.
+///
+/// Bloat control: the genes_size is the maximum number of nodes and the max_depth is the maximum
+/// depth of the tree (a single terminal has depth 0, defaults to 17). Offspring and mutants
+/// exceeding these limits are rejected and keep their original genes. The hoist mutation further
+/// counteracts bloat. Parsimony pressure can be added in the fitness using the genes length.
+///
+/// The sample_gene_index() and sample_gene_indices() sample from the maximum number of nodes,
+/// custom implementations should bound these to the actual length of the chromosome.
+///
+/// # Example:
+/// ```
+/// use genetic_algorithm::genotype::{Genotype, TreeGenotype};
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// enum Node { Add, Mul, Neg, X, One }
+/// genetic_algorithm::impl_allele!(Node);
+///
+/// let genotype = TreeGenotype::builder()
+///     .with_function_set(vec![(Node::Add, 2), (Node::Mul, 2), (Node::Neg, 1)])
+///     .with_terminal_set(vec![Node::X, Node::One])
+///     .with_genes_size(100) // maximum number of nodes
+///     .with_max_depth(10) // optional, defaults to 17
+///     .with_init_depth_range(1..=4) // optional, defaults to 2..=6
+///     .with_genes_hashing(true) // optional, defaults to true
+///     .with_chromosome_recycling(true) // optional, defaults to true
+///     .build()
+///     .unwrap();
+///
+/// // evaluate the prefix genes recursively, returning the value and the next index
+/// fn evaluate(genes: &[Node], index: usize, x: f32) -> (f32, usize) {
+///     match genes[index] {
+///         Node::X => (x, index + 1),
+///         Node::One => (1.0, index + 1),
+///         Node::Neg => {
+///             let (a, next) = evaluate(genes, index + 1, x);
+///             (-a, next)
+///         }
+///         Node::Add | Node::Mul => {
+///             let (a, next) = evaluate(genes, index + 1, x);
+///             let (b, next) = evaluate(genes, next, x);
+///             if genes[index] == Node::Add { (a + b, next) } else { (a * b, next) }
+///         }
+///     }
+/// }
+/// // x * (x + 1)
+/// let genes = vec![Node::Mul, Node::X, Node::Add, Node::X, Node::One];
+/// assert_eq!(evaluate(&genes, 0, 2.0), (6.0, 5));
+/// assert_eq!(genotype.depth(&genes), 2);
+/// assert_eq!(genotype.subtree_end(&genes, 2), 5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tree<T: Allele + PartialEq + Hash> {
+    pub genes_size: usize,
+    pub max_depth: usize,
+    pub init_depth_range: RangeInclusive<usize>,
+    pub function_set: Vec<(T, usize)>,
+    pub terminal_set: Vec<T>,
+    gene_index_sampler: Uniform<usize>,
+    init_depth_sampler: Uniform<usize>,
+    pub seed_genes_list: Vec<Vec<T>>,
+    pub genes_hashing: bool,
+    pub chromosome_recycling: bool,
+}
+
+impl<T: Allele + PartialEq + Hash> TryFrom<Builder<Self>> for Tree<T> {
+    type Error = TryFromBuilderError;
+
+    fn try_from(builder: Builder<Self>) -> Result<Self, Self::Error> {
+        let max_depth = builder.max_depth.unwrap_or(DEFAULT_MAX_DEPTH);
+        let init_depth_range = builder.init_depth_range.clone().unwrap_or(
+            (*DEFAULT_INIT_DEPTH_RANGE.start()).min(max_depth)
+                ..=(*DEFAULT_INIT_DEPTH_RANGE.end()).min(max_depth),
+        );
+        if !builder.genes_size.is_some_and(|x| x > 0) {
+            Err(TryFromBuilderError(
+                "TreeGenotype requires a genes_size > 0 (maximum number of nodes)",
+            ))
+        } else if !builder.terminal_set.as_ref().is_some_and(|o| !o.is_empty()) {
+            Err(TryFromBuilderError(
+                "TreeGenotype requires non-empty terminal_set",
+            ))
+        } else if builder
+            .function_set
+            .as_ref()
+            .is_some_and(|o| o.iter().any(|(_, arity)| *arity == 0))
+        {
+            Err(TryFromBuilderError(
+                "TreeGenotype requires function_set arities > 0",
+            ))
+        } else if init_depth_range.is_empty() || *init_depth_range.end() > max_depth {
+            Err(TryFromBuilderError(
+                "TreeGenotype requires a non-empty init_depth_range within max_depth",
+            ))
+        } else if !builder.gene_constraints.is_empty() {
+            Err(TryFromBuilderError(
+                "TreeGenotype does not support gene_constraints",
+            ))
+        } else {
+            let genes_size = builder.genes_size.unwrap();
+            Ok(Self {
+                genes_size,
+                max_depth,
+                init_depth_range: init_depth_range.clone(),
+                function_set: builder.function_set.unwrap_or_default(),
+                terminal_set: builder.terminal_set.unwrap(),
+                gene_index_sampler: Uniform::from(0..genes_size),
+                init_depth_sampler: Uniform::from(init_depth_range),
+                seed_genes_list: builder.seed_genes_list,
+                genes_hashing: builder.genes_hashing,
+                chromosome_recycling: builder.chromosome_recycling,
+            })
+        }
+    }
+}
+
+impl<T: Allele + PartialEq + Hash> Tree<T> {
+    /// The arity of the node, zero for terminals
+    pub fn arity(&self, node: &T) -> usize {
+        self.function_set
+            .iter()
+            .find(|(function, _)| function == node)
+            .map_or(0, |(_, arity)| *arity)
+    }
+    /// The exclusive end index of the subtree starting at the start index
+    pub fn subtree_end(&self, genes: &[T], start: usize) -> usize {
+        let mut open = 1;
+        let mut index = start;
+        while open > 0 && index < genes.len() {
+            open += self.arity(&genes[index]);
+            open -= 1;
+            index += 1;
+        }
+        index
+    }
+    /// The depth of the tree, a single terminal has depth 0
+    pub fn depth(&self, genes: &[T]) -> usize {
+        let mut max_depth = 0;
+        let mut open_children: Vec<usize> = Vec::new();
+        for node in genes {
+            max_depth = max_depth.max(open_children.len());
+            match self.arity(node) {
+                0 => {
+                    while let Some(open) = open_children.last_mut() {
+                        *open -= 1;
+                        if *open == 0 {
+                            open_children.pop();
+                        } else {
+                            break;
+                        }
+                    }
+                }
+                arity => open_children.push(arity),
+            }
+        }
+        max_depth
+    }
+    /// Whether the genes are within the genes_size and max_depth limits
+    pub fn within_limits(&self, genes: &[T]) -> bool {
+        genes.len() <= self.genes_size && self.depth(genes) <= self.max_depth
+    }
+
+    /// Append a random tree of at most the given depth (exactly the given depth if full)
+    fn grow_genes<R: Rng>(&self, genes: &mut Genes<T>, depth: usize, full: bool, rng: &mut R) {
+        let number_of_functions = if depth == 0 {
+            0
+        } else {
+            self.function_set.len()
+        };
+        let index = if full && number_of_functions > 0 {
+            rng.gen_range(0..number_of_functions)
+        } else {
+            rng.gen_range(0..number_of_functions + self.terminal_set.len())
+        };
+        if index < number_of_functions {
+            let (function, arity) = self.function_set[index];
+            genes.push(function);
+            for _ in 0..arity {
+                self.grow_genes(genes, depth - 1, full, rng);
+            }
+        } else {
+            genes.push(self.terminal_set[index - number_of_functions]);
+        }
+    }
+    /// Ramped half-and-half, lowering the depth until the genes_size is respected
+    fn ramped_half_and_half_genes<R: Rng>(&self, rng: &mut R) -> Genes<T> {
+        let mut depth = self.init_depth_sampler.sample(rng);
+        let full = rng.gen_bool(0.5);
+        let mut genes = Vec::with_capacity(self.genes_size);
+        loop {
+            self.grow_genes(&mut genes, depth, full, rng);
+            if genes.len() <= self.genes_size || depth == 0 {
+                return genes;
+            }
+            genes.clear();
+            depth -= 1;
+        }
+    }
+    fn sample_same_arity_node<R: Rng>(&self, node: &T, rng: &mut R) -> Option<T> {
+        match self.arity(node) {
+            0 => self
+                .terminal_set
+                .iter()
+                .filter(|terminal| *terminal != node)
+                .choose(rng)
+                .copied(),
+            arity => self
+                .function_set
+                .iter()
+                .filter(|(function, function_arity)| function != node && *function_arity == arity)
+                .map(|(function, _)| *function)
+                .choose(rng),
+        }
+    }
+    fn splice_genes(genes: &[T], start: usize, end: usize, subtree: &[T]) -> Genes<T> {
+        let mut spliced_genes = Vec::with_capacity(genes.len() - (end - start) + subtree.len());
+        spliced_genes.extend_from_slice(&genes[..start]);
+        spliced_genes.extend_from_slice(subtree);
+        spliced_genes.extend_from_slice(&genes[end..]);
+        spliced_genes
+    }
+    fn mutate_genes<R: Rng>(&self, genes: &mut Genes<T>, rng: &mut R) {
+        if genes.is_empty() {
+            return;
+        }
+        let start = rng.gen_range(0..genes.len());
+        let end = self.subtree_end(genes, start);
+        match [
+            TreeMutation::Subtree,
+            TreeMutation::Point,
+            TreeMutation::Hoist,
+        ]
+        .choose(rng)
+        .unwrap()
+        {
+            TreeMutation::Subtree => {
+                let depth = rng.gen_range(0..=*self.init_depth_range.end());
+                let mut subtree = Vec::new();
+                self.grow_genes(&mut subtree, depth, false, rng);
+                let mutated_genes = Self::splice_genes(genes, start, end, &subtree);
+                if self.within_limits(&mutated_genes) {
+                    *genes = mutated_genes;
+                }
+            }
+            TreeMutation::Point => {
+                if let Some(node) = self.sample_same_arity_node(&genes[start], rng) {
+                    genes[start] = node;
+                }
+            }
+            TreeMutation::Hoist => {
+                genes.truncate(end);
+                genes.drain(..start);
+            }
+        }
+    }
+    fn crossover_subtrees<R: Rng>(
+        &self,
+        father: &mut Genes<T>,
+        mother: &mut Genes<T>,
+        rng: &mut R,
+    ) {
+        if father.is_empty() || mother.is_empty() {
+            return;
+        }
+        let father_start = rng.gen_range(0..father.len());
+        let father_end = self.subtree_end(father, father_start);
+        let mother_start = rng.gen_range(0..mother.len());
+        let mother_end = self.subtree_end(mother, mother_start);
+
+        let father_child = Self::splice_genes(
+            father,
+            father_start,
+            father_end,
+            &mother[mother_start..mother_end],
+        );
+        let mother_child = Self::splice_genes(
+            mother,
+            mother_start,
+            mother_end,
+            &father[father_start..father_end],
+        );
+        if self.within_limits(&father_child) {
+            *father = father_child;
+        }
+        if self.within_limits(&mother_child) {
+            *mother = mother_child;
+        }
+    }
+}
+
+impl<T: Allele + PartialEq + Hash> Genotype for Tree<T> {
+    type Allele = T;
+
+    fn genes_size(&self) -> usize {
+        self.genes_size
+    }
+    fn sample_gene_index<R: Rng>(&self, rng: &mut R) -> usize {
+        self.gene_index_sampler.sample(rng)
+    }
+    fn sample_gene_indices<R: Rng>(
+        &self,
+        count: usize,
+        allow_duplicates: bool,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        if allow_duplicates {
+            rng.sample_iter(self.gene_index_sampler)
+                .take(count)
+                .collect()
+        } else {
+            rand::seq::index::sample(rng, self.genes_size, count.min(self.genes_size)).into_vec()
+        }
+    }
+
+    fn mutate_chromosome_genes<R: Rng>(
+        &self,
+        number_of_mutations: usize,
+        _allow_duplicates: bool,
+        chromosome: &mut Chromosome<Self::Allele>,
+        rng: &mut R,
+    ) {
+        for _ in 0..number_of_mutations {
+            self.mutate_genes(&mut chromosome.genes, rng);
+        }
+        chromosome.reset_metadata(self.genes_hashing);
+    }
+    fn set_seed_genes_list(&mut self, seed_genes_list: Vec<Genes<Self::Allele>>) {
+        self.seed_genes_list = seed_genes_list;
+    }
+    fn seed_genes_list(&self) -> &Vec<Genes<Self::Allele>> {
+        &self.seed_genes_list
+    }
+    fn set_genes_hashing(&mut self, genes_hashing: bool) {
+        self.genes_hashing = genes_hashing;
+    }
+    fn random_genes_factory<R: Rng>(&self, rng: &mut R) -> Vec<T> {
+        if self.seed_genes_list.is_empty() {
+            self.ramped_half_and_half_genes(rng)
+        } else {
+            self.seed_genes_list.choose(rng).unwrap().clone()
+        }
+    }
+    fn genes_capacity(&self) -> usize {
+        self.genes_size
+    }
+    fn genes_hashing(&self) -> bool {
+        self.genes_hashing
+    }
+    fn chromosome_recycling(&self) -> bool {
+        self.chromosome_recycling
+    }
+}
+
+impl<T: Allele + PartialEq + Hash> EvolveGenotype for Tree<T> {}
+impl<T: Allele + PartialEq + Hash> SupportsPointCrossover for Tree<T> {
+    fn crossover_chromosome_points<R: Rng>(
+        &self,
+        number_of_crossovers: usize,
+        _allow_duplicates: bool,
+        father: &mut Chromosome<Self::Allele>,
+        mother: &mut Chromosome<Self::Allele>,
+        rng: &mut R,
+    ) {
+        for _ in 0..number_of_crossovers {
+            self.crossover_subtrees(&mut father.genes, &mut mother.genes, rng);
+        }
+        mother.reset_metadata(self.genes_hashing);
+        father.reset_metadata(self.genes_hashing);
+    }
+}
+impl<T: Allele + PartialEq + Hash> HillClimbGenotype for Tree<T> {
+    /// All point mutations (replace a node with another node of the same arity)
+    fn fill_neighbouring_population<R: Rng>(
+        &self,
+        chromosome: &Chromosome<Self::Allele>,
+        population: &mut Population<Self::Allele>,
+        _rng: &mut R,
+    ) {
+        for (index, node) in chromosome.genes.iter().enumerate() {
+            let arity = self.arity(node);
+            let same_arity_nodes: Vec<T> = if arity == 0 {
+                self.terminal_set.clone()
+            } else {
+                self.function_set
+                    .iter()
+                    .filter(|(_, function_arity)| *function_arity == arity)
+                    .map(|(function, _)| *function)
+                    .collect()
+            };
+            for same_arity_node in same_arity_nodes {
+                if same_arity_node != *node {
+                    let mut new_chromosome = population.new_chromosome(chromosome);
+                    new_chromosome.genes[index] = same_arity_node;
+                    new_chromosome.reset_metadata(self.genes_hashing);
+                    population.chromosomes.push(new_chromosome);
+                }
+            }
+        }
+    }
+
+    /// Upper bound, the actual size depends on the nodes of the chromosome
+    fn neighbouring_population_size(&self) -> BigUint {
+        let max_same_arity_nodes = self
+            .function_set
+            .iter()
+            .map(|(_, arity)| arity)
+            .counts()
+            .into_values()
+            .chain(std::iter::once(self.terminal_set.len()))
+            .max()
+            .unwrap_or(1);
+        BigUint::from((max_same_arity_nodes - 1) * self.genes_size)
+    }
+}
+
+impl<T: Allele + PartialEq + Hash> fmt::Display for Tree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "genotype:")?;
+        writeln!(f, "  genes_size: {}", self.genes_size)?;
+        writeln!(f, "  max_depth: {}", self.max_depth)?;
+        writeln!(f, "  init_depth_range: {:?}", self.init_depth_range)?;
+        writeln!(f, "  function_set: {:?}", self.function_set)?;
+        writeln!(f, "  terminal_set: {:?}", self.terminal_set)?;
+        writeln!(
+            f,
+            "  neighbouring_population_size: {}",
+            self.neighbouring_population_size_report()
+        )?;
+        writeln!(f, "  seed_genes: {:?}", self.seed_genes_list.len())
+    }
+}
@@ -1,5 +1,8 @@
 use super::builder::{Builder, TryFromBuilderError};
-use super::{EvolveGenotype, Genotype, HillClimbGenotype, MutationType, PermutateGenotype};
+use super::{
+    EstimateGenotype, EvolveGenotype, Genotype, HillClimbGenotype, MutationType,
+    ParticleSwarmGenotype, PermutateGenotype,
+};
 use crate::allele::Allele;
 use crate::chromosome::{Chromosome, Genes};
 use crate::population::Population;
@@ -83,6 +86,10 @@ impl<T: Allele + Hash> TryFrom<Builder<Self>> for Unique<T> {
             Err(TryFromBuilderError(
                 "UniqueGenotype requires non-empty allele_list",
             ))
+        } else if !builder.gene_constraints.is_empty() {
+            Err(TryFromBuilderError(
+                "UniqueGenotype does not support gene_constraints",
+            ))
         } else {
             let allele_list = builder.allele_list.unwrap();
             let genes_size = allele_list.len();
@@ -250,6 +257,10 @@ impl<T: Allele + Hash> PermutateGenotype for Unique<T> {
     }
 }
 
+impl<T: Allele + Hash> EstimateGenotype for Unique<T> {}
+
+impl<T: Allele + Hash> ParticleSwarmGenotype for Unique<T> {}
+
 impl<T: Allele + Hash> fmt::Display for Unique<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "genotype:")?;
@@ -0,0 +1,434 @@
+use super::builder::{Builder, TryFromBuilderError};
+use super::{
+    EstimateGenotype, EvolveGenotype, Genotype, HillClimbGenotype, MutationType,
+    ParticleSwarmGenotype, PermutateGenotype, SupportsGeneCrossover, SupportsPointCrossover,
+};
+use crate::allele::Allele;
+use crate::chromosome::{Chromosome, Genes};
+use crate::population::Population;
+use itertools::Itertools;
+use num::BigUint;
+use rand::distributions::{Distribution, Uniform};
+use rand::prelude::*;
+use std::fmt;
+use std::hash::Hash;
+
+pub type DefaultAllele = usize;
+
+#[derive(Clone, Copy, Debug)]
+enum GeneMutation {
+    Substitute,
+    Insert,
+    Delete,
+}
+
+/// Genes are a vector of values with a variable length, each value taken from the allele_list
+/// using clone(). The length of the genes is part of the solution and is bounded by the
+/// genes_size_range (min..=max). Useful for things like rule sets, playlists and bin assignments
+/// where the number of elements is not known upfront.
+///
+/// On random initialization, the length is sampled uniformly from the genes_size_range and each
+/// gene gets a value from the allele_list with a uniform probability. Each mutation is either a
+/// substitution (random allele_list value at a random position), an insertion (random
+/// allele_list value at a random position) or a deletion (of a random position), with equal
+/// probability, restricted to the operations which keep the length within the genes_size_range.
+/// As the positions shift on insertion and deletion, the allow_duplicates flag is ignored for
+/// mutations.
+///
+/// Crossovers are length-aware:
+/// * Point crossover is a cut-and-splice crossover: each parent gets its own cut point and the
+///   tails are swapped, so the children lengths change. Multiple crossover points apply the
+///   cut-and-splice repeatedly (messy crossover). The cut points are sampled such that the
+///   children stay within the genes_size_range. The allow_duplicates flag is ignored.
+/// * Gene crossover swaps genes at the same position, restricted to the positions both parents
+///   have (the shortest length), so the children lengths are unchanged.
+///
+/// The genes_size() and genes_capacity() return the maximum length. The sample_gene_index() and
+/// sample_gene_indices() therefore sample from the maximum length as well, custom
+/// implementations should bound these to the actual length of the chromosome.
+///
+/// # Example (usize, default):
+/// ```
+/// use genetic_algorithm::genotype::{Genotype, VariableListGenotype};
+///
+/// let genotype = VariableListGenotype::builder()
+///     .with_genes_size_range(5..=20)
+///     .with_allele_list((0..10).collect())
+///     .with_genes_hashing(true) // optional, defaults to true
+///     .with_chromosome_recycling(true) // optional, defaults to true
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct VariableList<T: Allele + PartialEq + Hash = DefaultAllele> {
+    pub min_genes_size: usize,
+    pub max_genes_size: usize,
+    pub allele_list: Vec<T>,
+    gene_index_sampler: Uniform<usize>,
+    genes_size_sampler: Uniform<usize>,
+    allele_index_sampler: Uniform<usize>,
+    pub seed_genes_list: Vec<Vec<T>>,
+    pub genes_hashing: bool,
+    pub chromosome_recycling: bool,
+}
+
+impl<T: Allele + PartialEq + Hash> TryFrom<Builder<Self>> for VariableList<T> {
+    type Error = TryFromBuilderError;
+
+    fn try_from(builder: Builder<Self>) -> Result<Self, Self::Error> {
+        if builder.genes_size_range.is_none() {
+            if builder.genes_size.is_some() {
+                Err(TryFromBuilderError(
+                    "VariableListGenotype requires with_genes_size_range, not with_genes_size",
+                ))
+            } else {
+                Err(TryFromBuilderError(
+                    "VariableListGenotype requires genes_size_range",
+                ))
+            }
+        } else if builder
+            .genes_size_range
+            .as_ref()
+            .is_some_and(|r| r.is_empty() || *r.end() == 0)
+        {
+            Err(TryFromBuilderError(
+                "VariableListGenotype requires a non-empty genes_size_range with max > 0",
+            ))
+        } else if builder.allele_list.is_none() {
+            if builder.allele_lists.is_some() {
+                Err(TryFromBuilderError(
+                    "VariableListGenotype requires with_allele_list (singular), not with_allele_lists",
+                ))
+            } else {
+                Err(TryFromBuilderError(
+                    "VariableListGenotype requires allele_list",
+                ))
+            }
+        } else if builder.allele_list.as_ref().map(|o| o.is_empty()).unwrap() {
+            Err(TryFromBuilderError(
+                "VariableListGenotype requires non-empty allele_list",
+            ))
+        } else if !builder.gene_constraints.is_empty() {
+            Err(TryFromBuilderError(
+                "VariableListGenotype does not support gene_constraints",
+            ))
+        } else {
+            let genes_size_range = builder.genes_size_range.unwrap();
+            let allele_list = builder.allele_list.unwrap();
+            Ok(Self {
+                min_genes_size: *genes_size_range.start(),
+                max_genes_size: *genes_size_range.end(),
+                allele_list: allele_list.clone(),
+                gene_index_sampler: Uniform::from(0..*genes_size_range.end()),
+                genes_size_sampler: Uniform::from(genes_size_range),
+                allele_index_sampler: Uniform::from(0..allele_list.len()),
+                seed_genes_list: builder.seed_genes_list,
+                genes_hashing: builder.genes_hashing,
+                chromosome_recycling: builder.chromosome_recycling,
+            })
+        }
+    }
+}
+
+impl<T: Allele + PartialEq + Hash> VariableList<T> {
+    fn mutation_type(&self) -> &MutationType<T> {
+        &MutationType::Random
+    }
+    pub fn sample_gene_random<R: Rng>(&self, rng: &mut R) -> T {
+        self.allele_list[self.allele_index_sampler.sample(rng)]
+    }
+    fn mutate_genes<R: Rng>(&self, genes: &mut Genes<T>, rng: &mut R) {
+        let genes_size = genes.len();
+        let gene_mutation = [
+            (genes_size > 0, GeneMutation::Substitute),
+            (genes_size < self.max_genes_size, GeneMutation::Insert),
+            (genes_size > self.min_genes_size, GeneMutation::Delete),
+        ]
+        .into_iter()
+        .filter_map(|(allowed, gene_mutation)| allowed.then_some(gene_mutation))
+        .choose(rng);
+
+        match gene_mutation {
+            Some(GeneMutation::Substitute) => {
+                let index = rng.gen_range(0..genes_size);
+                genes[index] = self.sample_gene_random(rng);
+            }
+            Some(GeneMutation::Insert) => {
+                let index = rng.gen_range(0..=genes_size);
+                genes.insert(index, self.sample_gene_random(rng));
+            }
+            Some(GeneMutation::Delete) => {
+                let index = rng.gen_range(0..genes_size);
+                genes.remove(index);
+            }
+            None => (),
+        }
+    }
+    /// Swap the tails after an independent cut point per parent. The mother cut point is
+    /// restricted, so both children stay within the genes_size_range.
+    fn cut_and_splice<R: Rng>(&self, father: &mut Genes<T>, mother: &mut Genes<T>, rng: &mut R) {
+        let father_size = father.len() as isize;
+        let mother_size = mother.len() as isize;
+        let min_genes_size = self.min_genes_size as isize;
+        let max_genes_size = self.max_genes_size as isize;
+
+        let father_cut = rng.gen_range(0..=father_size);
+        // offset between the mother and father cut points
+        let lower_offset = (mother_size - max_genes_size)
+            .max(min_genes_size - father_size)
+            .max(-father_cut);
+        let upper_offset = (mother_size - min_genes_size)
+            .min(max_genes_size - father_size)
+            .min(mother_size - father_cut);
+        if lower_offset > upper_offset {
+            // only possible for seed genes outside of the genes_size_range
+            return;
+        }
+        let mother_cut = father_cut + rng.gen_range(lower_offset..=upper_offset);
+
+        let father_tail = father.split_off(father_cut as usize);
+        let mother_tail = mother.split_off(mother_cut as usize);
+        father.extend(mother_tail);
+        mother.extend(father_tail);
+    }
+}
+
+impl<T: Allele + PartialEq + Hash> Genotype for VariableList<T> {
+    type Allele = T;
+
+    fn genes_size(&self) -> usize {
+        self.max_genes_size
+    }
+    fn sample_gene_index<R: Rng>(&self, rng: &mut R) -> usize {
+        self.gene_index_sampler.sample(rng)
+    }
+    fn sample_gene_indices<R: Rng>(
+        &self,
+        count: usize,
+        allow_duplicates: bool,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        if allow_duplicates {
+            rng.sample_iter(self.gene_index_sampler)
+                .take(count)
+                .collect()
+        } else {
+            rand::seq::index::sample(rng, self.max_genes_size, count.min(self.max_genes_size))
+                .into_vec()
+        }
+    }
+
+    fn mutate_chromosome_genes<R: Rng>(
+        &self,
+        number_of_mutations: usize,
+        _allow_duplicates: bool,
+        chromosome: &mut Chromosome<Self::Allele>,
+        rng: &mut R,
+    ) {
+        for _ in 0..number_of_mutations {
+            self.mutate_genes(&mut chromosome.genes, rng);
+        }
+        chromosome.reset_metadata(self.genes_hashing);
+    }
+    fn set_seed_genes_list(&mut self, seed_genes_list: Vec<Genes<Self::Allele>>) {
+        self.seed_genes_list = seed_genes_list;
+    }
+    fn seed_genes_list(&self) -> &Vec<Genes<Self::Allele>> {
+        &self.seed_genes_list
+    }
+    fn set_genes_hashing(&mut self, genes_hashing: bool) {
+        self.genes_hashing = genes_hashing;
+    }
+    fn random_genes_factory<R: Rng>(&self, rng: &mut R) -> Vec<T> {
+        if self.seed_genes_list.is_empty() {
+            let genes_size = self.genes_size_sampler.sample(rng);
+            (0..genes_size)
+                .map(|_| self.sample_gene_random(rng))
+                .collect()
+        } else {
+            self.seed_genes_list.choose(rng).unwrap().clone()
+        }
+    }
+    fn genes_capacity(&self) -> usize {
+        self.max_genes_size
+    }
+    fn genes_hashing(&self) -> bool {
+        self.genes_hashing
+    }
+    fn chromosome_recycling(&self) -> bool {
+        self.chromosome_recycling
+    }
+}
+
+impl<T: Allele + PartialEq + Hash> EvolveGenotype for VariableList<T> {}
+impl<T: Allele + PartialEq + Hash> SupportsGeneCrossover for VariableList<T> {
+    fn crossover_chromosome_genes<R: Rng>(
+        &self,
+        number_of_crossovers: usize,
+        allow_duplicates: bool,
+        father: &mut Chromosome<Self::Allele>,
+        mother: &mut Chromosome<Self::Allele>,
+        rng: &mut R,
+    ) {
+        let shared_genes_size = father.genes.len().min(mother.genes.len());
+        if shared_genes_size > 0 {
+            if allow_duplicates {
+                for _ in 0..number_of_crossovers {
+                    let index = rng.gen_range(0..shared_genes_size);
+                    std::mem::swap(&mut father.genes[index], &mut mother.genes[index]);
+                }
+            } else {
+                rand::seq::index::sample(
+                    rng,
+                    shared_genes_size,
+                    number_of_crossovers.min(shared_genes_size),
+                )
+                .iter()
+                .for_each(|index| {
+                    std::mem::swap(&mut father.genes[index], &mut mother.genes[index]);
+                });
+            }
+        }
+        mother.reset_metadata(self.genes_hashing);
+        father.reset_metadata(self.genes_hashing);
+    }
+}
+impl<T: Allele + PartialEq + Hash> SupportsPointCrossover for VariableList<T> {
+    fn crossover_chromosome_points<R: Rng>(
+        &self,
+        number_of_crossovers: usize,
+        _allow_duplicates: bool,
+        father: &mut Chromosome<Self::Allele>,
+        mother: &mut Chromosome<Self::Allele>,
+        rng: &mut R,
+    ) {
+        for _ in 0..number_of_crossovers {
+            self.cut_and_splice(&mut father.genes, &mut mother.genes, rng);
+        }
+        mother.reset_metadata(self.genes_hashing);
+        father.reset_metadata(self.genes_hashing);
+    }
+}
+impl<T: Allele + PartialEq + Hash> HillClimbGenotype for VariableList<T> {
+    /// All substitutions, insertions (if below max) and deletions (if above min)
+    fn fill_neighbouring_population<R: Rng>(
+        &self,
+        chromosome: &Chromosome<Self::Allele>,
+        population: &mut Population<Self::Allele>,
+        _rng: &mut R,
+    ) {
+        let genes_size = chromosome.genes.len();
+        for index in 0..genes_size {
+            for allele_value in self.allele_list.clone() {
+                if chromosome.genes[index] != allele_value {
+                    let mut new_chromosome = population.new_chromosome(chromosome);
+                    new_chromosome.genes[index] = allele_value;
+                    new_chromosome.reset_metadata(self.genes_hashing);
+                    population.chromosomes.push(new_chromosome);
+                }
+            }
+        }
+        if genes_size < self.max_genes_size {
+            for index in 0..=genes_size {
+                for allele_value in self.allele_list.clone() {
+                    let mut new_chromosome = population.new_chromosome(chromosome);
+                    new_chromosome.genes.insert(index, allele_value);
+                    new_chromosome.reset_metadata(self.genes_hashing);
+                    population.chromosomes.push(new_chromosome);
+                }
+            }
+        }
+        if genes_size > self.min_genes_size {
+            for index in 0..genes_size {
+                let mut new_chromosome = population.new_chromosome(chromosome);
+                new_chromosome.genes.remove(index);
+                new_chromosome.reset_metadata(self.genes_hashing);
+                population.chromosomes.push(new_chromosome);
+            }
+        }
+    }
+
+    /// Upper bound, the actual size depends on the length of the chromosome
+    fn neighbouring_population_size(&self) -> BigUint {
+        BigUint::from(2 * self.allele_list.len() * self.max_genes_size)
+    }
+}
+
+impl<T: Allele + PartialEq + Hash> PermutateGenotype for VariableList<T> {
+    fn chromosome_permutations_into_iter<'a>(
+        &'a self,
+        _chromosome: Option<&Chromosome<Self::Allele>>,
+    ) -> Box<dyn Iterator<Item = Chromosome<Self::Allele>> + Send + 'a> {
+        if self.seed_genes_list.is_empty() {
+            Box::new(
+                (self.min_genes_size..=self.max_genes_size)
+                    .flat_map(
+                        move |genes_size| -> Box<dyn Iterator<Item = Vec<T>> + Send> {
+                            if genes_size == 0 {
+                                Box::new(std::iter::once(vec![]))
+                            } else {
+                                Box::new(
+                                    (0..genes_size)
+                                        .map(|_| self.allele_list.clone())
+                                        .multi_cartesian_product(),
+                                )
+                            }
+                        },
+                    )
+                    .map(Chromosome::new),
+            )
+        } else {
+            Box::new(
+                self.seed_genes_list
+                    .clone()
+                    .into_iter()
+                    .map(Chromosome::new),
+            )
+        }
+    }
+
+    fn chromosome_permutations_size(&self) -> BigUint {
+        if self.seed_genes_list.is_empty() {
+            (self.min_genes_size..=self.max_genes_size)
+                .map(|genes_size| BigUint::from(self.allele_list.len()).pow(genes_size as u32))
+                .sum()
+        } else {
+            self.seed_genes_list.len().into()
+        }
+    }
+    fn allows_permutation(&self) -> bool {
+        true
+    }
+}
+
+impl<T: Allele + PartialEq + Hash> EstimateGenotype for VariableList<T> {}
+
+impl<T: Allele + PartialEq + Hash> ParticleSwarmGenotype for VariableList<T> {}
+
+impl<T: Allele + PartialEq + Hash> fmt::Display for VariableList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "genotype:")?;
+        writeln!(
+            f,
+            "  genes_size_range: {}..={}",
+            self.min_genes_size, self.max_genes_size
+        )?;
+        writeln!(f, "  mutation_type: {:?}", self.mutation_type())?;
+        writeln!(
+            f,
+            "  chromosome_permutations_size: {}",
+            self.chromosome_permutations_size_report()
+        )?;
+        writeln!(
+            f,
+            "  neighbouring_population_size: {}",
+            self.neighbouring_population_size_report()
+        )?;
+        writeln!(
+            f,
+            "  expected_number_of_sampled_index_duplicates: {}",
+            self.expected_number_of_sampled_index_duplicates_report()
+        )?;
+        writeln!(f, "  seed_genes: {:?}", self.seed_genes_list.len())
+    }
+}
@@ -8,6 +8,9 @@
 //!     * [Evolve](crate::strategy::evolve::Evolve) (evolution strategy)
 //!     * [Permutate](crate::strategy::permutate::Permutate) (for small search spaces, with a 100% guarantee)
 //!     * [HillClimb](crate::strategy::hill_climb::HillClimb) (when search space is convex with little local optima or when crossover is impossible/inefficient)
+//!     * [Estimate](crate::strategy::estimate::Estimate) (learns a probability model per gene instead of recombining chromosomes)
+//!     * [ParticleSwarm](crate::strategy::particle_swarm::ParticleSwarm) (for continuous search spaces of a RangeGenotype or MultiRangeGenotype)
+//!     * [AntColony](crate::strategy::ant_colony::AntColony) (for permutations of a UniqueGenotype, like routing and sequencing problems)
 //!
 //! Terminology:
 //! * [Population](crate::population): a population has `population_size` number of individuals (called chromosomes).
@@ -18,15 +18,17 @@
 //! selected for a tournament if the `population_size` is larger than the
 //! `target_population_size`
 mod elite;
+mod novelty;
 mod tournament;
 mod wrapper;
 
 pub use self::elite::Elite as SelectElite;
+pub use self::novelty::Novelty as SelectNovelty;
 pub use self::tournament::Tournament as SelectTournament;
 pub use self::wrapper::Wrapper as SelectWrapper;
 
 use crate::chromosome::Chromosome;
-
+use crate::fitness::FitnessOrdering;
 use crate::genotype::{EvolveGenotype, Genotype};
 use crate::strategy::evolve::{EvolveConfig, EvolveState};
 use crate::strategy::StrategyReporter;
@@ -98,6 +100,10 @@ pub type SelectAllele<S> = <<S as Select>::Genotype as Genotype>::Allele;
 pub trait Select: Clone + Send + Sync + std::fmt::Debug {
     type Genotype: EvolveGenotype;
 
+    /// Reset the internal state of the plugin at the start of each run, called by
+    /// [Evolve](crate::strategy::evolve::Evolve) setup. Defaults to a no-op
+    fn setup(&mut self) {}
+
     /// filter by age, is basic first selection step, always applied as before hook
     fn before(
         &mut self,
@@ -136,6 +142,60 @@ pub trait Select: Clone + Send + Sync + std::fmt::Debug {
         state.update_population_cardinality(genotype, config);
     }
 
+    /// Pick a single parent from the population for the steady-state variant of
+    /// [Evolve](crate::strategy::evolve::Evolve) and return its index. Defaults to a binary
+    /// tournament on fitness
+    fn parent_index<R: Rng>(
+        &self,
+        state: &EvolveState<Self::Genotype>,
+        config: &EvolveConfig,
+        rng: &mut R,
+    ) -> usize {
+        self.tournament_parent_index(state, config, 2, rng)
+    }
+
+    /// The fraction of best chromosomes which the steady-state variant of
+    /// [Evolve](crate::strategy::evolve::Evolve) protects from replacement. Defaults to 0.0
+    fn elitism_rate(&self) -> f32 {
+        0.0
+    }
+
+    /// Whether parents can be picked one at a time for the steady-state variant of
+    /// [Evolve](crate::strategy::evolve::Evolve), checked by the builder. Defaults to true
+    fn allows_steady_state(&self) -> bool {
+        true
+    }
+
+    /// The best of tournament_size randomly sampled chromosomes (invalid fitness always loses)
+    fn tournament_parent_index<R: Rng>(
+        &self,
+        state: &EvolveState<Self::Genotype>,
+        config: &EvolveConfig,
+        tournament_size: usize,
+        rng: &mut R,
+    ) -> usize {
+        let chromosomes = &state.population.chromosomes;
+        let mut winning_index = rng.gen_range(0..chromosomes.len());
+        for _ in 1..tournament_size {
+            let sample_index = rng.gen_range(0..chromosomes.len());
+            let sample_wins = match (
+                chromosomes[sample_index].fitness_score(),
+                chromosomes[winning_index].fitness_score(),
+            ) {
+                (Some(_), None) => true,
+                (Some(sample), Some(winning)) => match config.fitness_ordering {
+                    FitnessOrdering::Maximize => sample > winning,
+                    FitnessOrdering::Minimize => sample < winning,
+                },
+                _ => false,
+            };
+            if sample_wins {
+                winning_index = sample_index;
+            }
+        }
+        winning_index
+    }
+
     fn extract_elite_chromosomes(
         &self,
         state: &mut EvolveState<Self::Genotype>,
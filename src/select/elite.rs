@@ -83,6 +83,10 @@ impl<G: EvolveGenotype> Select for Elite<G> {
 
         state.add_duration(StrategyAction::Select, now.elapsed());
     }
+
+    fn elitism_rate(&self) -> f32 {
+        self.elitism_rate
+    }
 }
 
 impl<G: EvolveGenotype> Elite<G> {
@@ -0,0 +1,306 @@
+use super::Select;
+use crate::chromosome::Chromosome;
+use crate::fitness::FitnessOrdering;
+use crate::genotype::EvolveGenotype;
+use crate::population::Population;
+use crate::strategy::evolve::{EvolveConfig, EvolveState};
+use crate::strategy::map_elites::BehaviourDescriptor;
+use crate::strategy::{StrategyAction, StrategyReporter, StrategyState};
+use rand::prelude::*;
+use std::time::Instant;
+
+/// Novelty search selection for deceptive problems, where the objective fitness leads away from
+/// the global optimum. Chromosomes are selected on behavioural novelty instead of fitness, with a
+/// client implemented [BehaviourDescriptor] (shared with
+/// [MapElites](crate::strategy::map_elites::MapElites)) characterizing the behaviour.
+///
+/// The novelty score is the sparseness: the mean euclidean distance to the k-nearest neighbours in
+/// behaviour space, compared against the current population plus the archive of past novel
+/// behaviours. Behaviours of offspring with a sparseness above the archive_threshold are added to
+/// the archive (bounded by the optional archive_max_size, dropping the oldest first). Surviving
+/// parents are not archived again. The archive is cleared at the start of each run.
+///
+/// With a fitness_weight > 0.0, novelty and fitness are combined on their normalized ranks in the
+/// population (0.0 is pure novelty, 1.0 is pure fitness).
+///
+/// The fitness score itself is untouched, so the best chromosome reporting and the ending
+/// conditions of [Evolve](crate::strategy::evolve::Evolve) remain on the true objective. The
+/// elitism_rate gate still selects on fitness, which keeps the best objective chromosomes in the
+/// population. Otherwise uses the same multi-pass process as Tournament (extract elite, partition
+/// parents/offspring, select separately, final pass), with tournaments on the novelty score.
+///
+/// Example:
+/// ```
+/// use genetic_algorithm::strategy::evolve::prelude::*;
+///
+/// #[derive(Clone, Debug)]
+/// pub struct CountTrueInFirstHalf;
+/// impl BehaviourDescriptor for CountTrueInFirstHalf {
+///     type Genotype = BinaryGenotype;
+///     fn calculate_for_chromosome(
+///         &mut self,
+///         chromosome: &Chromosome<bool>,
+///         _genotype: &Self::Genotype,
+///     ) -> Vec<f32> {
+///         let half = chromosome.genes.len() / 2;
+///         vec![chromosome.genes[..half].iter().filter(|v| **v).count() as f32]
+///     }
+/// }
+///
+/// let select = SelectNovelty::new(CountTrueInFirstHalf, 0.5, 0.02, 4, 15, 1.0)
+///     .with_fitness_weight(0.2)     // optional, combine with fitness rank, defaults to 0.0
+///     .with_archive_max_size(1000); // optional, defaults to unbounded
+/// ```
+#[derive(Clone, Debug)]
+pub struct Novelty<G: EvolveGenotype, D: BehaviourDescriptor<Genotype = G>> {
+    pub descriptor: D,
+    pub replacement_rate: f32,
+    pub elitism_rate: f32,
+    pub tournament_size: usize,
+    pub k_nearest: usize,
+    pub archive_threshold: f32,
+    pub archive_max_size: Option<usize>,
+    pub fitness_weight: f32,
+    /// The behaviours of past novel chromosomes, in insertion order
+    pub archive: Vec<Vec<f32>>,
+}
+
+impl<G: EvolveGenotype, D: BehaviourDescriptor<Genotype = G>> Select for Novelty<G, D> {
+    type Genotype = G;
+
+    /// the archive of a previous run doesn't apply to the next run
+    fn setup(&mut self) {
+        self.archive.clear();
+    }
+
+    fn call<R: Rng, SR: StrategyReporter<Genotype = G>>(
+        &mut self,
+        genotype: &G,
+        state: &mut EvolveState<G>,
+        config: &EvolveConfig,
+        _reporter: &mut SR,
+        rng: &mut R,
+    ) {
+        let now = Instant::now();
+
+        let mut elite_chromosomes =
+            self.extract_elite_chromosomes(state, config, self.elitism_rate);
+
+        let scores = self.calculate_scores(genotype, &state.population.chromosomes, config);
+
+        #[allow(clippy::type_complexity)]
+        let (mut offspring, mut parents): (
+            Vec<(f32, Chromosome<G::Allele>)>,
+            Vec<(f32, Chromosome<G::Allele>)>,
+        ) = scores
+            .into_iter()
+            .zip(state.population.chromosomes.drain(..))
+            .partition(|(_, c)| c.is_offspring());
+
+        let (new_parents_size, new_offspring_size) = self.parent_and_offspring_survival_sizes(
+            parents.len(),
+            offspring.len(),
+            config.target_population_size - elite_chromosomes.len(),
+            self.replacement_rate,
+        );
+
+        self.selection(&mut parents, new_parents_size, &mut state.population, rng);
+        self.selection(
+            &mut offspring,
+            new_offspring_size,
+            &mut state.population,
+            rng,
+        );
+
+        state.population.chromosomes.append(&mut elite_chromosomes);
+        state
+            .population
+            .chromosomes
+            .extend(offspring.into_iter().map(|(_, c)| c));
+        state
+            .population
+            .chromosomes
+            .extend(parents.into_iter().map(|(_, c)| c));
+
+        state.add_duration(StrategyAction::Select, now.elapsed());
+    }
+
+    /// the novelty scores are relative to the whole population, not available per step
+    fn allows_steady_state(&self) -> bool {
+        false
+    }
+}
+
+impl<G: EvolveGenotype, D: BehaviourDescriptor<Genotype = G>> Novelty<G, D> {
+    /// Create a new Novelty selection strategy.
+    /// * `descriptor` - the behaviour characterization of a chromosome
+    /// * `replacement_rate` - fraction of population replaced by offspring (0.3-0.7 typical)
+    /// * `elitism_rate` - fraction of best (fitness) chromosomes preserved across generations (0.01-0.05 typical)
+    /// * `tournament_size` - number of chromosomes competing per tournament (2-8 typical)
+    /// * `k_nearest` - number of nearest neighbours for the sparseness (10-20 typical)
+    /// * `archive_threshold` - minimum sparseness for adding a behaviour to the archive
+    pub fn new(
+        descriptor: D,
+        replacement_rate: f32,
+        elitism_rate: f32,
+        tournament_size: usize,
+        k_nearest: usize,
+        archive_threshold: f32,
+    ) -> Self {
+        Self {
+            descriptor,
+            replacement_rate,
+            elitism_rate,
+            tournament_size,
+            k_nearest,
+            archive_threshold,
+            archive_max_size: None,
+            fitness_weight: 0.0,
+            archive: vec![],
+        }
+    }
+    /// Combine novelty and fitness on their normalized ranks (0.0 is pure novelty, 1.0 is pure
+    /// fitness)
+    pub fn with_fitness_weight(mut self, fitness_weight: f32) -> Self {
+        self.fitness_weight = fitness_weight.clamp(0.0, 1.0);
+        self
+    }
+    /// Bound the archive size, dropping the oldest behaviours first
+    pub fn with_archive_max_size(mut self, archive_max_size: usize) -> Self {
+        self.archive_max_size = Some(archive_max_size);
+        self
+    }
+
+    /// The mean euclidean distance to the k-nearest neighbours, excluding the behaviour itself (by
+    /// index in the population)
+    pub fn sparseness(&self, index: usize, behaviours: &[Vec<f32>]) -> f32 {
+        let mut distances: Vec<f32> = behaviours
+            .iter()
+            .enumerate()
+            .filter(|(other_index, _)| *other_index != index)
+            .map(|(_, other)| other)
+            .chain(self.archive.iter())
+            .map(|other| euclidean_distance(&behaviours[index], other))
+            .collect();
+        if distances.is_empty() {
+            return 0.0;
+        }
+        let k = self.k_nearest.clamp(1, distances.len());
+        distances.select_nth_unstable_by(k - 1, |a, b| a.total_cmp(b));
+        distances[..k].iter().sum::<f32>() / k as f32
+    }
+
+    /// Calculate the novelty scores (optionally combined with fitness), aligned with the
+    /// chromosomes, and update the archive with the novel offspring behaviours
+    fn calculate_scores(
+        &mut self,
+        genotype: &G,
+        chromosomes: &[Chromosome<G::Allele>],
+        config: &EvolveConfig,
+    ) -> Vec<f32> {
+        let behaviours: Vec<Vec<f32>> = chromosomes
+            .iter()
+            .map(|c| self.descriptor.calculate_for_chromosome(c, genotype))
+            .collect();
+        let novelty_scores: Vec<f32> = (0..behaviours.len())
+            .map(|index| self.sparseness(index, &behaviours))
+            .collect();
+
+        // only archive the offspring, surviving parents have been considered before
+        behaviours
+            .into_iter()
+            .zip(novelty_scores.iter())
+            .zip(chromosomes.iter())
+            .filter(|((_, novelty_score), chromosome)| {
+                chromosome.is_offspring() && **novelty_score > self.archive_threshold
+            })
+            .for_each(|((behaviour, _), _)| self.archive.push(behaviour));
+        if let Some(archive_max_size) = self.archive_max_size {
+            if self.archive.len() > archive_max_size {
+                self.archive.drain(..self.archive.len() - archive_max_size);
+            }
+        }
+
+        if self.fitness_weight > 0.0 {
+            let novelty_ranks = normalized_ranks(&novelty_scores);
+            let fitness_scores: Vec<f32> = chromosomes
+                .iter()
+                .map(|c| match (c.fitness_score(), config.fitness_ordering) {
+                    (Some(score), FitnessOrdering::Maximize) => score as f32,
+                    (Some(score), FitnessOrdering::Minimize) => -(score as f32),
+                    (None, _) => f32::NEG_INFINITY,
+                })
+                .collect();
+            let fitness_ranks = normalized_ranks(&fitness_scores);
+            novelty_ranks
+                .into_iter()
+                .zip(fitness_ranks)
+                .map(|(novelty_rank, fitness_rank)| {
+                    (1.0 - self.fitness_weight) * novelty_rank + self.fitness_weight * fitness_rank
+                })
+                .collect()
+        } else {
+            novelty_scores
+        }
+    }
+
+    fn selection<R: Rng>(
+        &self,
+        scored_chromosomes: &mut Vec<(f32, Chromosome<G::Allele>)>,
+        selection_size: usize,
+        population: &mut Population<G::Allele>,
+        rng: &mut R,
+    ) {
+        let mut working_population_size = scored_chromosomes.len();
+        let tournament_size = std::cmp::min(self.tournament_size, working_population_size);
+        let selection_size = std::cmp::min(selection_size, working_population_size);
+
+        let mut selected: Vec<(f32, Chromosome<G::Allele>)> = Vec::with_capacity(selection_size);
+        for _ in 0..selection_size {
+            let mut winning_index = 0;
+            let mut winning_score = f32::NEG_INFINITY;
+            for _ in 0..tournament_size {
+                let sample_index = rng.gen_range(0..working_population_size);
+                let sample_score = scored_chromosomes[sample_index].0;
+                if sample_score >= winning_score {
+                    winning_index = sample_index;
+                    winning_score = sample_score;
+                }
+            }
+            selected.push(scored_chromosomes.swap_remove(winning_index));
+            working_population_size -= 1;
+        }
+        // Recycle all losing chromosomes to population's recycling bin
+        scored_chromosomes
+            .drain(..)
+            .for_each(|(_, c)| population.drop_chromosome(c));
+        scored_chromosomes.append(&mut selected);
+    }
+}
+
+fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
+    a.iter()
+        .zip(b.iter())
+        .map(|(x, y)| (x - y) * (x - y))
+        .sum::<f32>()
+        .sqrt()
+}
+
+/// Ranks normalized to 0.0 (lowest) ..= 1.0 (highest), ties share the lowest rank
+fn normalized_ranks(values: &[f32]) -> Vec<f32> {
+    if values.len() < 2 {
+        return vec![1.0; values.len()];
+    }
+    let mut indices: Vec<usize> = (0..values.len()).collect();
+    indices.sort_by(|a, b| values[*a].total_cmp(&values[*b]));
+    let mut ranks = vec![0.0; values.len()];
+    let max_rank = (values.len() - 1) as f32;
+    let mut rank = 0;
+    for (position, index) in indices.iter().enumerate() {
+        if position > 0 && values[*index] != values[indices[position - 1]] {
+            rank = position;
+        }
+        ranks[*index] = rank as f32 / max_rank;
+    }
+    ranks
+}
@@ -87,6 +87,19 @@ impl<G: EvolveGenotype> Select for Tournament<G> {
 
         state.add_duration(StrategyAction::Select, now.elapsed());
     }
+
+    fn parent_index<R: Rng>(
+        &self,
+        state: &EvolveState<G>,
+        config: &EvolveConfig,
+        rng: &mut R,
+    ) -> usize {
+        self.tournament_parent_index(state, config, self.tournament_size, rng)
+    }
+
+    fn elitism_rate(&self) -> f32 {
+        self.elitism_rate
+    }
 }
 
 impl<G: EvolveGenotype> Tournament<G> {
@@ -16,6 +16,13 @@ pub enum Wrapper<G: EvolveGenotype> {
 impl<G: EvolveGenotype> Select for Wrapper<G> {
     type Genotype = G;
 
+    fn setup(&mut self) {
+        match self {
+            Wrapper::Elite(select) => select.setup(),
+            Wrapper::Tournament(select) => select.setup(),
+        }
+    }
+
     fn before(&mut self, genotype: &G, state: &mut EvolveState<G>, config: &EvolveConfig) {
         match self {
             Wrapper::Elite(select) => select.before(genotype, state, config),
@@ -43,6 +50,25 @@ impl<G: EvolveGenotype> Select for Wrapper<G> {
             Wrapper::Tournament(select) => select.after(genotype, state, config),
         }
     }
+
+    fn parent_index<R: Rng>(
+        &self,
+        state: &EvolveState<G>,
+        config: &EvolveConfig,
+        rng: &mut R,
+    ) -> usize {
+        match self {
+            Wrapper::Elite(select) => select.parent_index(state, config, rng),
+            Wrapper::Tournament(select) => select.parent_index(state, config, rng),
+        }
+    }
+
+    fn elitism_rate(&self) -> f32 {
+        match self {
+            Wrapper::Elite(select) => select.elitism_rate(),
+            Wrapper::Tournament(select) => select.elitism_rate(),
+        }
+    }
 }
 
 impl<G: EvolveGenotype> From<SelectElite<G>> for Wrapper<G> {
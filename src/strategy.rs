@@ -1,10 +1,18 @@
 //! solution strategies for finding the best chromosomes.
 //!
-//! There are 4 strategies:
+//! There are 12 strategies:
 //! * [Evolve, Standard](self::evolve::Evolve)
 //! * [Permutate, Standard](self::permutate::Permutate)
 //! * [HillClimb, Stochastic](self::hill_climb::HillClimb)
 //! * [HillClimb, SteepestAscent](self::hill_climb::HillClimb)
+//! * [Estimate, Umda](self::estimate::Estimate)
+//! * [Estimate, Pbil](self::estimate::Estimate)
+//! * [Estimate, CompactGa](self::estimate::Estimate)
+//! * [ParticleSwarm, Global](self::particle_swarm::ParticleSwarm)
+//! * [ParticleSwarm, Ring](self::particle_swarm::ParticleSwarm)
+//! * [MapElites, Standard](self::map_elites::MapElites)
+//! * [AntColony, AntSystem](self::ant_colony::AntColony)
+//! * [AntColony, MaxMin](self::ant_colony::AntColony)
 //!
 //! See strategies for details. Normally, you build a specific strategy and call directly from the
 //! specific builder. But there is an option for building the superset [StrategyBuilder] and calling
@@ -17,13 +25,17 @@
 //!   * fallback to `call()` once for Permutate, but force `with_par_fitness(true)`
 //! * `call_speciated(usize)`, call repeatedly and then run one final round with the best chromosomes from the previous rounds as seeds
 //!   * fallback to `call()` once for Permutate
-//!   * fallback to `call_repeatedly(usize)` for HillClimb
+//!   * fallback to `call_repeatedly(usize)` for HillClimb, Estimate and ParticleSwarm
 //! * `call_par_speciated(usize)`, as above, but high level parallel execution
 //!   * fallback to `call()` once for Permutate, but force `with_par_fitness(true)`
-//!   * fallback to `call_par_repeatedly(usize)` for HillClimb
+//!   * fallback to `call_par_repeatedly(usize)` for HillClimb, Estimate and ParticleSwarm
 //!
 //! *Note: Only Genotypes which implement all strategies are eligable for the superset builder.*
+//! *MapElites is not supported by the superset builder, as it requires a behaviour descriptor and grid*
+//! *AntColony is not supported by the superset builder, as it only supports UniqueGenotype*
 //! *RangeGenotype and other floating point range based genotypes currently do not support Permutation unless scaled*
+//! *Estimate is only supported by genotypes with a finite allele list per gene (BinaryGenotype, ListGenotype and MultiListGenotype without gene_constraints)*
+//! *ParticleSwarm is only supported by genotypes with a numeric allele range per gene (RangeGenotype and MultiRangeGenotype without gene_constraints)*
 //!
 //! Example:
 //! ```
@@ -73,15 +85,23 @@
 //! assert_eq!(best_genes, vec![false; 10]);
 //! assert_eq!(best_fitness_score, 0);
 //! ````
+pub mod ant_colony;
 pub mod builder;
+pub mod estimate;
 pub mod evolve;
 pub mod hill_climb;
+pub mod map_elites;
+pub mod particle_swarm;
 pub mod permutate;
 pub mod prelude;
 pub mod reporter;
 
-use self::evolve::EvolveVariant;
+use self::ant_colony::AntColonyVariant;
+use self::estimate::EstimateVariant;
+use self::evolve::{EvolveVariant, SteadyStateReplacement};
 use self::hill_climb::HillClimbVariant;
+use self::map_elites::MapElitesVariant;
+use self::particle_swarm::ParticleSwarmVariant;
 use self::permutate::PermutateVariant;
 use crate::chromosome::{Chromosome, Genes};
 use crate::crossover::CrossoverEvent;
@@ -100,7 +120,10 @@ pub use self::builder::{
 };
 
 pub use self::reporter::Duration as StrategyReporterDuration;
+pub use self::reporter::Log as StrategyReporterLog;
 pub use self::reporter::Noop as StrategyReporterNoop;
+pub use self::reporter::Record as StrategyReporterRecord;
+pub use self::reporter::Recorder as StrategyReporterRecorder;
 pub use self::reporter::Simple as StrategyReporterSimple;
 
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
@@ -130,11 +153,23 @@ pub enum StrategyVariant {
     Evolve(EvolveVariant),
     HillClimb(HillClimbVariant),
     Permutate(PermutateVariant),
+    MapElites(MapElitesVariant),
+    Estimate(EstimateVariant),
+    AntColony(AntColonyVariant),
+    ParticleSwarm(ParticleSwarmVariant),
 }
 impl Display for StrategyVariant {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             StrategyVariant::Evolve(EvolveVariant::Standard) => write!(f, "evolve"),
+            StrategyVariant::Evolve(EvolveVariant::SteadyState(replacement)) => match replacement {
+                SteadyStateReplacement::Worst => write!(f, "evolve/steady_state/worst"),
+                SteadyStateReplacement::Oldest => write!(f, "evolve/steady_state/oldest"),
+                SteadyStateReplacement::Random => write!(f, "evolve/steady_state/random"),
+                SteadyStateReplacement::ParentIfBetter => {
+                    write!(f, "evolve/steady_state/parent_if_better")
+                }
+            },
             StrategyVariant::HillClimb(HillClimbVariant::Stochastic) => {
                 write!(f, "hill_climb/stochastic")
             }
@@ -142,6 +177,24 @@ impl Display for StrategyVariant {
                 write!(f, "hill_climb/steepest_ascent")
             }
             StrategyVariant::Permutate(PermutateVariant::Standard) => write!(f, "permutate"),
+            StrategyVariant::MapElites(MapElitesVariant::Standard) => write!(f, "map_elites"),
+            StrategyVariant::Estimate(EstimateVariant::Umda) => write!(f, "estimate/umda"),
+            StrategyVariant::Estimate(EstimateVariant::Pbil) => write!(f, "estimate/pbil"),
+            StrategyVariant::Estimate(EstimateVariant::CompactGa) => {
+                write!(f, "estimate/compact_ga")
+            }
+            StrategyVariant::AntColony(AntColonyVariant::AntSystem) => {
+                write!(f, "ant_colony/ant_system")
+            }
+            StrategyVariant::AntColony(AntColonyVariant::MaxMin) => {
+                write!(f, "ant_colony/max_min")
+            }
+            StrategyVariant::ParticleSwarm(ParticleSwarmVariant::Global) => {
+                write!(f, "particle_swarm/global")
+            }
+            StrategyVariant::ParticleSwarm(ParticleSwarmVariant::Ring) => {
+                write!(f, "particle_swarm/ring")
+            }
         }
     }
 }
@@ -194,6 +247,18 @@ pub trait StrategyState<G: Genotype>: Display {
     fn stale_generations(&self) -> usize;
     fn scale_generation(&self) -> usize;
     fn population_cardinality(&self) -> Option<usize>;
+    /// Number of occupied cells in the archive, only for quality-diversity strategies (MapElites)
+    fn archive_size(&self) -> Option<usize> {
+        None
+    }
+    /// Fraction of occupied cells in the archive, only for quality-diversity strategies (MapElites)
+    fn archive_coverage(&self) -> Option<f32> {
+        None
+    }
+    /// Sum of the fitness scores in the archive, only for quality-diversity strategies (MapElites)
+    fn archive_qd_score(&self) -> Option<FitnessValue> {
+        None
+    }
     fn durations(&self) -> &HashMap<StrategyAction, Duration>;
     fn add_duration(&mut self, action: StrategyAction, duration: Duration);
     fn total_duration(&self) -> Duration;
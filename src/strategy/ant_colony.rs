@@ -0,0 +1,952 @@
+//! A solution strategy for finding the best permutation, by ants constructing permutations guided
+//! by pheromone trails over allele-to-allele transitions
+mod builder;
+pub mod prelude;
+mod reporter;
+
+pub use self::builder::{
+    Builder as AntColonyBuilder, TryFromBuilderError as TryFromAntColonyBuilderError,
+};
+
+use super::{
+    Strategy, StrategyAction, StrategyConfig, StrategyReporter, StrategyReporterNoop,
+    StrategyState, StrategyVariant,
+};
+use crate::allele::Allele;
+use crate::chromosome::{Chromosome, Genes};
+use crate::fitness::{Fitness, FitnessCache, FitnessOrdering, FitnessValue};
+use crate::genotype::{Genotype, UniqueGenotype};
+use crate::population::Population;
+use rand::rngs::SmallRng;
+use rand::Rng;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+use thread_local::ThreadLocal;
+
+pub use self::reporter::Simple as AntColonyReporterSimple;
+pub use crate::strategy::reporter::Duration as AntColonyReporterDuration;
+pub use crate::strategy::reporter::Log as AntColonyReporterLog;
+pub use crate::strategy::reporter::Noop as AntColonyReporterNoop;
+pub use crate::strategy::reporter::Recorder as AntColonyReporterRecorder;
+
+#[derive(Copy, Clone, Debug, Default)]
+pub enum AntColonyVariant {
+    #[default]
+    AntSystem,
+    MaxMin,
+}
+
+/// The optional heuristic desirability of a transition between two alleles (e.g. the inverse
+/// distance between two cities). Like [Fitness], it is problem specific and implemented by the
+/// client. It is evaluated once for all allele pairs during setup.
+///
+/// Example:
+/// ```
+/// use genetic_algorithm::strategy::ant_colony::prelude::*;
+///
+/// #[derive(Clone, Debug)]
+/// pub struct InverseDistance(pub Vec<Vec<f32>>);
+/// impl AntColonyHeuristic for InverseDistance {
+///     type Allele = usize;
+///     fn desirability(&mut self, from: &usize, to: &usize) -> f32 {
+///         1.0 / self.0[*from][*to]
+///     }
+/// }
+/// ```
+pub trait AntColonyHeuristic: Clone + Send + Sync + fmt::Debug {
+    type Allele: Allele;
+    /// Must be implemented by client, the desirability of placing allele `to` directly after
+    /// allele `from`. Higher is more desirable, must not be negative.
+    fn desirability(&mut self, from: &Self::Allele, to: &Self::Allele) -> f32;
+}
+
+/// The placeholder for when no heuristic is present, all transitions are equally desirable
+#[derive(Clone, Debug)]
+pub struct AntColonyHeuristicNoop<T: Allele>(PhantomData<T>);
+impl<T: Allele> AntColonyHeuristic for AntColonyHeuristicNoop<T> {
+    type Allele = T;
+    fn desirability(&mut self, _from: &T, _to: &T) -> f32 {
+        1.0
+    }
+}
+impl<T: Allele> AntColonyHeuristicNoop<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+impl<T: Allele> Default for AntColonyHeuristicNoop<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The AntColony strategy is an Ant Colony Optimization (ACO) for
+/// [UniqueGenotype](crate::genotype::UniqueGenotype) permutations (routing and sequencing
+/// problems). Each generation a colony of ants (the target_population_size) constructs
+/// permutations of the genotype's allele_list, choosing the next allele with a probability
+/// proportional to `pheromone^pheromone_weight * desirability^heuristic_weight` over the
+/// remaining alleles. The first allele is chosen from a virtual start row of the pheromone matrix,
+/// so the position in the sequence is learned as well.
+///
+/// The pheromone matrix lives over allele-to-allele transitions (by index in the allele_list, plus
+/// the virtual start row as last row). After fitness calculation, all pheromones evaporate with
+/// the evaporation_rate and the ants deposit pheromone on the transitions of their permutation.
+///
+/// There are 2 variants:
+/// * [AntColonyVariant::AntSystem]: all ants deposit pheromone, relative to their fitness score
+///   within the colony (1.0 for the best ant down to 0.0 for the worst ant). This keeps the deposit
+///   independent of the sign and scale of the fitness score.
+/// * [AntColonyVariant::MaxMin]: only the best ant of the generation deposits pheromone (1.0) and
+///   the pheromones are bounded to `[max / (2 * genes_size), max]` with `max = 1 / evaporation_rate`.
+///   The pheromones are initialized at the maximum, which favours exploration at the start.
+///
+/// The optional [AntColonyHeuristic] desirability is evaluated once during setup. Without it, ants
+/// are guided by the pheromones only.
+///
+/// The first colony is constructed from the seed genes if present (see
+/// [UniqueGenotype](crate::genotype::UniqueGenotype)), otherwise from the initial pheromones. The
+/// pheromone matrix remains available in [AntColonyState] after the run.
+///
+/// The ending conditions are one or more of the following:
+/// * target_fitness_score: when the ultimate goal in terms of fitness score is known and reached
+/// * max_stale_generations: when the ultimate goal in terms of fitness score is unknown and one
+///   depends on some convergion threshold, or one wants a duration limitation next to the
+///   target_fitness_score
+/// * max_generations: when the ultimate goal in terms of fitness score is unknown and there is a effort constraint
+///
+/// There are reporting hooks in the loop receiving the [AntColonyState], which can by handled by an
+/// [StrategyReporter] (e.g. [AntColonyReporterDuration], [AntColonyReporterSimple]). But you are encouraged to
+/// roll your own, see [StrategyReporter].
+///
+/// Below is the exact order of actions and hooks
+/// * [reporter](crate::strategy::reporter) on_enter hook
+/// * setup (heuristic, pheromones, construct, fitness and deposit of first colony)
+/// * [reporter](crate::strategy::reporter) on_start hook
+/// * loop while not finished
+///   * increment generation
+///   * construct permutations by the ants
+///   * [fitness](crate::fitness) calculation
+///   * update best chromosome
+///   * evaporate and deposit pheromones
+///   * [reporter](crate::strategy::reporter) on_generation_complete hook
+///   * check ending conditions
+/// * [reporter](crate::strategy::reporter) on_finish hook
+/// * cleanup
+/// * [reporter](crate::strategy::reporter) on_exit hook
+///
+/// From the [AntColonyBuilder] level, there are several calling mechanisms:
+/// * [call](AntColonyBuilder::call): this runs a single [AntColony] strategy
+/// * [call_repeatedly](AntColonyBuilder::call_repeatedly): this runs multiple independent
+///   [AntColony] strategies and returns the best one (or short circuits when the
+///   target_fitness_score is reached)
+/// * [call_par_repeatedly](AntColonyBuilder::call_par_repeatedly): this runs multiple independent
+///   [AntColony] strategies in parallel and returns the best one (or short circuits when the
+///   target_fitness_score is reached). This is separate and independent from the
+///   `with_par_fitness()` flag on the builder, which determines multithreading of the fitness
+///   calculation inside the [AntColony] strategy. Both can be combined.
+///
+/// All multithreading mechanisms are implemented using [rayon::iter] and [std::sync::mpsc].
+///
+/// AntColony is not part of the superset [StrategyBuilder](crate::strategy::StrategyBuilder), as
+/// it only supports [UniqueGenotype](crate::genotype::UniqueGenotype). See [AntColonyBuilder] for
+/// initialization options.
+///
+/// Example:
+/// ```
+/// use genetic_algorithm::strategy::ant_colony::prelude::*;
+///
+/// // cities on a line, the shortest tour visits them in order and returns
+/// #[derive(Clone, Debug)]
+/// pub struct TourLength;
+/// impl Fitness for TourLength {
+///     type Genotype = UniqueGenotype<usize>;
+///     fn calculate_for_chromosome(
+///         &mut self,
+///         chromosome: &FitnessChromosome<Self>,
+///         _genotype: &FitnessGenotype<Self>,
+///     ) -> Option<FitnessValue> {
+///         let genes = &chromosome.genes;
+///         let length: usize = (0..genes.len())
+///             .map(|i| genes[i].abs_diff(genes[(i + 1) % genes.len()]))
+///             .sum();
+///         Some(length as FitnessValue)
+///     }
+/// }
+///
+/// #[derive(Clone, Debug)]
+/// pub struct InverseDistance;
+/// impl AntColonyHeuristic for InverseDistance {
+///     type Allele = usize;
+///     fn desirability(&mut self, from: &usize, to: &usize) -> f32 {
+///         1.0 / from.abs_diff(*to) as f32
+///     }
+/// }
+///
+/// // the search space
+/// let genotype = UniqueGenotype::builder()
+///     .with_allele_list((0..20).collect())
+///     .build()
+///     .unwrap();
+///
+/// // the search strategy
+/// let ant_colony = AntColony::builder()
+///     .with_genotype(genotype)
+///     .with_variant(AntColonyVariant::MaxMin)       // optional, defaults to AntColonyVariant::AntSystem
+///     .with_target_population_size(20)               // the number of ants per generation
+///     .with_pheromone_weight(1.0)                    // optional, defaults to 1.0
+///     .with_heuristic_weight(2.0)                    // optional, defaults to 2.0
+///     .with_evaporation_rate(0.1)                    // optional, defaults to 0.1
+///     .with_heuristic(InverseDistance)               // optional, defaults to no heuristic
+///     .with_fitness(TourLength)
+///     .with_fitness_ordering(FitnessOrdering::Minimize) // aim for the shortest tour
+///     .with_par_fitness(true)                        // optional, defaults to false, use parallel fitness calculation
+///     .with_target_fitness_score(38)                 // ending condition if the shortest tour is found
+///     .with_max_stale_generations(100)               // stop searching if there is no improvement in fitness score for 100 generations
+///     .with_reporter(AntColonyReporterSimple::new(100)) // optional, report every 100 generations
+///     .with_rng_seed_from_u64(0)                     // for testing with deterministic results
+///     .call()
+///     .unwrap();
+///
+/// // it's all about the best genes after all
+/// let (_best_genes, best_fitness_score) = ant_colony.best_genes_and_fitness_score().unwrap();
+/// assert_eq!(best_fitness_score, 38);
+/// ```
+pub struct AntColony<
+    T: Allele + Hash + PartialEq,
+    F: Fitness<Genotype = UniqueGenotype<T>>,
+    H: AntColonyHeuristic<Allele = T>,
+    SR: StrategyReporter<Genotype = UniqueGenotype<T>>,
+> {
+    pub genotype: UniqueGenotype<T>,
+    pub fitness: F,
+    pub heuristic: H,
+    pub config: AntColonyConfig,
+    pub state: AntColonyState<T>,
+    pub reporter: SR,
+    pub rng: SmallRng,
+}
+
+pub struct AntColonyConfig {
+    pub variant: AntColonyVariant,
+    pub fitness_ordering: FitnessOrdering,
+    pub par_fitness: bool,
+    pub replace_on_equal_fitness: bool,
+    pub target_population_size: usize,
+    pub pheromone_weight: f32,
+    pub heuristic_weight: f32,
+    pub evaporation_rate: f32,
+
+    pub target_fitness_score: Option<FitnessValue>,
+    pub max_stale_generations: Option<usize>,
+    pub max_generations: Option<usize>,
+    pub valid_fitness_score: Option<FitnessValue>,
+    pub fitness_cache: Option<FitnessCache>,
+}
+
+/// Stores the state of the AntColony strategy.
+pub struct AntColonyState<T: Allele + Hash + PartialEq> {
+    pub current_iteration: usize,
+    pub current_generation: usize,
+    pub stale_generations: usize,
+    pub best_generation: usize,
+    pub best_fitness_score: Option<FitnessValue>,
+    pub best_chromosome: Option<Chromosome<T>>,
+    pub chromosome: Option<Chromosome<T>>,
+    pub population: Population<T>,
+    /// The permutations of the ants as allele_list indices, aligned with the population
+    pub paths: Vec<Vec<usize>>,
+    /// The pheromone matrix over allele_list index transitions (from row to column), with an
+    /// additional last row for the virtual start
+    pub pheromones: Vec<Vec<f32>>,
+    /// The heuristic desirability matrix raised to the heuristic_weight, shaped as the pheromones
+    pub desirabilities: Vec<Vec<f32>>,
+    pub durations: HashMap<StrategyAction, Duration>,
+}
+
+impl<
+        T: Allele + Hash + PartialEq,
+        F: Fitness<Genotype = UniqueGenotype<T>>,
+        H: AntColonyHeuristic<Allele = T>,
+        SR: StrategyReporter<Genotype = UniqueGenotype<T>>,
+    > Strategy<UniqueGenotype<T>> for AntColony<T, F, H, SR>
+{
+    fn call(&mut self) {
+        let now = Instant::now();
+        self.reporter
+            .on_enter(&self.genotype, &self.state, &self.config);
+        let mut fitness_thread_local: Option<ThreadLocal<RefCell<F>>> = None;
+        if self.config.par_fitness {
+            fitness_thread_local = Some(ThreadLocal::new());
+        }
+
+        self.setup(fitness_thread_local.as_ref());
+        self.reporter
+            .on_start(&self.genotype, &self.state, &self.config);
+        while !self.is_finished() {
+            self.state.increment_generation();
+            self.state
+                .construct_population(&self.genotype, &self.config, &mut self.rng);
+            self.fitness.call_for_state_population(
+                &self.genotype,
+                &mut self.state,
+                &self.config,
+                fitness_thread_local.as_ref(),
+            );
+            self.state.update_best_chromosome_from_state_population(
+                &self.genotype,
+                &self.config,
+                &mut self.reporter,
+            );
+            self.state.update_pheromones(&self.config);
+            self.reporter
+                .on_generation_complete(&self.genotype, &self.state, &self.config);
+        }
+        self.reporter
+            .on_finish(&self.genotype, &self.state, &self.config);
+        self.cleanup(fitness_thread_local.as_mut());
+        self.state.close_duration(now.elapsed());
+        self.reporter
+            .on_exit(&self.genotype, &self.state, &self.config);
+    }
+    fn best_generation(&self) -> usize {
+        self.state.best_generation
+    }
+    fn best_fitness_score(&self) -> Option<FitnessValue> {
+        self.state.best_fitness_score()
+    }
+    fn best_genes(&self) -> Option<Genes<T>> {
+        self.state
+            .best_chromosome
+            .as_ref()
+            .map(|c| c.genes().clone())
+    }
+    fn flush_reporter(&mut self, output: &mut Vec<u8>) {
+        self.reporter.flush(output);
+    }
+}
+impl<
+        T: Allele + Hash + PartialEq,
+        F: Fitness<Genotype = UniqueGenotype<T>>,
+        H: AntColonyHeuristic<Allele = T>,
+        SR: StrategyReporter<Genotype = UniqueGenotype<T>>,
+    > AntColony<T, F, H, SR>
+{
+    pub fn best_chromosome(&self) -> Option<Chromosome<T>> {
+        if let Some(best_genes) = self.best_genes() {
+            let mut chromosome = Chromosome::<T>::new(best_genes);
+            chromosome.set_fitness_score(self.best_fitness_score());
+            Some(chromosome)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Allele + Hash + PartialEq, F: Fitness<Genotype = UniqueGenotype<T>>>
+    AntColony<T, F, AntColonyHeuristicNoop<T>, StrategyReporterNoop<UniqueGenotype<T>>>
+{
+    pub fn builder(
+    ) -> AntColonyBuilder<T, F, AntColonyHeuristicNoop<T>, StrategyReporterNoop<UniqueGenotype<T>>>
+    {
+        AntColonyBuilder::new()
+    }
+}
+impl<
+        T: Allele + Hash + PartialEq,
+        F: Fitness<Genotype = UniqueGenotype<T>>,
+        H: AntColonyHeuristic<Allele = T>,
+        SR: StrategyReporter<Genotype = UniqueGenotype<T>>,
+    > AntColony<T, F, H, SR>
+{
+    pub fn setup(&mut self, fitness_thread_local: Option<&ThreadLocal<RefCell<F>>>) {
+        let now = Instant::now();
+        let allele_list = &self.genotype.allele_list;
+        let allele_size = allele_list.len();
+        let heuristic_weight = self.config.heuristic_weight;
+        self.state.desirabilities = (0..=allele_size)
+            .map(|from_index| {
+                (0..allele_size)
+                    .map(|to_index| {
+                        if from_index == allele_size {
+                            1.0
+                        } else {
+                            self.heuristic
+                                .desirability(&allele_list[from_index], &allele_list[to_index])
+                                .max(0.0)
+                                .powf(heuristic_weight)
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let initial_pheromone = match self.config.variant {
+            AntColonyVariant::AntSystem => 1.0,
+            AntColonyVariant::MaxMin => self.config.max_pheromone(),
+        };
+        self.state.pheromones = vec![vec![initial_pheromone; allele_size]; allele_size + 1];
+
+        self.state.population = self
+            .genotype
+            .population_constructor(self.config.target_population_size, &mut self.rng);
+        if self.genotype.seed_genes_list().is_empty() {
+            self.state
+                .construct_population(&self.genotype, &self.config, &mut self.rng);
+        } else {
+            self.state.paths = self
+                .state
+                .population
+                .chromosomes
+                .iter()
+                .map(|chromosome| allele_list_indices(allele_list, &chromosome.genes))
+                .collect();
+        }
+        self.state
+            .add_duration(StrategyAction::SetupAndCleanup, now.elapsed());
+
+        self.fitness.call_for_state_population(
+            &self.genotype,
+            &mut self.state,
+            &self.config,
+            fitness_thread_local,
+        );
+        self.state.update_best_chromosome_from_state_population(
+            &self.genotype,
+            &self.config,
+            &mut self.reporter,
+        );
+        self.state.update_pheromones(&self.config);
+    }
+    pub fn cleanup(&mut self, fitness_thread_local: Option<&mut ThreadLocal<RefCell<F>>>) {
+        let now = Instant::now();
+        self.state.chromosome.take();
+        self.state.population.chromosomes.clear();
+        self.state.paths.clear();
+        if let Some(thread_local) = fitness_thread_local {
+            thread_local.clear();
+        }
+        self.state
+            .add_duration(StrategyAction::SetupAndCleanup, now.elapsed());
+    }
+    fn is_finished(&self) -> bool {
+        self.allow_finished_by_valid_fitness_score()
+            && (self.is_finished_by_max_stale_generations()
+                || self.is_finished_by_max_generations()
+                || self.is_finished_by_target_fitness_score())
+    }
+
+    fn is_finished_by_max_stale_generations(&self) -> bool {
+        if let Some(max_stale_generations) = self.config.max_stale_generations {
+            self.state.stale_generations >= max_stale_generations
+        } else {
+            false
+        }
+    }
+
+    fn is_finished_by_max_generations(&self) -> bool {
+        if let Some(max_generations) = self.config.max_generations {
+            self.state.current_generation >= max_generations
+        } else {
+            false
+        }
+    }
+
+    fn is_finished_by_target_fitness_score(&self) -> bool {
+        if let Some(target_fitness_score) = self.config.target_fitness_score {
+            if let Some(fitness_score) = self.best_fitness_score() {
+                match self.config.fitness_ordering {
+                    FitnessOrdering::Maximize => fitness_score >= target_fitness_score,
+                    FitnessOrdering::Minimize => fitness_score <= target_fitness_score,
+                }
+            } else {
+                false
+            }
+        } else {
+            false
+        }
+    }
+
+    fn allow_finished_by_valid_fitness_score(&self) -> bool {
+        if let Some(valid_fitness_score) = self.config.valid_fitness_score {
+            if let Some(fitness_score) = self.best_fitness_score() {
+                match self.config.fitness_ordering {
+                    FitnessOrdering::Maximize => fitness_score >= valid_fitness_score,
+                    FitnessOrdering::Minimize => fitness_score <= valid_fitness_score,
+                }
+            } else {
+                true
+            }
+        } else {
+            true
+        }
+    }
+}
+
+impl StrategyConfig for AntColonyConfig {
+    fn fitness_ordering(&self) -> FitnessOrdering {
+        self.fitness_ordering
+    }
+    fn fitness_cache(&self) -> Option<&FitnessCache> {
+        self.fitness_cache.as_ref()
+    }
+    fn par_fitness(&self) -> bool {
+        self.par_fitness
+    }
+    fn replace_on_equal_fitness(&self) -> bool {
+        self.replace_on_equal_fitness
+    }
+    fn variant(&self) -> StrategyVariant {
+        StrategyVariant::AntColony(self.variant)
+    }
+}
+
+impl<T: Allele + Hash + PartialEq> StrategyState<UniqueGenotype<T>> for AntColonyState<T> {
+    fn chromosome_as_ref(&self) -> &Option<Chromosome<T>> {
+        &self.chromosome
+    }
+    fn population_as_ref(&self) -> &Population<T> {
+        &self.population
+    }
+    fn chromosome_as_mut(&mut self) -> &mut Option<Chromosome<T>> {
+        &mut self.chromosome
+    }
+    fn population_as_mut(&mut self) -> &mut Population<T> {
+        &mut self.population
+    }
+    fn best_fitness_score(&self) -> Option<FitnessValue> {
+        self.best_fitness_score
+    }
+    fn best_generation(&self) -> usize {
+        self.best_generation
+    }
+    fn current_generation(&self) -> usize {
+        self.current_generation
+    }
+    fn current_iteration(&self) -> usize {
+        self.current_iteration
+    }
+    fn increment_generation(&mut self) {
+        self.current_generation += 1;
+    }
+    fn stale_generations(&self) -> usize {
+        self.stale_generations
+    }
+    fn increment_stale_generations(&mut self) {
+        self.stale_generations += 1;
+    }
+    fn reset_stale_generations(&mut self) {
+        self.stale_generations = 0;
+    }
+    fn scale_generation(&self) -> usize {
+        self.current_generation
+    }
+    fn reset_scale_generation(&mut self) {}
+    fn population_cardinality(&self) -> Option<usize> {
+        None
+    }
+    fn durations(&self) -> &HashMap<StrategyAction, Duration> {
+        &self.durations
+    }
+    fn add_duration(&mut self, action: StrategyAction, duration: Duration) {
+        *self.durations.entry(action).or_default() += duration;
+    }
+    fn total_duration(&self) -> Duration {
+        self.durations.values().sum()
+    }
+    fn best_genes(&self) -> Option<Genes<T>> {
+        self.best_chromosome.as_ref().map(|c| c.genes().clone())
+    }
+}
+
+impl<T: Allele + Hash + PartialEq> AntColonyState<T> {
+    /// Each ant (chromosome) constructs a new permutation, overwriting its genes in place
+    fn construct_population<R: Rng>(
+        &mut self,
+        genotype: &UniqueGenotype<T>,
+        config: &AntColonyConfig,
+        rng: &mut R,
+    ) {
+        let now = Instant::now();
+        let allele_size = genotype.allele_list.len();
+        let genes_hashing = genotype.genes_hashing();
+        self.paths.resize_with(self.population.size(), Vec::new);
+
+        let mut visited = vec![false; allele_size];
+        let mut weights = vec![0.0_f32; allele_size];
+        for (chromosome, path) in self
+            .population
+            .chromosomes
+            .iter_mut()
+            .zip(self.paths.iter_mut())
+        {
+            visited.iter_mut().for_each(|v| *v = false);
+            path.clear();
+            let mut from_index = allele_size;
+            for _ in 0..allele_size {
+                let mut total = 0.0;
+                for (to_index, weight) in weights.iter_mut().enumerate() {
+                    *weight = if visited[to_index] {
+                        0.0
+                    } else {
+                        self.pheromones[from_index][to_index].powf(config.pheromone_weight)
+                            * self.desirabilities[from_index][to_index]
+                    };
+                    total += *weight;
+                }
+                let to_index = if total > 0.0 {
+                    sample_weighted_index(&weights, total, rng)
+                } else {
+                    // no desirable transitions left, take a random unvisited allele
+                    let unvisited: Vec<usize> = (0..allele_size).filter(|i| !visited[*i]).collect();
+                    unvisited[rng.gen_range(0..unvisited.len())]
+                };
+                visited[to_index] = true;
+                path.push(to_index);
+                from_index = to_index;
+            }
+            chromosome
+                .genes
+                .iter_mut()
+                .zip(path.iter())
+                .for_each(|(gene, allele_index)| *gene = genotype.allele_list[*allele_index]);
+            chromosome.reset_metadata(genes_hashing);
+        }
+        self.add_duration(StrategyAction::Other, now.elapsed());
+    }
+
+    /// Evaporate all pheromones and deposit pheromone on the transitions of the ants
+    fn update_pheromones(&mut self, config: &AntColonyConfig) {
+        let now = Instant::now();
+        let retention = 1.0 - config.evaporation_rate;
+        self.pheromones
+            .iter_mut()
+            .flatten()
+            .for_each(|pheromone| *pheromone *= retention);
+
+        match config.variant {
+            AntColonyVariant::AntSystem => {
+                let scores: Vec<Option<FitnessValue>> = self
+                    .population
+                    .chromosomes
+                    .iter()
+                    .map(|c| c.fitness_score())
+                    .collect();
+                let best_score = scores.iter().flatten().copied().max();
+                let worst_score = scores.iter().flatten().copied().min();
+                if let (Some(max_score), Some(min_score)) = (best_score, worst_score) {
+                    let range = (max_score - min_score) as f32;
+                    for (score, path) in scores.iter().zip(self.paths.iter()) {
+                        if let Some(score) = score {
+                            let deposit = if range > 0.0 {
+                                match config.fitness_ordering {
+                                    FitnessOrdering::Maximize => (score - min_score) as f32 / range,
+                                    FitnessOrdering::Minimize => (max_score - score) as f32 / range,
+                                }
+                            } else {
+                                1.0
+                            };
+                            deposit_on_path(&mut self.pheromones, path, deposit);
+                        }
+                    }
+                }
+            }
+            AntColonyVariant::MaxMin => {
+                if let Some(index) = self
+                    .population
+                    .best_chromosome_index(config.fitness_ordering)
+                {
+                    if self.population.chromosomes[index].fitness_score().is_some() {
+                        deposit_on_path(&mut self.pheromones, &self.paths[index], 1.0);
+                    }
+                }
+                let max_pheromone = config.max_pheromone();
+                let min_pheromone =
+                    max_pheromone / (2 * self.paths.first().map_or(1, Vec::len).max(1)) as f32;
+                self.pheromones.iter_mut().flatten().for_each(|pheromone| {
+                    *pheromone = pheromone.clamp(min_pheromone, max_pheromone)
+                });
+            }
+        }
+        self.add_duration(StrategyAction::Other, now.elapsed());
+    }
+
+    fn update_best_chromosome_from_state_population<
+        SR: StrategyReporter<Genotype = UniqueGenotype<T>>,
+    >(
+        &mut self,
+        genotype: &UniqueGenotype<T>,
+        config: &AntColonyConfig,
+        reporter: &mut SR,
+    ) {
+        let now = Instant::now();
+        if let Some(contending_chromosome) =
+            self.population.best_chromosome(config.fitness_ordering)
+        {
+            match self.is_better_chromosome(
+                contending_chromosome,
+                &config.fitness_ordering,
+                config.replace_on_equal_fitness,
+            ) {
+                (true, true) => {
+                    self.best_generation = self.current_generation;
+                    self.best_fitness_score = contending_chromosome.fitness_score();
+                    self.best_chromosome = Some(contending_chromosome.clone());
+                    reporter.on_new_best_chromosome(genotype, self, config);
+                    self.reset_stale_generations();
+                }
+                (true, false) => {
+                    self.best_chromosome = Some(contending_chromosome.clone());
+                    reporter.on_new_best_chromosome_equal_fitness(genotype, self, config);
+                    self.increment_stale_generations()
+                }
+                _ => self.increment_stale_generations(),
+            }
+        } else {
+            self.increment_stale_generations();
+        }
+        self.add_duration(StrategyAction::UpdateBestChromosome, now.elapsed());
+    }
+}
+
+/// Deposit on the start transition and all consecutive transitions of the path
+fn deposit_on_path(pheromones: &mut [Vec<f32>], path: &[usize], deposit: f32) {
+    let start_index = pheromones.len() - 1;
+    let mut from_index = start_index;
+    for to_index in path {
+        pheromones[from_index][*to_index] += deposit;
+        from_index = *to_index;
+    }
+}
+
+/// Roulette sampling of an index, falls back to the last positive weight on rounding errors
+fn sample_weighted_index<R: Rng>(weights: &[f32], total: f32, rng: &mut R) -> usize {
+    let mut remaining = rng.gen::<f32>() * total;
+    let mut last_positive_index = 0;
+    for (index, weight) in weights.iter().enumerate() {
+        if *weight > 0.0 {
+            if remaining < *weight {
+                return index;
+            }
+            remaining -= weight;
+            last_positive_index = index;
+        }
+    }
+    last_positive_index
+}
+
+/// Map genes to allele_list indices, taking each index at most once (the allele_list values are
+/// only positionally unique)
+fn allele_list_indices<T: Allele + PartialEq>(allele_list: &[T], genes: &[T]) -> Vec<usize> {
+    let mut used = vec![false; allele_list.len()];
+    genes
+        .iter()
+        .map(|gene| {
+            let index = (0..allele_list.len())
+                .find(|index| !used[*index] && allele_list[*index] == *gene)
+                .unwrap_or_else(|| used.iter().position(|u| !u).unwrap_or(0));
+            used[index] = true;
+            index
+        })
+        .collect()
+}
+
+impl<
+        T: Allele + Hash + PartialEq,
+        F: Fitness<Genotype = UniqueGenotype<T>>,
+        H: AntColonyHeuristic<Allele = T>,
+        SR: StrategyReporter<Genotype = UniqueGenotype<T>>,
+    > TryFrom<AntColonyBuilder<T, F, H, SR>> for AntColony<T, F, H, SR>
+{
+    type Error = TryFromAntColonyBuilderError;
+
+    fn try_from(builder: AntColonyBuilder<T, F, H, SR>) -> Result<Self, Self::Error> {
+        if builder.genotype.is_none() {
+            Err(TryFromAntColonyBuilderError(
+                "AntColony requires a UniqueGenotype",
+            ))
+        } else if builder.fitness.is_none() {
+            Err(TryFromAntColonyBuilderError("AntColony requires a Fitness"))
+        } else if builder.target_population_size == 0 {
+            Err(TryFromAntColonyBuilderError(
+                "AntColony requires a target_population_size > 0",
+            ))
+        } else if !(builder.evaporation_rate > 0.0 && builder.evaporation_rate <= 1.0) {
+            Err(TryFromAntColonyBuilderError(
+                "AntColony requires an evaporation_rate > 0.0 and <= 1.0",
+            ))
+        } else if builder.max_stale_generations.is_none()
+            && builder.max_generations.is_none()
+            && builder.target_fitness_score.is_none()
+        {
+            Err(TryFromAntColonyBuilderError(
+                "AntColony requires at least a max_stale_generations, max_generations or target_fitness_score ending condition",
+            ))
+        } else {
+            let rng = builder.rng();
+            let mut genotype = builder.genotype.unwrap();
+            if builder.fitness_cache.is_none() {
+                genotype.set_genes_hashing(false);
+            }
+            let state = AntColonyState::new(&genotype);
+
+            Ok(Self {
+                genotype,
+                fitness: builder.fitness.unwrap(),
+                heuristic: builder.heuristic,
+                config: AntColonyConfig {
+                    variant: builder.variant.unwrap_or_default(),
+                    fitness_ordering: builder.fitness_ordering,
+                    fitness_cache: builder.fitness_cache,
+                    par_fitness: builder.par_fitness,
+                    target_population_size: builder.target_population_size,
+                    pheromone_weight: builder.pheromone_weight,
+                    heuristic_weight: builder.heuristic_weight,
+                    evaporation_rate: builder.evaporation_rate,
+                    max_stale_generations: builder.max_stale_generations,
+                    max_generations: builder.max_generations,
+                    target_fitness_score: builder.target_fitness_score,
+                    valid_fitness_score: builder.valid_fitness_score,
+                    replace_on_equal_fitness: builder.replace_on_equal_fitness,
+                },
+                state,
+                reporter: builder.reporter,
+                rng,
+            })
+        }
+    }
+}
+
+impl Default for AntColonyConfig {
+    fn default() -> Self {
+        Self {
+            variant: Default::default(),
+            fitness_ordering: FitnessOrdering::Maximize,
+            fitness_cache: None,
+            par_fitness: false,
+            target_population_size: 100,
+            pheromone_weight: 1.0,
+            heuristic_weight: 2.0,
+            evaporation_rate: 0.1,
+            max_stale_generations: None,
+            max_generations: None,
+            target_fitness_score: None,
+            valid_fitness_score: None,
+            replace_on_equal_fitness: true,
+        }
+    }
+}
+impl AntColonyConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// The upper pheromone bound for [AntColonyVariant::MaxMin], the equilibrium of evaporation
+    /// and a deposit of 1.0 per generation
+    pub fn max_pheromone(&self) -> f32 {
+        1.0 / self.evaporation_rate
+    }
+}
+
+impl<T: Allele + Hash + PartialEq> AntColonyState<T> {
+    pub fn new(genotype: &UniqueGenotype<T>) -> Self {
+        Self {
+            current_iteration: 0,
+            current_generation: 0,
+            stale_generations: 0,
+            best_generation: 0,
+            best_fitness_score: None,
+            chromosome: None,
+            population: Population::new_empty(genotype.chromosome_recycling()),
+            paths: vec![],
+            pheromones: vec![],
+            desirabilities: vec![],
+            durations: HashMap::new(),
+            best_chromosome: None,
+        }
+    }
+    /// The allele_list indices in order of the strongest pheromone trail, starting from the
+    /// virtual start and greedily following the strongest transition to an unvisited allele
+    pub fn strongest_path(&self) -> Vec<usize> {
+        let Some(start_row) = self.pheromones.last() else {
+            return vec![];
+        };
+        let allele_size = start_row.len();
+        let mut visited = vec![false; allele_size];
+        let mut path = Vec::with_capacity(allele_size);
+        let mut from_index = allele_size;
+        for _ in 0..allele_size {
+            let to_index = (0..allele_size)
+                .filter(|index| !visited[*index])
+                .fold(None, |acc: Option<usize>, index| match acc {
+                    Some(best)
+                        if self.pheromones[from_index][best]
+                            >= self.pheromones[from_index][index] =>
+                    {
+                        Some(best)
+                    }
+                    _ => Some(index),
+                })
+                .unwrap();
+            visited[to_index] = true;
+            path.push(to_index);
+            from_index = to_index;
+        }
+        path
+    }
+}
+
+impl<
+        T: Allele + Hash + PartialEq,
+        F: Fitness<Genotype = UniqueGenotype<T>>,
+        H: AntColonyHeuristic<Allele = T>,
+        SR: StrategyReporter<Genotype = UniqueGenotype<T>>,
+    > fmt::Display for AntColony<T, F, H, SR>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "ant_colony:")?;
+        writeln!(f, "  fitness: {:?}", self.fitness)?;
+        writeln!(f, "  heuristic: {:?}", self.heuristic)?;
+        writeln!(f)?;
+
+        writeln!(f, "{}", self.config)?;
+        writeln!(f, "{}", self.state)?;
+        writeln!(f, "{}", self.genotype)
+    }
+}
+
+impl fmt::Display for AntColonyConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "ant_colony_config:")?;
+        writeln!(f, "  variant: {:?}", self.variant)?;
+        writeln!(
+            f,
+            "  target_population_size: {}",
+            self.target_population_size
+        )?;
+        writeln!(f, "  pheromone_weight: {}", self.pheromone_weight)?;
+        writeln!(f, "  heuristic_weight: {}", self.heuristic_weight)?;
+        writeln!(f, "  evaporation_rate: {}", self.evaporation_rate)?;
+        writeln!(
+            f,
+            "  max_stale_generations: {:?}",
+            self.max_stale_generations
+        )?;
+        writeln!(f, "  max_generations: {:?}", self.max_generations)?;
+        writeln!(f, "  valid_fitness_score: {:?}", self.valid_fitness_score)?;
+        writeln!(f, "  target_fitness_score: {:?}", self.target_fitness_score)?;
+        writeln!(f, "  fitness_ordering: {:?}", self.fitness_ordering)?;
+        writeln!(f, "  par_fitness: {:?}", self.par_fitness)
+    }
+}
+
+impl<T: Allele + Hash + PartialEq> fmt::Display for AntColonyState<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "ant_colony_state:")?;
+        writeln!(f, "  current iteration: {:?}", self.current_iteration)?;
+        writeln!(f, "  current generation: {:?}", self.current_generation)?;
+        writeln!(f, "  stale generations: {:?}", self.stale_generations)?;
+        writeln!(f, "  best fitness score: {:?}", self.best_fitness_score())
+    }
+}
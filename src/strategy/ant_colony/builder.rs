@@ -0,0 +1,394 @@
+use super::{AntColony, AntColonyHeuristic, AntColonyHeuristicNoop, AntColonyVariant};
+use crate::allele::Allele;
+pub use crate::errors::TryFromStrategyBuilderError as TryFromBuilderError;
+use crate::fitness::{Fitness, FitnessCache, FitnessOrdering, FitnessValue};
+use crate::genotype::UniqueGenotype;
+use crate::strategy::Strategy;
+pub use crate::strategy::{StrategyReporter, StrategyReporterNoop, StrategyState};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+use rayon::prelude::*;
+use std::hash::Hash;
+use std::sync::mpsc::channel;
+
+/// The builder for an AntColony struct.
+#[derive(Clone, Debug)]
+pub struct Builder<
+    T: Allele + Hash + PartialEq,
+    F: Fitness<Genotype = UniqueGenotype<T>>,
+    H: AntColonyHeuristic<Allele = T>,
+    SR: StrategyReporter<Genotype = UniqueGenotype<T>>,
+> {
+    pub genotype: Option<UniqueGenotype<T>>,
+    pub variant: Option<AntColonyVariant>,
+    pub target_population_size: usize,
+    pub pheromone_weight: f32,
+    pub heuristic_weight: f32,
+    pub evaporation_rate: f32,
+    pub heuristic: H,
+    pub fitness: Option<F>,
+    pub fitness_ordering: FitnessOrdering,
+    pub fitness_cache: Option<FitnessCache>,
+    pub par_fitness: bool,
+    pub max_stale_generations: Option<usize>,
+    pub max_generations: Option<usize>,
+    pub target_fitness_score: Option<FitnessValue>,
+    pub valid_fitness_score: Option<FitnessValue>,
+    pub replace_on_equal_fitness: bool,
+    pub reporter: SR,
+    pub rng_seed: Option<u64>,
+}
+
+impl<T: Allele + Hash + PartialEq, F: Fitness<Genotype = UniqueGenotype<T>>> Default
+    for Builder<T, F, AntColonyHeuristicNoop<T>, StrategyReporterNoop<UniqueGenotype<T>>>
+{
+    fn default() -> Self {
+        Self {
+            genotype: None,
+            variant: None,
+            target_population_size: 0,
+            pheromone_weight: 1.0,
+            heuristic_weight: 2.0,
+            evaporation_rate: 0.1,
+            heuristic: AntColonyHeuristicNoop::new(),
+            fitness: None,
+            fitness_ordering: FitnessOrdering::Maximize,
+            fitness_cache: None,
+            par_fitness: false,
+            max_stale_generations: None,
+            max_generations: None,
+            target_fitness_score: None,
+            valid_fitness_score: None,
+            replace_on_equal_fitness: true,
+            reporter: StrategyReporterNoop::new(),
+            rng_seed: None,
+        }
+    }
+}
+impl<T: Allele + Hash + PartialEq, F: Fitness<Genotype = UniqueGenotype<T>>>
+    Builder<T, F, AntColonyHeuristicNoop<T>, StrategyReporterNoop<UniqueGenotype<T>>>
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<
+        T: Allele + Hash + PartialEq,
+        F: Fitness<Genotype = UniqueGenotype<T>>,
+        H: AntColonyHeuristic<Allele = T>,
+        SR: StrategyReporter<Genotype = UniqueGenotype<T>>,
+    > Builder<T, F, H, SR>
+{
+    pub fn build(self) -> Result<AntColony<T, F, H, SR>, TryFromBuilderError> {
+        self.try_into()
+    }
+    pub fn with_genotype(mut self, genotype: UniqueGenotype<T>) -> Self {
+        self.genotype = Some(genotype);
+        self
+    }
+    pub fn with_variant(mut self, variant: AntColonyVariant) -> Self {
+        self.variant = Some(variant);
+        self
+    }
+    /// The number of ants constructing a permutation per generation
+    pub fn with_target_population_size(mut self, target_population_size: usize) -> Self {
+        self.target_population_size = target_population_size;
+        self
+    }
+    /// The exponent of the pheromone in the transition weight (alpha)
+    pub fn with_pheromone_weight(mut self, pheromone_weight: f32) -> Self {
+        self.pheromone_weight = pheromone_weight;
+        self
+    }
+    /// The exponent of the heuristic desirability in the transition weight (beta)
+    pub fn with_heuristic_weight(mut self, heuristic_weight: f32) -> Self {
+        self.heuristic_weight = heuristic_weight;
+        self
+    }
+    /// The fraction of pheromone evaporating each generation (rho)
+    pub fn with_evaporation_rate(mut self, evaporation_rate: f32) -> Self {
+        self.evaporation_rate = evaporation_rate;
+        self
+    }
+    pub fn with_heuristic<H2: AntColonyHeuristic<Allele = T>>(
+        self,
+        heuristic: H2,
+    ) -> Builder<T, F, H2, SR> {
+        Builder {
+            genotype: self.genotype,
+            variant: self.variant,
+            target_population_size: self.target_population_size,
+            pheromone_weight: self.pheromone_weight,
+            heuristic_weight: self.heuristic_weight,
+            evaporation_rate: self.evaporation_rate,
+            heuristic,
+            fitness: self.fitness,
+            fitness_ordering: self.fitness_ordering,
+            fitness_cache: self.fitness_cache,
+            par_fitness: self.par_fitness,
+            max_stale_generations: self.max_stale_generations,
+            max_generations: self.max_generations,
+            target_fitness_score: self.target_fitness_score,
+            valid_fitness_score: self.valid_fitness_score,
+            replace_on_equal_fitness: self.replace_on_equal_fitness,
+            reporter: self.reporter,
+            rng_seed: self.rng_seed,
+        }
+    }
+    pub fn with_fitness_ordering(mut self, fitness_ordering: FitnessOrdering) -> Self {
+        self.fitness_ordering = fitness_ordering;
+        self
+    }
+    /// Only works when genes_hash is stored on chromosome, as this is the cache key.
+    /// Only useful for long stale runs.
+    /// Silently ignore cache_size of zero, to support superset builder which delays specialization
+    pub fn with_fitness_cache(mut self, fitness_cache_size: usize) -> Self {
+        match FitnessCache::try_new_sharded(
+            fitness_cache_size,
+            FitnessCache::DEFAULT_NUMBER_OF_SHARDS,
+        ) {
+            Ok(cache) => self.fitness_cache = Some(cache),
+            Err(_error) => (),
+        }
+        self
+    }
+    /// Use a prebuilt (and possibly shared) cache, e.g. with another backend
+    /// ([FitnessCache::new_unbounded], [FitnessCache::try_new_file]) or with genes in the key
+    /// ([FitnessCache::with_genes_in_key]). Only works when genes_hash is stored on chromosome.
+    pub fn with_shared_fitness_cache(mut self, fitness_cache: FitnessCache) -> Self {
+        self.fitness_cache = Some(fitness_cache);
+        self
+    }
+    pub fn with_par_fitness(mut self, par_fitness: bool) -> Self {
+        self.par_fitness = par_fitness;
+        self
+    }
+    pub fn with_fitness(mut self, fitness: F) -> Self {
+        self.fitness = Some(fitness);
+        self
+    }
+    pub fn with_max_stale_generations(mut self, max_stale_generations: usize) -> Self {
+        self.max_stale_generations = Some(max_stale_generations);
+        self
+    }
+    pub fn with_max_stale_generations_option(
+        mut self,
+        max_stale_generations_option: Option<usize>,
+    ) -> Self {
+        self.max_stale_generations = max_stale_generations_option;
+        self
+    }
+    pub fn with_max_generations(mut self, max_generations: usize) -> Self {
+        self.max_generations = Some(max_generations);
+        self
+    }
+    pub fn with_max_generations_option(mut self, max_generations_option: Option<usize>) -> Self {
+        self.max_generations = max_generations_option;
+        self
+    }
+    pub fn with_target_fitness_score(mut self, target_fitness_score: FitnessValue) -> Self {
+        self.target_fitness_score = Some(target_fitness_score);
+        self
+    }
+    pub fn with_target_fitness_score_option(
+        mut self,
+        target_fitness_score_option: Option<FitnessValue>,
+    ) -> Self {
+        self.target_fitness_score = target_fitness_score_option;
+        self
+    }
+    pub fn with_valid_fitness_score(mut self, valid_fitness_score: FitnessValue) -> Self {
+        self.valid_fitness_score = Some(valid_fitness_score);
+        self
+    }
+    pub fn with_valid_fitness_score_option(
+        mut self,
+        valid_fitness_score_option: Option<FitnessValue>,
+    ) -> Self {
+        self.valid_fitness_score = valid_fitness_score_option;
+        self
+    }
+    pub fn with_replace_on_equal_fitness(mut self, replace_on_equal_fitness: bool) -> Self {
+        self.replace_on_equal_fitness = replace_on_equal_fitness;
+        self
+    }
+    pub fn with_reporter<SR2: StrategyReporter<Genotype = UniqueGenotype<T>>>(
+        self,
+        reporter: SR2,
+    ) -> Builder<T, F, H, SR2> {
+        Builder {
+            genotype: self.genotype,
+            variant: self.variant,
+            target_population_size: self.target_population_size,
+            pheromone_weight: self.pheromone_weight,
+            heuristic_weight: self.heuristic_weight,
+            evaporation_rate: self.evaporation_rate,
+            heuristic: self.heuristic,
+            fitness: self.fitness,
+            fitness_ordering: self.fitness_ordering,
+            fitness_cache: self.fitness_cache,
+            par_fitness: self.par_fitness,
+            max_stale_generations: self.max_stale_generations,
+            max_generations: self.max_generations,
+            target_fitness_score: self.target_fitness_score,
+            valid_fitness_score: self.valid_fitness_score,
+            replace_on_equal_fitness: self.replace_on_equal_fitness,
+            reporter,
+            rng_seed: self.rng_seed,
+        }
+    }
+    /// Add a reporter next to the existing one (combined as a tuple), every hook is forwarded to
+    /// the existing reporter first and then to the additional reporter
+    pub fn with_additional_reporter<SR2: StrategyReporter<Genotype = UniqueGenotype<T>>>(
+        self,
+        reporter: SR2,
+    ) -> Builder<T, F, H, (SR, SR2)> {
+        Builder {
+            genotype: self.genotype,
+            variant: self.variant,
+            target_population_size: self.target_population_size,
+            pheromone_weight: self.pheromone_weight,
+            heuristic_weight: self.heuristic_weight,
+            evaporation_rate: self.evaporation_rate,
+            heuristic: self.heuristic,
+            fitness: self.fitness,
+            fitness_ordering: self.fitness_ordering,
+            fitness_cache: self.fitness_cache,
+            par_fitness: self.par_fitness,
+            max_stale_generations: self.max_stale_generations,
+            max_generations: self.max_generations,
+            target_fitness_score: self.target_fitness_score,
+            valid_fitness_score: self.valid_fitness_score,
+            replace_on_equal_fitness: self.replace_on_equal_fitness,
+            reporter: (self.reporter, reporter),
+            rng_seed: self.rng_seed,
+        }
+    }
+    pub fn with_rng_seed_from_u64(mut self, rng_seed: u64) -> Self {
+        self.rng_seed = Some(rng_seed);
+        self
+    }
+    pub fn with_rng_seed_from_u64_option(mut self, rng_seed_option: Option<u64>) -> Self {
+        self.rng_seed = rng_seed_option;
+        self
+    }
+}
+
+#[allow(clippy::type_complexity)]
+impl<
+        T: Allele + Hash + PartialEq,
+        F: Fitness<Genotype = UniqueGenotype<T>>,
+        H: AntColonyHeuristic<Allele = T>,
+        SR: StrategyReporter<Genotype = UniqueGenotype<T>>,
+    > Builder<T, F, H, SR>
+{
+    pub fn rng(&self) -> SmallRng {
+        if let Some(seed) = self.rng_seed {
+            SmallRng::seed_from_u64(seed)
+        } else {
+            // SmallRng::from_entropy()
+            SmallRng::from_rng(rand::thread_rng()).unwrap()
+        }
+    }
+    pub fn call(self) -> Result<AntColony<T, F, H, SR>, TryFromBuilderError> {
+        let mut ant_colony: AntColony<T, F, H, SR> = self.try_into()?;
+        ant_colony.call();
+        Ok(ant_colony)
+    }
+
+    pub fn call_repeatedly(
+        self,
+        max_repeats: usize,
+    ) -> Result<(AntColony<T, F, H, SR>, Vec<AntColony<T, F, H, SR>>), TryFromBuilderError> {
+        let mut runs: Vec<AntColony<T, F, H, SR>> = vec![];
+        (0..max_repeats)
+            .filter_map(|iteration| {
+                let mut contending_run: AntColony<T, F, H, SR> = self.clone().try_into().ok()?;
+                contending_run.state.current_iteration = iteration;
+                Some(contending_run)
+            })
+            .map(|mut contending_run| {
+                contending_run.call();
+                let stop = contending_run.is_finished_by_target_fitness_score();
+                runs.push(contending_run);
+                stop
+            })
+            .any(|x| x);
+
+        let best_run = self.extract_best_run(&mut runs);
+        Ok((best_run, runs))
+    }
+
+    pub fn call_par_repeatedly(
+        self,
+        max_repeats: usize,
+    ) -> Result<(AntColony<T, F, H, SR>, Vec<AntColony<T, F, H, SR>>), TryFromBuilderError> {
+        let _valid_builder: AntColony<T, F, H, SR> = self.clone().try_into()?;
+        let mut runs: Vec<AntColony<T, F, H, SR>> = vec![];
+        rayon::scope(|s| {
+            let builder = &self;
+            let (sender, receiver) = channel();
+
+            s.spawn(move |_| {
+                (0..max_repeats)
+                    .filter_map(|iteration| {
+                        let mut contending_run: AntColony<T, F, H, SR> =
+                            builder.clone().try_into().ok()?;
+                        contending_run.state.current_iteration = iteration;
+                        Some(contending_run)
+                    })
+                    .par_bridge()
+                    .map_with(sender, |sender, mut contending_run| {
+                        contending_run.call();
+                        let stop = contending_run.is_finished_by_target_fitness_score();
+                        sender.send(contending_run).unwrap();
+                        stop
+                    })
+                    .any(|x| x);
+            });
+
+            receiver.iter().for_each(|contending_run| {
+                runs.push(contending_run);
+            });
+        });
+        let best_run = self.extract_best_run(&mut runs);
+        Ok((best_run, runs))
+    }
+
+    pub fn extract_best_run(
+        &self,
+        runs: &mut Vec<AntColony<T, F, H, SR>>,
+    ) -> AntColony<T, F, H, SR> {
+        let mut best_index = 0;
+        let mut best_fitness_score: Option<FitnessValue> = None;
+        runs.iter().enumerate().for_each(|(index, contending_run)| {
+            let contending_fitness_score = contending_run.best_fitness_score();
+            match (best_fitness_score, contending_fitness_score) {
+                (None, None) => {}
+                (Some(_), None) => {}
+                (None, Some(_)) => {
+                    best_index = index;
+                    best_fitness_score = contending_fitness_score;
+                }
+                (Some(current_fitness_value), Some(contending_fitness_value)) => {
+                    match self.fitness_ordering {
+                        FitnessOrdering::Maximize => {
+                            if contending_fitness_value >= current_fitness_value {
+                                best_index = index;
+                                best_fitness_score = contending_fitness_score;
+                            }
+                        }
+                        FitnessOrdering::Minimize => {
+                            if contending_fitness_value <= current_fitness_value {
+                                best_index = index;
+                                best_fitness_score = contending_fitness_score;
+                            }
+                        }
+                    }
+                }
+            }
+        });
+        runs.remove(best_index)
+    }
+}
@@ -2,11 +2,15 @@ use crate::crossover::Crossover;
 pub use crate::errors::TryFromStrategyBuilderError as TryFromBuilderError;
 use crate::extension::{Extension, ExtensionNoop};
 use crate::fitness::{Fitness, FitnessCache, FitnessOrdering, FitnessValue};
-use crate::genotype::{EvolveGenotype, HillClimbGenotype, PermutateGenotype};
+use crate::genotype::{
+    EstimateGenotype, EvolveGenotype, HillClimbGenotype, ParticleSwarmGenotype, PermutateGenotype,
+};
 use crate::mutate::Mutate;
 use crate::select::Select;
+use crate::strategy::estimate::EstimateBuilder;
 use crate::strategy::evolve::EvolveBuilder;
 use crate::strategy::hill_climb::HillClimbBuilder;
+use crate::strategy::particle_swarm::ParticleSwarmBuilder;
 use crate::strategy::permutate::PermutateBuilder;
 use crate::strategy::{Strategy, StrategyReporter, StrategyReporterNoop, StrategyVariant};
 
@@ -15,9 +19,17 @@ use crate::strategy::{Strategy, StrategyReporter, StrategyReporterNoop, Strategy
 /// *Note: Only Genotypes which implement all strategies are eligible for the superset builder.*
 /// *All standard genotypes qualify. RangeGenotype/MultiRangeGenotype support Permutation only*
 /// *with MutationType::Step, StepScaled, or Discrete (runtime check via allows_permutation()).*
+/// *Estimate is only supported by BinaryGenotype, ListGenotype and MultiListGenotype (runtime*
+/// *check via allows_estimation()).*
+/// *ParticleSwarm is only supported by RangeGenotype and MultiRangeGenotype (runtime check via*
+/// *allows_particle_swarm()).*
 #[derive(Clone, Debug)]
 pub struct Builder<
-    G: EvolveGenotype + HillClimbGenotype + PermutateGenotype,
+    G: EstimateGenotype
+        + EvolveGenotype
+        + HillClimbGenotype
+        + ParticleSwarmGenotype
+        + PermutateGenotype,
     M: Mutate,
     F: Fitness<Genotype = G>,
     S: Crossover,
@@ -47,7 +59,11 @@ pub struct Builder<
 }
 
 impl<
-        G: EvolveGenotype + HillClimbGenotype + PermutateGenotype,
+        G: EstimateGenotype
+            + EvolveGenotype
+            + HillClimbGenotype
+            + ParticleSwarmGenotype
+            + PermutateGenotype,
         M: Mutate<Genotype = G>,
         F: Fitness<Genotype = G>,
         S: Crossover<Genotype = G>,
@@ -79,7 +95,11 @@ impl<
     }
 }
 impl<
-        G: EvolveGenotype + HillClimbGenotype + PermutateGenotype,
+        G: EstimateGenotype
+            + EvolveGenotype
+            + HillClimbGenotype
+            + ParticleSwarmGenotype
+            + PermutateGenotype,
         M: Mutate<Genotype = G>,
         F: Fitness<Genotype = G>,
         S: Crossover<Genotype = G>,
@@ -93,7 +113,11 @@ impl<
 
 #[allow(clippy::type_complexity)]
 impl<
-        G: EvolveGenotype + HillClimbGenotype + PermutateGenotype,
+        G: EstimateGenotype
+            + EvolveGenotype
+            + HillClimbGenotype
+            + ParticleSwarmGenotype
+            + PermutateGenotype,
         M: Mutate<Genotype = G>,
         F: Fitness<Genotype = G>,
         S: Crossover<Genotype = G>,
@@ -174,12 +198,22 @@ impl<
     /// Only useful for long stale runs, but better to increase population diversity.
     /// Silently ignore cache_size of zero, to support superset builder which delays specialization
     pub fn with_fitness_cache(mut self, fitness_cache_size: usize) -> Self {
-        match FitnessCache::try_new(fitness_cache_size) {
+        match FitnessCache::try_new_sharded(
+            fitness_cache_size,
+            FitnessCache::DEFAULT_NUMBER_OF_SHARDS,
+        ) {
             Ok(cache) => self.fitness_cache = Some(cache),
             Err(_error) => (),
         }
         self
     }
+    /// Use a prebuilt (and possibly shared) cache, e.g. with another backend
+    /// ([FitnessCache::new_unbounded], [FitnessCache::try_new_file]) or with genes in the key
+    /// ([FitnessCache::with_genes_in_key]). Only works when genes_hash is stored on chromosome.
+    pub fn with_shared_fitness_cache(mut self, fitness_cache: FitnessCache) -> Self {
+        self.fitness_cache = Some(fitness_cache);
+        self
+    }
     pub fn with_par_fitness(mut self, par_fitness: bool) -> Self {
         self.par_fitness = par_fitness;
         self
@@ -256,6 +290,34 @@ impl<
             rng_seed: self.rng_seed,
         }
     }
+    /// Add a reporter next to the existing one (combined as a tuple), every hook is forwarded to
+    /// the existing reporter first and then to the additional reporter
+    pub fn with_additional_reporter<SR2: StrategyReporter<Genotype = G>>(
+        self,
+        reporter: SR2,
+    ) -> Builder<G, M, F, S, C, E, (SR, SR2)> {
+        Builder {
+            genotype: self.genotype,
+            variant: self.variant,
+            target_population_size: self.target_population_size,
+            max_stale_generations: self.max_stale_generations,
+            max_generations: self.max_generations,
+            max_chromosome_age: self.max_chromosome_age,
+            target_fitness_score: self.target_fitness_score,
+            valid_fitness_score: self.valid_fitness_score,
+            fitness_ordering: self.fitness_ordering,
+            fitness_cache: self.fitness_cache,
+            par_fitness: self.par_fitness,
+            replace_on_equal_fitness: self.replace_on_equal_fitness,
+            mutate: self.mutate,
+            fitness: self.fitness,
+            crossover: self.crossover,
+            select: self.select,
+            extension: self.extension,
+            reporter: (self.reporter, reporter),
+            rng_seed: self.rng_seed,
+        }
+    }
     pub fn with_rng_seed_from_u64(mut self, rng_seed: u64) -> Self {
         self.rng_seed = Some(rng_seed);
         self
@@ -269,7 +331,12 @@ impl<
 #[allow(clippy::type_complexity)]
 impl<
         'a,
-        G: EvolveGenotype + HillClimbGenotype + PermutateGenotype + 'a,
+        G: EstimateGenotype
+            + EvolveGenotype
+            + HillClimbGenotype
+            + ParticleSwarmGenotype
+            + PermutateGenotype
+            + 'a,
         M: Mutate<Genotype = G> + 'a,
         F: Fitness<Genotype = G> + 'a,
         S: Crossover<Genotype = G> + 'a,
@@ -283,12 +350,32 @@ impl<
             Some(StrategyVariant::Permutate(_)) => {
                 Ok(Box::new(self.to_permutate_builder().build()?))
             }
-            Some(StrategyVariant::Evolve(_)) => Ok(Box::new(self.to_evolve_builder().build()?)),
+            Some(StrategyVariant::Evolve(evolve_variant)) => Ok(Box::new(
+                self.to_evolve_builder()
+                    .with_variant(evolve_variant)
+                    .build()?,
+            )),
             Some(StrategyVariant::HillClimb(hill_climb_variant)) => Ok(Box::new(
                 self.to_hill_climb_builder()
                     .with_variant(hill_climb_variant)
                     .build()?,
             )),
+            Some(StrategyVariant::Estimate(estimate_variant)) => Ok(Box::new(
+                self.to_estimate_builder()
+                    .with_variant(estimate_variant)
+                    .build()?,
+            )),
+            Some(StrategyVariant::ParticleSwarm(particle_swarm_variant)) => Ok(Box::new(
+                self.to_particle_swarm_builder()
+                    .with_variant(particle_swarm_variant)
+                    .build()?,
+            )),
+            Some(StrategyVariant::MapElites(_)) => Err(TryFromBuilderError(
+                "MapElites is not supported by the superset StrategyBuilder, use MapElitesBuilder",
+            )),
+            Some(StrategyVariant::AntColony(_)) => Err(TryFromBuilderError(
+                "AntColony is not supported by the superset StrategyBuilder, use AntColonyBuilder",
+            )),
             None => Err(TryFromBuilderError("StrategyVariant is required")),
         }
     }
@@ -296,6 +383,7 @@ impl<
         PermutateBuilder {
             genotype: self.genotype,
             fitness_ordering: self.fitness_ordering,
+            fitness_cache: self.fitness_cache,
             par_fitness: self.par_fitness,
             replace_on_equal_fitness: self.replace_on_equal_fitness,
             fitness: self.fitness,
@@ -305,6 +393,7 @@ impl<
     pub fn to_evolve_builder(self) -> EvolveBuilder<G, M, F, S, C, E, SR> {
         EvolveBuilder {
             genotype: self.genotype,
+            variant: None,
             target_population_size: self.target_population_size,
             max_stale_generations: self.max_stale_generations,
             max_generations: self.max_generations,
@@ -335,6 +424,49 @@ impl<
             fitness_ordering: self.fitness_ordering,
             fitness_cache: self.fitness_cache,
             par_fitness: self.par_fitness,
+            tabu_evaluated: false,
+            replace_on_equal_fitness: self.replace_on_equal_fitness,
+            fitness: self.fitness,
+            reporter: self.reporter,
+            rng_seed: self.rng_seed,
+        }
+    }
+    pub fn to_estimate_builder(self) -> EstimateBuilder<G, F, SR> {
+        EstimateBuilder {
+            genotype: self.genotype,
+            variant: None,
+            target_population_size: self.target_population_size,
+            selection_rate: 0.5,
+            learning_rate: 0.1,
+            max_stale_generations: self.max_stale_generations,
+            max_generations: self.max_generations,
+            target_fitness_score: self.target_fitness_score,
+            valid_fitness_score: self.valid_fitness_score,
+            fitness_ordering: self.fitness_ordering,
+            fitness_cache: self.fitness_cache,
+            par_fitness: self.par_fitness,
+            replace_on_equal_fitness: self.replace_on_equal_fitness,
+            fitness: self.fitness,
+            reporter: self.reporter,
+            rng_seed: self.rng_seed,
+        }
+    }
+    pub fn to_particle_swarm_builder(self) -> ParticleSwarmBuilder<G, F, SR> {
+        ParticleSwarmBuilder {
+            genotype: self.genotype,
+            variant: None,
+            target_population_size: self.target_population_size,
+            schedule: Default::default(),
+            cognitive_weight: 1.49618,
+            social_weight: 1.49618,
+            max_velocity_rate: 0.5,
+            max_stale_generations: self.max_stale_generations,
+            max_generations: self.max_generations,
+            target_fitness_score: self.target_fitness_score,
+            valid_fitness_score: self.valid_fitness_score,
+            fitness_ordering: self.fitness_ordering,
+            fitness_cache: self.fitness_cache,
+            par_fitness: self.par_fitness,
             replace_on_equal_fitness: self.replace_on_equal_fitness,
             fitness: self.fitness,
             reporter: self.reporter,
@@ -346,7 +478,12 @@ impl<
 #[allow(clippy::type_complexity)]
 impl<
         'a,
-        G: EvolveGenotype + HillClimbGenotype + PermutateGenotype + 'a,
+        G: EstimateGenotype
+            + EvolveGenotype
+            + HillClimbGenotype
+            + ParticleSwarmGenotype
+            + PermutateGenotype
+            + 'a,
         M: Mutate<Genotype = G> + 'a,
         F: Fitness<Genotype = G> + 'a,
         S: Crossover<Genotype = G> + 'a,
@@ -364,6 +501,8 @@ impl<
     /// Permutate: call (once)
     /// Evolve: call_repeatedly
     /// HillClimb: call_repeatedly
+    /// Estimate: call_repeatedly
+    /// ParticleSwarm: call_repeatedly
     pub fn call_repeatedly(
         self,
         max_repeats: usize,
@@ -374,8 +513,11 @@ impl<
                 let run = self.to_permutate_builder().call()?;
                 Ok((Box::new(run), vec![]))
             }
-            Some(StrategyVariant::Evolve(_)) => {
-                let (run, runs) = self.to_evolve_builder().call_repeatedly(max_repeats)?;
+            Some(StrategyVariant::Evolve(evolve_variant)) => {
+                let (run, runs) = self
+                    .to_evolve_builder()
+                    .with_variant(evolve_variant)
+                    .call_repeatedly(max_repeats)?;
                 Ok((
                     Box::new(run),
                     runs.into_iter().map(|r| Box::new(r) as _).collect(),
@@ -391,6 +533,32 @@ impl<
                     runs.into_iter().map(|r| Box::new(r) as _).collect(),
                 ))
             }
+            Some(StrategyVariant::Estimate(estimate_variant)) => {
+                let (run, runs) = self
+                    .to_estimate_builder()
+                    .with_variant(estimate_variant)
+                    .call_repeatedly(max_repeats)?;
+                Ok((
+                    Box::new(run),
+                    runs.into_iter().map(|r| Box::new(r) as _).collect(),
+                ))
+            }
+            Some(StrategyVariant::ParticleSwarm(particle_swarm_variant)) => {
+                let (run, runs) = self
+                    .to_particle_swarm_builder()
+                    .with_variant(particle_swarm_variant)
+                    .call_repeatedly(max_repeats)?;
+                Ok((
+                    Box::new(run),
+                    runs.into_iter().map(|r| Box::new(r) as _).collect(),
+                ))
+            }
+            Some(StrategyVariant::MapElites(_)) => Err(TryFromBuilderError(
+                "MapElites is not supported by the superset StrategyBuilder, use MapElitesBuilder",
+            )),
+            Some(StrategyVariant::AntColony(_)) => Err(TryFromBuilderError(
+                "AntColony is not supported by the superset StrategyBuilder, use AntColonyBuilder",
+            )),
             None => Err(TryFromBuilderError("StrategyVariant is required")),
         }
     }
@@ -398,6 +566,8 @@ impl<
     /// Permutate: call (force with_par_fitness)
     /// Evolve: call_par_repeatedly
     /// HillClimb: call_par_repeatedly
+    /// Estimate: call_par_repeatedly
+    /// ParticleSwarm: call_par_repeatedly
     pub fn call_par_repeatedly(
         self,
         max_repeats: usize,
@@ -408,8 +578,11 @@ impl<
                 let run = self.to_permutate_builder().with_par_fitness(true).call()?;
                 Ok((Box::new(run), vec![]))
             }
-            Some(StrategyVariant::Evolve(_)) => {
-                let (run, runs) = self.to_evolve_builder().call_par_repeatedly(max_repeats)?;
+            Some(StrategyVariant::Evolve(evolve_variant)) => {
+                let (run, runs) = self
+                    .to_evolve_builder()
+                    .with_variant(evolve_variant)
+                    .call_par_repeatedly(max_repeats)?;
                 Ok((
                     Box::new(run),
                     runs.into_iter().map(|r| Box::new(r) as _).collect(),
@@ -425,6 +598,32 @@ impl<
                     runs.into_iter().map(|r| Box::new(r) as _).collect(),
                 ))
             }
+            Some(StrategyVariant::Estimate(estimate_variant)) => {
+                let (run, runs) = self
+                    .to_estimate_builder()
+                    .with_variant(estimate_variant)
+                    .call_par_repeatedly(max_repeats)?;
+                Ok((
+                    Box::new(run),
+                    runs.into_iter().map(|r| Box::new(r) as _).collect(),
+                ))
+            }
+            Some(StrategyVariant::ParticleSwarm(particle_swarm_variant)) => {
+                let (run, runs) = self
+                    .to_particle_swarm_builder()
+                    .with_variant(particle_swarm_variant)
+                    .call_par_repeatedly(max_repeats)?;
+                Ok((
+                    Box::new(run),
+                    runs.into_iter().map(|r| Box::new(r) as _).collect(),
+                ))
+            }
+            Some(StrategyVariant::MapElites(_)) => Err(TryFromBuilderError(
+                "MapElites is not supported by the superset StrategyBuilder, use MapElitesBuilder",
+            )),
+            Some(StrategyVariant::AntColony(_)) => Err(TryFromBuilderError(
+                "AntColony is not supported by the superset StrategyBuilder, use AntColonyBuilder",
+            )),
             None => Err(TryFromBuilderError("StrategyVariant is required")),
         }
     }
@@ -432,6 +631,8 @@ impl<
     /// Permutate: call (once)
     /// Evolve: call_speciated
     /// HillClimb: call_repeatedly
+    /// Estimate: call_repeatedly
+    /// ParticleSwarm: call_repeatedly
     pub fn call_speciated(
         self,
         number_of_species: usize,
@@ -442,8 +643,11 @@ impl<
                 let run = self.to_permutate_builder().call()?;
                 Ok((Box::new(run), vec![]))
             }
-            Some(StrategyVariant::Evolve(_)) => {
-                let (run, runs) = self.to_evolve_builder().call_speciated(number_of_species)?;
+            Some(StrategyVariant::Evolve(evolve_variant)) => {
+                let (run, runs) = self
+                    .to_evolve_builder()
+                    .with_variant(evolve_variant)
+                    .call_speciated(number_of_species)?;
                 Ok((
                     Box::new(run),
                     runs.into_iter().map(|r| Box::new(r) as _).collect(),
@@ -459,6 +663,32 @@ impl<
                     runs.into_iter().map(|r| Box::new(r) as _).collect(),
                 ))
             }
+            Some(StrategyVariant::Estimate(estimate_variant)) => {
+                let (run, runs) = self
+                    .to_estimate_builder()
+                    .with_variant(estimate_variant)
+                    .call_repeatedly(number_of_species)?;
+                Ok((
+                    Box::new(run),
+                    runs.into_iter().map(|r| Box::new(r) as _).collect(),
+                ))
+            }
+            Some(StrategyVariant::ParticleSwarm(particle_swarm_variant)) => {
+                let (run, runs) = self
+                    .to_particle_swarm_builder()
+                    .with_variant(particle_swarm_variant)
+                    .call_repeatedly(number_of_species)?;
+                Ok((
+                    Box::new(run),
+                    runs.into_iter().map(|r| Box::new(r) as _).collect(),
+                ))
+            }
+            Some(StrategyVariant::MapElites(_)) => Err(TryFromBuilderError(
+                "MapElites is not supported by the superset StrategyBuilder, use MapElitesBuilder",
+            )),
+            Some(StrategyVariant::AntColony(_)) => Err(TryFromBuilderError(
+                "AntColony is not supported by the superset StrategyBuilder, use AntColonyBuilder",
+            )),
             None => Err(TryFromBuilderError("StrategyVariant is required")),
         }
     }
@@ -466,6 +696,8 @@ impl<
     /// Permutate: call (force with_par_fitness)
     /// Evolve: call_par_speciated
     /// HillClimb: call_par_repeatedly
+    /// Estimate: call_par_repeatedly
+    /// ParticleSwarm: call_par_repeatedly
     pub fn call_par_speciated(
         self,
         number_of_species: usize,
@@ -476,9 +708,10 @@ impl<
                 let run = self.to_permutate_builder().with_par_fitness(true).call()?;
                 Ok((Box::new(run), vec![]))
             }
-            Some(StrategyVariant::Evolve(_)) => {
+            Some(StrategyVariant::Evolve(evolve_variant)) => {
                 let (run, runs) = self
                     .to_evolve_builder()
+                    .with_variant(evolve_variant)
                     .call_par_speciated(number_of_species)?;
                 Ok((
                     Box::new(run),
@@ -495,6 +728,32 @@ impl<
                     runs.into_iter().map(|r| Box::new(r) as _).collect(),
                 ))
             }
+            Some(StrategyVariant::Estimate(estimate_variant)) => {
+                let (run, runs) = self
+                    .to_estimate_builder()
+                    .with_variant(estimate_variant)
+                    .call_par_repeatedly(number_of_species)?;
+                Ok((
+                    Box::new(run),
+                    runs.into_iter().map(|r| Box::new(r) as _).collect(),
+                ))
+            }
+            Some(StrategyVariant::ParticleSwarm(particle_swarm_variant)) => {
+                let (run, runs) = self
+                    .to_particle_swarm_builder()
+                    .with_variant(particle_swarm_variant)
+                    .call_par_repeatedly(number_of_species)?;
+                Ok((
+                    Box::new(run),
+                    runs.into_iter().map(|r| Box::new(r) as _).collect(),
+                ))
+            }
+            Some(StrategyVariant::MapElites(_)) => Err(TryFromBuilderError(
+                "MapElites is not supported by the superset StrategyBuilder, use MapElitesBuilder",
+            )),
+            Some(StrategyVariant::AntColony(_)) => Err(TryFromBuilderError(
+                "AntColony is not supported by the superset StrategyBuilder, use AntColonyBuilder",
+            )),
             None => Err(TryFromBuilderError("StrategyVariant is required")),
         }
     }
@@ -0,0 +1,840 @@
+//! A solution strategy for finding the best chromosome, by learning a probability model of the good
+//! solutions instead of recombining them
+mod builder;
+pub mod prelude;
+mod reporter;
+
+pub use self::builder::{
+    Builder as EstimateBuilder, TryFromBuilderError as TryFromEstimateBuilderError,
+};
+
+use super::{
+    Strategy, StrategyAction, StrategyConfig, StrategyReporter, StrategyReporterNoop,
+    StrategyState, StrategyVariant,
+};
+use crate::chromosome::{Chromosome, Genes};
+use crate::fitness::{Fitness, FitnessCache, FitnessOrdering, FitnessValue};
+use crate::genotype::EstimateGenotype;
+use crate::population::Population;
+use rand::rngs::SmallRng;
+use rand::Rng;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+use thread_local::ThreadLocal;
+
+pub use self::reporter::Simple as EstimateReporterSimple;
+pub use crate::strategy::reporter::Duration as EstimateReporterDuration;
+pub use crate::strategy::reporter::Log as EstimateReporterLog;
+pub use crate::strategy::reporter::Noop as EstimateReporterNoop;
+pub use crate::strategy::reporter::Recorder as EstimateReporterRecorder;
+
+#[derive(Copy, Clone, Debug, Default)]
+pub enum EstimateVariant {
+    #[default]
+    Umda,
+    Pbil,
+    CompactGa,
+}
+
+/// The Estimate strategy is an estimation of distribution algorithm (EDA). Instead of
+/// recombining chromosomes, it maintains a probability vector over the alleles of each gene,
+/// samples a population from it and updates the probabilities from the best samples.
+///
+/// There are 3 variants:
+/// * [EstimateVariant::Umda]: Univariate Marginal Distribution Algorithm. The probabilities are
+///   replaced by the allele frequencies of the selected best samples (the learning_rate is ignored)
+/// * [EstimateVariant::Pbil]: Population-Based Incremental Learning. The probabilities are shifted
+///   towards the allele frequencies of the selected best samples with the learning_rate
+/// * [EstimateVariant::CompactGa]: Compact Genetic Algorithm. Only two chromosomes are sampled per
+///   generation, the probabilities of the differing genes are shifted by 1/target_population_size
+///   towards the winner. The target_population_size is the size of the simulated population
+///   (the selection_rate and learning_rate are ignored)
+///
+/// For Umda and Pbil the probabilities are kept at least 1/(genes_size * allele_size) per allele,
+/// so an allele missing from one selection can still be sampled later on (avoiding premature
+/// convergence of the model).
+///
+/// Only genotypes with a finite allele list per gene allow estimation, see [EstimateGenotype]:
+/// [BinaryGenotype](crate::genotype::BinaryGenotype), [ListGenotype](crate::genotype::ListGenotype)
+/// and [MultiListGenotype](crate::genotype::MultiListGenotype).
+/// The first population is constructed by the genotype (random or from the seed genes), all later
+/// populations are sampled from the probability model. The learned probability model remains
+/// available in [EstimateState] after the run.
+///
+/// The ending conditions are one or more of the following:
+/// * target_fitness_score: when the ultimate goal in terms of fitness score is known and reached
+/// * max_stale_generations: when the ultimate goal in terms of fitness score is unknown and one
+///   depends on some convergion threshold, or one wants a duration limitation next to the
+///   target_fitness_score. Set to a higher value for [EstimateVariant::CompactGa], as only two
+///   chromosomes are sampled per generation
+/// * max_generations: when the ultimate goal in terms of fitness score is unknown and there is a effort constraint
+///
+/// There are reporting hooks in the loop receiving the [EstimateState], which can by handled by an
+/// [StrategyReporter] (e.g. [EstimateReporterDuration], [EstimateReporterSimple]). But you are encouraged to
+/// roll your own, see [StrategyReporter].
+///
+/// Below is the exact order of actions and hooks
+/// * [reporter](crate::strategy::reporter) on_enter hook
+/// * setup (construct, fitness and update model of first population)
+/// * [reporter](crate::strategy::reporter) on_start hook
+/// * loop while not finished
+///   * increment generation
+///   * sample population from probability model
+///   * [fitness](crate::fitness) calculation
+///   * update best chromosome
+///   * update probability model
+///   * [reporter](crate::strategy::reporter) on_generation_complete hook
+///   * check ending conditions
+/// * [reporter](crate::strategy::reporter) on_finish hook
+/// * cleanup
+/// * [reporter](crate::strategy::reporter) on_exit hook
+///
+/// From the [EstimateBuilder] level, there are several calling mechanisms:
+/// * [call](EstimateBuilder::call): this runs a single [Estimate] strategy
+/// * [call_repeatedly](EstimateBuilder::call_repeatedly): this runs multiple independent [Estimate]
+///   strategies and returns the best one (or short circuits when the target_fitness_score is
+///   reached)
+/// * [call_par_repeatedly](EstimateBuilder::call_par_repeatedly): this runs multiple independent
+///   [Estimate] strategies in parallel and returns the best one (or short circuits when the
+///   target_fitness_score is reached). This is separate and independent from the
+///   `with_par_fitness()` flag on the builder, which determines multithreading of the fitness
+///   calculation inside the [Estimate] strategy. Both can be combined.
+///
+/// All multithreading mechanisms are implemented using [rayon::iter] and [std::sync::mpsc].
+///
+/// See [EstimateBuilder] for initialization options.
+///
+/// Example:
+/// ```
+/// use genetic_algorithm::strategy::estimate::prelude::*;
+/// use genetic_algorithm::fitness::placeholders::CountTrue;
+///
+/// // the search space
+/// let genotype = BinaryGenotype::builder() // boolean alleles
+///     .with_genes_size(100)                // 100 genes per chromosome
+///     .build()
+///     .unwrap();
+///
+/// // the search strategy
+/// let estimate = Estimate::builder()
+///     .with_genotype(genotype)
+///     .with_variant(EstimateVariant::Pbil)             // optional, defaults to EstimateVariant::Umda
+///     .with_target_population_size(100)                // sample 100 chromosomes per generation
+///     .with_selection_rate(0.2)                        // optional, defaults to 0.5, update the model from the best 20% of the samples
+///     .with_learning_rate(0.1)                         // optional, defaults to 0.1, only used by EstimateVariant::Pbil
+///     .with_fitness(CountTrue)                         // count the number of true values in the chromosomes
+///     .with_fitness_ordering(FitnessOrdering::Maximize) // optional, defaults to Maximize, aim for the most true values
+///     .with_par_fitness(true)                          // optional, defaults to false, use parallel fitness calculation
+///     .with_target_fitness_score(100)                  // ending condition if 100 times true in the best chromosome
+///     .with_max_stale_generations(100)                 // stop searching if there is no improvement in fitness score for 100 generations
+///     .with_reporter(EstimateReporterSimple::new(100)) // optional, report every 100 generations
+///     .with_rng_seed_from_u64(0)                       // for testing with deterministic results
+///     .call()
+///     .unwrap();
+///
+/// // it's all about the best genes after all
+/// let (best_genes, best_fitness_score) = estimate.best_genes_and_fitness_score().unwrap();
+/// assert_eq!(best_genes, vec![true; 100]);
+/// assert_eq!(best_fitness_score, 100);
+///
+/// // the learned probability model, per gene the probability of false and true
+/// assert!(estimate.state.probabilities.iter().all(|p| p[1] > 0.5));
+/// assert_eq!(estimate.most_likely_genes(), Some(vec![true; 100]));
+/// ```
+pub struct Estimate<
+    G: EstimateGenotype,
+    F: Fitness<Genotype = G>,
+    SR: StrategyReporter<Genotype = G>,
+> {
+    pub genotype: G,
+    pub fitness: F,
+    pub config: EstimateConfig,
+    pub state: EstimateState<G>,
+    pub reporter: SR,
+    pub rng: SmallRng,
+}
+
+pub struct EstimateConfig {
+    pub variant: EstimateVariant,
+    pub fitness_ordering: FitnessOrdering,
+    pub par_fitness: bool,
+    pub replace_on_equal_fitness: bool,
+    pub target_population_size: usize,
+    pub selection_rate: f32,
+    pub learning_rate: f32,
+
+    pub target_fitness_score: Option<FitnessValue>,
+    pub max_stale_generations: Option<usize>,
+    pub max_generations: Option<usize>,
+    pub valid_fitness_score: Option<FitnessValue>,
+    pub fitness_cache: Option<FitnessCache>,
+}
+
+/// Stores the state of the Estimate strategy.
+pub struct EstimateState<G: EstimateGenotype> {
+    pub current_iteration: usize,
+    pub current_generation: usize,
+    pub stale_generations: usize,
+    pub best_generation: usize,
+    pub best_fitness_score: Option<FitnessValue>,
+    pub best_chromosome: Option<Chromosome<G::Allele>>,
+    pub chromosome: Option<Chromosome<G::Allele>>,
+    pub population: Population<G::Allele>,
+    /// The learned probability model, per gene the probabilities of the alleles, indexed as the
+    /// allele list of the gene (see [EstimateGenotype])
+    pub probabilities: Vec<Vec<f32>>,
+    pub durations: HashMap<StrategyAction, Duration>,
+}
+
+impl<G: EstimateGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genotype = G>> Strategy<G>
+    for Estimate<G, F, SR>
+{
+    fn call(&mut self) {
+        let now = Instant::now();
+        self.reporter
+            .on_enter(&self.genotype, &self.state, &self.config);
+        let mut fitness_thread_local: Option<ThreadLocal<RefCell<F>>> = None;
+        if self.config.par_fitness {
+            fitness_thread_local = Some(ThreadLocal::new());
+        }
+
+        self.setup(fitness_thread_local.as_ref());
+        self.reporter
+            .on_start(&self.genotype, &self.state, &self.config);
+        while !self.is_finished() {
+            self.state.increment_generation();
+            self.state.sample_population(&self.genotype, &mut self.rng);
+            self.fitness.call_for_state_population(
+                &self.genotype,
+                &mut self.state,
+                &self.config,
+                fitness_thread_local.as_ref(),
+            );
+            self.state.update_best_chromosome_from_state_population(
+                &self.genotype,
+                &self.config,
+                &mut self.reporter,
+            );
+            self.state
+                .update_probabilities(&self.genotype, &self.config);
+            self.reporter
+                .on_generation_complete(&self.genotype, &self.state, &self.config);
+        }
+        self.reporter
+            .on_finish(&self.genotype, &self.state, &self.config);
+        self.cleanup(fitness_thread_local.as_mut());
+        self.state.close_duration(now.elapsed());
+        self.reporter
+            .on_exit(&self.genotype, &self.state, &self.config);
+    }
+    fn best_generation(&self) -> usize {
+        self.state.best_generation
+    }
+    fn best_fitness_score(&self) -> Option<FitnessValue> {
+        self.state.best_fitness_score()
+    }
+    fn best_genes(&self) -> Option<Genes<G::Allele>> {
+        self.state
+            .best_chromosome
+            .as_ref()
+            .map(|c| c.genes().clone())
+    }
+    fn flush_reporter(&mut self, output: &mut Vec<u8>) {
+        self.reporter.flush(output);
+    }
+}
+impl<G: EstimateGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genotype = G>>
+    Estimate<G, F, SR>
+{
+    pub fn best_chromosome(&self) -> Option<Chromosome<G::Allele>> {
+        if let Some(best_genes) = self.best_genes() {
+            let mut chromosome = Chromosome::<G::Allele>::new(best_genes);
+            chromosome.set_fitness_score(self.best_fitness_score());
+            Some(chromosome)
+        } else {
+            None
+        }
+    }
+    /// The genes with the most likely allele per gene in the learned probability model (the mode
+    /// of the model, which is not necessarily the best chromosome). None before the run.
+    pub fn most_likely_genes(&self) -> Option<Genes<G::Allele>> {
+        let mut chromosome = self.best_chromosome()?;
+        self.state
+            .most_likely_allele_indices()
+            .into_iter()
+            .enumerate()
+            .for_each(|(gene_index, allele_index)| {
+                self.genotype
+                    .set_gene_by_allele_index(&mut chromosome, gene_index, allele_index)
+            });
+        Some(chromosome.genes)
+    }
+}
+
+impl<G: EstimateGenotype, F: Fitness<Genotype = G>> Estimate<G, F, StrategyReporterNoop<G>> {
+    pub fn builder() -> EstimateBuilder<G, F, StrategyReporterNoop<G>> {
+        EstimateBuilder::new()
+    }
+}
+impl<G: EstimateGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genotype = G>>
+    Estimate<G, F, SR>
+{
+    pub fn setup(&mut self, fitness_thread_local: Option<&ThreadLocal<RefCell<F>>>) {
+        let now = Instant::now();
+        self.state.probabilities = self
+            .genotype
+            .allele_sizes()
+            .into_iter()
+            .map(|allele_size| vec![1.0 / allele_size as f32; allele_size])
+            .collect();
+        self.state.population = self
+            .genotype
+            .population_constructor(self.config.sample_size(), &mut self.rng);
+        self.state
+            .add_duration(StrategyAction::SetupAndCleanup, now.elapsed());
+
+        self.fitness.call_for_state_population(
+            &self.genotype,
+            &mut self.state,
+            &self.config,
+            fitness_thread_local,
+        );
+        self.state.update_best_chromosome_from_state_population(
+            &self.genotype,
+            &self.config,
+            &mut self.reporter,
+        );
+        self.state
+            .update_probabilities(&self.genotype, &self.config);
+    }
+    pub fn cleanup(&mut self, fitness_thread_local: Option<&mut ThreadLocal<RefCell<F>>>) {
+        let now = Instant::now();
+        self.state.chromosome.take();
+        self.state.population.chromosomes.clear();
+        if let Some(thread_local) = fitness_thread_local {
+            thread_local.clear();
+        }
+        self.state
+            .add_duration(StrategyAction::SetupAndCleanup, now.elapsed());
+    }
+    fn is_finished(&self) -> bool {
+        self.allow_finished_by_valid_fitness_score()
+            && (self.is_finished_by_max_stale_generations()
+                || self.is_finished_by_max_generations()
+                || self.is_finished_by_target_fitness_score())
+    }
+
+    fn is_finished_by_max_stale_generations(&self) -> bool {
+        if let Some(max_stale_generations) = self.config.max_stale_generations {
+            self.state.stale_generations >= max_stale_generations
+        } else {
+            false
+        }
+    }
+
+    fn is_finished_by_max_generations(&self) -> bool {
+        if let Some(max_generations) = self.config.max_generations {
+            self.state.current_generation >= max_generations
+        } else {
+            false
+        }
+    }
+
+    fn is_finished_by_target_fitness_score(&self) -> bool {
+        if let Some(target_fitness_score) = self.config.target_fitness_score {
+            if let Some(fitness_score) = self.best_fitness_score() {
+                match self.config.fitness_ordering {
+                    FitnessOrdering::Maximize => fitness_score >= target_fitness_score,
+                    FitnessOrdering::Minimize => fitness_score <= target_fitness_score,
+                }
+            } else {
+                false
+            }
+        } else {
+            false
+        }
+    }
+
+    fn allow_finished_by_valid_fitness_score(&self) -> bool {
+        if let Some(valid_fitness_score) = self.config.valid_fitness_score {
+            if let Some(fitness_score) = self.best_fitness_score() {
+                match self.config.fitness_ordering {
+                    FitnessOrdering::Maximize => fitness_score >= valid_fitness_score,
+                    FitnessOrdering::Minimize => fitness_score <= valid_fitness_score,
+                }
+            } else {
+                true
+            }
+        } else {
+            true
+        }
+    }
+}
+
+impl StrategyConfig for EstimateConfig {
+    fn fitness_ordering(&self) -> FitnessOrdering {
+        self.fitness_ordering
+    }
+    fn fitness_cache(&self) -> Option<&FitnessCache> {
+        self.fitness_cache.as_ref()
+    }
+    fn par_fitness(&self) -> bool {
+        self.par_fitness
+    }
+    fn replace_on_equal_fitness(&self) -> bool {
+        self.replace_on_equal_fitness
+    }
+    fn variant(&self) -> StrategyVariant {
+        StrategyVariant::Estimate(self.variant)
+    }
+}
+
+impl<G: EstimateGenotype> StrategyState<G> for EstimateState<G> {
+    fn chromosome_as_ref(&self) -> &Option<Chromosome<G::Allele>> {
+        &self.chromosome
+    }
+    fn population_as_ref(&self) -> &Population<G::Allele> {
+        &self.population
+    }
+    fn chromosome_as_mut(&mut self) -> &mut Option<Chromosome<G::Allele>> {
+        &mut self.chromosome
+    }
+    fn population_as_mut(&mut self) -> &mut Population<G::Allele> {
+        &mut self.population
+    }
+    fn best_fitness_score(&self) -> Option<FitnessValue> {
+        self.best_fitness_score
+    }
+    fn best_generation(&self) -> usize {
+        self.best_generation
+    }
+    fn current_generation(&self) -> usize {
+        self.current_generation
+    }
+    fn current_iteration(&self) -> usize {
+        self.current_iteration
+    }
+    fn increment_generation(&mut self) {
+        self.current_generation += 1;
+    }
+    fn stale_generations(&self) -> usize {
+        self.stale_generations
+    }
+    fn increment_stale_generations(&mut self) {
+        self.stale_generations += 1;
+    }
+    fn reset_stale_generations(&mut self) {
+        self.stale_generations = 0;
+    }
+    fn scale_generation(&self) -> usize {
+        self.current_generation
+    }
+    fn reset_scale_generation(&mut self) {}
+    fn population_cardinality(&self) -> Option<usize> {
+        None
+    }
+    fn durations(&self) -> &HashMap<StrategyAction, Duration> {
+        &self.durations
+    }
+    fn add_duration(&mut self, action: StrategyAction, duration: Duration) {
+        *self.durations.entry(action).or_default() += duration;
+    }
+    fn total_duration(&self) -> Duration {
+        self.durations.values().sum()
+    }
+    fn best_genes(&self) -> Option<Genes<G::Allele>> {
+        self.best_chromosome.as_ref().map(|c| c.genes().clone())
+    }
+}
+
+impl<G: EstimateGenotype> EstimateState<G> {
+    /// The index of the most likely allele per gene (the first one on equal probabilities)
+    pub fn most_likely_allele_indices(&self) -> Vec<usize> {
+        self.probabilities
+            .iter()
+            .map(|probabilities| {
+                probabilities
+                    .iter()
+                    .enumerate()
+                    .fold((0, f32::NEG_INFINITY), |acc, (index, probability)| {
+                        if *probability > acc.1 {
+                            (index, *probability)
+                        } else {
+                            acc
+                        }
+                    })
+                    .0
+            })
+            .collect()
+    }
+    /// The mean over the genes of the probability of the most likely allele. Ranges from
+    /// 1/allele_size for an uninformed model up to 1.0 for a fully converged model
+    pub fn model_convergence(&self) -> f32 {
+        if self.probabilities.is_empty() {
+            return 0.0;
+        }
+        self.probabilities
+            .iter()
+            .map(|probabilities| probabilities.iter().copied().fold(0.0, f32::max))
+            .sum::<f32>()
+            / self.probabilities.len() as f32
+    }
+
+    /// Resample the genes of the existing chromosomes in place from the probability model
+    fn sample_population<R: Rng>(&mut self, genotype: &G, rng: &mut R) {
+        let now = Instant::now();
+        let genes_hashing = genotype.genes_hashing();
+        for chromosome in self.population.chromosomes.iter_mut() {
+            for (gene_index, probabilities) in self.probabilities.iter().enumerate() {
+                let allele_index = sample_allele_index(probabilities, rng);
+                genotype.set_gene_by_allele_index(chromosome, gene_index, allele_index);
+            }
+            chromosome.reset_metadata(genes_hashing);
+        }
+        self.add_duration(StrategyAction::Other, now.elapsed());
+    }
+
+    fn update_probabilities(&mut self, genotype: &G, config: &EstimateConfig) {
+        let now = Instant::now();
+        match config.variant {
+            EstimateVariant::Umda => {
+                self.update_probabilities_from_selection(genotype, config, 1.0)
+            }
+            EstimateVariant::Pbil => {
+                self.update_probabilities_from_selection(genotype, config, config.learning_rate)
+            }
+            EstimateVariant::CompactGa => {
+                self.update_probabilities_from_tournament(genotype, config)
+            }
+        }
+        self.add_duration(StrategyAction::Other, now.elapsed());
+    }
+
+    /// Shift the probabilities towards the allele frequencies of the best selection_rate of the
+    /// population. A learning_rate of 1.0 replaces the probabilities with the frequencies.
+    fn update_probabilities_from_selection(
+        &mut self,
+        genotype: &G,
+        config: &EstimateConfig,
+        learning_rate: f32,
+    ) {
+        let mut scored: Vec<(usize, FitnessValue)> = self
+            .population
+            .chromosomes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, c)| c.fitness_score().map(|score| (index, score)))
+            .collect();
+        if scored.is_empty() {
+            return;
+        }
+        match config.fitness_ordering {
+            FitnessOrdering::Maximize => scored.sort_by_key(|(_, score)| std::cmp::Reverse(*score)),
+            FitnessOrdering::Minimize => scored.sort_by_key(|(_, score)| *score),
+        }
+        let selection_size =
+            ((scored.len() as f32 * config.selection_rate).ceil() as usize).clamp(1, scored.len());
+        let selected = &scored[..selection_size];
+
+        for (gene_index, probabilities) in self.probabilities.iter_mut().enumerate() {
+            let mut frequencies = vec![0.0_f32; probabilities.len()];
+            let mut total = 0.0_f32;
+            for (index, _) in selected {
+                let gene = &self.population.chromosomes[*index].genes[gene_index];
+                if let Some(allele_index) = genotype.allele_index(gene_index, gene) {
+                    frequencies[allele_index] += 1.0;
+                    total += 1.0;
+                }
+            }
+            if total > 0.0 {
+                probabilities
+                    .iter_mut()
+                    .zip(frequencies)
+                    .for_each(|(probability, frequency)| {
+                        *probability =
+                            (1.0 - learning_rate) * *probability + learning_rate * frequency / total
+                    });
+                let floor = 1.0 / (genotype.genes_size() * probabilities.len()) as f32;
+                apply_probability_floor(probabilities, floor);
+            }
+        }
+    }
+
+    /// Compare two chromosomes and shift the probabilities of the differing genes by
+    /// 1/target_population_size towards the alleles of the winner
+    fn update_probabilities_from_tournament(&mut self, genotype: &G, config: &EstimateConfig) {
+        let (first, second) = match self.population.chromosomes.as_slice() {
+            [first, second, ..] => (first, second),
+            _ => return,
+        };
+        let (winner, loser) = match (first.fitness_score(), second.fitness_score()) {
+            (Some(first_score), Some(second_score)) if first_score != second_score => {
+                let first_wins = match config.fitness_ordering {
+                    FitnessOrdering::Maximize => first_score > second_score,
+                    FitnessOrdering::Minimize => first_score < second_score,
+                };
+                if first_wins {
+                    (first, second)
+                } else {
+                    (second, first)
+                }
+            }
+            (Some(_), None) => (first, second),
+            (None, Some(_)) => (second, first),
+            _ => return,
+        };
+        let step = 1.0 / config.target_population_size as f32;
+        for (gene_index, probabilities) in self.probabilities.iter_mut().enumerate() {
+            let winner_allele_index = genotype.allele_index(gene_index, &winner.genes[gene_index]);
+            let loser_allele_index = genotype.allele_index(gene_index, &loser.genes[gene_index]);
+            if let (Some(winner_allele_index), Some(loser_allele_index)) =
+                (winner_allele_index, loser_allele_index)
+            {
+                if winner_allele_index != loser_allele_index {
+                    let shift = step.min(probabilities[loser_allele_index]);
+                    probabilities[loser_allele_index] -= shift;
+                    probabilities[winner_allele_index] += shift;
+                }
+            }
+        }
+    }
+
+    fn update_best_chromosome_from_state_population<SR: StrategyReporter<Genotype = G>>(
+        &mut self,
+        genotype: &G,
+        config: &EstimateConfig,
+        reporter: &mut SR,
+    ) {
+        let now = Instant::now();
+        if let Some(contending_chromosome) =
+            self.population.best_chromosome(config.fitness_ordering)
+        {
+            match self.is_better_chromosome(
+                contending_chromosome,
+                &config.fitness_ordering,
+                config.replace_on_equal_fitness,
+            ) {
+                (true, true) => {
+                    self.best_generation = self.current_generation;
+                    self.best_fitness_score = contending_chromosome.fitness_score();
+                    self.best_chromosome = Some(contending_chromosome.clone());
+                    reporter.on_new_best_chromosome(genotype, self, config);
+                    self.reset_stale_generations();
+                }
+                (true, false) => {
+                    self.best_chromosome = Some(contending_chromosome.clone());
+                    reporter.on_new_best_chromosome_equal_fitness(genotype, self, config);
+                    self.increment_stale_generations()
+                }
+                _ => self.increment_stale_generations(),
+            }
+        } else {
+            self.increment_stale_generations();
+        }
+        self.add_duration(StrategyAction::UpdateBestChromosome, now.elapsed());
+    }
+}
+
+/// Roulette sampling of an allele index, falls back to the last allele on rounding errors
+fn sample_allele_index<R: Rng>(probabilities: &[f32], rng: &mut R) -> usize {
+    let mut remaining = rng.gen::<f32>() * probabilities.iter().sum::<f32>();
+    for (index, probability) in probabilities.iter().enumerate() {
+        if remaining < *probability {
+            return index;
+        }
+        remaining -= probability;
+    }
+    probabilities.len().saturating_sub(1)
+}
+
+impl<G: EstimateGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genotype = G>>
+    TryFrom<EstimateBuilder<G, F, SR>> for Estimate<G, F, SR>
+{
+    type Error = TryFromEstimateBuilderError;
+
+    fn try_from(builder: EstimateBuilder<G, F, SR>) -> Result<Self, Self::Error> {
+        if builder.genotype.is_none() {
+            Err(TryFromEstimateBuilderError(
+                "Estimate requires a EstimateGenotype",
+            ))
+        } else if !builder.genotype.as_ref().unwrap().allows_estimation() {
+            Err(TryFromEstimateBuilderError(
+                "Estimate requires a genotype with a finite allele list per gene (BinaryGenotype, ListGenotype or MultiListGenotype without gene_constraints)",
+            ))
+        } else if builder.fitness.is_none() {
+            Err(TryFromEstimateBuilderError("Estimate requires a Fitness"))
+        } else if builder.target_population_size == 0 {
+            Err(TryFromEstimateBuilderError(
+                "Estimate requires a target_population_size > 0",
+            ))
+        } else if !(builder.selection_rate > 0.0 && builder.selection_rate <= 1.0) {
+            Err(TryFromEstimateBuilderError(
+                "Estimate requires a selection_rate > 0.0 and <= 1.0",
+            ))
+        } else if !(builder.learning_rate > 0.0 && builder.learning_rate <= 1.0) {
+            Err(TryFromEstimateBuilderError(
+                "Estimate requires a learning_rate > 0.0 and <= 1.0",
+            ))
+        } else if builder.max_stale_generations.is_none()
+            && builder.max_generations.is_none()
+            && builder.target_fitness_score.is_none()
+        {
+            Err(TryFromEstimateBuilderError(
+                "Estimate requires at least a max_stale_generations, max_generations or target_fitness_score ending condition",
+            ))
+        } else {
+            let rng = builder.rng();
+            let mut genotype = builder.genotype.unwrap();
+            if builder.fitness_cache.is_none() {
+                genotype.set_genes_hashing(false);
+            }
+            let state = EstimateState::new(&genotype);
+
+            Ok(Self {
+                genotype,
+                fitness: builder.fitness.unwrap(),
+                config: EstimateConfig {
+                    variant: builder.variant.unwrap_or_default(),
+                    fitness_ordering: builder.fitness_ordering,
+                    fitness_cache: builder.fitness_cache,
+                    par_fitness: builder.par_fitness,
+                    target_population_size: builder.target_population_size,
+                    selection_rate: builder.selection_rate,
+                    learning_rate: builder.learning_rate,
+                    max_stale_generations: builder.max_stale_generations,
+                    max_generations: builder.max_generations,
+                    target_fitness_score: builder.target_fitness_score,
+                    valid_fitness_score: builder.valid_fitness_score,
+                    replace_on_equal_fitness: builder.replace_on_equal_fitness,
+                },
+                state,
+                reporter: builder.reporter,
+                rng,
+            })
+        }
+    }
+}
+
+impl Default for EstimateConfig {
+    fn default() -> Self {
+        Self {
+            variant: Default::default(),
+            fitness_ordering: FitnessOrdering::Maximize,
+            fitness_cache: None,
+            par_fitness: false,
+            target_population_size: 100,
+            selection_rate: 0.5,
+            learning_rate: 0.1,
+            max_stale_generations: None,
+            max_generations: None,
+            target_fitness_score: None,
+            valid_fitness_score: None,
+            replace_on_equal_fitness: true,
+        }
+    }
+}
+impl EstimateConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// The number of chromosomes sampled per generation, two for the
+    /// [EstimateVariant::CompactGa] (the target_population_size is simulated)
+    pub fn sample_size(&self) -> usize {
+        match self.variant {
+            EstimateVariant::CompactGa => 2,
+            _ => self.target_population_size,
+        }
+    }
+}
+
+impl<G: EstimateGenotype> EstimateState<G> {
+    pub fn new(genotype: &G) -> Self {
+        Self {
+            current_iteration: 0,
+            current_generation: 0,
+            stale_generations: 0,
+            best_generation: 0,
+            best_fitness_score: None,
+            chromosome: None,
+            population: Population::new_empty(genotype.chromosome_recycling()),
+            probabilities: vec![],
+            durations: HashMap::new(),
+            best_chromosome: None,
+        }
+    }
+}
+
+impl<G: EstimateGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genotype = G>> fmt::Display
+    for Estimate<G, F, SR>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "estimate:")?;
+        writeln!(f, "  fitness: {:?}", self.fitness)?;
+        writeln!(f)?;
+
+        writeln!(f, "{}", self.config)?;
+        writeln!(f, "{}", self.state)?;
+        writeln!(f, "{}", self.genotype)
+    }
+}
+
+impl fmt::Display for EstimateConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "estimate_config:")?;
+        writeln!(f, "  variant: {:?}", self.variant)?;
+        writeln!(
+            f,
+            "  target_population_size: {}",
+            self.target_population_size
+        )?;
+        writeln!(f, "  selection_rate: {}", self.selection_rate)?;
+        writeln!(f, "  learning_rate: {}", self.learning_rate)?;
+        writeln!(
+            f,
+            "  max_stale_generations: {:?}",
+            self.max_stale_generations
+        )?;
+        writeln!(f, "  max_generations: {:?}", self.max_generations)?;
+        writeln!(f, "  valid_fitness_score: {:?}", self.valid_fitness_score)?;
+        writeln!(f, "  target_fitness_score: {:?}", self.target_fitness_score)?;
+        writeln!(f, "  fitness_ordering: {:?}", self.fitness_ordering)?;
+        writeln!(f, "  par_fitness: {:?}", self.par_fitness)
+    }
+}
+
+impl<G: EstimateGenotype> fmt::Display for EstimateState<G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "estimate_state:")?;
+        writeln!(f, "  current iteration: {:?}", self.current_iteration)?;
+        writeln!(f, "  current generation: {:?}", self.current_generation)?;
+        writeln!(f, "  stale generations: {:?}", self.stale_generations)?;
+        writeln!(f, "  best fitness score: {:?}", self.best_fitness_score())?;
+        writeln!(f, "  model convergence: {:.3}", self.model_convergence())
+    }
+}
+
+/// Raise the probabilities below the floor to the floor, taking the deficit from the
+/// probabilities above the floor (proportionally to their distance to the floor). The sum remains
+/// 1.0, as floor * probabilities.len() <= 1.0
+fn apply_probability_floor(probabilities: &mut [f32], floor: f32) {
+    let deficit: f32 = probabilities
+        .iter()
+        .map(|probability| (floor - probability).max(0.0))
+        .sum();
+    if deficit <= 0.0 {
+        return;
+    }
+    let surplus: f32 = probabilities
+        .iter()
+        .map(|probability| (probability - floor).max(0.0))
+        .sum();
+    probabilities.iter_mut().for_each(|probability| {
+        if *probability <= floor {
+            *probability = floor;
+        } else {
+            *probability -= deficit * (*probability - floor) / surplus;
+        }
+    });
+}
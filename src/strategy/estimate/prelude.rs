@@ -0,0 +1,29 @@
+#[doc(no_inline)]
+pub use crate::chromosome::{Chromosome, GenesHash};
+#[doc(no_inline)]
+pub use crate::fitness::{
+    fitness_value, Fitness, FitnessCache, FitnessChromosome, FitnessGenes, FitnessGenotype,
+    FitnessOrdering, FitnessPopulation, FitnessValue,
+};
+#[doc(no_inline)]
+pub use crate::genotype::{
+    Allele, AlleleScale, BinaryGenotype, BitGenotype, CompositeGenotype, EstimateGenotype,
+    GeneConstraint, Genotype, GenotypeBuilder, Grammar, GrammaticalGenotype, GridGenotype,
+    LinearBound, ListGenotype, MultiListGenotype, MultiRangeGenotype, MultiUniqueGenotype,
+    MutationType, PartialPermutationGenotype, RangeAllele, RangeGenotype, SubsetGenotype,
+    TreeGenotype, TryFromGenotypeBuilderError, UniqueGenotype, VariableListGenotype,
+};
+#[doc(no_inline)]
+pub use crate::impl_allele;
+#[doc(no_inline)]
+pub use crate::strategy::estimate::{
+    Estimate, EstimateBuilder, EstimateConfig, EstimateReporterDuration, EstimateReporterLog,
+    EstimateReporterNoop, EstimateReporterRecorder, EstimateReporterSimple, EstimateState,
+    EstimateVariant, TryFromEstimateBuilderError,
+};
+#[doc(no_inline)]
+pub use crate::strategy::{
+    Strategy, StrategyBuilder, StrategyConfig, StrategyReporter, StrategyReporterDuration,
+    StrategyReporterLog, StrategyReporterNoop, StrategyReporterRecord, StrategyReporterRecorder,
+    StrategyReporterSimple, StrategyState, TryFromStrategyBuilderError, STRATEGY_ACTIONS,
+};
@@ -2,10 +2,15 @@
 mod builder;
 pub mod prelude;
 mod reporter;
+mod tuner;
 
 pub use self::builder::{
     Builder as EvolveBuilder, TryFromBuilderError as TryFromEvolveBuilderError,
 };
+pub use self::tuner::{
+    Parameters as EvolveTuneParameters, Run as EvolveTuneRun, SearchSpace as EvolveTuneSearchSpace,
+    TuneResult as EvolveTuneResult, Tuner as EvolveTuner,
+};
 
 use super::{
     Strategy, StrategyAction, StrategyConfig, StrategyReporter, StrategyReporterNoop,
@@ -20,7 +25,9 @@ use crate::mutate::Mutate;
 use crate::population::Population;
 use crate::select::Select;
 use rand::rngs::SmallRng;
+use rand::Rng;
 use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::fmt;
 use std::time::{Duration, Instant};
@@ -28,12 +35,32 @@ use thread_local::ThreadLocal;
 
 pub use self::reporter::Simple as EvolveReporterSimple;
 pub use crate::strategy::reporter::Duration as EvolveReporterDuration;
+pub use crate::strategy::reporter::Log as EvolveReporterLog;
 pub use crate::strategy::reporter::Noop as EvolveReporterNoop;
+pub use crate::strategy::reporter::Recorder as EvolveReporterRecorder;
 
 #[derive(Copy, Clone, Debug, Default)]
 pub enum EvolveVariant {
     #[default]
     Standard,
+    SteadyState(SteadyStateReplacement),
+}
+
+/// The replacement policy of [EvolveVariant::SteadyState], which individual of the population is
+/// replaced by each new offspring (once the population is at the target_population_size):
+/// * [SteadyStateReplacement::Worst]: the worst chromosome (invalid fitness first)
+/// * [SteadyStateReplacement::Oldest]: the oldest chromosome (worst first on equal age)
+/// * [SteadyStateReplacement::Random]: a random chromosome
+/// * [SteadyStateReplacement::ParentIfBetter]: the corresponding parent (father for the first
+///   offspring, mother for the second), only if the offspring is better than that parent was before
+///   the step (or equal with replace_on_equal_fitness), otherwise the offspring is discarded
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SteadyStateReplacement {
+    #[default]
+    Worst,
+    Oldest,
+    Random,
+    ParentIfBetter,
 }
 
 /// The Evolve strategy initializes with a random population of chromosomes (unless the genotype
@@ -47,6 +74,25 @@ pub enum EvolveVariant {
 /// * calculate [fitness](crate::fitness) for all chromosomes
 /// * store best chromosome and check ending conditions
 ///
+/// There are 2 variants ([EvolveVariant], set with `with_variant()`):
+/// * [EvolveVariant::Standard]: the generational loop above, the whole population is selected,
+///   crossed over, mutated and evaluated in batch each generation
+/// * [EvolveVariant::SteadyState]: each step picks two parents through the select plugin (a
+///   tournament of tournament_size for SelectTournament, a binary tournament otherwise), applies
+///   the crossover and mutate plugins to just this parent pair (always yielding two offspring, the
+///   selection_rate does not apply) and evaluates the offspring immediately. Each offspring then
+///   replaces an individual of the population by the [SteadyStateReplacement] policy, the best
+///   elitism_rate fraction of the population is never replaced by the Worst, Oldest and Random
+///   policies. A generation is counted per target_population_size worth of offspring, so the
+///   ending conditions keep their meaning (the target_fitness_score is also checked after each
+///   step). The select plugin further only applies its age filter (max_chromosome_age) at the
+///   start of each generation, the replacement takes the place of its survival selection (the
+///   replacement_rate does not apply). SelectNovelty is not supported, as its novelty scores are
+///   relative to the whole population. The population is refilled with offspring (instead of
+///   replacing) while below the target_population_size (e.g. after a mass extinction). Of the
+///   reporter and extension hooks only on_selection_complete (at the start of the generation) and
+///   on_generation_complete are called, not the per step crossover and mutation hooks
+///
 /// The ending conditions are one or more of the following:
 /// * target_fitness_score: when the ultimate goal in terms of fitness score is known and reached
 /// * max_stale_generations: when the ultimate goal in terms of fitness score is unknown and one depends on some convergion
@@ -283,104 +329,14 @@ impl<
         self.reporter
             .on_start(&self.genotype, &self.state, &self.config);
         while !self.is_finished() {
-            self.state.increment_generation();
-
-            // select
-            self.plugins
-                .select
-                .before(&self.genotype, &mut self.state, &self.config);
-            self.plugins.select.call(
-                &self.genotype,
-                &mut self.state,
-                &self.config,
-                &mut self.reporter,
-                &mut self.rng,
-            );
-            self.plugins
-                .select
-                .after(&self.genotype, &mut self.state, &self.config);
-            self.reporter
-                .on_selection_complete(&self.genotype, &self.state, &self.config);
-            self.plugins.extension.after_selection_complete(
-                &mut self.genotype,
-                &mut self.state,
-                &self.config,
-                &mut self.reporter,
-                &mut self.rng,
-            );
-
-            // crossover
-            self.state.population.increment_age();
-            self.plugins
-                .crossover
-                .before(&self.genotype, &mut self.state, &self.config);
-            self.plugins.crossover.call(
-                &self.genotype,
-                &mut self.state,
-                &self.config,
-                &mut self.reporter,
-                &mut self.rng,
-            );
-            self.plugins
-                .crossover
-                .after(&self.genotype, &mut self.state, &self.config);
-            self.reporter
-                .on_crossover_complete(&self.genotype, &self.state, &self.config);
-            self.plugins.extension.after_crossover_complete(
-                &mut self.genotype,
-                &mut self.state,
-                &self.config,
-                &mut self.reporter,
-                &mut self.rng,
-            );
-
-            // mutate
-            self.plugins
-                .mutate
-                .before(&self.genotype, &mut self.state, &self.config);
-            self.plugins.mutate.call(
-                &self.genotype,
-                &mut self.state,
-                &self.config,
-                &mut self.reporter,
-                &mut self.rng,
-            );
-            self.plugins
-                .mutate
-                .after(&self.genotype, &mut self.state, &self.config);
-            self.reporter
-                .on_mutation_complete(&self.genotype, &self.state, &self.config);
-            self.plugins.extension.after_mutation_complete(
-                &mut self.genotype,
-                &mut self.state,
-                &self.config,
-                &mut self.reporter,
-                &mut self.rng,
-            );
-
-            // fitness
-            self.fitness.call_for_state_population(
-                &self.genotype,
-                &mut self.state,
-                &self.config,
-                fitness_thread_local.as_ref(),
-            );
-            self.state.update_best_chromosome_and_report(
-                &self.genotype,
-                &self.config,
-                &mut self.reporter,
-            );
-
-            // end of generation
-            self.reporter
-                .on_generation_complete(&self.genotype, &self.state, &self.config);
-            self.plugins.extension.after_generation_complete(
-                &mut self.genotype,
-                &mut self.state,
-                &self.config,
-                &mut self.reporter,
-                &mut self.rng,
-            );
+            match self.config.variant {
+                EvolveVariant::Standard => {
+                    self.generation_standard(fitness_thread_local.as_ref());
+                }
+                EvolveVariant::SteadyState(replacement) => {
+                    self.generation_steady_state(replacement, fitness_thread_local.as_ref());
+                }
+            }
             self.state.scale(&mut self.genotype, &self.config);
         }
         self.reporter
@@ -452,6 +408,7 @@ impl<
 {
     pub fn setup(&mut self, fitness_thread_local: Option<&ThreadLocal<RefCell<F>>>) {
         let now = Instant::now();
+        self.plugins.select.setup();
         self.state.population = self
             .genotype
             .population_constructor(self.config.target_population_size, &mut self.rng);
@@ -480,6 +437,364 @@ impl<
         }
     }
 
+    fn generation_standard(&mut self, fitness_thread_local: Option<&ThreadLocal<RefCell<F>>>) {
+        self.state.increment_generation();
+
+        // select
+        self.plugins
+            .select
+            .before(&self.genotype, &mut self.state, &self.config);
+        self.plugins.select.call(
+            &self.genotype,
+            &mut self.state,
+            &self.config,
+            &mut self.reporter,
+            &mut self.rng,
+        );
+        self.plugins
+            .select
+            .after(&self.genotype, &mut self.state, &self.config);
+        self.reporter
+            .on_selection_complete(&self.genotype, &self.state, &self.config);
+        self.plugins.extension.after_selection_complete(
+            &mut self.genotype,
+            &mut self.state,
+            &self.config,
+            &mut self.reporter,
+            &mut self.rng,
+        );
+
+        // crossover
+        self.state.population.increment_age();
+        self.plugins
+            .crossover
+            .before(&self.genotype, &mut self.state, &self.config);
+        self.plugins.crossover.call(
+            &self.genotype,
+            &mut self.state,
+            &self.config,
+            &mut self.reporter,
+            &mut self.rng,
+        );
+        self.plugins
+            .crossover
+            .after(&self.genotype, &mut self.state, &self.config);
+        self.reporter
+            .on_crossover_complete(&self.genotype, &self.state, &self.config);
+        self.plugins.extension.after_crossover_complete(
+            &mut self.genotype,
+            &mut self.state,
+            &self.config,
+            &mut self.reporter,
+            &mut self.rng,
+        );
+
+        // mutate
+        self.plugins
+            .mutate
+            .before(&self.genotype, &mut self.state, &self.config);
+        self.plugins.mutate.call(
+            &self.genotype,
+            &mut self.state,
+            &self.config,
+            &mut self.reporter,
+            &mut self.rng,
+        );
+        self.plugins
+            .mutate
+            .after(&self.genotype, &mut self.state, &self.config);
+        self.reporter
+            .on_mutation_complete(&self.genotype, &self.state, &self.config);
+        self.plugins.extension.after_mutation_complete(
+            &mut self.genotype,
+            &mut self.state,
+            &self.config,
+            &mut self.reporter,
+            &mut self.rng,
+        );
+
+        // fitness
+        self.fitness.call_for_state_population(
+            &self.genotype,
+            &mut self.state,
+            &self.config,
+            fitness_thread_local,
+        );
+        self.state.update_best_chromosome_and_report(
+            &self.genotype,
+            &self.config,
+            &mut self.reporter,
+        );
+
+        // end of generation
+        self.reporter
+            .on_generation_complete(&self.genotype, &self.state, &self.config);
+        self.plugins.extension.after_generation_complete(
+            &mut self.genotype,
+            &mut self.state,
+            &self.config,
+            &mut self.reporter,
+            &mut self.rng,
+        );
+    }
+
+    fn generation_steady_state(
+        &mut self,
+        replacement: SteadyStateReplacement,
+        fitness_thread_local: Option<&ThreadLocal<RefCell<F>>>,
+    ) {
+        self.state.increment_generation();
+
+        // select (only the age filter, parents are picked per step by the select plugin)
+        self.plugins
+            .select
+            .before(&self.genotype, &mut self.state, &self.config);
+        self.state
+            .update_population_cardinality(&self.genotype, &self.config);
+        self.reporter
+            .on_selection_complete(&self.genotype, &self.state, &self.config);
+        self.plugins.extension.after_selection_complete(
+            &mut self.genotype,
+            &mut self.state,
+            &self.config,
+            &mut self.reporter,
+            &mut self.rng,
+        );
+        self.state.population.increment_age();
+
+        // steps, until a population size worth of offspring is produced
+        let mut breeding_population = Population::new_empty(self.genotype.chromosome_recycling());
+        let mut improved = false;
+        let mut number_of_offspring = 0;
+        // computed once the population is full, then updated incrementally by each replacement
+        let mut elite_indices: Option<Vec<usize>> = None;
+        while number_of_offspring < self.config.target_population_size
+            && !self.state.population.chromosomes.is_empty()
+        {
+            let father_index =
+                self.plugins
+                    .select
+                    .parent_index(&self.state, &self.config, &mut self.rng);
+            let mother_index =
+                self.plugins
+                    .select
+                    .parent_index(&self.state, &self.config, &mut self.rng);
+            // ParentIfBetter compares against the parents before this step's replacements
+            let parent_fitness_scores = [
+                self.state.population.chromosomes[father_index].fitness_score(),
+                self.state.population.chromosomes[mother_index].fitness_score(),
+            ];
+            for index in [father_index, mother_index] {
+                let mut parent =
+                    breeding_population.new_chromosome(&self.state.population.chromosomes[index]);
+                // offspring of earlier steps in this generation are still of age 0
+                parent.increment_age();
+                breeding_population.chromosomes.push(parent);
+            }
+
+            // crossover and mutate the parents in isolation
+            std::mem::swap(&mut self.state.population, &mut breeding_population);
+            self.plugins
+                .crossover
+                .before(&self.genotype, &mut self.state, &self.config);
+            self.plugins.crossover.call(
+                &self.genotype,
+                &mut self.state,
+                &self.config,
+                &mut self.reporter,
+                &mut self.rng,
+            );
+            self.plugins
+                .crossover
+                .after(&self.genotype, &mut self.state, &self.config);
+            self.plugins
+                .mutate
+                .before(&self.genotype, &mut self.state, &self.config);
+            self.plugins.mutate.call(
+                &self.genotype,
+                &mut self.state,
+                &self.config,
+                &mut self.reporter,
+                &mut self.rng,
+            );
+            self.plugins
+                .mutate
+                .after(&self.genotype, &mut self.state, &self.config);
+
+            // keep the offspring only and evaluate them immediately
+            let (offspring, parents): (Vec<_>, Vec<_>) = self
+                .state
+                .population
+                .chromosomes
+                .drain(..)
+                .partition(|chromosome| chromosome.is_offspring());
+            self.state.population.chromosomes = offspring;
+            parents
+                .into_iter()
+                .for_each(|chromosome| self.state.population.drop_chromosome(chromosome));
+            self.fitness.call_for_state_population(
+                &self.genotype,
+                &mut self.state,
+                &self.config,
+                fitness_thread_local,
+            );
+            improved |=
+                self.state
+                    .update_best_chromosome(&self.genotype, &self.config, &mut self.reporter);
+            std::mem::swap(&mut self.state.population, &mut breeding_population);
+
+            // replace
+            let offspring: Vec<_> = breeding_population.chromosomes.drain(..).collect();
+            number_of_offspring += offspring.len().max(1);
+            for (offspring_index, chromosome) in offspring.into_iter().enumerate() {
+                let parent = if offspring_index % 2 == 0 {
+                    (father_index, parent_fitness_scores[0])
+                } else {
+                    (mother_index, parent_fitness_scores[1])
+                };
+                self.replace_chromosome(
+                    replacement,
+                    chromosome,
+                    parent,
+                    &mut elite_indices,
+                    &mut breeding_population,
+                );
+            }
+
+            if self.allow_finished_by_valid_fitness_score()
+                && self.is_finished_by_target_fitness_score()
+            {
+                break;
+            }
+        }
+        if improved {
+            self.state.reset_stale_generations();
+        } else {
+            self.state.increment_stale_generations();
+        }
+
+        // end of generation
+        self.reporter
+            .on_generation_complete(&self.genotype, &self.state, &self.config);
+        self.plugins.extension.after_generation_complete(
+            &mut self.genotype,
+            &mut self.state,
+            &self.config,
+            &mut self.reporter,
+            &mut self.rng,
+        );
+    }
+
+    /// replace a chromosome of the population by the offspring (or add it while the population is
+    /// below the target_population_size), the replaced or discarded chromosome is recycled in the
+    /// breeding_population. The parent is the index and the fitness score (before the step) of the
+    /// corresponding parent. The elite_indices are protected from replacement (not for
+    /// ParentIfBetter, which never lowers the fitness score) and kept up to date
+    fn replace_chromosome(
+        &mut self,
+        replacement: SteadyStateReplacement,
+        offspring: Chromosome<G::Allele>,
+        parent: (usize, Option<FitnessValue>),
+        elite_indices: &mut Option<Vec<usize>>,
+        breeding_population: &mut Population<G::Allele>,
+    ) {
+        let now = Instant::now();
+        let fitness_ordering = self.config.fitness_ordering;
+        let chromosomes = &mut self.state.population.chromosomes;
+        if chromosomes.len() < self.config.target_population_size {
+            chromosomes.push(offspring);
+        } else {
+            let elite_indices = elite_indices.get_or_insert_with(|| {
+                if replacement == SteadyStateReplacement::ParentIfBetter {
+                    return vec![];
+                }
+                let elitism_size = ((chromosomes.len() as f32 * self.plugins.select.elitism_rate())
+                    .ceil() as usize)
+                    .min(chromosomes.len() - 1);
+                let mut indices: Vec<usize> = (0..chromosomes.len()).collect();
+                if elitism_size > 0 {
+                    // best first
+                    indices.select_nth_unstable_by(elitism_size - 1, |a, b| {
+                        compare_fitness_scores(
+                            chromosomes[*b].fitness_score(),
+                            chromosomes[*a].fitness_score(),
+                            fitness_ordering,
+                        )
+                    });
+                }
+                indices.truncate(elitism_size);
+                indices
+            });
+            let replace_index = match replacement {
+                SteadyStateReplacement::Worst => (0..chromosomes.len())
+                    .filter(|index| !elite_indices.contains(index))
+                    .min_by(|a, b| {
+                        compare_fitness_scores(
+                            chromosomes[*a].fitness_score(),
+                            chromosomes[*b].fitness_score(),
+                            fitness_ordering,
+                        )
+                    }),
+                SteadyStateReplacement::Oldest => (0..chromosomes.len())
+                    .filter(|index| !elite_indices.contains(index))
+                    .max_by(|a, b| {
+                        chromosomes[*a].age().cmp(&chromosomes[*b].age()).then(
+                            compare_fitness_scores(
+                                chromosomes[*b].fitness_score(),
+                                chromosomes[*a].fitness_score(),
+                                fitness_ordering,
+                            ),
+                        )
+                    }),
+                SteadyStateReplacement::Random => loop {
+                    let index = self.rng.gen_range(0..chromosomes.len());
+                    if !elite_indices.contains(&index) {
+                        break Some(index);
+                    }
+                },
+                SteadyStateReplacement::ParentIfBetter => {
+                    let (parent_index, parent_fitness_score) = parent;
+                    match compare_fitness_scores(
+                        offspring.fitness_score(),
+                        parent_fitness_score,
+                        fitness_ordering,
+                    ) {
+                        Ordering::Greater => Some(parent_index),
+                        Ordering::Equal if self.config.replace_on_equal_fitness => {
+                            Some(parent_index)
+                        }
+                        _ => None,
+                    }
+                }
+            };
+            if let Some(replace_index) = replace_index {
+                // the offspring takes the place of the worst elite chromosome, if better
+                if let Some(worst_elite_position) = (0..elite_indices.len()).min_by(|a, b| {
+                    compare_fitness_scores(
+                        chromosomes[elite_indices[*a]].fitness_score(),
+                        chromosomes[elite_indices[*b]].fitness_score(),
+                        fitness_ordering,
+                    )
+                }) {
+                    if compare_fitness_scores(
+                        offspring.fitness_score(),
+                        chromosomes[elite_indices[worst_elite_position]].fitness_score(),
+                        fitness_ordering,
+                    ) == Ordering::Greater
+                    {
+                        elite_indices[worst_elite_position] = replace_index;
+                    }
+                }
+                let replaced = std::mem::replace(&mut chromosomes[replace_index], offspring);
+                breeding_population.drop_chromosome(replaced);
+            } else {
+                breeding_population.drop_chromosome(offspring);
+            }
+        }
+        self.state
+            .add_duration(StrategyAction::Other, now.elapsed());
+    }
+
     pub fn cleanup(&mut self, fitness_thread_local: Option<&mut ThreadLocal<RefCell<F>>>) {
         let now = Instant::now();
         self.state.chromosome.take();
@@ -631,7 +946,21 @@ impl<G: EvolveGenotype> EvolveState<G> {
         config: &EvolveConfig,
         reporter: &mut SR,
     ) {
+        if self.update_best_chromosome(genotype, config, reporter) {
+            self.reset_stale_generations();
+        } else {
+            self.increment_stale_generations();
+        }
+    }
+    /// returns true if the best fitness score improved, does not touch the stale generations
+    fn update_best_chromosome<SR: StrategyReporter<Genotype = G>>(
+        &mut self,
+        genotype: &G,
+        config: &EvolveConfig,
+        reporter: &mut SR,
+    ) -> bool {
         let now = Instant::now();
+        let mut improved = false;
         if let Some(contending_chromosome) =
             self.population.best_chromosome(config.fitness_ordering)
         {
@@ -645,19 +974,17 @@ impl<G: EvolveGenotype> EvolveState<G> {
                     self.best_fitness_score = contending_chromosome.fitness_score();
                     self.best_chromosome = Some(contending_chromosome.clone());
                     reporter.on_new_best_chromosome(genotype, self, config);
-                    self.reset_stale_generations();
+                    improved = true;
                 }
                 (true, false) => {
                     self.best_chromosome = Some(contending_chromosome.clone());
                     reporter.on_new_best_chromosome_equal_fitness(genotype, self, config);
-                    self.increment_stale_generations();
                 }
-                _ => self.increment_stale_generations(),
+                _ => {}
             }
-        } else {
-            self.increment_stale_generations();
         }
         self.add_duration(StrategyAction::UpdateBestChromosome, now.elapsed());
+        improved
     }
     fn scale(&mut self, genotype: &mut G, config: &EvolveConfig) {
         if let Some(max_generations) = config.max_generations {
@@ -713,6 +1040,12 @@ impl<
             Err(TryFromEvolveBuilderError(
                 "Evolve requires a Select strategy",
             ))
+        } else if matches!(builder.variant, Some(EvolveVariant::SteadyState(_)))
+            && !builder.select.as_ref().unwrap().allows_steady_state()
+        {
+            Err(TryFromEvolveBuilderError(
+                "Evolve SteadyState variant requires a Select strategy which picks parents one at a time (SelectElite or SelectTournament)",
+            ))
         } else if builder.max_stale_generations.is_none()
             && builder.max_generations.is_none()
             && builder.target_fitness_score.is_none()
@@ -736,6 +1069,7 @@ impl<
                     extension: builder.extension,
                 },
                 config: EvolveConfig {
+                    variant: builder.variant.unwrap_or_default(),
                     target_population_size,
                     max_stale_generations: builder.max_stale_generations,
                     max_generations: builder.max_generations,
@@ -746,7 +1080,6 @@ impl<
                     fitness_cache: builder.fitness_cache,
                     par_fitness: builder.par_fitness,
                     replace_on_equal_fitness: builder.replace_on_equal_fitness,
-                    ..Default::default()
                 },
                 state,
                 reporter: builder.reporter,
@@ -777,6 +1110,14 @@ impl EvolveConfig {
     pub fn new() -> Self {
         Self::default()
     }
+    /// The number of parents selected for reproduction by the crossover: the selection_rate
+    /// fraction of the population, or always the full parent pair in the steady-state variant
+    pub fn crossover_selection_size(&self, population_size: usize, selection_rate: f32) -> usize {
+        match self.variant {
+            EvolveVariant::Standard => (population_size as f32 * selection_rate).ceil() as usize,
+            EvolveVariant::SteadyState(_) => population_size,
+        }
+    }
 }
 
 impl<G: EvolveGenotype> EvolveState<G> {
@@ -832,6 +1173,7 @@ impl<M: Mutate, S: Crossover, C: Select, E: Extension> fmt::Display for EvolvePl
 impl fmt::Display for EvolveConfig {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "evolve_config:")?;
+        writeln!(f, "  variant: {:?}", self.variant)?;
         writeln!(
             f,
             "  target_population_size: {}",
@@ -865,3 +1207,20 @@ impl<G: EvolveGenotype> fmt::Display for EvolveState<G> {
         writeln!(f, "  best fitness score: {:?}", self.best_fitness_score())
     }
 }
+
+/// Ordering of fitness scores where greater is better, invalid (None) fitness is always worst
+fn compare_fitness_scores(
+    a: Option<FitnessValue>,
+    b: Option<FitnessValue>,
+    fitness_ordering: FitnessOrdering,
+) -> Ordering {
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (Some(a), Some(b)) => match fitness_ordering {
+            FitnessOrdering::Maximize => a.cmp(&b),
+            FitnessOrdering::Minimize => b.cmp(&a),
+        },
+    }
+}
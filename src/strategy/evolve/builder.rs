@@ -1,4 +1,4 @@
-use super::Evolve;
+use super::{Evolve, EvolveVariant};
 use crate::crossover::Crossover;
 pub use crate::errors::TryFromStrategyBuilderError as TryFromBuilderError;
 use crate::extension::{Extension, ExtensionNoop};
@@ -24,6 +24,7 @@ pub struct Builder<
     SR: StrategyReporter<Genotype = G>,
 > {
     pub genotype: Option<G>,
+    pub variant: Option<EvolveVariant>,
     pub target_population_size: usize,
     pub max_stale_generations: Option<usize>,
     pub max_generations: Option<usize>,
@@ -54,6 +55,7 @@ impl<
     fn default() -> Self {
         Self {
             genotype: None,
+            variant: None,
             target_population_size: 100,
             max_stale_generations: None,
             max_generations: None,
@@ -106,6 +108,10 @@ impl<
         self.genotype = Some(genotype);
         self
     }
+    pub fn with_variant(mut self, variant: EvolveVariant) -> Self {
+        self.variant = Some(variant);
+        self
+    }
     pub fn with_target_population_size(mut self, target_population_size: usize) -> Self {
         self.target_population_size = target_population_size;
         self
@@ -170,12 +176,22 @@ impl<
     /// Only useful for long stale runs, but better to increase population diversity.
     /// Silently ignore cache_size of zero, to support superset builder which delays specialization
     pub fn with_fitness_cache(mut self, fitness_cache_size: usize) -> Self {
-        match FitnessCache::try_new(fitness_cache_size) {
+        match FitnessCache::try_new_sharded(
+            fitness_cache_size,
+            FitnessCache::DEFAULT_NUMBER_OF_SHARDS,
+        ) {
             Ok(cache) => self.fitness_cache = Some(cache),
             Err(_error) => (),
         }
         self
     }
+    /// Use a prebuilt (and possibly shared) cache, e.g. with another backend
+    /// ([FitnessCache::new_unbounded], [FitnessCache::try_new_file]) or with genes in the key
+    /// ([FitnessCache::with_genes_in_key]). Only works when genes_hash is stored on chromosome.
+    pub fn with_shared_fitness_cache(mut self, fitness_cache: FitnessCache) -> Self {
+        self.fitness_cache = Some(fitness_cache);
+        self
+    }
     pub fn with_par_fitness(mut self, par_fitness: bool) -> Self {
         self.par_fitness = par_fitness;
         self
@@ -206,6 +222,7 @@ impl<
     ) -> Builder<G, M, F, S, C, E2, SR> {
         Builder {
             genotype: self.genotype,
+            variant: self.variant,
             target_population_size: self.target_population_size,
             max_stale_generations: self.max_stale_generations,
             max_generations: self.max_generations,
@@ -231,6 +248,7 @@ impl<
     ) -> Builder<G, M, F, S, C, E, SR2> {
         Builder {
             genotype: self.genotype,
+            variant: self.variant,
             target_population_size: self.target_population_size,
             max_stale_generations: self.max_stale_generations,
             max_generations: self.max_generations,
@@ -250,6 +268,34 @@ impl<
             rng_seed: self.rng_seed,
         }
     }
+    /// Add a reporter next to the existing one (combined as a tuple), every hook is forwarded to
+    /// the existing reporter first and then to the additional reporter
+    pub fn with_additional_reporter<SR2: StrategyReporter<Genotype = G>>(
+        self,
+        reporter: SR2,
+    ) -> Builder<G, M, F, S, C, E, (SR, SR2)> {
+        Builder {
+            genotype: self.genotype,
+            variant: self.variant,
+            target_population_size: self.target_population_size,
+            max_stale_generations: self.max_stale_generations,
+            max_generations: self.max_generations,
+            max_chromosome_age: self.max_chromosome_age,
+            target_fitness_score: self.target_fitness_score,
+            valid_fitness_score: self.valid_fitness_score,
+            fitness_ordering: self.fitness_ordering,
+            fitness_cache: self.fitness_cache,
+            par_fitness: self.par_fitness,
+            replace_on_equal_fitness: self.replace_on_equal_fitness,
+            mutate: self.mutate,
+            fitness: self.fitness,
+            crossover: self.crossover,
+            select: self.select,
+            extension: self.extension,
+            reporter: (self.reporter, reporter),
+            rng_seed: self.rng_seed,
+        }
+    }
     pub fn with_rng_seed_from_u64(mut self, rng_seed: u64) -> Self {
         self.rng_seed = Some(rng_seed);
         self
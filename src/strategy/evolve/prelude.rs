@@ -13,15 +13,17 @@ pub use crate::extension::{
 };
 #[doc(no_inline)]
 pub use crate::fitness::{
-    fitness_value, Fitness, FitnessChromosome, FitnessGenes, FitnessGenotype, FitnessOrdering,
-    FitnessPopulation, FitnessValue,
+    fitness_value, Fitness, FitnessCache, FitnessChromosome, FitnessGenes, FitnessGenotype,
+    FitnessOrdering, FitnessPopulation, FitnessValue,
 };
 #[doc(no_inline)]
 pub use crate::genotype::{
-    Allele, BinaryGenotype, EvolveGenotype, Genotype, GenotypeBuilder, ListGenotype,
-    MultiListGenotype, MultiRangeGenotype, MultiUniqueGenotype, MutationType, RangeAllele,
-    RangeGenotype, SupportsGeneCrossover, SupportsPointCrossover, TryFromGenotypeBuilderError,
-    UniqueGenotype,
+    Allele, AlleleScale, BinaryGenotype, BitGenotype, CompositeGenotype, EvolveGenotype,
+    GeneConstraint, Genotype, GenotypeBuilder, Grammar, GrammaticalGenotype, GridGenotype,
+    LinearBound, ListGenotype, MultiListGenotype, MultiRangeGenotype, MultiUniqueGenotype,
+    MutationType, PartialPermutationGenotype, RangeAllele, RangeGenotype, SubsetGenotype,
+    SupportsGeneCrossover, SupportsPointCrossover, TreeGenotype, TryFromGenotypeBuilderError,
+    UniqueGenotype, VariableListGenotype,
 };
 #[doc(no_inline)]
 pub use crate::impl_allele;
@@ -33,15 +35,22 @@ pub use crate::mutate::{
 #[doc(no_inline)]
 pub use crate::population::Population;
 #[doc(no_inline)]
-pub use crate::select::{Select, SelectElite, SelectEvent, SelectTournament, SelectWrapper};
+pub use crate::select::{
+    Select, SelectElite, SelectEvent, SelectNovelty, SelectTournament, SelectWrapper,
+};
 #[doc(no_inline)]
 pub use crate::strategy::evolve::{
-    Evolve, EvolveBuilder, EvolveConfig, EvolveReporterDuration, EvolveReporterNoop,
-    EvolveReporterSimple, EvolveState, EvolveVariant, TryFromEvolveBuilderError,
+    Evolve, EvolveBuilder, EvolveConfig, EvolveReporterDuration, EvolveReporterLog,
+    EvolveReporterNoop, EvolveReporterRecorder, EvolveReporterSimple, EvolveState,
+    EvolveTuneParameters, EvolveTuneResult, EvolveTuneRun, EvolveTuneSearchSpace, EvolveTuner,
+    EvolveVariant, SteadyStateReplacement, TryFromEvolveBuilderError,
 };
 #[doc(no_inline)]
+pub use crate::strategy::map_elites::BehaviourDescriptor;
+#[doc(no_inline)]
 pub use crate::strategy::{
     Strategy, StrategyAction, StrategyBuilder, StrategyConfig, StrategyReporter,
-    StrategyReporterDuration, StrategyReporterNoop, StrategyReporterSimple, StrategyState,
-    TryFromStrategyBuilderError, STRATEGY_ACTIONS,
+    StrategyReporterDuration, StrategyReporterLog, StrategyReporterNoop, StrategyReporterRecord,
+    StrategyReporterRecorder, StrategyReporterSimple, StrategyState, TryFromStrategyBuilderError,
+    STRATEGY_ACTIONS,
 };
@@ -0,0 +1,425 @@
+use super::{Evolve, EvolveBuilder, TryFromEvolveBuilderError};
+use crate::crossover::Crossover;
+use crate::extension::Extension;
+use crate::fitness::{Fitness, FitnessOrdering, FitnessValue};
+use crate::genotype::EvolveGenotype;
+use crate::mutate::Mutate;
+use crate::select::Select;
+use crate::strategy::{Strategy, StrategyReporter, StrategyState};
+use itertools::iproduct;
+use rand::Rng;
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// The common [Evolve] hyperparameters, for use as candidate parameters of the [Tuner]. The
+/// factory maps them onto the builder (e.g. `SelectElite::new(p.replacement_rate,
+/// p.elitism_rate)`), so unused parameters can be left at any value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Parameters {
+    pub target_population_size: usize,
+    pub selection_rate: f32,
+    pub crossover_rate: f32,
+    pub mutation_probability: f32,
+    pub replacement_rate: f32,
+    pub elitism_rate: f32,
+}
+
+/// A grid search space over the [Parameters], each field lists the values to try. The
+/// candidates are the cartesian product of all fields.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchSpace {
+    pub target_population_sizes: Vec<usize>,
+    pub selection_rates: Vec<f32>,
+    pub crossover_rates: Vec<f32>,
+    pub mutation_probabilities: Vec<f32>,
+    pub replacement_rates: Vec<f32>,
+    pub elitism_rates: Vec<f32>,
+}
+
+impl Default for SearchSpace {
+    fn default() -> Self {
+        Self {
+            target_population_sizes: vec![100],
+            selection_rates: vec![0.5],
+            crossover_rates: vec![0.7],
+            mutation_probabilities: vec![0.2],
+            replacement_rates: vec![0.5],
+            elitism_rates: vec![0.02],
+        }
+    }
+}
+
+impl SearchSpace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_target_population_sizes(mut self, values: Vec<usize>) -> Self {
+        self.target_population_sizes = values;
+        self
+    }
+    pub fn with_selection_rates(mut self, values: Vec<f32>) -> Self {
+        self.selection_rates = values;
+        self
+    }
+    pub fn with_crossover_rates(mut self, values: Vec<f32>) -> Self {
+        self.crossover_rates = values;
+        self
+    }
+    pub fn with_mutation_probabilities(mut self, values: Vec<f32>) -> Self {
+        self.mutation_probabilities = values;
+        self
+    }
+    pub fn with_replacement_rates(mut self, values: Vec<f32>) -> Self {
+        self.replacement_rates = values;
+        self
+    }
+    pub fn with_elitism_rates(mut self, values: Vec<f32>) -> Self {
+        self.elitism_rates = values;
+        self
+    }
+    pub fn candidates(&self) -> Vec<Parameters> {
+        iproduct!(
+            self.target_population_sizes.iter(),
+            self.selection_rates.iter(),
+            self.crossover_rates.iter(),
+            self.mutation_probabilities.iter(),
+            self.replacement_rates.iter(),
+            self.elitism_rates.iter()
+        )
+        .map(
+            |(
+                &target_population_size,
+                &selection_rate,
+                &crossover_rate,
+                &mutation_probability,
+                &replacement_rate,
+                &elitism_rate,
+            )| Parameters {
+                target_population_size,
+                selection_rate,
+                crossover_rate,
+                mutation_probability,
+                replacement_rate,
+                elitism_rate,
+            },
+        )
+        .collect()
+    }
+}
+
+/// The outcome of a single seeded [Evolve] run
+#[derive(Clone, Debug)]
+pub struct Run {
+    pub rng_seed: u64,
+    pub best_fitness_score: Option<FitnessValue>,
+    pub target_reached: bool,
+    pub generations: usize,
+    pub duration: Duration,
+}
+
+/// The accumulated runs of a candidate. The rounds are the number of successive halving rounds
+/// the candidate survived (so it is ranked above candidates which were dropped earlier).
+#[derive(Clone, Debug)]
+pub struct TuneResult<P> {
+    pub parameters: P,
+    pub rounds: usize,
+    pub runs: Vec<Run>,
+}
+
+impl<P> TuneResult<P> {
+    fn best_fitness_scores(&self) -> impl Iterator<Item = FitnessValue> + '_ {
+        self.runs.iter().filter_map(|run| run.best_fitness_score)
+    }
+    /// The mean of the best fitness scores, None if no run has a valid fitness score
+    pub fn best_fitness_score_mean(&self) -> Option<f64> {
+        if self.best_fitness_scores().next().is_some() {
+            Some(stats::mean(self.best_fitness_scores()))
+        } else {
+            None
+        }
+    }
+    /// The (population) variance of the best fitness scores, None if no run has a valid fitness
+    /// score
+    pub fn best_fitness_score_variance(&self) -> Option<f64> {
+        if self.best_fitness_scores().next().is_some() {
+            Some(stats::variance(self.best_fitness_scores()))
+        } else {
+            None
+        }
+    }
+    /// The fraction of runs which reached the target_fitness_score
+    pub fn target_rate(&self) -> f64 {
+        if self.runs.is_empty() {
+            0.0
+        } else {
+            self.runs.iter().filter(|run| run.target_reached).count() as f64
+                / self.runs.len() as f64
+        }
+    }
+    /// The mean duration of the runs which reached the target_fitness_score
+    pub fn time_to_target_mean(&self) -> Option<Duration> {
+        let durations: Vec<Duration> = self
+            .runs
+            .iter()
+            .filter(|run| run.target_reached)
+            .map(|run| run.duration)
+            .collect();
+        if durations.is_empty() {
+            None
+        } else {
+            Some(durations.iter().sum::<Duration>() / durations.len() as u32)
+        }
+    }
+    /// The mean number of generations of the runs which reached the target_fitness_score
+    pub fn generations_to_target_mean(&self) -> Option<f64> {
+        if self.runs.iter().any(|run| run.target_reached) {
+            Some(stats::mean(
+                self.runs
+                    .iter()
+                    .filter(|run| run.target_reached)
+                    .map(|run| run.generations),
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Ranks by rounds survived, then mean best fitness score, then target rate and then time to
+    /// target. Less is better.
+    fn compare(&self, other: &Self, fitness_ordering: FitnessOrdering) -> Ordering {
+        let mean_ordering = match (
+            self.best_fitness_score_mean(),
+            other.best_fitness_score_mean(),
+        ) {
+            (Some(a), Some(b)) => match fitness_ordering {
+                FitnessOrdering::Maximize => b.total_cmp(&a),
+                FitnessOrdering::Minimize => a.total_cmp(&b),
+            },
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        other
+            .rounds
+            .cmp(&self.rounds)
+            .then(mean_ordering)
+            .then(other.target_rate().total_cmp(&self.target_rate()))
+            .then(
+                match (self.time_to_target_mean(), other.time_to_target_mean()) {
+                    (Some(a), Some(b)) => a.cmp(&b),
+                    _ => Ordering::Equal,
+                },
+            )
+    }
+}
+
+impl<P: fmt::Debug> fmt::Display for TuneResult<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "rounds: {}, runs: {}, best_fitness_score mean: {:?}, variance: {:?}, target_rate: {:.2}, time_to_target: {:?}, generations_to_target: {:?}, parameters: {:?}",
+            self.rounds,
+            self.runs.len(),
+            self.best_fitness_score_mean(),
+            self.best_fitness_score_variance(),
+            self.target_rate(),
+            self.time_to_target_mean(),
+            self.generations_to_target_mean(),
+            self.parameters,
+        )
+    }
+}
+
+/// Hyperparameter tuning for [Evolve] by racing the candidate parameters with successive halving.
+///
+/// Each round, all remaining candidates are evaluated with `repeats_per_round` seeded runs. The
+/// seeds are shared between candidates (common random numbers), so the comparison is fair. After
+/// each round the candidates are ranked and only the best `1 / reduction_factor` fraction
+/// continues to the next round. The racing stops when a single candidate remains or after
+/// `max_rounds`.
+///
+/// The candidates are mapped to an [EvolveBuilder] by a factory closure, which must set the
+/// ending conditions (set a target_fitness_score for the time-to-target statistics). The
+/// rng_seed of the builder is overwritten per run.
+///
+/// Returns a [TuneResult] per candidate ranked by rounds survived, mean best fitness score,
+/// target rate and time to target (best first).
+///
+/// Example:
+/// ```
+/// use genetic_algorithm::strategy::evolve::prelude::*;
+/// use genetic_algorithm::fitness::placeholders::CountTrue;
+///
+/// let genotype = BinaryGenotype::builder().with_genes_size(20).build().unwrap();
+///
+/// let search_space = EvolveTuneSearchSpace::new()
+///     .with_target_population_sizes(vec![20, 50])
+///     .with_mutation_probabilities(vec![0.05, 0.5]);
+///
+/// let results = EvolveTuner::new(search_space.candidates())
+///     .with_repeats_per_round(2)
+///     .with_rng_seed_from_u64(0)
+///     .call(|parameters: &EvolveTuneParameters| {
+///         Evolve::builder()
+///             .with_genotype(genotype.clone())
+///             .with_target_population_size(parameters.target_population_size)
+///             .with_target_fitness_score(20)
+///             .with_max_stale_generations(100)
+///             .with_fitness(CountTrue)
+///             .with_select(SelectElite::new(parameters.replacement_rate, parameters.elitism_rate))
+///             .with_crossover(CrossoverUniform::new(parameters.selection_rate, parameters.crossover_rate))
+///             .with_mutate(MutateSingleGene::new(parameters.mutation_probability))
+///     })
+///     .unwrap();
+///
+/// assert_eq!(results.len(), 4);
+/// results.iter().for_each(|result| println!("{}", result));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Tuner<P> {
+    pub candidates: Vec<P>,
+    pub repeats_per_round: usize,
+    pub reduction_factor: usize,
+    pub max_rounds: Option<usize>,
+    pub par_runs: bool,
+    pub rng_seed: Option<u64>,
+}
+
+impl<P: Clone + Send + Sync> Tuner<P> {
+    pub fn new(candidates: Vec<P>) -> Self {
+        Self {
+            candidates,
+            repeats_per_round: 3,
+            reduction_factor: 2,
+            max_rounds: None,
+            par_runs: true,
+            rng_seed: None,
+        }
+    }
+    /// The number of seeded runs per candidate per round, minimum of 1
+    pub fn with_repeats_per_round(mut self, repeats_per_round: usize) -> Self {
+        self.repeats_per_round = repeats_per_round.max(1);
+        self
+    }
+    /// Keep the best `1 / reduction_factor` fraction of the candidates after each round (rounded
+    /// up), minimum of 2
+    pub fn with_reduction_factor(mut self, reduction_factor: usize) -> Self {
+        self.reduction_factor = reduction_factor.max(2);
+        self
+    }
+    pub fn with_max_rounds(mut self, max_rounds: usize) -> Self {
+        self.max_rounds = Some(max_rounds.max(1));
+        self
+    }
+    /// Run the seeded runs of a round in parallel, defaults to true. This is separate and
+    /// independent from the `with_par_fitness()` flag on the builder.
+    pub fn with_par_runs(mut self, par_runs: bool) -> Self {
+        self.par_runs = par_runs;
+        self
+    }
+    /// The seed of the first run, the seeds of the other runs increment from it
+    pub fn with_rng_seed_from_u64(mut self, rng_seed: u64) -> Self {
+        self.rng_seed = Some(rng_seed);
+        self
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub fn call<G, M, F, S, C, E, SR, FB>(
+        &self,
+        factory: FB,
+    ) -> Result<Vec<TuneResult<P>>, TryFromEvolveBuilderError>
+    where
+        G: EvolveGenotype,
+        M: Mutate<Genotype = G>,
+        F: Fitness<Genotype = G>,
+        S: Crossover<Genotype = G>,
+        C: Select<Genotype = G>,
+        E: Extension<Genotype = G>,
+        SR: StrategyReporter<Genotype = G>,
+        FB: Fn(&P) -> EvolveBuilder<G, M, F, S, C, E, SR> + Sync,
+    {
+        let mut results: Vec<TuneResult<P>> = self
+            .candidates
+            .iter()
+            .map(|parameters| TuneResult {
+                parameters: parameters.clone(),
+                rounds: 0,
+                runs: vec![],
+            })
+            .collect();
+        let Some(first_candidate) = self.candidates.first() else {
+            return Ok(results);
+        };
+        let fitness_ordering = factory(first_candidate).fitness_ordering;
+        let base_rng_seed = self
+            .rng_seed
+            .unwrap_or_else(|| rand::thread_rng().gen::<u64>());
+
+        let mut remaining: Vec<usize> = (0..results.len()).collect();
+        let mut round = 0;
+        loop {
+            let rng_seeds: Vec<u64> = (0..self.repeats_per_round)
+                .map(|repeat| {
+                    base_rng_seed.wrapping_add((round * self.repeats_per_round + repeat) as u64)
+                })
+                .collect();
+            let jobs: Vec<(usize, u64)> = remaining
+                .iter()
+                .flat_map(|&index| rng_seeds.iter().map(move |&rng_seed| (index, rng_seed)))
+                .collect();
+            let run = |&(index, rng_seed): &(usize, u64)| {
+                Self::run(factory(&self.candidates[index]), rng_seed).map(|run| (index, run))
+            };
+            let runs: Vec<(usize, Run)> = if self.par_runs {
+                jobs.par_iter().map(run).collect::<Result<_, _>>()?
+            } else {
+                jobs.iter().map(run).collect::<Result<_, _>>()?
+            };
+            runs.into_iter().for_each(|(index, run)| {
+                results[index].runs.push(run);
+            });
+            remaining
+                .iter()
+                .for_each(|&index| results[index].rounds = round + 1);
+            round += 1;
+
+            if remaining.len() <= 1 || self.max_rounds.map_or(false, |max| round >= max) {
+                break;
+            }
+            remaining.sort_by(|&a, &b| results[a].compare(&results[b], fitness_ordering));
+            let keep = (remaining.len() + self.reduction_factor - 1) / self.reduction_factor;
+            remaining.truncate(keep);
+        }
+
+        results.sort_by(|a, b| a.compare(b, fitness_ordering));
+        Ok(results)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn run<G, M, F, S, C, E, SR>(
+        builder: EvolveBuilder<G, M, F, S, C, E, SR>,
+        rng_seed: u64,
+    ) -> Result<Run, TryFromEvolveBuilderError>
+    where
+        G: EvolveGenotype,
+        M: Mutate<Genotype = G>,
+        F: Fitness<Genotype = G>,
+        S: Crossover<Genotype = G>,
+        C: Select<Genotype = G>,
+        E: Extension<Genotype = G>,
+        SR: StrategyReporter<Genotype = G>,
+    {
+        let now = Instant::now();
+        let evolve: Evolve<G, M, F, S, C, E, SR> =
+            builder.with_rng_seed_from_u64(rng_seed).call()?;
+        Ok(Run {
+            rng_seed,
+            best_fitness_score: evolve.best_fitness_score(),
+            target_reached: evolve.is_finished_by_target_fitness_score(),
+            generations: evolve.state.current_generation(),
+            duration: now.elapsed(),
+        })
+    }
+}
@@ -11,21 +11,23 @@ use super::{
     Strategy, StrategyAction, StrategyConfig, StrategyReporter, StrategyReporterNoop,
     StrategyState, StrategyVariant,
 };
-use crate::chromosome::{Chromosome, Genes};
+use crate::chromosome::{Chromosome, Genes, GenesHash};
 use crate::fitness::{Fitness, FitnessCache, FitnessOrdering, FitnessValue};
 use crate::genotype::HillClimbGenotype;
 use crate::population::Population;
 use rand::prelude::SliceRandom;
 use rand::rngs::SmallRng;
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::time::{Duration, Instant};
 use thread_local::ThreadLocal;
 
 pub use self::reporter::Simple as HillClimbReporterSimple;
 pub use crate::strategy::reporter::Duration as HillClimbReporterDuration;
+pub use crate::strategy::reporter::Log as HillClimbReporterLog;
 pub use crate::strategy::reporter::Noop as HillClimbReporterNoop;
+pub use crate::strategy::reporter::Recorder as HillClimbReporterRecorder;
 
 #[derive(Copy, Clone, Debug, Default)]
 pub enum HillClimbVariant {
@@ -92,6 +94,12 @@ pub enum HillClimbVariant {
 ///         * Traverse all values for [HillClimbVariant::SteepestAscent]
 ///     * max_stale_generations could be set to 1, as there is no remaining randomness
 ///
+/// Neighbourhoods of [HillClimbVariant::SteepestAscent] overlap between generations (e.g. the
+/// step back to the previous best chromosome). With `with_tabu_evaluated(true)` the genes_hash of
+/// each evaluated chromosome is kept during the run and revisited neighbours are dropped before
+/// the fitness calculation. Alternatively, use `with_fitness_cache(size)` to reuse the fitness
+/// of revisited chromosomes (also across runs), without changing the search path.
+///
 /// There are reporting hooks in the loop receiving the [HillClimbState], which can by handled by an
 /// [StrategyReporter] (e.g. [HillClimbReporterDuration], [HillClimbReporterSimple]). But you are encouraged to
 /// roll your own, see [StrategyReporter].
@@ -140,7 +148,7 @@ pub enum HillClimbVariant {
 /// // the search space
 /// let genotype = RangeGenotype::builder()     // f32 alleles
 ///     .with_genes_size(16)                    // 16 genes
-///     // genes_hashing is auto-disabled for HillClimb (unless fitness_cache is set)
+///     // genes_hashing is auto-disabled for HillClimb (unless fitness_cache or tabu_evaluated is set)
 ///     .with_chromosome_recycling(true)        // recycle genes memory allocations, maybe useful
 ///     .with_allele_range(0.0..=1.0)           // allow gene values between 0.0 and 1.0
 ///     .with_mutation_type(MutationType::Range(0.1)) // optional, neighbouring step size randomly sampled from range
@@ -195,6 +203,7 @@ pub struct HillClimbConfig {
     pub max_generations: Option<usize>,
     pub valid_fitness_score: Option<FitnessValue>,
     pub fitness_cache: Option<FitnessCache>,
+    pub tabu_evaluated: bool,
 }
 
 /// Stores the state of the HillClimb strategy.
@@ -208,6 +217,7 @@ pub struct HillClimbState<G: HillClimbGenotype> {
     pub best_chromosome: Option<Chromosome<G::Allele>>,
     pub chromosome: Option<Chromosome<G::Allele>>,
     pub population: Population<G::Allele>,
+    pub evaluated_genes_hashes: HashSet<GenesHash>,
     pub durations: HashMap<StrategyAction, Duration>,
 }
 
@@ -260,6 +270,9 @@ impl<G: HillClimbGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genoty
                         &mut self.state.population,
                         &mut self.rng,
                     );
+                    if self.config.tabu_evaluated {
+                        self.state.drop_evaluated_from_population();
+                    }
                     self.fitness.call_for_state_population(
                         &self.genotype,
                         &mut self.state,
@@ -350,6 +363,9 @@ impl<G: HillClimbGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genoty
                 self.state.population = self
                     .genotype
                     .population_constructor(population_size, &mut self.rng);
+                if self.config.tabu_evaluated {
+                    self.state.drop_evaluated_from_population();
+                }
 
                 self.fitness.call_for_state_population(
                     &self.genotype,
@@ -380,6 +396,7 @@ impl<G: HillClimbGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genoty
         let now = Instant::now();
         self.state.chromosome.take();
         self.state.population.chromosomes.clear();
+        self.state.evaluated_genes_hashes.clear();
         if let Some(thread_local) = fitness_thread_local {
             thread_local.clear();
         }
@@ -589,6 +606,24 @@ impl<G: HillClimbGenotype> HillClimbState<G> {
         }
         self.add_duration(StrategyAction::UpdateBestChromosome, now.elapsed());
     }
+    /// Drop the chromosomes which have been evaluated before (or are duplicates within the
+    /// population) and mark the remaining ones as evaluated
+    fn drop_evaluated_from_population(&mut self) {
+        let now = Instant::now();
+        let chromosomes = std::mem::take(&mut self.population.chromosomes);
+        self.population.chromosomes.reserve(chromosomes.len());
+        chromosomes.into_iter().for_each(|chromosome| {
+            let unevaluated = chromosome.genes_hash().map_or(true, |genes_hash| {
+                self.evaluated_genes_hashes.insert(genes_hash)
+            });
+            if unevaluated {
+                self.population.chromosomes.push(chromosome);
+            } else {
+                self.population.drop_chromosome(chromosome);
+            }
+        });
+        self.add_duration(StrategyAction::Other, now.elapsed());
+    }
     fn scale(&mut self, genotype: &mut G, config: &HillClimbConfig) {
         if let Some(max_generations) = config.max_generations {
             if self.scale_generation >= max_generations && genotype.increment_scale_index() {
@@ -624,10 +659,21 @@ impl<G: HillClimbGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genoty
             Err(TryFromHillClimbBuilderError(
                 "HillClimb requires at least a max_stale_generations, max_generations or target_fitness_score ending condition",
             ))
+        } else if builder.tabu_evaluated
+            && !matches!(
+                builder.variant.unwrap_or_default(),
+                HillClimbVariant::SteepestAscent
+            )
+        {
+            Err(TryFromHillClimbBuilderError(
+                "HillClimb tabu_evaluated is only supported for the SteepestAscent variant",
+            ))
         } else {
             let rng = builder.rng();
             let mut genotype = builder.genotype.unwrap();
-            if builder.fitness_cache.is_none() {
+            if builder.tabu_evaluated {
+                genotype.set_genes_hashing(true);
+            } else if builder.fitness_cache.is_none() {
                 genotype.set_genes_hashing(false);
             }
             let state = HillClimbState::new(&genotype);
@@ -640,6 +686,7 @@ impl<G: HillClimbGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genoty
                     fitness_ordering: builder.fitness_ordering,
                     fitness_cache: builder.fitness_cache,
                     par_fitness: builder.par_fitness,
+                    tabu_evaluated: builder.tabu_evaluated,
                     max_stale_generations: builder.max_stale_generations,
                     max_generations: builder.max_generations,
                     target_fitness_score: builder.target_fitness_score,
@@ -661,6 +708,7 @@ impl Default for HillClimbConfig {
             fitness_ordering: FitnessOrdering::Maximize,
             fitness_cache: None,
             par_fitness: false,
+            tabu_evaluated: false,
             max_stale_generations: None,
             max_generations: None,
             target_fitness_score: None,
@@ -686,6 +734,7 @@ impl<G: HillClimbGenotype> HillClimbState<G> {
             best_fitness_score: None,
             chromosome: None,
             population: Population::new_empty(genotype.chromosome_recycling()),
+            evaluated_genes_hashes: HashSet::new(),
             durations: HashMap::new(),
             best_chromosome: None,
         }
@@ -720,7 +769,8 @@ impl fmt::Display for HillClimbConfig {
         writeln!(f, "  valid_fitness_score: {:?}", self.valid_fitness_score)?;
         writeln!(f, "  target_fitness_score: {:?}", self.target_fitness_score)?;
         writeln!(f, "  fitness_ordering: {:?}", self.fitness_ordering)?;
-        writeln!(f, "  par_fitness: {:?}", self.par_fitness)
+        writeln!(f, "  par_fitness: {:?}", self.par_fitness)?;
+        writeln!(f, "  tabu_evaluated: {:?}", self.tabu_evaluated)
     }
 }
 
@@ -22,6 +22,7 @@ pub struct Builder<
     pub fitness_ordering: FitnessOrdering,
     pub fitness_cache: Option<FitnessCache>,
     pub par_fitness: bool,
+    pub tabu_evaluated: bool,
     pub max_stale_generations: Option<usize>,
     pub max_generations: Option<usize>,
     pub target_fitness_score: Option<FitnessValue>,
@@ -42,6 +43,7 @@ impl<G: HillClimbGenotype, F: Fitness<Genotype = G>> Default
             fitness_ordering: FitnessOrdering::Maximize,
             fitness_cache: None,
             par_fitness: false,
+            tabu_evaluated: false,
             max_stale_generations: None,
             max_generations: None,
             target_fitness_score: None,
@@ -80,16 +82,34 @@ impl<G: HillClimbGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genoty
     /// Only useful for long stale runs.
     /// Silently ignore cache_size of zero, to support superset builder which delays specialization
     pub fn with_fitness_cache(mut self, fitness_cache_size: usize) -> Self {
-        match FitnessCache::try_new(fitness_cache_size) {
+        match FitnessCache::try_new_sharded(
+            fitness_cache_size,
+            FitnessCache::DEFAULT_NUMBER_OF_SHARDS,
+        ) {
             Ok(cache) => self.fitness_cache = Some(cache),
             Err(_error) => (),
         }
         self
     }
+    /// Use a prebuilt (and possibly shared) cache, e.g. with another backend
+    /// ([FitnessCache::new_unbounded], [FitnessCache::try_new_file]) or with genes in the key
+    /// ([FitnessCache::with_genes_in_key]). Only works when genes_hash is stored on chromosome.
+    pub fn with_shared_fitness_cache(mut self, fitness_cache: FitnessCache) -> Self {
+        self.fitness_cache = Some(fitness_cache);
+        self
+    }
     pub fn with_par_fitness(mut self, par_fitness: bool) -> Self {
         self.par_fitness = par_fitness;
         self
     }
+    /// Only for [HillClimbVariant::SteepestAscent]. Keep the genes_hash of every evaluated
+    /// chromosome during the run and drop revisited neighbours before fitness calculation (tabu),
+    /// so they are not recomputed and the search doesn't cycle back. Enables genes_hashing. The
+    /// builder returns an error when combined with [HillClimbVariant::Stochastic].
+    pub fn with_tabu_evaluated(mut self, tabu_evaluated: bool) -> Self {
+        self.tabu_evaluated = tabu_evaluated;
+        self
+    }
     pub fn with_fitness(mut self, fitness: F) -> Self {
         self.fitness = Some(fitness);
         self
@@ -150,6 +170,7 @@ impl<G: HillClimbGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genoty
             fitness_ordering: self.fitness_ordering,
             fitness_cache: self.fitness_cache,
             par_fitness: self.par_fitness,
+            tabu_evaluated: self.tabu_evaluated,
             max_stale_generations: self.max_stale_generations,
             max_generations: self.max_generations,
             target_fitness_score: self.target_fitness_score,
@@ -159,6 +180,29 @@ impl<G: HillClimbGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genoty
             rng_seed: self.rng_seed,
         }
     }
+    /// Add a reporter next to the existing one (combined as a tuple), every hook is forwarded to
+    /// the existing reporter first and then to the additional reporter
+    pub fn with_additional_reporter<SR2: StrategyReporter<Genotype = G>>(
+        self,
+        reporter: SR2,
+    ) -> Builder<G, F, (SR, SR2)> {
+        Builder {
+            genotype: self.genotype,
+            variant: self.variant,
+            fitness: self.fitness,
+            fitness_ordering: self.fitness_ordering,
+            fitness_cache: self.fitness_cache,
+            par_fitness: self.par_fitness,
+            tabu_evaluated: self.tabu_evaluated,
+            max_stale_generations: self.max_stale_generations,
+            max_generations: self.max_generations,
+            target_fitness_score: self.target_fitness_score,
+            valid_fitness_score: self.valid_fitness_score,
+            replace_on_equal_fitness: self.replace_on_equal_fitness,
+            reporter: (self.reporter, reporter),
+            rng_seed: self.rng_seed,
+        }
+    }
     pub fn with_rng_seed_from_u64(mut self, rng_seed: u64) -> Self {
         self.rng_seed = Some(rng_seed);
         self
@@ -2,25 +2,28 @@
 pub use crate::chromosome::{Chromosome, GenesHash};
 #[doc(no_inline)]
 pub use crate::fitness::{
-    fitness_value, Fitness, FitnessChromosome, FitnessGenes, FitnessGenotype, FitnessOrdering,
-    FitnessPopulation, FitnessValue,
+    fitness_value, Fitness, FitnessCache, FitnessChromosome, FitnessGenes, FitnessGenotype,
+    FitnessOrdering, FitnessPopulation, FitnessValue,
 };
 #[doc(no_inline)]
 pub use crate::genotype::{
-    Allele, BinaryGenotype, Genotype, GenotypeBuilder, HillClimbGenotype, ListGenotype,
-    MultiListGenotype, MultiRangeGenotype, MultiUniqueGenotype, MutationType, RangeAllele,
-    RangeGenotype, TryFromGenotypeBuilderError, UniqueGenotype,
+    Allele, AlleleScale, BinaryGenotype, BitGenotype, CompositeGenotype, GeneConstraint, Genotype,
+    GenotypeBuilder, Grammar, GrammaticalGenotype, GridGenotype, HillClimbGenotype, LinearBound,
+    ListGenotype, MultiListGenotype, MultiRangeGenotype, MultiUniqueGenotype, MutationType,
+    PartialPermutationGenotype, RangeAllele, RangeGenotype, SubsetGenotype, TreeGenotype,
+    TryFromGenotypeBuilderError, UniqueGenotype, VariableListGenotype,
 };
 #[doc(no_inline)]
 pub use crate::impl_allele;
 #[doc(no_inline)]
 pub use crate::strategy::hill_climb::{
-    HillClimb, HillClimbBuilder, HillClimbConfig, HillClimbReporterDuration, HillClimbReporterNoop,
-    HillClimbReporterSimple, HillClimbState, HillClimbVariant, TryFromHillClimbBuilderError,
+    HillClimb, HillClimbBuilder, HillClimbConfig, HillClimbReporterDuration, HillClimbReporterLog,
+    HillClimbReporterNoop, HillClimbReporterRecorder, HillClimbReporterSimple, HillClimbState,
+    HillClimbVariant, TryFromHillClimbBuilderError,
 };
 #[doc(no_inline)]
 pub use crate::strategy::{
     Strategy, StrategyBuilder, StrategyConfig, StrategyReporter, StrategyReporterDuration,
-    StrategyReporterNoop, StrategyReporterSimple, StrategyState, TryFromStrategyBuilderError,
-    STRATEGY_ACTIONS,
+    StrategyReporterLog, StrategyReporterNoop, StrategyReporterRecord, StrategyReporterRecorder,
+    StrategyReporterSimple, StrategyState, TryFromStrategyBuilderError, STRATEGY_ACTIONS,
 };
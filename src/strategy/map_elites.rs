@@ -0,0 +1,813 @@
+//! A quality-diversity strategy, filling a grid of behaviour space cells with the best chromosome
+//! per cell, instead of searching for a single best chromosome
+mod archive;
+mod builder;
+pub mod prelude;
+mod reporter;
+
+pub use self::archive::{
+    Archive as MapElitesArchive, Elite as MapElitesElite, Grid as MapElitesGrid,
+};
+pub use self::builder::{
+    Builder as MapElitesBuilder, TryFromBuilderError as TryFromMapElitesBuilderError,
+};
+
+use super::{
+    Strategy, StrategyAction, StrategyConfig, StrategyReporter, StrategyReporterNoop,
+    StrategyState, StrategyVariant,
+};
+use crate::chromosome::{Chromosome, Genes};
+use crate::fitness::{Fitness, FitnessCache, FitnessOrdering, FitnessValue};
+use crate::genotype::{EvolveGenotype, Genotype};
+use crate::population::Population;
+use rand::rngs::SmallRng;
+use rand::Rng;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+use thread_local::ThreadLocal;
+
+pub use self::reporter::Simple as MapElitesReporterSimple;
+pub use crate::strategy::reporter::Duration as MapElitesReporterDuration;
+pub use crate::strategy::reporter::Log as MapElitesReporterLog;
+pub use crate::strategy::reporter::Noop as MapElitesReporterNoop;
+pub use crate::strategy::reporter::Recorder as MapElitesReporterRecorder;
+
+#[derive(Copy, Clone, Debug, Default)]
+pub enum MapElitesVariant {
+    #[default]
+    Standard,
+}
+
+/// The crossover function of the genotype, see [MapElitesBuilder::with_point_crossover] and
+/// [MapElitesBuilder::with_gene_crossover]
+pub type MapElitesCrossover<G> = fn(
+    &G,
+    usize,
+    bool,
+    &mut Chromosome<<G as Genotype>::Allele>,
+    &mut Chromosome<<G as Genotype>::Allele>,
+    &mut SmallRng,
+);
+
+/// The behaviour descriptor maps a chromosome to a point in behaviour space (one value per grid
+/// dimension). Like [Fitness], it is problem specific and needs to be implemented by the client.
+///
+/// Example:
+/// ```rust
+/// use genetic_algorithm::strategy::map_elites::prelude::*;
+///
+/// #[derive(Clone, Debug)]
+/// pub struct FirstAndLastGene;
+/// impl BehaviourDescriptor for FirstAndLastGene {
+///     type Genotype = RangeGenotype<f32>;
+///     fn calculate_for_chromosome(
+///         &mut self,
+///         chromosome: &Chromosome<f32>,
+///         _genotype: &Self::Genotype,
+///     ) -> Vec<f32> {
+///         vec![chromosome.genes[0], *chromosome.genes.last().unwrap()]
+///     }
+/// }
+/// ```
+pub trait BehaviourDescriptor: Clone + Send + Sync + fmt::Debug {
+    type Genotype: Genotype;
+    /// Must be implemented by client, the length must match the number of grid dimensions
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &Chromosome<<Self::Genotype as Genotype>::Allele>,
+        genotype: &Self::Genotype,
+    ) -> Vec<f32>;
+}
+
+/// The MapElites strategy (Multi-dimensional Archive of Phenotypic Elites) is a quality-diversity
+/// algorithm. Instead of a single best chromosome, it keeps an archive with the best chromosome
+/// (elite) for each cell of a grid over behaviour space. The behaviour of a chromosome is
+/// determined by a client implemented [BehaviourDescriptor], the grid is defined by a
+/// [MapElitesGrid] (range and number of bins per behaviour dimension).
+///
+/// Each generation a batch of offspring is created from uniformly sampled elites, by optional
+/// crossover with another sampled elite (using the genotype crossover traits) and mutation (using
+/// the genotype `mutate_chromosome_genes`). Each offspring is placed in its cell if the cell is
+/// empty or the offspring is better than the current elite. The overall best chromosome is tracked
+/// as well, so the [Strategy] interface still applies.
+///
+/// The archive, coverage (fraction of occupied cells) and QD-score (sum of elite fitness scores)
+/// are available on the [MapElitesState] and through the [StrategyState] archive methods in the
+/// reporter hooks.
+///
+/// The ending conditions are one or more of the following:
+/// * target_fitness_score: when the ultimate goal in terms of fitness score is known and reached
+/// * max_stale_generations: when there are no archive improvements (new or better elites) for the
+///   given number of generations
+/// * max_generations: when there is a effort constraint
+///
+/// There are reporting hooks in the loop receiving the [MapElitesState], which can by handled by an
+/// [StrategyReporter] (e.g. [MapElitesReporterDuration], [MapElitesReporterSimple]). But you are encouraged to
+/// roll your own, see [StrategyReporter].
+///
+/// Below is the exact order of actions and hooks
+/// * [reporter](crate::strategy::reporter) on_enter hook
+/// * setup: random initial population, [fitness](crate::fitness) calculation and archive update
+/// * [reporter](crate::strategy::reporter) on_start hook
+/// * loop while not finished
+///   * increment generation
+///   * sample elites and crossover offspring
+///   * [reporter](crate::strategy::reporter) on_crossover_complete hook
+///   * mutate offspring
+///   * [reporter](crate::strategy::reporter) on_mutation_complete hook
+///   * [fitness](crate::fitness) calculation
+///   * update archive and best chromosome
+///   * [reporter](crate::strategy::reporter) on_generation_complete hook
+///   * check ending conditions
+/// * [reporter](crate::strategy::reporter) on_finish hook
+/// * cleanup
+/// * [reporter](crate::strategy::reporter) on_exit hook
+///
+/// MapElites is not part of the superset [StrategyBuilder](crate::strategy::StrategyBuilder), as
+/// it requires a behaviour descriptor and grid. See [MapElitesBuilder] for initialization options.
+///
+/// Example:
+/// ```
+/// use genetic_algorithm::strategy::map_elites::prelude::*;
+/// use genetic_algorithm::fitness::placeholders::SumGenes;
+///
+/// #[derive(Clone, Debug)]
+/// pub struct FirstAndLastGene;
+/// impl BehaviourDescriptor for FirstAndLastGene {
+///     type Genotype = RangeGenotype<f32>;
+///     fn calculate_for_chromosome(
+///         &mut self,
+///         chromosome: &Chromosome<f32>,
+///         _genotype: &Self::Genotype,
+///     ) -> Vec<f32> {
+///         vec![chromosome.genes[0], chromosome.genes[9]]
+///     }
+/// }
+///
+/// // the search space
+/// let genotype = RangeGenotype::builder()
+///     .with_genes_size(10)
+///     .with_allele_range(0.0..=1.0)
+///     .with_mutation_type(MutationType::Random) // uniform mutation, for diversity
+///     .build()
+///     .unwrap();
+///
+/// // the search strategy
+/// let map_elites = MapElites::builder()
+///     .with_genotype(genotype)
+///     .with_descriptor(FirstAndLastGene)                  // behaviour of the chromosome
+///     .with_grid(MapElitesGrid::new(vec![(0.0..=1.0, 5), (0.0..=1.0, 5)])) // 5x5 cells
+///     .with_fitness(SumGenes::new_with_precision(1e-3))   // quality of the chromosome
+///     .with_initial_population_size(100)                  // random chromosomes to seed the archive
+///     .with_batch_size(50)                                // offspring per generation
+///     .with_number_of_mutations(2)                        // mutated genes per offspring
+///     .with_point_crossover(0.5)                          // optional, crossover rate between elites
+///     .with_max_generations(100)                          // stop after 100 generations
+///     .with_reporter(MapElitesReporterSimple::new(10))    // optional, report every 10 generations
+///     .with_rng_seed_from_u64(0)                          // for testing with deterministic results
+///     .call()
+///     .unwrap();
+///
+/// // the archive is the result, next to the overall best chromosome
+/// assert_eq!(map_elites.state.archive.size(), 25);
+/// assert_eq!(map_elites.state.archive_coverage(), Some(1.0));
+/// assert!(map_elites.best_fitness_score().unwrap() > 8_000);
+/// ```
+pub struct MapElites<
+    G: EvolveGenotype,
+    F: Fitness<Genotype = G>,
+    D: BehaviourDescriptor<Genotype = G>,
+    SR: StrategyReporter<Genotype = G>,
+> {
+    pub genotype: G,
+    pub fitness: F,
+    pub descriptor: D,
+    pub crossover: Option<MapElitesCrossover<G>>,
+    pub config: MapElitesConfig,
+    pub state: MapElitesState<G>,
+    pub reporter: SR,
+    pub rng: SmallRng,
+}
+
+pub struct MapElitesConfig {
+    pub variant: MapElitesVariant,
+    pub fitness_ordering: FitnessOrdering,
+    pub par_fitness: bool,
+    pub replace_on_equal_fitness: bool,
+    pub grid: MapElitesGrid,
+    pub initial_population_size: usize,
+    pub batch_size: usize,
+    pub number_of_mutations: usize,
+    pub crossover_rate: f32,
+
+    pub target_fitness_score: Option<FitnessValue>,
+    pub max_stale_generations: Option<usize>,
+    pub max_generations: Option<usize>,
+    pub valid_fitness_score: Option<FitnessValue>,
+    pub fitness_cache: Option<FitnessCache>,
+}
+
+/// Stores the state of the MapElites strategy.
+pub struct MapElitesState<G: EvolveGenotype> {
+    pub current_iteration: usize,
+    pub current_generation: usize,
+    pub stale_generations: usize,
+    pub best_generation: usize,
+    pub best_fitness_score: Option<FitnessValue>,
+    pub best_chromosome: Option<Chromosome<G::Allele>>,
+    pub chromosome: Option<Chromosome<G::Allele>>,
+    pub population: Population<G::Allele>,
+    pub archive: MapElitesArchive<G::Allele>,
+    /// Number of new or better elites in the last generation
+    pub archive_improvements: usize,
+    pub durations: HashMap<StrategyAction, Duration>,
+}
+
+impl<
+        G: EvolveGenotype,
+        F: Fitness<Genotype = G>,
+        D: BehaviourDescriptor<Genotype = G>,
+        SR: StrategyReporter<Genotype = G>,
+    > Strategy<G> for MapElites<G, F, D, SR>
+{
+    fn call(&mut self) {
+        let now = Instant::now();
+        self.reporter
+            .on_enter(&self.genotype, &self.state, &self.config);
+        let mut fitness_thread_local: Option<ThreadLocal<RefCell<F>>> = None;
+        if self.config.par_fitness {
+            fitness_thread_local = Some(ThreadLocal::new());
+        }
+
+        self.setup(fitness_thread_local.as_ref());
+        self.reporter
+            .on_start(&self.genotype, &self.state, &self.config);
+        while !self.is_finished() {
+            self.state.increment_generation();
+            self.crossover();
+            self.reporter
+                .on_crossover_complete(&self.genotype, &self.state, &self.config);
+            self.mutate();
+            self.reporter
+                .on_mutation_complete(&self.genotype, &self.state, &self.config);
+            self.fitness.call_for_state_population(
+                &self.genotype,
+                &mut self.state,
+                &self.config,
+                fitness_thread_local.as_ref(),
+            );
+            self.state.update_archive_from_state_population(
+                &self.genotype,
+                &mut self.descriptor,
+                &self.config,
+                &mut self.reporter,
+            );
+            self.reporter
+                .on_generation_complete(&self.genotype, &self.state, &self.config);
+        }
+        self.reporter
+            .on_finish(&self.genotype, &self.state, &self.config);
+        self.cleanup(fitness_thread_local.as_mut());
+        self.state.close_duration(now.elapsed());
+        self.reporter
+            .on_exit(&self.genotype, &self.state, &self.config);
+    }
+    fn best_generation(&self) -> usize {
+        self.state.best_generation
+    }
+    fn best_fitness_score(&self) -> Option<FitnessValue> {
+        self.state.best_fitness_score()
+    }
+    fn best_genes(&self) -> Option<Genes<G::Allele>> {
+        self.state
+            .best_chromosome
+            .as_ref()
+            .map(|c| c.genes().clone())
+    }
+    fn flush_reporter(&mut self, output: &mut Vec<u8>) {
+        self.reporter.flush(output);
+    }
+}
+impl<
+        G: EvolveGenotype,
+        F: Fitness<Genotype = G>,
+        D: BehaviourDescriptor<Genotype = G>,
+        SR: StrategyReporter<Genotype = G>,
+    > MapElites<G, F, D, SR>
+{
+    pub fn best_chromosome(&self) -> Option<Chromosome<G::Allele>> {
+        if let Some(best_genes) = self.best_genes() {
+            let mut chromosome = Chromosome::<G::Allele>::new(best_genes);
+            chromosome.set_fitness_score(self.best_fitness_score());
+            Some(chromosome)
+        } else {
+            None
+        }
+    }
+}
+
+impl<G: EvolveGenotype, F: Fitness<Genotype = G>, D: BehaviourDescriptor<Genotype = G>>
+    MapElites<G, F, D, StrategyReporterNoop<G>>
+{
+    pub fn builder() -> MapElitesBuilder<G, F, D, StrategyReporterNoop<G>> {
+        MapElitesBuilder::new()
+    }
+}
+impl<
+        G: EvolveGenotype,
+        F: Fitness<Genotype = G>,
+        D: BehaviourDescriptor<Genotype = G>,
+        SR: StrategyReporter<Genotype = G>,
+    > MapElites<G, F, D, SR>
+{
+    pub fn setup(&mut self, fitness_thread_local: Option<&ThreadLocal<RefCell<F>>>) {
+        let now = Instant::now();
+        self.state.archive = MapElitesArchive::new(self.config.grid.number_of_cells());
+        self.state.population = self
+            .genotype
+            .population_constructor(self.config.initial_population_size, &mut self.rng);
+        self.state
+            .add_duration(StrategyAction::SetupAndCleanup, now.elapsed());
+
+        self.fitness.call_for_state_population(
+            &self.genotype,
+            &mut self.state,
+            &self.config,
+            fitness_thread_local,
+        );
+        self.state.update_archive_from_state_population(
+            &self.genotype,
+            &mut self.descriptor,
+            &self.config,
+            &mut self.reporter,
+        );
+    }
+    pub fn cleanup(&mut self, fitness_thread_local: Option<&mut ThreadLocal<RefCell<F>>>) {
+        let now = Instant::now();
+        self.state.chromosome.take();
+        self.state.population.chromosomes.clear();
+        if let Some(thread_local) = fitness_thread_local {
+            thread_local.clear();
+        }
+        self.state
+            .add_duration(StrategyAction::SetupAndCleanup, now.elapsed());
+    }
+    /// Sample parents uniformly from the archive and apply the optional crossover with another
+    /// sampled elite. Falls back to a random chromosome when the archive is still empty.
+    fn crossover(&mut self) {
+        let now = Instant::now();
+        let population = &mut self.state.population;
+        let archive = &self.state.archive;
+        population.truncate(0);
+        for _ in 0..self.config.batch_size {
+            if archive.is_empty() {
+                let chromosome = self.genotype.chromosome_constructor_random(&mut self.rng);
+                population.chromosomes.push(chromosome);
+                continue;
+            }
+            let father = archive
+                .get(sample_cell_index(archive, &mut self.rng))
+                .unwrap();
+            let mut offspring = population.new_chromosome(&father.chromosome);
+            if let Some(crossover) = self.crossover {
+                if self.rng.gen::<f32>() < self.config.crossover_rate {
+                    let mother = archive
+                        .get(sample_cell_index(archive, &mut self.rng))
+                        .unwrap();
+                    let mut mate = population.new_chromosome(&mother.chromosome);
+                    crossover(
+                        &self.genotype,
+                        1,
+                        true,
+                        &mut offspring,
+                        &mut mate,
+                        &mut self.rng,
+                    );
+                    population.drop_chromosome(mate);
+                }
+            }
+            population.chromosomes.push(offspring);
+        }
+        self.state
+            .add_duration(StrategyAction::Crossover, now.elapsed());
+    }
+    fn mutate(&mut self) {
+        let now = Instant::now();
+        for chromosome in self.state.population.chromosomes.iter_mut() {
+            self.genotype.mutate_chromosome_genes(
+                self.config.number_of_mutations,
+                true,
+                chromosome,
+                &mut self.rng,
+            );
+        }
+        self.state
+            .add_duration(StrategyAction::Mutate, now.elapsed());
+    }
+    fn is_finished(&self) -> bool {
+        self.allow_finished_by_valid_fitness_score()
+            && (self.is_finished_by_max_stale_generations()
+                || self.is_finished_by_max_generations()
+                || self.is_finished_by_target_fitness_score())
+    }
+
+    fn is_finished_by_max_stale_generations(&self) -> bool {
+        if let Some(max_stale_generations) = self.config.max_stale_generations {
+            self.state.stale_generations >= max_stale_generations
+        } else {
+            false
+        }
+    }
+
+    fn is_finished_by_max_generations(&self) -> bool {
+        if let Some(max_generations) = self.config.max_generations {
+            self.state.current_generation >= max_generations
+        } else {
+            false
+        }
+    }
+
+    fn is_finished_by_target_fitness_score(&self) -> bool {
+        if let Some(target_fitness_score) = self.config.target_fitness_score {
+            if let Some(fitness_score) = self.best_fitness_score() {
+                match self.config.fitness_ordering {
+                    FitnessOrdering::Maximize => fitness_score >= target_fitness_score,
+                    FitnessOrdering::Minimize => fitness_score <= target_fitness_score,
+                }
+            } else {
+                false
+            }
+        } else {
+            false
+        }
+    }
+
+    fn allow_finished_by_valid_fitness_score(&self) -> bool {
+        if let Some(valid_fitness_score) = self.config.valid_fitness_score {
+            if let Some(fitness_score) = self.best_fitness_score() {
+                match self.config.fitness_ordering {
+                    FitnessOrdering::Maximize => fitness_score >= valid_fitness_score,
+                    FitnessOrdering::Minimize => fitness_score <= valid_fitness_score,
+                }
+            } else {
+                true
+            }
+        } else {
+            true
+        }
+    }
+}
+
+fn sample_cell_index<T: crate::allele::Allele, R: Rng>(
+    archive: &MapElitesArchive<T>,
+    rng: &mut R,
+) -> usize {
+    archive.occupied_cell_indices[rng.gen_range(0..archive.occupied_cell_indices.len())]
+}
+
+impl StrategyConfig for MapElitesConfig {
+    fn fitness_ordering(&self) -> FitnessOrdering {
+        self.fitness_ordering
+    }
+    fn fitness_cache(&self) -> Option<&FitnessCache> {
+        self.fitness_cache.as_ref()
+    }
+    fn par_fitness(&self) -> bool {
+        self.par_fitness
+    }
+    fn replace_on_equal_fitness(&self) -> bool {
+        self.replace_on_equal_fitness
+    }
+    fn variant(&self) -> StrategyVariant {
+        StrategyVariant::MapElites(self.variant)
+    }
+}
+
+impl<G: EvolveGenotype> StrategyState<G> for MapElitesState<G> {
+    fn chromosome_as_ref(&self) -> &Option<Chromosome<G::Allele>> {
+        &self.chromosome
+    }
+    fn population_as_ref(&self) -> &Population<G::Allele> {
+        &self.population
+    }
+    fn chromosome_as_mut(&mut self) -> &mut Option<Chromosome<G::Allele>> {
+        &mut self.chromosome
+    }
+    fn population_as_mut(&mut self) -> &mut Population<G::Allele> {
+        &mut self.population
+    }
+    fn best_fitness_score(&self) -> Option<FitnessValue> {
+        self.best_fitness_score
+    }
+    fn best_generation(&self) -> usize {
+        self.best_generation
+    }
+    fn current_generation(&self) -> usize {
+        self.current_generation
+    }
+    fn current_iteration(&self) -> usize {
+        self.current_iteration
+    }
+    fn increment_generation(&mut self) {
+        self.current_generation += 1;
+    }
+    fn stale_generations(&self) -> usize {
+        self.stale_generations
+    }
+    fn increment_stale_generations(&mut self) {
+        self.stale_generations += 1;
+    }
+    fn reset_stale_generations(&mut self) {
+        self.stale_generations = 0;
+    }
+    fn scale_generation(&self) -> usize {
+        self.current_generation
+    }
+    fn reset_scale_generation(&mut self) {}
+    fn population_cardinality(&self) -> Option<usize> {
+        None
+    }
+    fn archive_size(&self) -> Option<usize> {
+        Some(self.archive.size())
+    }
+    fn archive_coverage(&self) -> Option<f32> {
+        Some(self.archive.coverage())
+    }
+    fn archive_qd_score(&self) -> Option<FitnessValue> {
+        Some(self.archive.qd_score())
+    }
+    fn durations(&self) -> &HashMap<StrategyAction, Duration> {
+        &self.durations
+    }
+    fn add_duration(&mut self, action: StrategyAction, duration: Duration) {
+        *self.durations.entry(action).or_default() += duration;
+    }
+    fn total_duration(&self) -> Duration {
+        self.durations.values().sum()
+    }
+    fn best_genes(&self) -> Option<Genes<G::Allele>> {
+        self.best_chromosome.as_ref().map(|c| c.genes().clone())
+    }
+}
+
+impl<G: EvolveGenotype> MapElitesState<G> {
+    /// Place each chromosome of the population in the archive and track the overall best
+    /// chromosome. A generation without new or better elites is considered stale (replacements
+    /// with equal fitness are not improvements).
+    fn update_archive_from_state_population<
+        D: BehaviourDescriptor<Genotype = G>,
+        SR: StrategyReporter<Genotype = G>,
+    >(
+        &mut self,
+        genotype: &G,
+        descriptor: &mut D,
+        config: &MapElitesConfig,
+        reporter: &mut SR,
+    ) {
+        let now = Instant::now();
+        let mut archive_improvements = 0;
+        let mut best_index: Option<usize> = None;
+        for (index, chromosome) in self.population.chromosomes.iter().enumerate() {
+            if chromosome.fitness_score().is_none() {
+                continue;
+            }
+            let behaviour = descriptor.calculate_for_chromosome(chromosome, genotype);
+            if let Some(cell_index) = config.grid.cell_index(&behaviour) {
+                if let (_, true) = self.archive.insert(
+                    cell_index,
+                    behaviour,
+                    chromosome,
+                    config.fitness_ordering,
+                    config.replace_on_equal_fitness,
+                ) {
+                    archive_improvements += 1;
+                }
+            }
+            let is_better = match best_index {
+                None => true,
+                Some(best_index) => {
+                    let best_fitness_score =
+                        self.population.chromosomes[best_index].fitness_score();
+                    match config.fitness_ordering {
+                        FitnessOrdering::Maximize => {
+                            chromosome.fitness_score() > best_fitness_score
+                        }
+                        FitnessOrdering::Minimize => {
+                            chromosome.fitness_score() < best_fitness_score
+                        }
+                    }
+                }
+            };
+            if is_better {
+                best_index = Some(index);
+            }
+        }
+        self.archive_improvements = archive_improvements;
+        if archive_improvements > 0 {
+            self.reset_stale_generations();
+        } else {
+            self.increment_stale_generations();
+        }
+
+        if let Some(best_index) = best_index {
+            let contending_chromosome = &self.population.chromosomes[best_index];
+            match self.is_better_chromosome(
+                contending_chromosome,
+                &config.fitness_ordering,
+                config.replace_on_equal_fitness,
+            ) {
+                (true, true) => {
+                    self.best_generation = self.current_generation;
+                    self.best_fitness_score = contending_chromosome.fitness_score();
+                    self.best_chromosome = Some(contending_chromosome.clone());
+                    reporter.on_new_best_chromosome(genotype, self, config);
+                }
+                (true, false) => {
+                    self.best_chromosome = Some(contending_chromosome.clone());
+                    reporter.on_new_best_chromosome_equal_fitness(genotype, self, config);
+                }
+                _ => {}
+            }
+        }
+        self.add_duration(StrategyAction::UpdateBestChromosome, now.elapsed());
+    }
+}
+
+impl<
+        G: EvolveGenotype,
+        F: Fitness<Genotype = G>,
+        D: BehaviourDescriptor<Genotype = G>,
+        SR: StrategyReporter<Genotype = G>,
+    > TryFrom<MapElitesBuilder<G, F, D, SR>> for MapElites<G, F, D, SR>
+{
+    type Error = TryFromMapElitesBuilderError;
+
+    fn try_from(builder: MapElitesBuilder<G, F, D, SR>) -> Result<Self, Self::Error> {
+        if builder.genotype.is_none() {
+            Err(TryFromMapElitesBuilderError(
+                "MapElites requires a EvolveGenotype",
+            ))
+        } else if builder.fitness.is_none() {
+            Err(TryFromMapElitesBuilderError("MapElites requires a Fitness"))
+        } else if builder.descriptor.is_none() {
+            Err(TryFromMapElitesBuilderError(
+                "MapElites requires a BehaviourDescriptor",
+            ))
+        } else if !builder.grid.as_ref().is_some_and(|grid| grid.is_valid()) {
+            Err(TryFromMapElitesBuilderError(
+                "MapElites requires a grid with at least one dimension and bins > 0",
+            ))
+        } else if builder.batch_size == 0 {
+            Err(TryFromMapElitesBuilderError(
+                "MapElites requires a batch_size > 0",
+            ))
+        } else if builder.max_stale_generations.is_none()
+            && builder.max_generations.is_none()
+            && builder.target_fitness_score.is_none()
+        {
+            Err(TryFromMapElitesBuilderError(
+                "MapElites requires at least a max_stale_generations, max_generations or target_fitness_score ending condition",
+            ))
+        } else {
+            let rng = builder.rng();
+            let mut genotype = builder.genotype.unwrap();
+            if builder.fitness_cache.is_none() {
+                genotype.set_genes_hashing(false);
+            }
+            let grid = builder.grid.unwrap();
+            let state = MapElitesState::new(&genotype, grid.number_of_cells());
+
+            Ok(Self {
+                genotype,
+                fitness: builder.fitness.unwrap(),
+                descriptor: builder.descriptor.unwrap(),
+                crossover: builder.crossover,
+                config: MapElitesConfig {
+                    variant: builder.variant.unwrap_or_default(),
+                    fitness_ordering: builder.fitness_ordering,
+                    fitness_cache: builder.fitness_cache,
+                    par_fitness: builder.par_fitness,
+                    grid,
+                    initial_population_size: builder
+                        .initial_population_size
+                        .unwrap_or(builder.batch_size),
+                    batch_size: builder.batch_size,
+                    number_of_mutations: builder.number_of_mutations,
+                    crossover_rate: builder.crossover_rate,
+                    max_stale_generations: builder.max_stale_generations,
+                    max_generations: builder.max_generations,
+                    target_fitness_score: builder.target_fitness_score,
+                    valid_fitness_score: builder.valid_fitness_score,
+                    replace_on_equal_fitness: builder.replace_on_equal_fitness,
+                },
+                state,
+                reporter: builder.reporter,
+                rng,
+            })
+        }
+    }
+}
+
+impl Default for MapElitesConfig {
+    fn default() -> Self {
+        Self {
+            variant: Default::default(),
+            fitness_ordering: FitnessOrdering::Maximize,
+            fitness_cache: None,
+            par_fitness: false,
+            grid: MapElitesGrid::default(),
+            initial_population_size: 100,
+            batch_size: 100,
+            number_of_mutations: 1,
+            crossover_rate: 0.0,
+            max_stale_generations: None,
+            max_generations: None,
+            target_fitness_score: None,
+            valid_fitness_score: None,
+            replace_on_equal_fitness: true,
+        }
+    }
+}
+impl MapElitesConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<G: EvolveGenotype> MapElitesState<G> {
+    pub fn new(genotype: &G, number_of_cells: usize) -> Self {
+        Self {
+            current_iteration: 0,
+            current_generation: 0,
+            stale_generations: 0,
+            best_generation: 0,
+            best_fitness_score: None,
+            chromosome: None,
+            population: Population::new_empty(genotype.chromosome_recycling()),
+            archive: MapElitesArchive::new(number_of_cells),
+            archive_improvements: 0,
+            durations: HashMap::new(),
+            best_chromosome: None,
+        }
+    }
+}
+
+impl<
+        G: EvolveGenotype,
+        F: Fitness<Genotype = G>,
+        D: BehaviourDescriptor<Genotype = G>,
+        SR: StrategyReporter<Genotype = G>,
+    > fmt::Display for MapElites<G, F, D, SR>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "map_elites:")?;
+        writeln!(f, "  fitness: {:?}", self.fitness)?;
+        writeln!(f, "  descriptor: {:?}", self.descriptor)?;
+        writeln!(f)?;
+
+        writeln!(f, "{}", self.config)?;
+        writeln!(f, "{}", self.state)?;
+        writeln!(f, "{}", self.genotype)
+    }
+}
+
+impl fmt::Display for MapElitesConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "map_elites_config:")?;
+        writeln!(f, "  variant: {:?}", self.variant)?;
+        writeln!(f, "  grid: {:?}", self.grid.dimensions)?;
+        writeln!(
+            f,
+            "  initial_population_size: {:?}",
+            self.initial_population_size
+        )?;
+        writeln!(f, "  batch_size: {:?}", self.batch_size)?;
+        writeln!(f, "  number_of_mutations: {:?}", self.number_of_mutations)?;
+        writeln!(f, "  crossover_rate: {:?}", self.crossover_rate)?;
+        writeln!(
+            f,
+            "  max_stale_generations: {:?}",
+            self.max_stale_generations
+        )?;
+        writeln!(f, "  max_generations: {:?}", self.max_generations)?;
+        writeln!(f, "  valid_fitness_score: {:?}", self.valid_fitness_score)?;
+        writeln!(f, "  target_fitness_score: {:?}", self.target_fitness_score)?;
+        writeln!(f, "  fitness_ordering: {:?}", self.fitness_ordering)?;
+        writeln!(f, "  par_fitness: {:?}", self.par_fitness)
+    }
+}
+
+impl<G: EvolveGenotype> fmt::Display for MapElitesState<G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "map_elites_state:")?;
+        writeln!(f, "  current iteration: {:?}", self.current_iteration)?;
+        writeln!(f, "  current generation: {:?}", self.current_generation)?;
+        writeln!(f, "  stale generations: {:?}", self.stale_generations)?;
+        writeln!(f, "  archive size: {:?}", self.archive.size())?;
+        writeln!(f, "  archive coverage: {:?}", self.archive.coverage())?;
+        writeln!(f, "  archive qd score: {:?}", self.archive.qd_score())?;
+        writeln!(f, "  best fitness score: {:?}", self.best_fitness_score())
+    }
+}
@@ -0,0 +1,181 @@
+use crate::allele::Allele;
+use crate::chromosome::Chromosome;
+use crate::fitness::{FitnessOrdering, FitnessValue};
+use std::ops::RangeInclusive;
+
+/// The grid of behaviour space cells. Each dimension of the behaviour descriptor is binned
+/// uniformly over its range into the given number of bins. Descriptor values outside of the range
+/// are clamped to the outer bins.
+#[derive(Clone, Debug, Default)]
+pub struct Grid {
+    pub dimensions: Vec<(RangeInclusive<f32>, usize)>,
+}
+
+impl Grid {
+    pub fn new(dimensions: Vec<(RangeInclusive<f32>, usize)>) -> Self {
+        Self { dimensions }
+    }
+    pub fn number_of_cells(&self) -> usize {
+        if self.dimensions.is_empty() {
+            0
+        } else {
+            self.dimensions.iter().map(|(_, bins)| *bins).product()
+        }
+    }
+    /// Mixed radix cell index, with the first dimension as most significant. Returns None if the
+    /// descriptor does not match the number of dimensions or contains NaN values.
+    pub fn cell_index(&self, descriptor: &[f32]) -> Option<usize> {
+        if descriptor.len() != self.dimensions.len() {
+            return None;
+        }
+        let mut cell_index = 0;
+        for ((range, bins), value) in self.dimensions.iter().zip(descriptor.iter()) {
+            if value.is_nan() {
+                return None;
+            }
+            let width = range.end() - range.start();
+            let bin = if width > 0.0 {
+                let fraction = (value - range.start()) / width;
+                ((fraction * *bins as f32).floor().max(0.0) as usize).min(bins - 1)
+            } else {
+                0
+            };
+            cell_index = cell_index * bins + bin;
+        }
+        Some(cell_index)
+    }
+    /// Inverse of cell_index, returns the bin per dimension
+    pub fn cell_coordinates(&self, mut cell_index: usize) -> Vec<usize> {
+        let mut coordinates = vec![0; self.dimensions.len()];
+        for (coordinate, (_, bins)) in coordinates.iter_mut().zip(self.dimensions.iter()).rev() {
+            *coordinate = cell_index % bins;
+            cell_index /= bins;
+        }
+        coordinates
+    }
+    pub fn is_valid(&self) -> bool {
+        !self.dimensions.is_empty()
+            && self
+                .dimensions
+                .iter()
+                .all(|(range, bins)| *bins > 0 && range.start() <= range.end())
+    }
+}
+
+/// The best chromosome found for a single cell of the grid
+#[derive(Clone, Debug)]
+pub struct Elite<T: Allele> {
+    pub cell_index: usize,
+    pub descriptor: Vec<f32>,
+    pub chromosome: Chromosome<T>,
+}
+
+/// The MAP-Elites archive, keeping the best chromosome (elite) per grid cell.
+/// Occupied cells are tracked in insertion order, for deterministic random parent sampling.
+#[derive(Clone, Debug)]
+pub struct Archive<T: Allele> {
+    pub cells: Vec<Option<Elite<T>>>,
+    pub occupied_cell_indices: Vec<usize>,
+}
+
+impl<T: Allele> Archive<T> {
+    pub fn new(number_of_cells: usize) -> Self {
+        Self {
+            cells: (0..number_of_cells).map(|_| None).collect(),
+            occupied_cell_indices: vec![],
+        }
+    }
+    pub fn number_of_cells(&self) -> usize {
+        self.cells.len()
+    }
+    /// Number of occupied cells
+    pub fn size(&self) -> usize {
+        self.occupied_cell_indices.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.occupied_cell_indices.is_empty()
+    }
+    /// Fraction of occupied cells
+    pub fn coverage(&self) -> f32 {
+        if self.cells.is_empty() {
+            0.0
+        } else {
+            self.size() as f32 / self.number_of_cells() as f32
+        }
+    }
+    /// Sum of the elite fitness scores. Only meaningful for non-negative fitness scores when
+    /// maximizing, as in the original MAP-Elites formulation
+    pub fn qd_score(&self) -> FitnessValue {
+        self.elites()
+            .filter_map(|elite| elite.chromosome.fitness_score())
+            .sum()
+    }
+    pub fn get(&self, cell_index: usize) -> Option<&Elite<T>> {
+        self.cells.get(cell_index).and_then(|cell| cell.as_ref())
+    }
+    /// Elites in insertion order of their cells
+    pub fn elites(&self) -> impl Iterator<Item = &Elite<T>> {
+        self.occupied_cell_indices
+            .iter()
+            .filter_map(|cell_index| self.get(*cell_index))
+    }
+    /// Insert the chromosome if the cell is empty or the chromosome is better than the current
+    /// elite. Chromosomes without fitness score are never inserted.
+    /// Returns (inserted, improved), like [is_better_chromosome](crate::strategy::StrategyState::is_better_chromosome),
+    /// so a replacement with equal fitness can be distinguished from an improvement.
+    pub fn insert(
+        &mut self,
+        cell_index: usize,
+        descriptor: Vec<f32>,
+        chromosome: &Chromosome<T>,
+        fitness_ordering: FitnessOrdering,
+        replace_on_equal_fitness: bool,
+    ) -> (bool, bool) {
+        let contending_fitness_score = match chromosome.fitness_score() {
+            Some(fitness_score) => fitness_score,
+            None => return (false, false),
+        };
+        let cell = match self.cells.get_mut(cell_index) {
+            Some(cell) => cell,
+            None => return (false, false),
+        };
+        let result = match cell
+            .as_ref()
+            .and_then(|elite| elite.chromosome.fitness_score())
+        {
+            None => (true, true),
+            Some(current_fitness_score) => {
+                let improved = match fitness_ordering {
+                    FitnessOrdering::Maximize => contending_fitness_score > current_fitness_score,
+                    FitnessOrdering::Minimize => contending_fitness_score < current_fitness_score,
+                };
+                if improved {
+                    (true, true)
+                } else if replace_on_equal_fitness
+                    && contending_fitness_score == current_fitness_score
+                {
+                    (true, false)
+                } else {
+                    (false, false)
+                }
+            }
+        };
+        if result.0 {
+            match cell {
+                Some(elite) => {
+                    elite.descriptor = descriptor;
+                    elite.chromosome.copy_from(chromosome);
+                }
+                None => {
+                    *cell = Some(Elite {
+                        cell_index,
+                        descriptor,
+                        chromosome: chromosome.clone(),
+                    });
+                    self.occupied_cell_indices.push(cell_index);
+                }
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,313 @@
+use super::{BehaviourDescriptor, MapElites, MapElitesCrossover, MapElitesGrid, MapElitesVariant};
+pub use crate::errors::TryFromStrategyBuilderError as TryFromBuilderError;
+use crate::fitness::{Fitness, FitnessCache, FitnessOrdering, FitnessValue};
+use crate::genotype::{EvolveGenotype, SupportsGeneCrossover, SupportsPointCrossover};
+use crate::strategy::Strategy;
+pub use crate::strategy::{StrategyReporter, StrategyReporterNoop};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+/// The builder for an MapElites struct.
+#[derive(Clone, Debug)]
+pub struct Builder<
+    G: EvolveGenotype,
+    F: Fitness<Genotype = G>,
+    D: BehaviourDescriptor<Genotype = G>,
+    SR: StrategyReporter<Genotype = G>,
+> {
+    pub genotype: Option<G>,
+    pub variant: Option<MapElitesVariant>,
+    pub fitness: Option<F>,
+    pub descriptor: Option<D>,
+    pub grid: Option<MapElitesGrid>,
+    pub fitness_ordering: FitnessOrdering,
+    pub fitness_cache: Option<FitnessCache>,
+    pub par_fitness: bool,
+    pub initial_population_size: Option<usize>,
+    pub batch_size: usize,
+    pub number_of_mutations: usize,
+    pub crossover: Option<MapElitesCrossover<G>>,
+    pub crossover_rate: f32,
+    pub max_stale_generations: Option<usize>,
+    pub max_generations: Option<usize>,
+    pub target_fitness_score: Option<FitnessValue>,
+    pub valid_fitness_score: Option<FitnessValue>,
+    pub replace_on_equal_fitness: bool,
+    pub reporter: SR,
+    pub rng_seed: Option<u64>,
+}
+
+impl<G: EvolveGenotype, F: Fitness<Genotype = G>, D: BehaviourDescriptor<Genotype = G>> Default
+    for Builder<G, F, D, StrategyReporterNoop<G>>
+{
+    fn default() -> Self {
+        Self {
+            genotype: None,
+            variant: None,
+            fitness: None,
+            descriptor: None,
+            grid: None,
+            fitness_ordering: FitnessOrdering::Maximize,
+            fitness_cache: None,
+            par_fitness: false,
+            initial_population_size: None,
+            batch_size: 100,
+            number_of_mutations: 1,
+            crossover: None,
+            crossover_rate: 0.0,
+            max_stale_generations: None,
+            max_generations: None,
+            target_fitness_score: None,
+            valid_fitness_score: None,
+            replace_on_equal_fitness: true,
+            reporter: StrategyReporterNoop::new(),
+            rng_seed: None,
+        }
+    }
+}
+impl<G: EvolveGenotype, F: Fitness<Genotype = G>, D: BehaviourDescriptor<Genotype = G>>
+    Builder<G, F, D, StrategyReporterNoop<G>>
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<
+        G: EvolveGenotype,
+        F: Fitness<Genotype = G>,
+        D: BehaviourDescriptor<Genotype = G>,
+        SR: StrategyReporter<Genotype = G>,
+    > Builder<G, F, D, SR>
+{
+    pub fn build(self) -> Result<MapElites<G, F, D, SR>, TryFromBuilderError> {
+        self.try_into()
+    }
+    pub fn with_genotype(mut self, genotype: G) -> Self {
+        self.genotype = Some(genotype);
+        self
+    }
+    pub fn with_variant(mut self, variant: MapElitesVariant) -> Self {
+        self.variant = Some(variant);
+        self
+    }
+    pub fn with_fitness_ordering(mut self, fitness_ordering: FitnessOrdering) -> Self {
+        self.fitness_ordering = fitness_ordering;
+        self
+    }
+    /// Only works when genes_hash is stored on chromosome, as this is the cache key.
+    /// Silently ignore cache_size of zero
+    pub fn with_fitness_cache(mut self, fitness_cache_size: usize) -> Self {
+        match FitnessCache::try_new_sharded(
+            fitness_cache_size,
+            FitnessCache::DEFAULT_NUMBER_OF_SHARDS,
+        ) {
+            Ok(cache) => self.fitness_cache = Some(cache),
+            Err(_error) => (),
+        }
+        self
+    }
+    /// Use a prebuilt (and possibly shared) cache, e.g. with another backend
+    /// ([FitnessCache::new_unbounded], [FitnessCache::try_new_file]) or with genes in the key
+    /// ([FitnessCache::with_genes_in_key]). Only works when genes_hash is stored on chromosome.
+    pub fn with_shared_fitness_cache(mut self, fitness_cache: FitnessCache) -> Self {
+        self.fitness_cache = Some(fitness_cache);
+        self
+    }
+    pub fn with_par_fitness(mut self, par_fitness: bool) -> Self {
+        self.par_fitness = par_fitness;
+        self
+    }
+    pub fn with_fitness(mut self, fitness: F) -> Self {
+        self.fitness = Some(fitness);
+        self
+    }
+    pub fn with_descriptor(mut self, descriptor: D) -> Self {
+        self.descriptor = Some(descriptor);
+        self
+    }
+    pub fn with_grid(mut self, grid: MapElitesGrid) -> Self {
+        self.grid = Some(grid);
+        self
+    }
+    /// Number of random chromosomes to seed the archive with, defaults to the batch_size
+    pub fn with_initial_population_size(mut self, initial_population_size: usize) -> Self {
+        self.initial_population_size = Some(initial_population_size);
+        self
+    }
+    /// Number of offspring per generation, defaults to 100
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+    /// Number of gene mutations per offspring, defaults to 1
+    pub fn with_number_of_mutations(mut self, number_of_mutations: usize) -> Self {
+        self.number_of_mutations = number_of_mutations;
+        self
+    }
+    pub fn with_max_stale_generations(mut self, max_stale_generations: usize) -> Self {
+        self.max_stale_generations = Some(max_stale_generations);
+        self
+    }
+    pub fn with_max_stale_generations_option(
+        mut self,
+        max_stale_generations_option: Option<usize>,
+    ) -> Self {
+        self.max_stale_generations = max_stale_generations_option;
+        self
+    }
+    pub fn with_max_generations(mut self, max_generations: usize) -> Self {
+        self.max_generations = Some(max_generations);
+        self
+    }
+    pub fn with_max_generations_option(mut self, max_generations_option: Option<usize>) -> Self {
+        self.max_generations = max_generations_option;
+        self
+    }
+    pub fn with_target_fitness_score(mut self, target_fitness_score: FitnessValue) -> Self {
+        self.target_fitness_score = Some(target_fitness_score);
+        self
+    }
+    pub fn with_target_fitness_score_option(
+        mut self,
+        target_fitness_score_option: Option<FitnessValue>,
+    ) -> Self {
+        self.target_fitness_score = target_fitness_score_option;
+        self
+    }
+    pub fn with_valid_fitness_score(mut self, valid_fitness_score: FitnessValue) -> Self {
+        self.valid_fitness_score = Some(valid_fitness_score);
+        self
+    }
+    pub fn with_valid_fitness_score_option(
+        mut self,
+        valid_fitness_score_option: Option<FitnessValue>,
+    ) -> Self {
+        self.valid_fitness_score = valid_fitness_score_option;
+        self
+    }
+    /// Replace the elite of a cell by offspring with equal fitness, defaults to true
+    pub fn with_replace_on_equal_fitness(mut self, replace_on_equal_fitness: bool) -> Self {
+        self.replace_on_equal_fitness = replace_on_equal_fitness;
+        self
+    }
+    pub fn with_reporter<SR2: StrategyReporter<Genotype = G>>(
+        self,
+        reporter: SR2,
+    ) -> Builder<G, F, D, SR2> {
+        Builder {
+            genotype: self.genotype,
+            variant: self.variant,
+            fitness: self.fitness,
+            descriptor: self.descriptor,
+            grid: self.grid,
+            fitness_ordering: self.fitness_ordering,
+            fitness_cache: self.fitness_cache,
+            par_fitness: self.par_fitness,
+            initial_population_size: self.initial_population_size,
+            batch_size: self.batch_size,
+            number_of_mutations: self.number_of_mutations,
+            crossover: self.crossover,
+            crossover_rate: self.crossover_rate,
+            max_stale_generations: self.max_stale_generations,
+            max_generations: self.max_generations,
+            target_fitness_score: self.target_fitness_score,
+            valid_fitness_score: self.valid_fitness_score,
+            replace_on_equal_fitness: self.replace_on_equal_fitness,
+            reporter,
+            rng_seed: self.rng_seed,
+        }
+    }
+    /// Add a reporter next to the existing one (combined as a tuple), every hook is forwarded to
+    /// the existing reporter first and then to the additional reporter
+    pub fn with_additional_reporter<SR2: StrategyReporter<Genotype = G>>(
+        self,
+        reporter: SR2,
+    ) -> Builder<G, F, D, (SR, SR2)> {
+        Builder {
+            genotype: self.genotype,
+            variant: self.variant,
+            fitness: self.fitness,
+            descriptor: self.descriptor,
+            grid: self.grid,
+            fitness_ordering: self.fitness_ordering,
+            fitness_cache: self.fitness_cache,
+            par_fitness: self.par_fitness,
+            initial_population_size: self.initial_population_size,
+            batch_size: self.batch_size,
+            number_of_mutations: self.number_of_mutations,
+            crossover: self.crossover,
+            crossover_rate: self.crossover_rate,
+            max_stale_generations: self.max_stale_generations,
+            max_generations: self.max_generations,
+            target_fitness_score: self.target_fitness_score,
+            valid_fitness_score: self.valid_fitness_score,
+            replace_on_equal_fitness: self.replace_on_equal_fitness,
+            reporter: (self.reporter, reporter),
+            rng_seed: self.rng_seed,
+        }
+    }
+    pub fn with_rng_seed_from_u64(mut self, rng_seed: u64) -> Self {
+        self.rng_seed = Some(rng_seed);
+        self
+    }
+    pub fn with_rng_seed_from_u64_option(mut self, rng_seed_option: Option<u64>) -> Self {
+        self.rng_seed = rng_seed_option;
+        self
+    }
+}
+
+impl<
+        G: EvolveGenotype + SupportsPointCrossover,
+        F: Fitness<Genotype = G>,
+        D: BehaviourDescriptor<Genotype = G>,
+        SR: StrategyReporter<Genotype = G>,
+    > Builder<G, F, D, SR>
+{
+    /// Crossover the sampled elite with another sampled elite (single point) with the given rate,
+    /// before mutation. Only available for genotypes supporting point crossover.
+    pub fn with_point_crossover(mut self, crossover_rate: f32) -> Self {
+        self.crossover =
+            Some(<G as SupportsPointCrossover>::crossover_chromosome_points::<SmallRng>);
+        self.crossover_rate = crossover_rate;
+        self
+    }
+}
+
+impl<
+        G: EvolveGenotype + SupportsGeneCrossover,
+        F: Fitness<Genotype = G>,
+        D: BehaviourDescriptor<Genotype = G>,
+        SR: StrategyReporter<Genotype = G>,
+    > Builder<G, F, D, SR>
+{
+    /// Crossover the sampled elite with another sampled elite (single gene) with the given rate,
+    /// before mutation. Only available for genotypes supporting gene crossover.
+    pub fn with_gene_crossover(mut self, crossover_rate: f32) -> Self {
+        self.crossover = Some(<G as SupportsGeneCrossover>::crossover_chromosome_genes::<SmallRng>);
+        self.crossover_rate = crossover_rate;
+        self
+    }
+}
+
+impl<
+        G: EvolveGenotype,
+        F: Fitness<Genotype = G>,
+        D: BehaviourDescriptor<Genotype = G>,
+        SR: StrategyReporter<Genotype = G>,
+    > Builder<G, F, D, SR>
+{
+    pub fn rng(&self) -> SmallRng {
+        if let Some(seed) = self.rng_seed {
+            SmallRng::seed_from_u64(seed)
+        } else {
+            // SmallRng::from_entropy()
+            SmallRng::from_rng(rand::thread_rng()).unwrap()
+        }
+    }
+    pub fn call(self) -> Result<MapElites<G, F, D, SR>, TryFromBuilderError> {
+        let mut map_elites: MapElites<G, F, D, SR> = self.try_into()?;
+        map_elites.call();
+        Ok(map_elites)
+    }
+}
@@ -0,0 +1,31 @@
+#[doc(no_inline)]
+pub use crate::chromosome::{Chromosome, GenesHash};
+#[doc(no_inline)]
+pub use crate::fitness::{
+    fitness_value, Fitness, FitnessCache, FitnessChromosome, FitnessGenes, FitnessGenotype,
+    FitnessOrdering, FitnessPopulation, FitnessValue,
+};
+#[doc(no_inline)]
+pub use crate::genotype::{
+    Allele, AlleleScale, BinaryGenotype, BitGenotype, CompositeGenotype, EvolveGenotype,
+    GeneConstraint, Genotype, GenotypeBuilder, Grammar, GrammaticalGenotype, GridGenotype,
+    LinearBound, ListGenotype, MultiListGenotype, MultiRangeGenotype, MultiUniqueGenotype,
+    MutationType, PartialPermutationGenotype, RangeAllele, RangeGenotype, SubsetGenotype,
+    SupportsGeneCrossover, SupportsPointCrossover, TreeGenotype, TryFromGenotypeBuilderError,
+    UniqueGenotype, VariableListGenotype,
+};
+#[doc(no_inline)]
+pub use crate::impl_allele;
+#[doc(no_inline)]
+pub use crate::strategy::map_elites::{
+    BehaviourDescriptor, MapElites, MapElitesArchive, MapElitesBuilder, MapElitesConfig,
+    MapElitesElite, MapElitesGrid, MapElitesReporterDuration, MapElitesReporterLog,
+    MapElitesReporterNoop, MapElitesReporterRecorder, MapElitesReporterSimple, MapElitesState,
+    MapElitesVariant, TryFromMapElitesBuilderError,
+};
+#[doc(no_inline)]
+pub use crate::strategy::{
+    Strategy, StrategyConfig, StrategyReporter, StrategyReporterDuration, StrategyReporterLog,
+    StrategyReporterNoop, StrategyReporterRecord, StrategyReporterRecorder, StrategyReporterSimple,
+    StrategyState, TryFromStrategyBuilderError, STRATEGY_ACTIONS,
+};
@@ -0,0 +1,880 @@
+//! A solution strategy for finding the best chromosome in a continuous search space, by moving a
+//! swarm of particles towards their own and their neighbours' best positions
+mod builder;
+pub mod prelude;
+mod reporter;
+
+pub use self::builder::{
+    Builder as ParticleSwarmBuilder, TryFromBuilderError as TryFromParticleSwarmBuilderError,
+};
+
+use super::{
+    Strategy, StrategyAction, StrategyConfig, StrategyReporter, StrategyReporterNoop,
+    StrategyState, StrategyVariant,
+};
+use crate::chromosome::{Chromosome, Genes};
+use crate::fitness::{Fitness, FitnessCache, FitnessOrdering, FitnessValue};
+use crate::genotype::ParticleSwarmGenotype;
+use crate::population::Population;
+use rand::rngs::SmallRng;
+use rand::Rng;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+use thread_local::ThreadLocal;
+
+pub use self::reporter::Simple as ParticleSwarmReporterSimple;
+pub use crate::strategy::reporter::Duration as ParticleSwarmReporterDuration;
+pub use crate::strategy::reporter::Log as ParticleSwarmReporterLog;
+pub use crate::strategy::reporter::Noop as ParticleSwarmReporterNoop;
+pub use crate::strategy::reporter::Recorder as ParticleSwarmReporterRecorder;
+
+/// The neighbourhood topology, which determines the social attraction of a particle
+#[derive(Copy, Clone, Debug, Default)]
+pub enum ParticleSwarmVariant {
+    /// All particles are attracted to the best position of the whole swarm
+    #[default]
+    Global,
+    /// Each particle is attracted to the best position of itself and its two direct neighbours in
+    /// the swarm (wrapping around), which converges slower but explores more
+    Ring,
+}
+
+/// The schedule of the velocity update. The attractions are
+/// `cognitive_weight * r1 * (personal_best - position) + social_weight * r2 * (neighbourhood_best - position)`
+/// with r1 and r2 uniformly sampled from [0, 1) per gene.
+#[derive(Copy, Clone, Debug)]
+pub enum ParticleSwarmSchedule {
+    /// Constant inertia weight: `velocity = inertia * velocity + attractions`
+    Inertia(f32),
+    /// Inertia weight decreasing linearly from the first to the second value over the
+    /// max_generations (requires the max_generations ending condition)
+    LinearInertia(f32, f32),
+    /// Clerc-Kennedy constriction: `velocity = factor * (velocity + attractions)`, with the factor
+    /// derived from the sum of the cognitive and social weights (which must exceed 4.0, e.g. 2.05
+    /// each)
+    Constriction,
+}
+impl Default for ParticleSwarmSchedule {
+    fn default() -> Self {
+        Self::Inertia(0.7298)
+    }
+}
+
+/// The ParticleSwarm strategy is a Particle Swarm Optimization (PSO). Each chromosome in the
+/// population is a particle with a position (the genes), a velocity and a personal best position.
+/// Each generation the velocities are updated with attractions towards the personal best position
+/// and the best position in the neighbourhood (see [ParticleSwarmVariant] for the topologies),
+/// after which the particles move. The velocity update is determined by the
+/// [ParticleSwarmSchedule] (inertia weight, linearly decreasing inertia weight or constriction).
+///
+/// Velocities are clamped per gene to the max_velocity_rate of the allele range width. Positions
+/// are clamped to the allele range, a particle hitting a bound loses its velocity in that
+/// dimension. The genes are set from the positions, rounding the genes with
+/// [MutationType::Discrete](crate::genotype::MutationType::Discrete) (and integer alleles) to the
+/// nearest value, while the particle keeps its continuous position.
+///
+/// Only genotypes with a numeric allele range per gene allow particle swarms, see
+/// [ParticleSwarmGenotype]: [RangeGenotype](crate::genotype::RangeGenotype) and
+/// [MultiRangeGenotype](crate::genotype::MultiRangeGenotype).
+/// The initial positions are constructed by the genotype (random or from the seed genes), the
+/// initial velocities are uniformly sampled within the velocity bounds. The particle state remains
+/// available in [ParticleSwarmState] after the run.
+///
+/// The ending conditions are one or more of the following:
+/// * target_fitness_score: when the ultimate goal in terms of fitness score is known and reached
+/// * max_stale_generations: when the ultimate goal in terms of fitness score is unknown and one
+///   depends on some convergion threshold, or one wants a duration limitation next to the
+///   target_fitness_score
+/// * max_generations: when the ultimate goal in terms of fitness score is unknown and there is a effort constraint
+///
+/// There are reporting hooks in the loop receiving the [ParticleSwarmState], which can by handled by an
+/// [StrategyReporter] (e.g. [ParticleSwarmReporterDuration], [ParticleSwarmReporterSimple]). But you are encouraged to
+/// roll your own, see [StrategyReporter].
+///
+/// Below is the exact order of actions and hooks
+/// * [reporter](crate::strategy::reporter) on_enter hook
+/// * setup (construct, fitness and personal bests of initial swarm)
+/// * [reporter](crate::strategy::reporter) on_start hook
+/// * loop while not finished
+///   * increment generation
+///   * update velocities and move particles
+///   * [fitness](crate::fitness) calculation
+///   * update personal bests
+///   * update best chromosome
+///   * [reporter](crate::strategy::reporter) on_generation_complete hook
+///   * check ending conditions
+/// * [reporter](crate::strategy::reporter) on_finish hook
+/// * cleanup
+/// * [reporter](crate::strategy::reporter) on_exit hook
+///
+/// From the [ParticleSwarmBuilder] level, there are several calling mechanisms:
+/// * [call](ParticleSwarmBuilder::call): this runs a single [ParticleSwarm] strategy
+/// * [call_repeatedly](ParticleSwarmBuilder::call_repeatedly): this runs multiple independent
+///   [ParticleSwarm] strategies and returns the best one (or short circuits when the
+///   target_fitness_score is reached)
+/// * [call_par_repeatedly](ParticleSwarmBuilder::call_par_repeatedly): this runs multiple
+///   independent [ParticleSwarm] strategies in parallel and returns the best one (or short circuits
+///   when the target_fitness_score is reached). This is separate and independent from the
+///   `with_par_fitness()` flag on the builder, which determines multithreading of the fitness
+///   calculation inside the [ParticleSwarm] strategy. Both can be combined.
+///
+/// All multithreading mechanisms are implemented using [rayon::iter] and [std::sync::mpsc].
+///
+/// See [ParticleSwarmBuilder] for initialization options.
+///
+/// Example:
+/// ```
+/// use genetic_algorithm::strategy::particle_swarm::prelude::*;
+///
+/// // minimize the sum of squares, with a precision of 1e-3
+/// #[derive(Clone, Debug)]
+/// pub struct Sphere;
+/// impl Fitness for Sphere {
+///     type Genotype = RangeGenotype<f32>;
+///     fn calculate_for_chromosome(
+///         &mut self,
+///         chromosome: &FitnessChromosome<Self>,
+///         _genotype: &FitnessGenotype<Self>,
+///     ) -> Option<FitnessValue> {
+///         let sum_of_squares: f32 = chromosome.genes.iter().map(|gene| gene * gene).sum();
+///         Some((sum_of_squares / 1e-3) as FitnessValue)
+///     }
+/// }
+///
+/// // the search space
+/// let genotype = RangeGenotype::builder()
+///     .with_genes_size(10)
+///     .with_allele_range(-5.0..=5.0)
+///     .build()
+///     .unwrap();
+///
+/// // the search strategy
+/// let particle_swarm = ParticleSwarm::builder()
+///     .with_genotype(genotype)
+///     .with_variant(ParticleSwarmVariant::Global)   // optional, defaults to ParticleSwarmVariant::Global
+///     .with_schedule(ParticleSwarmSchedule::Inertia(0.7298)) // optional, defaults to ParticleSwarmSchedule::Inertia(0.7298)
+///     .with_cognitive_weight(1.49618)               // optional, defaults to 1.49618
+///     .with_social_weight(1.49618)                  // optional, defaults to 1.49618
+///     .with_max_velocity_rate(0.5)                  // optional, defaults to 0.5 of the allele range width
+///     .with_target_population_size(30)              // the number of particles
+///     .with_fitness(Sphere)
+///     .with_fitness_ordering(FitnessOrdering::Minimize)
+///     .with_par_fitness(true)                       // optional, defaults to false, use parallel fitness calculation
+///     .with_target_fitness_score(0)                 // ending condition if the sum of squares is below 1e-3
+///     .with_max_stale_generations(100)              // stop searching if there is no improvement in fitness score for 100 generations
+///     .with_reporter(ParticleSwarmReporterSimple::new(100)) // optional, report every 100 generations
+///     .with_rng_seed_from_u64(0)                    // for testing with deterministic results
+///     .call()
+///     .unwrap();
+///
+/// // it's all about the best genes after all
+/// let (best_genes, best_fitness_score) = particle_swarm.best_genes_and_fitness_score().unwrap();
+/// assert_eq!(best_fitness_score, 0);
+/// assert!(best_genes.iter().all(|gene| gene.abs() < 0.1));
+/// ```
+pub struct ParticleSwarm<
+    G: ParticleSwarmGenotype,
+    F: Fitness<Genotype = G>,
+    SR: StrategyReporter<Genotype = G>,
+> {
+    pub genotype: G,
+    pub fitness: F,
+    pub config: ParticleSwarmConfig,
+    pub state: ParticleSwarmState<G>,
+    pub reporter: SR,
+    pub rng: SmallRng,
+}
+
+pub struct ParticleSwarmConfig {
+    pub variant: ParticleSwarmVariant,
+    pub fitness_ordering: FitnessOrdering,
+    pub par_fitness: bool,
+    pub replace_on_equal_fitness: bool,
+    pub target_population_size: usize,
+    pub schedule: ParticleSwarmSchedule,
+    pub cognitive_weight: f32,
+    pub social_weight: f32,
+    pub max_velocity_rate: f32,
+
+    pub target_fitness_score: Option<FitnessValue>,
+    pub max_stale_generations: Option<usize>,
+    pub max_generations: Option<usize>,
+    pub valid_fitness_score: Option<FitnessValue>,
+    pub fitness_cache: Option<FitnessCache>,
+}
+
+/// Stores the state of the ParticleSwarm strategy. The particle vectors are aligned with the
+/// population.
+pub struct ParticleSwarmState<G: ParticleSwarmGenotype> {
+    pub current_iteration: usize,
+    pub current_generation: usize,
+    pub stale_generations: usize,
+    pub best_generation: usize,
+    pub best_fitness_score: Option<FitnessValue>,
+    pub best_chromosome: Option<Chromosome<G::Allele>>,
+    pub chromosome: Option<Chromosome<G::Allele>>,
+    pub population: Population<G::Allele>,
+    /// The (start, end) bounds of the positions per gene
+    pub position_bounds: Vec<(f64, f64)>,
+    /// The continuous positions of the particles (the genes are set from these)
+    pub positions: Vec<Vec<f64>>,
+    pub velocities: Vec<Vec<f64>>,
+    pub personal_best_positions: Vec<Vec<f64>>,
+    pub personal_best_fitness_scores: Vec<Option<FitnessValue>>,
+    pub durations: HashMap<StrategyAction, Duration>,
+}
+
+impl<G: ParticleSwarmGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genotype = G>>
+    Strategy<G> for ParticleSwarm<G, F, SR>
+{
+    fn call(&mut self) {
+        let now = Instant::now();
+        self.reporter
+            .on_enter(&self.genotype, &self.state, &self.config);
+        let mut fitness_thread_local: Option<ThreadLocal<RefCell<F>>> = None;
+        if self.config.par_fitness {
+            fitness_thread_local = Some(ThreadLocal::new());
+        }
+
+        self.setup(fitness_thread_local.as_ref());
+        self.reporter
+            .on_start(&self.genotype, &self.state, &self.config);
+        while !self.is_finished() {
+            self.state.increment_generation();
+            self.state
+                .move_particles(&self.genotype, &self.config, &mut self.rng);
+            self.fitness.call_for_state_population(
+                &self.genotype,
+                &mut self.state,
+                &self.config,
+                fitness_thread_local.as_ref(),
+            );
+            self.state.update_personal_bests(&self.config);
+            self.state.update_best_chromosome_from_state_population(
+                &self.genotype,
+                &self.config,
+                &mut self.reporter,
+            );
+            self.reporter
+                .on_generation_complete(&self.genotype, &self.state, &self.config);
+        }
+        self.reporter
+            .on_finish(&self.genotype, &self.state, &self.config);
+        self.cleanup(fitness_thread_local.as_mut());
+        self.state.close_duration(now.elapsed());
+        self.reporter
+            .on_exit(&self.genotype, &self.state, &self.config);
+    }
+    fn best_generation(&self) -> usize {
+        self.state.best_generation
+    }
+    fn best_fitness_score(&self) -> Option<FitnessValue> {
+        self.state.best_fitness_score()
+    }
+    fn best_genes(&self) -> Option<Genes<G::Allele>> {
+        self.state
+            .best_chromosome
+            .as_ref()
+            .map(|c| c.genes().clone())
+    }
+    fn flush_reporter(&mut self, output: &mut Vec<u8>) {
+        self.reporter.flush(output);
+    }
+}
+impl<G: ParticleSwarmGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genotype = G>>
+    ParticleSwarm<G, F, SR>
+{
+    pub fn best_chromosome(&self) -> Option<Chromosome<G::Allele>> {
+        if let Some(best_genes) = self.best_genes() {
+            let mut chromosome = Chromosome::<G::Allele>::new(best_genes);
+            chromosome.set_fitness_score(self.best_fitness_score());
+            Some(chromosome)
+        } else {
+            None
+        }
+    }
+}
+
+impl<G: ParticleSwarmGenotype, F: Fitness<Genotype = G>>
+    ParticleSwarm<G, F, StrategyReporterNoop<G>>
+{
+    pub fn builder() -> ParticleSwarmBuilder<G, F, StrategyReporterNoop<G>> {
+        ParticleSwarmBuilder::new()
+    }
+}
+impl<G: ParticleSwarmGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genotype = G>>
+    ParticleSwarm<G, F, SR>
+{
+    pub fn setup(&mut self, fitness_thread_local: Option<&ThreadLocal<RefCell<F>>>) {
+        let now = Instant::now();
+        self.state.position_bounds = self.genotype.position_bounds();
+        self.state.population = self
+            .genotype
+            .population_constructor(self.config.target_population_size, &mut self.rng);
+        self.state.positions = self
+            .state
+            .population
+            .chromosomes
+            .iter()
+            .map(|chromosome| self.genotype.genes_to_position(&chromosome.genes))
+            .collect();
+        let max_velocities = self.state.max_velocities(&self.config);
+        self.state.velocities = self
+            .state
+            .positions
+            .iter()
+            .map(|_| {
+                max_velocities
+                    .iter()
+                    .map(|max_velocity| {
+                        if *max_velocity > 0.0 {
+                            self.rng.gen_range(-max_velocity..=*max_velocity)
+                        } else {
+                            0.0
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        self.state
+            .personal_best_positions
+            .clone_from(&self.state.positions);
+        self.state.personal_best_fitness_scores = vec![None; self.state.positions.len()];
+        self.state
+            .add_duration(StrategyAction::SetupAndCleanup, now.elapsed());
+
+        self.fitness.call_for_state_population(
+            &self.genotype,
+            &mut self.state,
+            &self.config,
+            fitness_thread_local,
+        );
+        self.state.update_personal_bests(&self.config);
+        self.state.update_best_chromosome_from_state_population(
+            &self.genotype,
+            &self.config,
+            &mut self.reporter,
+        );
+    }
+    pub fn cleanup(&mut self, fitness_thread_local: Option<&mut ThreadLocal<RefCell<F>>>) {
+        let now = Instant::now();
+        self.state.chromosome.take();
+        self.state.population.chromosomes.clear();
+        if let Some(thread_local) = fitness_thread_local {
+            thread_local.clear();
+        }
+        self.state
+            .add_duration(StrategyAction::SetupAndCleanup, now.elapsed());
+    }
+    fn is_finished(&self) -> bool {
+        self.allow_finished_by_valid_fitness_score()
+            && (self.is_finished_by_max_stale_generations()
+                || self.is_finished_by_max_generations()
+                || self.is_finished_by_target_fitness_score())
+    }
+
+    fn is_finished_by_max_stale_generations(&self) -> bool {
+        if let Some(max_stale_generations) = self.config.max_stale_generations {
+            self.state.stale_generations >= max_stale_generations
+        } else {
+            false
+        }
+    }
+
+    fn is_finished_by_max_generations(&self) -> bool {
+        if let Some(max_generations) = self.config.max_generations {
+            self.state.current_generation >= max_generations
+        } else {
+            false
+        }
+    }
+
+    fn is_finished_by_target_fitness_score(&self) -> bool {
+        if let Some(target_fitness_score) = self.config.target_fitness_score {
+            if let Some(fitness_score) = self.best_fitness_score() {
+                match self.config.fitness_ordering {
+                    FitnessOrdering::Maximize => fitness_score >= target_fitness_score,
+                    FitnessOrdering::Minimize => fitness_score <= target_fitness_score,
+                }
+            } else {
+                false
+            }
+        } else {
+            false
+        }
+    }
+
+    fn allow_finished_by_valid_fitness_score(&self) -> bool {
+        if let Some(valid_fitness_score) = self.config.valid_fitness_score {
+            if let Some(fitness_score) = self.best_fitness_score() {
+                match self.config.fitness_ordering {
+                    FitnessOrdering::Maximize => fitness_score >= valid_fitness_score,
+                    FitnessOrdering::Minimize => fitness_score <= valid_fitness_score,
+                }
+            } else {
+                true
+            }
+        } else {
+            true
+        }
+    }
+}
+
+impl StrategyConfig for ParticleSwarmConfig {
+    fn fitness_ordering(&self) -> FitnessOrdering {
+        self.fitness_ordering
+    }
+    fn fitness_cache(&self) -> Option<&FitnessCache> {
+        self.fitness_cache.as_ref()
+    }
+    fn par_fitness(&self) -> bool {
+        self.par_fitness
+    }
+    fn replace_on_equal_fitness(&self) -> bool {
+        self.replace_on_equal_fitness
+    }
+    fn variant(&self) -> StrategyVariant {
+        StrategyVariant::ParticleSwarm(self.variant)
+    }
+}
+
+impl<G: ParticleSwarmGenotype> StrategyState<G> for ParticleSwarmState<G> {
+    fn chromosome_as_ref(&self) -> &Option<Chromosome<G::Allele>> {
+        &self.chromosome
+    }
+    fn population_as_ref(&self) -> &Population<G::Allele> {
+        &self.population
+    }
+    fn chromosome_as_mut(&mut self) -> &mut Option<Chromosome<G::Allele>> {
+        &mut self.chromosome
+    }
+    fn population_as_mut(&mut self) -> &mut Population<G::Allele> {
+        &mut self.population
+    }
+    fn best_fitness_score(&self) -> Option<FitnessValue> {
+        self.best_fitness_score
+    }
+    fn best_generation(&self) -> usize {
+        self.best_generation
+    }
+    fn current_generation(&self) -> usize {
+        self.current_generation
+    }
+    fn current_iteration(&self) -> usize {
+        self.current_iteration
+    }
+    fn increment_generation(&mut self) {
+        self.current_generation += 1;
+    }
+    fn stale_generations(&self) -> usize {
+        self.stale_generations
+    }
+    fn increment_stale_generations(&mut self) {
+        self.stale_generations += 1;
+    }
+    fn reset_stale_generations(&mut self) {
+        self.stale_generations = 0;
+    }
+    fn scale_generation(&self) -> usize {
+        self.current_generation
+    }
+    fn reset_scale_generation(&mut self) {}
+    fn population_cardinality(&self) -> Option<usize> {
+        None
+    }
+    fn durations(&self) -> &HashMap<StrategyAction, Duration> {
+        &self.durations
+    }
+    fn add_duration(&mut self, action: StrategyAction, duration: Duration) {
+        *self.durations.entry(action).or_default() += duration;
+    }
+    fn total_duration(&self) -> Duration {
+        self.durations.values().sum()
+    }
+    fn best_genes(&self) -> Option<Genes<G::Allele>> {
+        self.best_chromosome.as_ref().map(|c| c.genes().clone())
+    }
+}
+
+impl<G: ParticleSwarmGenotype> ParticleSwarmState<G> {
+    /// The maximum absolute velocity per gene, the max_velocity_rate of the allele range width
+    pub fn max_velocities(&self, config: &ParticleSwarmConfig) -> Vec<f64> {
+        self.position_bounds
+            .iter()
+            .map(|(start, end)| (end - start) * config.max_velocity_rate as f64)
+            .collect()
+    }
+    /// The mean absolute velocity over all particles and genes, a measure of swarm convergence
+    pub fn mean_absolute_velocity(&self) -> f64 {
+        let count = self.velocities.iter().map(Vec::len).sum::<usize>();
+        if count == 0 {
+            0.0
+        } else {
+            self.velocities
+                .iter()
+                .flatten()
+                .map(|v| v.abs())
+                .sum::<f64>()
+                / count as f64
+        }
+    }
+    /// The index of the particle with the best personal best in the neighbourhood of the particle
+    fn neighbourhood_best_index(
+        &self,
+        index: usize,
+        global_best_index: usize,
+        config: &ParticleSwarmConfig,
+    ) -> usize {
+        match config.variant {
+            ParticleSwarmVariant::Global => global_best_index,
+            ParticleSwarmVariant::Ring => {
+                let size = self.personal_best_fitness_scores.len();
+                [(index + size - 1) % size, (index + 1) % size]
+                    .into_iter()
+                    .fold(index, |best_index, neighbour_index| {
+                        if is_better_fitness_score(
+                            self.personal_best_fitness_scores[neighbour_index],
+                            self.personal_best_fitness_scores[best_index],
+                            config.fitness_ordering,
+                        ) {
+                            neighbour_index
+                        } else {
+                            best_index
+                        }
+                    })
+            }
+        }
+    }
+    /// Update the velocities, move the particles within the bounds and set the genes
+    fn move_particles<R: Rng>(&mut self, genotype: &G, config: &ParticleSwarmConfig, rng: &mut R) {
+        let now = Instant::now();
+        let max_velocities = self.max_velocities(config);
+        let cognitive_weight = config.cognitive_weight as f64;
+        let social_weight = config.social_weight as f64;
+        let (inertia, constriction) = match config.schedule {
+            ParticleSwarmSchedule::Inertia(inertia) => (inertia as f64, 1.0),
+            ParticleSwarmSchedule::LinearInertia(start, end) => {
+                let progress = config.max_generations.map_or(0.0, |max_generations| {
+                    (self.current_generation as f64 / max_generations.max(1) as f64).min(1.0)
+                });
+                (start as f64 + (end as f64 - start as f64) * progress, 1.0)
+            }
+            ParticleSwarmSchedule::Constriction => (1.0, config.constriction_factor() as f64),
+        };
+        let global_best_index =
+            (0..self.personal_best_fitness_scores.len()).fold(0, |best_index, index| {
+                if is_better_fitness_score(
+                    self.personal_best_fitness_scores[index],
+                    self.personal_best_fitness_scores[best_index],
+                    config.fitness_ordering,
+                ) {
+                    index
+                } else {
+                    best_index
+                }
+            });
+        let neighbourhood_best_indices: Vec<usize> = (0..self.positions.len())
+            .map(|index| self.neighbourhood_best_index(index, global_best_index, config))
+            .collect();
+
+        let genes_hashing = genotype.genes_hashing();
+        for (index, chromosome) in self.population.chromosomes.iter_mut().enumerate() {
+            let position = &mut self.positions[index];
+            let velocity = &mut self.velocities[index];
+            let personal_best_position = &self.personal_best_positions[index];
+            let neighbourhood_best_position =
+                &self.personal_best_positions[neighbourhood_best_indices[index]];
+            for gene_index in 0..position.len() {
+                let attraction = cognitive_weight
+                    * rng.gen::<f64>()
+                    * (personal_best_position[gene_index] - position[gene_index])
+                    + social_weight
+                        * rng.gen::<f64>()
+                        * (neighbourhood_best_position[gene_index] - position[gene_index]);
+                let max_velocity = max_velocities[gene_index];
+                velocity[gene_index] = (constriction
+                    * (inertia * velocity[gene_index] + attraction))
+                    .clamp(-max_velocity, max_velocity);
+
+                let (start, end) = self.position_bounds[gene_index];
+                position[gene_index] += velocity[gene_index];
+                if position[gene_index] < start {
+                    position[gene_index] = start;
+                    velocity[gene_index] = 0.0;
+                } else if position[gene_index] > end {
+                    position[gene_index] = end;
+                    velocity[gene_index] = 0.0;
+                }
+            }
+            genotype.set_genes_from_position(chromosome, position);
+            chromosome.reset_metadata(genes_hashing);
+        }
+        self.add_duration(StrategyAction::Other, now.elapsed());
+    }
+    /// Replace the personal bests of the particles which improved (or equalled, depending on
+    /// replace_on_equal_fitness)
+    fn update_personal_bests(&mut self, config: &ParticleSwarmConfig) {
+        let now = Instant::now();
+        for (index, chromosome) in self.population.chromosomes.iter().enumerate() {
+            let fitness_score = chromosome.fitness_score();
+            let personal_best_fitness_score = self.personal_best_fitness_scores[index];
+            let replace = is_better_fitness_score(
+                fitness_score,
+                personal_best_fitness_score,
+                config.fitness_ordering,
+            ) || (config.replace_on_equal_fitness
+                && fitness_score.is_some()
+                && fitness_score == personal_best_fitness_score);
+            if replace {
+                self.personal_best_fitness_scores[index] = fitness_score;
+                self.personal_best_positions[index].clone_from(&self.positions[index]);
+            }
+        }
+        self.add_duration(StrategyAction::UpdateBestChromosome, now.elapsed());
+    }
+
+    fn update_best_chromosome_from_state_population<SR: StrategyReporter<Genotype = G>>(
+        &mut self,
+        genotype: &G,
+        config: &ParticleSwarmConfig,
+        reporter: &mut SR,
+    ) {
+        let now = Instant::now();
+        if let Some(contending_chromosome) =
+            self.population.best_chromosome(config.fitness_ordering)
+        {
+            match self.is_better_chromosome(
+                contending_chromosome,
+                &config.fitness_ordering,
+                config.replace_on_equal_fitness,
+            ) {
+                (true, true) => {
+                    self.best_generation = self.current_generation;
+                    self.best_fitness_score = contending_chromosome.fitness_score();
+                    self.best_chromosome = Some(contending_chromosome.clone());
+                    reporter.on_new_best_chromosome(genotype, self, config);
+                    self.reset_stale_generations();
+                }
+                (true, false) => {
+                    self.best_chromosome = Some(contending_chromosome.clone());
+                    reporter.on_new_best_chromosome_equal_fitness(genotype, self, config);
+                    self.increment_stale_generations()
+                }
+                _ => self.increment_stale_generations(),
+            }
+        } else {
+            self.increment_stale_generations();
+        }
+        self.add_duration(StrategyAction::UpdateBestChromosome, now.elapsed());
+    }
+}
+
+/// Strictly better, a missing fitness score is never better
+fn is_better_fitness_score(
+    contending: Option<FitnessValue>,
+    current: Option<FitnessValue>,
+    fitness_ordering: FitnessOrdering,
+) -> bool {
+    match (contending, current) {
+        (Some(_), None) => true,
+        (Some(contending), Some(current)) => match fitness_ordering {
+            FitnessOrdering::Maximize => contending > current,
+            FitnessOrdering::Minimize => contending < current,
+        },
+        _ => false,
+    }
+}
+
+impl<G: ParticleSwarmGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genotype = G>>
+    TryFrom<ParticleSwarmBuilder<G, F, SR>> for ParticleSwarm<G, F, SR>
+{
+    type Error = TryFromParticleSwarmBuilderError;
+
+    fn try_from(builder: ParticleSwarmBuilder<G, F, SR>) -> Result<Self, Self::Error> {
+        if builder.genotype.is_none() {
+            Err(TryFromParticleSwarmBuilderError(
+                "ParticleSwarm requires a ParticleSwarmGenotype",
+            ))
+        } else if !builder.genotype.as_ref().unwrap().allows_particle_swarm() {
+            Err(TryFromParticleSwarmBuilderError(
+                "ParticleSwarm requires a genotype with a numeric allele range per gene (RangeGenotype or MultiRangeGenotype without gene_constraints)",
+            ))
+        } else if builder.fitness.is_none() {
+            Err(TryFromParticleSwarmBuilderError(
+                "ParticleSwarm requires a Fitness",
+            ))
+        } else if builder.target_population_size == 0 {
+            Err(TryFromParticleSwarmBuilderError(
+                "ParticleSwarm requires a target_population_size > 0",
+            ))
+        } else if builder.max_velocity_rate <= 0.0 {
+            Err(TryFromParticleSwarmBuilderError(
+                "ParticleSwarm requires a max_velocity_rate > 0.0",
+            ))
+        } else if matches!(builder.schedule, ParticleSwarmSchedule::Constriction)
+            && builder.cognitive_weight + builder.social_weight <= 4.0
+        {
+            Err(TryFromParticleSwarmBuilderError(
+                "ParticleSwarm requires a cognitive_weight + social_weight > 4.0 for the Constriction schedule",
+            ))
+        } else if matches!(builder.schedule, ParticleSwarmSchedule::LinearInertia(_, _))
+            && builder.max_generations.is_none()
+        {
+            Err(TryFromParticleSwarmBuilderError(
+                "ParticleSwarm requires a max_generations for the LinearInertia schedule",
+            ))
+        } else if builder.max_stale_generations.is_none()
+            && builder.max_generations.is_none()
+            && builder.target_fitness_score.is_none()
+        {
+            Err(TryFromParticleSwarmBuilderError(
+                "ParticleSwarm requires at least a max_stale_generations, max_generations or target_fitness_score ending condition",
+            ))
+        } else {
+            let rng = builder.rng();
+            let mut genotype = builder.genotype.unwrap();
+            if builder.fitness_cache.is_none() {
+                genotype.set_genes_hashing(false);
+            }
+            let state = ParticleSwarmState::new(&genotype);
+
+            Ok(Self {
+                genotype,
+                fitness: builder.fitness.unwrap(),
+                config: ParticleSwarmConfig {
+                    variant: builder.variant.unwrap_or_default(),
+                    fitness_ordering: builder.fitness_ordering,
+                    fitness_cache: builder.fitness_cache,
+                    par_fitness: builder.par_fitness,
+                    target_population_size: builder.target_population_size,
+                    schedule: builder.schedule,
+                    cognitive_weight: builder.cognitive_weight,
+                    social_weight: builder.social_weight,
+                    max_velocity_rate: builder.max_velocity_rate,
+                    max_stale_generations: builder.max_stale_generations,
+                    max_generations: builder.max_generations,
+                    target_fitness_score: builder.target_fitness_score,
+                    valid_fitness_score: builder.valid_fitness_score,
+                    replace_on_equal_fitness: builder.replace_on_equal_fitness,
+                },
+                state,
+                reporter: builder.reporter,
+                rng,
+            })
+        }
+    }
+}
+
+impl Default for ParticleSwarmConfig {
+    fn default() -> Self {
+        Self {
+            variant: Default::default(),
+            fitness_ordering: FitnessOrdering::Maximize,
+            fitness_cache: None,
+            par_fitness: false,
+            target_population_size: 100,
+            schedule: Default::default(),
+            cognitive_weight: 1.49618,
+            social_weight: 1.49618,
+            max_velocity_rate: 0.5,
+            max_stale_generations: None,
+            max_generations: None,
+            target_fitness_score: None,
+            valid_fitness_score: None,
+            replace_on_equal_fitness: true,
+        }
+    }
+}
+impl ParticleSwarmConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// The Clerc-Kennedy constriction factor for the sum of the cognitive and social weights (phi),
+    /// `2 / |2 - phi - sqrt(phi^2 - 4 * phi)|`. Returns 1.0 when phi does not exceed 4.0
+    pub fn constriction_factor(&self) -> f32 {
+        let phi = self.cognitive_weight + self.social_weight;
+        if phi > 4.0 {
+            2.0 / (2.0 - phi - (phi * phi - 4.0 * phi).sqrt()).abs()
+        } else {
+            1.0
+        }
+    }
+}
+
+impl<G: ParticleSwarmGenotype> ParticleSwarmState<G> {
+    pub fn new(genotype: &G) -> Self {
+        Self {
+            current_iteration: 0,
+            current_generation: 0,
+            stale_generations: 0,
+            best_generation: 0,
+            best_fitness_score: None,
+            chromosome: None,
+            population: Population::new_empty(genotype.chromosome_recycling()),
+            position_bounds: vec![],
+            positions: vec![],
+            velocities: vec![],
+            personal_best_positions: vec![],
+            personal_best_fitness_scores: vec![],
+            durations: HashMap::new(),
+            best_chromosome: None,
+        }
+    }
+}
+
+impl<G: ParticleSwarmGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genotype = G>>
+    fmt::Display for ParticleSwarm<G, F, SR>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "particle_swarm:")?;
+        writeln!(f, "  fitness: {:?}", self.fitness)?;
+        writeln!(f)?;
+
+        writeln!(f, "{}", self.config)?;
+        writeln!(f, "{}", self.state)?;
+        writeln!(f, "{}", self.genotype)
+    }
+}
+
+impl fmt::Display for ParticleSwarmConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "particle_swarm_config:")?;
+        writeln!(f, "  variant: {:?}", self.variant)?;
+        writeln!(
+            f,
+            "  target_population_size: {}",
+            self.target_population_size
+        )?;
+        writeln!(f, "  schedule: {:?}", self.schedule)?;
+        writeln!(f, "  cognitive_weight: {}", self.cognitive_weight)?;
+        writeln!(f, "  social_weight: {}", self.social_weight)?;
+        writeln!(f, "  max_velocity_rate: {}", self.max_velocity_rate)?;
+        writeln!(
+            f,
+            "  max_stale_generations: {:?}",
+            self.max_stale_generations
+        )?;
+        writeln!(f, "  max_generations: {:?}", self.max_generations)?;
+        writeln!(f, "  valid_fitness_score: {:?}", self.valid_fitness_score)?;
+        writeln!(f, "  target_fitness_score: {:?}", self.target_fitness_score)?;
+        writeln!(f, "  fitness_ordering: {:?}", self.fitness_ordering)?;
+        writeln!(f, "  par_fitness: {:?}", self.par_fitness)
+    }
+}
+
+impl<G: ParticleSwarmGenotype> fmt::Display for ParticleSwarmState<G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "particle_swarm_state:")?;
+        writeln!(f, "  current iteration: {:?}", self.current_iteration)?;
+        writeln!(f, "  current generation: {:?}", self.current_generation)?;
+        writeln!(f, "  stale generations: {:?}", self.stale_generations)?;
+        writeln!(f, "  best fitness score: {:?}", self.best_fitness_score())?;
+        writeln!(
+            f,
+            "  mean absolute velocity: {:.3}",
+            self.mean_absolute_velocity()
+        )
+    }
+}
@@ -12,7 +12,7 @@ use super::{
     StrategyState, StrategyVariant,
 };
 use crate::chromosome::{Chromosome, Genes};
-use crate::fitness::{Fitness, FitnessOrdering, FitnessValue};
+use crate::fitness::{Fitness, FitnessCache, FitnessOrdering, FitnessValue};
 use crate::genotype::PermutateGenotype;
 use crate::population::Population;
 use rayon::prelude::*;
@@ -23,7 +23,9 @@ use std::time::{Duration, Instant};
 
 pub use self::reporter::Simple as PermutateReporterSimple;
 pub use crate::strategy::reporter::Duration as PermutateReporterDuration;
+pub use crate::strategy::reporter::Log as PermutateReporterLog;
 pub use crate::strategy::reporter::Noop as PermutateReporterNoop;
+pub use crate::strategy::reporter::Recorder as PermutateReporterRecorder;
 
 #[derive(Copy, Clone, Debug, Default)]
 pub enum PermutateVariant {
@@ -122,6 +124,7 @@ pub struct PermutateConfig {
     pub fitness_ordering: FitnessOrdering,
     pub par_fitness: bool,
     pub replace_on_equal_fitness: bool,
+    pub fitness_cache: Option<FitnessCache>,
 }
 
 /// Stores the state of the Permutate strategy
@@ -204,7 +207,15 @@ impl<G: PermutateGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genoty
 {
     pub fn setup(&mut self) {
         let now = Instant::now();
-        self.state.chromosome = self.genotype.chromosome_permutations_into_iter(None).next();
+        let genes_hashing = self.genotype.genes_hashing();
+        self.state.chromosome = self
+            .genotype
+            .chromosome_permutations_into_iter(None)
+            .next()
+            .map(|mut chromosome| {
+                chromosome.reset_metadata(genes_hashing);
+                chromosome
+            });
         self.state
             .add_duration(StrategyAction::SetupAndCleanup, now.elapsed());
         self.fitness
@@ -240,10 +251,12 @@ impl<G: PermutateGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genoty
     }
 
     fn call_sequential(&mut self) {
+        let genes_hashing = self.genotype.genes_hashing();
         self.genotype
             .clone()
             .chromosome_permutations_into_iter(self.state.best_chromosome.as_ref())
-            .for_each(|chromosome| {
+            .for_each(|mut chromosome| {
+                chromosome.reset_metadata(genes_hashing);
                 self.state.increment_generation();
                 self.state.chromosome.replace(chromosome);
                 self.fitness.call_for_state_chromosome(
@@ -273,6 +286,7 @@ impl<G: PermutateGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genoty
                     .chromosome_permutations_into_iter(thread_best_chromosome.as_ref())
                     .par_bridge()
                     .for_each_with((sender, fitness), |(sender, fitness), mut chromosome| {
+                        chromosome.reset_metadata(thread_genotype.genes_hashing());
                         let now = Instant::now();
                         fitness.call_for_chromosome(
                             &mut chromosome,
@@ -304,6 +318,9 @@ impl StrategyConfig for PermutateConfig {
     fn fitness_ordering(&self) -> FitnessOrdering {
         self.fitness_ordering
     }
+    fn fitness_cache(&self) -> Option<&FitnessCache> {
+        self.fitness_cache.as_ref()
+    }
     fn par_fitness(&self) -> bool {
         self.par_fitness
     }
@@ -437,7 +454,10 @@ impl<G: PermutateGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genoty
                 "The Genotype's mutation_type does not allow permutation. RangeGenotype/MultiRangeGenotype require MutationType::Step, StepScaled, or Discrete for permutation",
             ))
         } else {
-            let genotype = builder.genotype.unwrap();
+            let mut genotype = builder.genotype.unwrap();
+            if builder.fitness_cache.is_none() {
+                genotype.set_genes_hashing(false);
+            }
             let state = PermutateState::new(&genotype);
 
             Ok(Self {
@@ -446,6 +466,7 @@ impl<G: PermutateGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genoty
 
                 config: PermutateConfig {
                     fitness_ordering: builder.fitness_ordering,
+                    fitness_cache: builder.fitness_cache,
                     par_fitness: builder.par_fitness,
                     replace_on_equal_fitness: builder.replace_on_equal_fitness,
                     ..Default::default()
@@ -464,6 +485,7 @@ impl Default for PermutateConfig {
             fitness_ordering: FitnessOrdering::Maximize,
             par_fitness: false,
             replace_on_equal_fitness: true,
+            fitness_cache: None,
         }
     }
 }
@@ -1,6 +1,6 @@
 use super::Permutate;
 pub use crate::errors::TryFromStrategyBuilderError as TryFromBuilderError;
-use crate::fitness::{Fitness, FitnessOrdering};
+use crate::fitness::{Fitness, FitnessCache, FitnessOrdering};
 use crate::genotype::PermutateGenotype;
 use crate::strategy::{Strategy, StrategyReporter, StrategyReporterNoop};
 
@@ -14,6 +14,7 @@ pub struct Builder<
     pub genotype: Option<G>,
     pub fitness: Option<F>,
     pub fitness_ordering: FitnessOrdering,
+    pub fitness_cache: Option<FitnessCache>,
     pub par_fitness: bool,
     pub replace_on_equal_fitness: bool,
     pub reporter: SR,
@@ -26,6 +27,7 @@ impl<G: PermutateGenotype, F: Fitness<Genotype = G>> Default
         Self {
             genotype: None,
             fitness_ordering: FitnessOrdering::Maximize,
+            fitness_cache: None,
             par_fitness: false,
             replace_on_equal_fitness: true,
             fitness: None,
@@ -53,6 +55,26 @@ impl<G: PermutateGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genoty
         self.fitness_ordering = fitness_ordering;
         self
     }
+    /// Only works when genes_hash is stored on chromosome, as this is the cache key.
+    /// Only useful for MutationType::StepScaled, where the scales revisit chromosomes.
+    /// Silently ignore cache_size of zero, to support superset builder which delays specialization
+    pub fn with_fitness_cache(mut self, fitness_cache_size: usize) -> Self {
+        match FitnessCache::try_new_sharded(
+            fitness_cache_size,
+            FitnessCache::DEFAULT_NUMBER_OF_SHARDS,
+        ) {
+            Ok(cache) => self.fitness_cache = Some(cache),
+            Err(_error) => (),
+        }
+        self
+    }
+    /// Use a prebuilt (and possibly shared) cache, e.g. with another backend
+    /// ([FitnessCache::new_unbounded], [FitnessCache::try_new_file]) or with genes in the key
+    /// ([FitnessCache::with_genes_in_key]). Only works when genes_hash is stored on chromosome.
+    pub fn with_shared_fitness_cache(mut self, fitness_cache: FitnessCache) -> Self {
+        self.fitness_cache = Some(fitness_cache);
+        self
+    }
     pub fn with_par_fitness(mut self, par_fitness: bool) -> Self {
         self.par_fitness = par_fitness;
         self
@@ -72,12 +94,29 @@ impl<G: PermutateGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genoty
         Builder {
             genotype: self.genotype,
             fitness_ordering: self.fitness_ordering,
+            fitness_cache: self.fitness_cache,
             par_fitness: self.par_fitness,
             replace_on_equal_fitness: self.replace_on_equal_fitness,
             fitness: self.fitness,
             reporter,
         }
     }
+    /// Add a reporter next to the existing one (combined as a tuple), every hook is forwarded to
+    /// the existing reporter first and then to the additional reporter
+    pub fn with_additional_reporter<SR2: StrategyReporter<Genotype = G>>(
+        self,
+        reporter: SR2,
+    ) -> Builder<G, F, (SR, SR2)> {
+        Builder {
+            genotype: self.genotype,
+            fitness_ordering: self.fitness_ordering,
+            fitness_cache: self.fitness_cache,
+            par_fitness: self.par_fitness,
+            replace_on_equal_fitness: self.replace_on_equal_fitness,
+            fitness: self.fitness,
+            reporter: (self.reporter, reporter),
+        }
+    }
 }
 impl<G: PermutateGenotype, F: Fitness<Genotype = G>, SR: StrategyReporter<Genotype = G>>
     Builder<G, F, SR>
@@ -2,26 +2,29 @@
 pub use crate::chromosome::{Chromosome, GenesHash};
 #[doc(no_inline)]
 pub use crate::fitness::{
-    fitness_value, Fitness, FitnessChromosome, FitnessGenes, FitnessGenotype, FitnessOrdering,
-    FitnessPopulation, FitnessValue,
+    fitness_value, Fitness, FitnessCache, FitnessChromosome, FitnessGenes, FitnessGenotype,
+    FitnessOrdering, FitnessPopulation, FitnessValue,
 };
 #[doc(no_inline)]
 pub use crate::genotype::{
-    Allele, BinaryGenotype, Genotype, GenotypeBuilder, ListGenotype, MultiListGenotype,
-    MultiRangeGenotype, MultiUniqueGenotype, MutationType, PermutateGenotype, RangeAllele,
-    RangeGenotype, TryFromGenotypeBuilderError, UniqueGenotype,
+    Allele, AlleleScale, BinaryGenotype, BitGenotype, CompositeGenotype, GeneConstraint, Genotype,
+    GenotypeBuilder, Grammar, GrammaticalGenotype, GridGenotype, LinearBound, ListGenotype,
+    MultiListGenotype, MultiRangeGenotype, MultiUniqueGenotype, MutationType,
+    PartialPermutationGenotype, PermutateGenotype, RangeAllele, RangeGenotype, SubsetGenotype,
+    TryFromGenotypeBuilderError, UniqueGenotype, VariableListGenotype,
 };
 #[doc(no_inline)]
 pub use crate::impl_allele;
 #[doc(no_inline)]
 pub use crate::strategy::permutate::{
-    Permutate, PermutateBuilder, PermutateConfig, PermutateReporterDuration, PermutateReporterNoop,
-    PermutateReporterSimple, PermutateState, PermutateVariant, TryFromPermutateBuilderError,
+    Permutate, PermutateBuilder, PermutateConfig, PermutateReporterDuration, PermutateReporterLog,
+    PermutateReporterNoop, PermutateReporterRecorder, PermutateReporterSimple, PermutateState,
+    PermutateVariant, TryFromPermutateBuilderError,
 };
 #[doc(no_inline)]
 pub use crate::strategy::{
     Strategy, StrategyBuilder, StrategyConfig, StrategyReporter, StrategyReporterDuration,
-    StrategyReporterNoop, StrategyReporterSimple, StrategyState, TryFromStrategyBuilderError,
-    STRATEGY_ACTIONS,
+    StrategyReporterLog, StrategyReporterNoop, StrategyReporterRecord, StrategyReporterRecorder,
+    StrategyReporterSimple, StrategyState, TryFromStrategyBuilderError, STRATEGY_ACTIONS,
 };
 pub use num::BigUint;
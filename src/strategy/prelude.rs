@@ -13,15 +13,17 @@ pub use crate::extension::{
 };
 #[doc(no_inline)]
 pub use crate::fitness::{
-    fitness_value, Fitness, FitnessChromosome, FitnessGenes, FitnessGenotype, FitnessOrdering,
-    FitnessPopulation, FitnessValue,
+    fitness_value, Fitness, FitnessCache, FitnessChromosome, FitnessGenes, FitnessGenotype,
+    FitnessOrdering, FitnessPopulation, FitnessValue,
 };
 #[doc(no_inline)]
 pub use crate::genotype::{
-    Allele, BinaryGenotype, EvolveGenotype, Genotype, GenotypeBuilder, ListGenotype,
-    MultiListGenotype, MultiRangeGenotype, MultiUniqueGenotype, MutationType, RangeAllele,
-    RangeGenotype, SupportsGeneCrossover, SupportsPointCrossover, TryFromGenotypeBuilderError,
-    UniqueGenotype,
+    Allele, AlleleScale, BinaryGenotype, BitGenotype, CompositeGenotype, EstimateGenotype,
+    EvolveGenotype, GeneConstraint, Genotype, GenotypeBuilder, Grammar, GrammaticalGenotype,
+    GridGenotype, LinearBound, ListGenotype, MultiListGenotype, MultiRangeGenotype,
+    MultiUniqueGenotype, MutationType, PartialPermutationGenotype, ParticleSwarmGenotype,
+    RangeAllele, RangeGenotype, SubsetGenotype, SupportsGeneCrossover, SupportsPointCrossover,
+    TreeGenotype, TryFromGenotypeBuilderError, UniqueGenotype, VariableListGenotype,
 };
 #[doc(no_inline)]
 pub use crate::mutate::{
@@ -31,26 +33,57 @@ pub use crate::mutate::{
 #[doc(no_inline)]
 pub use crate::population::Population;
 #[doc(no_inline)]
-pub use crate::select::{Select, SelectElite, SelectEvent, SelectTournament, SelectWrapper};
+pub use crate::select::{
+    Select, SelectElite, SelectEvent, SelectNovelty, SelectTournament, SelectWrapper,
+};
+#[doc(no_inline)]
+pub use crate::strategy::ant_colony::{
+    AntColony, AntColonyBuilder, AntColonyConfig, AntColonyHeuristic, AntColonyHeuristicNoop,
+    AntColonyReporterDuration, AntColonyReporterLog, AntColonyReporterNoop,
+    AntColonyReporterRecorder, AntColonyReporterSimple, AntColonyState, AntColonyVariant,
+};
+#[doc(no_inline)]
+pub use crate::strategy::estimate::{
+    Estimate, EstimateBuilder, EstimateConfig, EstimateReporterDuration, EstimateReporterLog,
+    EstimateReporterNoop, EstimateReporterRecorder, EstimateReporterSimple, EstimateState,
+    EstimateVariant,
+};
 #[doc(no_inline)]
 pub use crate::strategy::evolve::{
-    Evolve, EvolveBuilder, EvolveConfig, EvolveReporterDuration, EvolveReporterNoop,
-    EvolveReporterSimple, EvolveState, EvolveVariant,
+    Evolve, EvolveBuilder, EvolveConfig, EvolveReporterDuration, EvolveReporterLog,
+    EvolveReporterNoop, EvolveReporterRecorder, EvolveReporterSimple, EvolveState, EvolveVariant,
+    SteadyStateReplacement,
 };
 #[doc(no_inline)]
 pub use crate::strategy::hill_climb::{
-    HillClimb, HillClimbBuilder, HillClimbConfig, HillClimbReporterDuration, HillClimbReporterNoop,
-    HillClimbReporterSimple, HillClimbState, HillClimbVariant,
+    HillClimb, HillClimbBuilder, HillClimbConfig, HillClimbReporterDuration, HillClimbReporterLog,
+    HillClimbReporterNoop, HillClimbReporterRecorder, HillClimbReporterSimple, HillClimbState,
+    HillClimbVariant,
+};
+#[doc(no_inline)]
+pub use crate::strategy::map_elites::{
+    BehaviourDescriptor, MapElites, MapElitesArchive, MapElitesBuilder, MapElitesConfig,
+    MapElitesElite, MapElitesGrid, MapElitesReporterDuration, MapElitesReporterLog,
+    MapElitesReporterNoop, MapElitesReporterRecorder, MapElitesReporterSimple, MapElitesState,
+    MapElitesVariant,
+};
+#[doc(no_inline)]
+pub use crate::strategy::particle_swarm::{
+    ParticleSwarm, ParticleSwarmBuilder, ParticleSwarmConfig, ParticleSwarmReporterDuration,
+    ParticleSwarmReporterLog, ParticleSwarmReporterNoop, ParticleSwarmReporterRecorder,
+    ParticleSwarmReporterSimple, ParticleSwarmSchedule, ParticleSwarmState, ParticleSwarmVariant,
 };
 #[doc(no_inline)]
 pub use crate::strategy::permutate::{
-    Permutate, PermutateBuilder, PermutateConfig, PermutateReporterDuration, PermutateReporterNoop,
-    PermutateReporterSimple, PermutateState, PermutateVariant,
+    Permutate, PermutateBuilder, PermutateConfig, PermutateReporterDuration, PermutateReporterLog,
+    PermutateReporterNoop, PermutateReporterRecorder, PermutateReporterSimple, PermutateState,
+    PermutateVariant,
 };
 #[doc(no_inline)]
 pub use crate::strategy::{
     Strategy, StrategyBuilder, StrategyConfig, StrategyReporter, StrategyReporterDuration,
-    StrategyReporterNoop, StrategyReporterSimple, StrategyState, StrategyVariant,
-    TryFromStrategyBuilderError, STRATEGY_ACTIONS,
+    StrategyReporterLog, StrategyReporterNoop, StrategyReporterRecord, StrategyReporterRecorder,
+    StrategyReporterSimple, StrategyState, StrategyVariant, TryFromStrategyBuilderError,
+    STRATEGY_ACTIONS,
 };
 pub use num::BigUint;
@@ -1,14 +1,30 @@
 //! Generic strategy reporters:
 //! * [Duration], only reports duration, non-strategy specific
 //! * [Noop], silences reporting, non-strategy specific
+//! * [Recorder], records a structured run history in memory, non-strategy specific
+//! * [Log], forwards reporting to the [log] crate with structured fields, non-strategy specific
 //! * [Simple], prefer to use strategy specific implementations:
 //!     * [EvolveReporterSimple](crate::strategy::evolve::EvolveReporterSimple)
 //!     * [PermutateReporterSimple](crate::strategy::permutate::PermutateReporterSimple)
 //!     * [HillClimbReporterSimple](crate::strategy::hill_climb::HillClimbReporterSimple)
 //!
+//! Reporters can be combined, as [StrategyReporter] is implemented for tuples (up to 4 reporters
+//! with the same Genotype) and for a `Vec` of reporters of the same type. Every hook (including
+//! `flush` and the event hooks) is forwarded to each contained reporter in order. On the strategy
+//! builders, use `with_additional_reporter` to add a reporter next to the existing one.
+//!
+use crate::crossover::CrossoverEvent;
+use crate::extension::ExtensionEvent;
+use crate::fitness::FitnessValue;
 use crate::genotype::Genotype;
-use crate::strategy::{StrategyConfig, StrategyReporter, StrategyState, STRATEGY_ACTIONS};
-use std::fmt::Arguments;
+use crate::mutate::MutateEvent;
+use crate::select::SelectEvent;
+use crate::strategy::{
+    StrategyAction, StrategyConfig, StrategyReporter, StrategyState, StrategyVariant,
+    STRATEGY_ACTIONS,
+};
+use log::Level;
+use std::fmt::{Arguments, Write as FmtWrite};
 use std::io::Write;
 use std::marker::PhantomData;
 
@@ -276,3 +292,903 @@ impl<G: Genotype> StrategyReporter for Simple<G> {
         }
     }
 }
+
+/// A single row in the run history of the [Recorder] reporter.
+///
+/// Population statistics are taken from the fitness scores in the population at the time of
+/// recording. Strategies without a population at that point (HillClimb Stochastic and Permutate)
+/// record `None` for these statistics. The event counters are reset after each record, so they
+/// count the events since the previous record. The durations are cumulative per
+/// [StrategyAction] (in [STRATEGY_ACTIONS] order).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub iteration: usize,
+    pub generation: usize,
+    pub stale_generations: usize,
+    pub best_generation: usize,
+    pub best_fitness_score: Option<FitnessValue>,
+    pub fitness_score_mean: Option<f32>,
+    pub fitness_score_median: Option<FitnessValue>,
+    pub fitness_score_stddev: Option<f32>,
+    pub population_cardinality: Option<usize>,
+    pub population_size: usize,
+    pub parents_size: usize,
+    pub offspring_size: usize,
+    pub scale_index: Option<usize>,
+    pub number_of_select_events: usize,
+    pub number_of_extension_events: usize,
+    pub number_of_crossover_events: usize,
+    pub number_of_mutate_events: usize,
+    pub durations: [std::time::Duration; STRATEGY_ACTIONS.len()],
+}
+
+impl Record {
+    pub const CSV_HEADER: [&'static str; 17] = [
+        "iteration",
+        "generation",
+        "stale_generations",
+        "best_generation",
+        "best_fitness_score",
+        "fitness_score_mean",
+        "fitness_score_median",
+        "fitness_score_stddev",
+        "population_cardinality",
+        "population_size",
+        "parents_size",
+        "offspring_size",
+        "scale_index",
+        "number_of_select_events",
+        "number_of_extension_events",
+        "number_of_crossover_events",
+        "number_of_mutate_events",
+    ];
+
+    /// The duration for a single action, cumulative up to this record
+    pub fn duration(&self, action: StrategyAction) -> std::time::Duration {
+        STRATEGY_ACTIONS
+            .iter()
+            .position(|a| *a == action)
+            .map(|index| self.durations[index])
+            .unwrap_or_default()
+    }
+
+    fn values(&self) -> [String; 17] {
+        [
+            self.iteration.to_string(),
+            self.generation.to_string(),
+            self.stale_generations.to_string(),
+            self.best_generation.to_string(),
+            option_to_string(self.best_fitness_score),
+            option_f32_to_string(self.fitness_score_mean),
+            option_to_string(self.fitness_score_median),
+            option_f32_to_string(self.fitness_score_stddev),
+            option_to_string(self.population_cardinality),
+            self.population_size.to_string(),
+            self.parents_size.to_string(),
+            self.offspring_size.to_string(),
+            option_to_string(self.scale_index),
+            self.number_of_select_events.to_string(),
+            self.number_of_extension_events.to_string(),
+            self.number_of_crossover_events.to_string(),
+            self.number_of_mutate_events.to_string(),
+        ]
+    }
+}
+
+fn option_to_string<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// non-finite values (NaN, inf) are exported as missing, as they are invalid JSON numbers
+fn option_f32_to_string(value: Option<f32>) -> String {
+    option_to_string(value.filter(|v| v.is_finite()))
+}
+
+fn action_column(action: &StrategyAction) -> &'static str {
+    match action {
+        StrategyAction::SetupAndCleanup => "duration_setup_and_cleanup",
+        StrategyAction::Extension => "duration_extension",
+        StrategyAction::Select => "duration_select",
+        StrategyAction::Crossover => "duration_crossover",
+        StrategyAction::Mutate => "duration_mutate",
+        StrategyAction::Fitness => "duration_fitness",
+        StrategyAction::UpdateBestChromosome => "duration_update_best_chromosome",
+        StrategyAction::Other => "duration_other",
+    }
+}
+
+/// A Recorder reporter generic over Genotype, works for all strategies.
+/// A record is stored every period generations, the records are retrievable after the run through
+/// the public `records` field (e.g. `evolve.reporter.records`) and can be exported with
+/// [Recorder::to_csv] and [Recorder::to_jsonl] for plotting convergence and comparing
+/// configurations. The durations are exported in seconds.
+///
+/// Records are stored for the `on_generation_complete` hook, which is called for all strategies.
+/// Note that for Evolve the population still contains the offspring at that point, while the
+/// population_cardinality has been determined after selection.
+///
+/// Example:
+/// ```
+/// use genetic_algorithm::strategy::evolve::prelude::*;
+/// use genetic_algorithm::fitness::placeholders::CountTrue;
+///
+/// let genotype = BinaryGenotype::builder()
+///     .with_genes_size(10)
+///     .build()
+///     .unwrap();
+///
+/// let evolve = Evolve::builder()
+///     .with_genotype(genotype)
+///     .with_target_population_size(20)
+///     .with_max_generations(10)
+///     .with_fitness(CountTrue)
+///     .with_select(SelectElite::new(0.5, 0.02))
+///     .with_crossover(CrossoverUniform::new(0.7, 0.8))
+///     .with_mutate(MutateSingleGene::new(0.2))
+///     .with_reporter(EvolveReporterRecorder::new(1))
+///     .call()
+///     .unwrap();
+///
+/// assert_eq!(evolve.reporter.records.len(), 10);
+/// let csv = evolve.reporter.to_csv();
+/// assert!(csv.starts_with("iteration,generation,"));
+/// ```
+#[derive(Clone)]
+pub struct Recorder<G: Genotype> {
+    pub period: usize,
+    pub records: Vec<Record>,
+    number_of_select_events: usize,
+    number_of_extension_events: usize,
+    number_of_crossover_events: usize,
+    number_of_mutate_events: usize,
+    _phantom: PhantomData<G>,
+}
+impl<G: Genotype> Default for Recorder<G> {
+    fn default() -> Self {
+        Self {
+            period: 1,
+            records: vec![],
+            number_of_select_events: 0,
+            number_of_extension_events: 0,
+            number_of_crossover_events: 0,
+            number_of_mutate_events: 0,
+            _phantom: PhantomData,
+        }
+    }
+}
+impl<G: Genotype> Recorder<G> {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            ..Default::default()
+        }
+    }
+
+    /// Export the records as CSV, with a header row
+    pub fn to_csv(&self) -> String {
+        let mut output = String::new();
+        let header = Record::CSV_HEADER
+            .iter()
+            .copied()
+            .chain(STRATEGY_ACTIONS.iter().map(action_column))
+            .collect::<Vec<_>>()
+            .join(",");
+        writeln!(output, "{}", header).unwrap_or(());
+        self.records.iter().for_each(|record| {
+            let row = record
+                .values()
+                .into_iter()
+                .chain(record.durations.iter().map(|d| d.as_secs_f64().to_string()))
+                .collect::<Vec<_>>()
+                .join(",");
+            writeln!(output, "{}", row).unwrap_or(());
+        });
+        output
+    }
+
+    /// Export the records as JSON Lines, one JSON object per record. Missing and non-finite values
+    /// are `null`
+    pub fn to_jsonl(&self) -> String {
+        let mut output = String::new();
+        self.records.iter().for_each(|record| {
+            let fields = Record::CSV_HEADER
+                .iter()
+                .zip(record.values())
+                .map(|(key, value)| {
+                    if value.is_empty() {
+                        format!("\"{}\":null", key)
+                    } else {
+                        format!("\"{}\":{}", key, value)
+                    }
+                })
+                .chain(STRATEGY_ACTIONS.iter().zip(record.durations.iter()).map(
+                    |(action, duration)| {
+                        format!("\"{}\":{}", action_column(action), duration.as_secs_f64())
+                    },
+                ))
+                .collect::<Vec<_>>()
+                .join(",");
+            writeln!(output, "{{{}}}", fields).unwrap_or(());
+        });
+        output
+    }
+
+    fn record<S: StrategyState<G>>(&mut self, genotype: &G, state: &S) {
+        let population = state.population_as_ref();
+        let has_fitness_scores = population.fitness_score_count() > 0;
+        let (parents_size, offspring_size) = population.parents_and_offspring_size();
+        let mut durations = [std::time::Duration::default(); STRATEGY_ACTIONS.len()];
+        STRATEGY_ACTIONS
+            .iter()
+            .zip(durations.iter_mut())
+            .for_each(|(action, duration)| {
+                if let Some(value) = state.durations().get(action) {
+                    *duration = *value;
+                }
+            });
+
+        self.records.push(Record {
+            iteration: state.current_iteration(),
+            generation: state.current_generation(),
+            stale_generations: state.stale_generations(),
+            best_generation: state.best_generation(),
+            best_fitness_score: state.best_fitness_score(),
+            fitness_score_mean: has_fitness_scores.then(|| population.fitness_score_mean()),
+            fitness_score_median: population.fitness_score_median(),
+            fitness_score_stddev: has_fitness_scores.then(|| population.fitness_score_stddev()),
+            population_cardinality: state.population_cardinality(),
+            population_size: population.size(),
+            parents_size,
+            offspring_size,
+            scale_index: genotype.current_scale_index(),
+            number_of_select_events: self.number_of_select_events,
+            number_of_extension_events: self.number_of_extension_events,
+            number_of_crossover_events: self.number_of_crossover_events,
+            number_of_mutate_events: self.number_of_mutate_events,
+            durations,
+        });
+
+        // reset event counters
+        self.number_of_select_events = 0;
+        self.number_of_extension_events = 0;
+        self.number_of_crossover_events = 0;
+        self.number_of_mutate_events = 0;
+    }
+}
+impl<G: Genotype> StrategyReporter for Recorder<G> {
+    type Genotype = G;
+
+    fn on_enter<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        _genotype: &Self::Genotype,
+        _state: &S,
+        _config: &C,
+    ) {
+        self.records.clear();
+        self.number_of_select_events = 0;
+        self.number_of_extension_events = 0;
+        self.number_of_crossover_events = 0;
+        self.number_of_mutate_events = 0;
+    }
+
+    fn on_generation_complete<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        genotype: &Self::Genotype,
+        state: &S,
+        _config: &C,
+    ) {
+        if state.current_generation() % self.period == 0 {
+            self.record(genotype, state);
+        }
+    }
+
+    fn on_select_event<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        _event: SelectEvent,
+        _genotype: &Self::Genotype,
+        _state: &S,
+        _config: &C,
+    ) {
+        self.number_of_select_events += 1;
+    }
+
+    fn on_extension_event<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        _event: ExtensionEvent,
+        _genotype: &Self::Genotype,
+        _state: &S,
+        _config: &C,
+    ) {
+        self.number_of_extension_events += 1;
+    }
+
+    fn on_crossover_event<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        _event: CrossoverEvent,
+        _genotype: &Self::Genotype,
+        _state: &S,
+        _config: &C,
+    ) {
+        self.number_of_crossover_events += 1;
+    }
+
+    fn on_mutate_event<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        _event: MutateEvent,
+        _genotype: &Self::Genotype,
+        _state: &S,
+        _config: &C,
+    ) {
+        self.number_of_mutate_events += 1;
+    }
+}
+
+/// A Log reporter generic over Genotype, works for all strategies.
+/// Forwards the reporting to the [log] crate (with structured key-values), so the progress can be
+/// routed into an existing logging pipeline instead of stdout. Any [log] implementation can be
+/// used (e.g. env_logger).
+///
+/// The target defaults per strategy variant (`genetic_algorithm::strategy::evolve`,
+/// `genetic_algorithm::strategy::hill_climb` and `genetic_algorithm::strategy::permutate`), but
+/// can be overridden with a fixed target. The levels are configurable per kind of report:
+/// * `lifecycle_level`: `on_enter` and `on_exit` (including the durations), default Info
+/// * `new_best_level`: `on_new_best_chromosome`, default Info
+/// * `periodic_level`: `on_generation_complete` every period generations, default Debug
+/// * `event_level`: select, extension, crossover and mutate events, default Debug
+///
+/// Example:
+/// ```
+/// use genetic_algorithm::strategy::evolve::prelude::*;
+/// use genetic_algorithm::fitness::placeholders::CountTrue;
+/// use log::Level;
+///
+/// let genotype = BinaryGenotype::builder()
+///     .with_genes_size(10)
+///     .build()
+///     .unwrap();
+///
+/// let evolve = Evolve::builder()
+///     .with_genotype(genotype)
+///     .with_target_population_size(20)
+///     .with_max_generations(10)
+///     .with_fitness(CountTrue)
+///     .with_select(SelectElite::new(0.5, 0.02))
+///     .with_crossover(CrossoverUniform::new(0.7, 0.8))
+///     .with_mutate(MutateSingleGene::new(0.2))
+///     .with_reporter(EvolveReporterLog::new_with_levels(
+///         100,
+///         Some("my_service::ga"),
+///         Level::Info,
+///         Level::Debug,
+///         Level::Debug,
+///         Level::Trace,
+///     ))
+///     .call()
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct Log<G: Genotype> {
+    pub period: usize,
+    pub target: Option<&'static str>,
+    pub lifecycle_level: Level,
+    pub new_best_level: Level,
+    pub periodic_level: Level,
+    pub event_level: Level,
+    _phantom: PhantomData<G>,
+}
+impl<G: Genotype> Default for Log<G> {
+    fn default() -> Self {
+        Self {
+            period: 1,
+            target: None,
+            lifecycle_level: Level::Info,
+            new_best_level: Level::Info,
+            periodic_level: Level::Debug,
+            event_level: Level::Debug,
+            _phantom: PhantomData,
+        }
+    }
+}
+impl<G: Genotype> Log<G> {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            ..Default::default()
+        }
+    }
+    pub fn new_with_target(period: usize, target: &'static str) -> Self {
+        Self {
+            period,
+            target: Some(target),
+            ..Default::default()
+        }
+    }
+    pub fn new_with_levels(
+        period: usize,
+        target: Option<&'static str>,
+        lifecycle_level: Level,
+        new_best_level: Level,
+        periodic_level: Level,
+        event_level: Level,
+    ) -> Self {
+        Self {
+            period,
+            target,
+            lifecycle_level,
+            new_best_level,
+            periodic_level,
+            event_level,
+            ..Default::default()
+        }
+    }
+    fn target<C: StrategyConfig>(&self, config: &C) -> &'static str {
+        self.target.unwrap_or(match config.variant() {
+            StrategyVariant::Evolve(_) => "genetic_algorithm::strategy::evolve",
+            StrategyVariant::HillClimb(_) => "genetic_algorithm::strategy::hill_climb",
+            StrategyVariant::Permutate(_) => "genetic_algorithm::strategy::permutate",
+            StrategyVariant::MapElites(_) => "genetic_algorithm::strategy::map_elites",
+            StrategyVariant::Estimate(_) => "genetic_algorithm::strategy::estimate",
+            StrategyVariant::AntColony(_) => "genetic_algorithm::strategy::ant_colony",
+            StrategyVariant::ParticleSwarm(_) => "genetic_algorithm::strategy::particle_swarm",
+        })
+    }
+}
+impl<G: Genotype> StrategyReporter for Log<G> {
+    type Genotype = G;
+
+    fn on_enter<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        let variant = config.variant().to_string();
+        log::log!(
+            target: self.target(config),
+            self.lifecycle_level,
+            variant = variant.as_str(),
+            iteration = state.current_iteration(),
+            seed_genes = genotype.seed_genes_list().len();
+            "enter - {}, iteration: {}",
+            variant,
+            state.current_iteration()
+        );
+    }
+
+    fn on_exit<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        _genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        let target = self.target(config);
+        let variant = config.variant().to_string();
+        log::log!(
+            target: target,
+            self.lifecycle_level,
+            variant = variant.as_str(),
+            iteration = state.current_iteration(),
+            best_generation = state.best_generation(),
+            best_fitness_score:? = state.best_fitness_score();
+            "exit - {}, iteration: {}",
+            variant,
+            state.current_iteration()
+        );
+        STRATEGY_ACTIONS.iter().for_each(|action| {
+            if let Some(duration) = state.durations().get(action) {
+                log::log!(
+                    target: target,
+                    self.lifecycle_level,
+                    action:? = action,
+                    duration_secs = duration.as_secs_f64();
+                    "  {:?}: {:.3?}",
+                    action,
+                    duration
+                );
+            }
+        });
+        log::log!(
+            target: target,
+            self.lifecycle_level,
+            total_duration_secs = state.total_duration().as_secs_f64(),
+            fitness_duration_rate = state.fitness_duration_rate();
+            "  Total: {:.3?} ({:.0}% fitness)",
+            &state.total_duration(),
+            state.fitness_duration_rate() * 100.0
+        );
+    }
+
+    fn on_generation_complete<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        if state.current_generation() % self.period == 0 {
+            log::log!(
+                target: self.target(config),
+                self.periodic_level,
+                current_generation = state.current_generation(),
+                stale_generations = state.stale_generations(),
+                best_generation = state.best_generation(),
+                best_fitness_score:? = state.best_fitness_score(),
+                scale_index:? = genotype.current_scale_index(),
+                population_cardinality:? = state.population_cardinality(),
+                population_size = state.population_as_ref().size();
+                "periodic - current_generation: {}, stale_generations: {}, best_generation: {}, scale_index: {:?}",
+                state.current_generation(),
+                state.stale_generations(),
+                state.best_generation(),
+                genotype.current_scale_index(),
+            );
+        }
+    }
+
+    fn on_new_best_chromosome<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        log::log!(
+            target: self.target(config),
+            self.new_best_level,
+            generation = state.current_generation(),
+            fitness_score:? = state.best_fitness_score(),
+            scale_index:? = genotype.current_scale_index();
+            "new best - generation: {}, fitness_score: {:?}, scale_index: {:?}",
+            state.current_generation(),
+            state.best_fitness_score(),
+            genotype.current_scale_index(),
+        );
+    }
+
+    fn on_select_event<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        event: SelectEvent,
+        _genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        log::log!(
+            target: self.target(config),
+            self.event_level,
+            event = "select",
+            generation = state.current_generation();
+            "select event - generation {} - {}",
+            state.current_generation(),
+            event.0,
+        );
+    }
+
+    fn on_extension_event<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        event: ExtensionEvent,
+        _genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        log::log!(
+            target: self.target(config),
+            self.event_level,
+            event = "extension",
+            generation = state.current_generation();
+            "extension event - generation {} - {}",
+            state.current_generation(),
+            event.0,
+        );
+    }
+
+    fn on_crossover_event<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        event: CrossoverEvent,
+        _genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        log::log!(
+            target: self.target(config),
+            self.event_level,
+            event = "crossover",
+            generation = state.current_generation();
+            "crossover event - generation {} - {}",
+            state.current_generation(),
+            event.0,
+        );
+    }
+
+    fn on_mutate_event<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        event: MutateEvent,
+        _genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        log::log!(
+            target: self.target(config),
+            self.event_level,
+            event = "mutate",
+            generation = state.current_generation();
+            "mutate event - generation {} - {}",
+            state.current_generation(),
+            event.0,
+        );
+    }
+}
+
+macro_rules! impl_strategy_reporter_for_tuple {
+    ($first:ident, $($rest:ident),+; $($index:tt),+) => {
+        /// Fan-out to multiple reporters, forwarding every hook in order
+        impl<$first: StrategyReporter, $($rest: StrategyReporter<Genotype = $first::Genotype>),+>
+            StrategyReporter for ($first, $($rest),+)
+        {
+            type Genotype = $first::Genotype;
+
+            fn flush(&mut self, output: &mut Vec<u8>) {
+                $( self.$index.flush(output); )+
+            }
+            fn on_enter<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+                &mut self,
+                genotype: &Self::Genotype,
+                state: &S,
+                config: &C,
+            ) {
+                $( self.$index.on_enter(genotype, state, config); )+
+            }
+            fn on_exit<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+                &mut self,
+                genotype: &Self::Genotype,
+                state: &S,
+                config: &C,
+            ) {
+                $( self.$index.on_exit(genotype, state, config); )+
+            }
+            fn on_start<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+                &mut self,
+                genotype: &Self::Genotype,
+                state: &S,
+                config: &C,
+            ) {
+                $( self.$index.on_start(genotype, state, config); )+
+            }
+            fn on_finish<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+                &mut self,
+                genotype: &Self::Genotype,
+                state: &S,
+                config: &C,
+            ) {
+                $( self.$index.on_finish(genotype, state, config); )+
+            }
+            fn on_selection_complete<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+                &mut self,
+                genotype: &Self::Genotype,
+                state: &S,
+                config: &C,
+            ) {
+                $( self.$index.on_selection_complete(genotype, state, config); )+
+            }
+            fn on_crossover_complete<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+                &mut self,
+                genotype: &Self::Genotype,
+                state: &S,
+                config: &C,
+            ) {
+                $( self.$index.on_crossover_complete(genotype, state, config); )+
+            }
+            fn on_mutation_complete<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+                &mut self,
+                genotype: &Self::Genotype,
+                state: &S,
+                config: &C,
+            ) {
+                $( self.$index.on_mutation_complete(genotype, state, config); )+
+            }
+            fn on_generation_complete<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+                &mut self,
+                genotype: &Self::Genotype,
+                state: &S,
+                config: &C,
+            ) {
+                $( self.$index.on_generation_complete(genotype, state, config); )+
+            }
+            fn on_new_best_chromosome<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+                &mut self,
+                genotype: &Self::Genotype,
+                state: &S,
+                config: &C,
+            ) {
+                $( self.$index.on_new_best_chromosome(genotype, state, config); )+
+            }
+            fn on_new_best_chromosome_equal_fitness<
+                S: StrategyState<Self::Genotype>,
+                C: StrategyConfig,
+            >(
+                &mut self,
+                genotype: &Self::Genotype,
+                state: &S,
+                config: &C,
+            ) {
+                $( self.$index.on_new_best_chromosome_equal_fitness(genotype, state, config); )+
+            }
+            fn on_select_event<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+                &mut self,
+                event: SelectEvent,
+                genotype: &Self::Genotype,
+                state: &S,
+                config: &C,
+            ) {
+                $( self.$index.on_select_event(event.clone(), genotype, state, config); )+
+            }
+            fn on_extension_event<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+                &mut self,
+                event: ExtensionEvent,
+                genotype: &Self::Genotype,
+                state: &S,
+                config: &C,
+            ) {
+                $( self.$index.on_extension_event(event.clone(), genotype, state, config); )+
+            }
+            fn on_crossover_event<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+                &mut self,
+                event: CrossoverEvent,
+                genotype: &Self::Genotype,
+                state: &S,
+                config: &C,
+            ) {
+                $( self.$index.on_crossover_event(event.clone(), genotype, state, config); )+
+            }
+            fn on_mutate_event<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+                &mut self,
+                event: MutateEvent,
+                genotype: &Self::Genotype,
+                state: &S,
+                config: &C,
+            ) {
+                $( self.$index.on_mutate_event(event.clone(), genotype, state, config); )+
+            }
+        }
+    };
+}
+
+impl_strategy_reporter_for_tuple!(R1, R2; 0, 1);
+impl_strategy_reporter_for_tuple!(R1, R2, R3; 0, 1, 2);
+impl_strategy_reporter_for_tuple!(R1, R2, R3, R4; 0, 1, 2, 3);
+
+/// Fan-out to multiple reporters of the same type, forwarding every hook in order
+impl<R: StrategyReporter> StrategyReporter for Vec<R> {
+    type Genotype = R::Genotype;
+
+    fn flush(&mut self, output: &mut Vec<u8>) {
+        self.iter_mut().for_each(|r| r.flush(output));
+    }
+    fn on_enter<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        self.iter_mut()
+            .for_each(|r| r.on_enter(genotype, state, config));
+    }
+    fn on_exit<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        self.iter_mut()
+            .for_each(|r| r.on_exit(genotype, state, config));
+    }
+    fn on_start<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        self.iter_mut()
+            .for_each(|r| r.on_start(genotype, state, config));
+    }
+    fn on_finish<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        self.iter_mut()
+            .for_each(|r| r.on_finish(genotype, state, config));
+    }
+    fn on_selection_complete<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        self.iter_mut()
+            .for_each(|r| r.on_selection_complete(genotype, state, config));
+    }
+    fn on_crossover_complete<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        self.iter_mut()
+            .for_each(|r| r.on_crossover_complete(genotype, state, config));
+    }
+    fn on_mutation_complete<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        self.iter_mut()
+            .for_each(|r| r.on_mutation_complete(genotype, state, config));
+    }
+    fn on_generation_complete<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        self.iter_mut()
+            .for_each(|r| r.on_generation_complete(genotype, state, config));
+    }
+    fn on_new_best_chromosome<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        self.iter_mut()
+            .for_each(|r| r.on_new_best_chromosome(genotype, state, config));
+    }
+    fn on_new_best_chromosome_equal_fitness<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        self.iter_mut()
+            .for_each(|r| r.on_new_best_chromosome_equal_fitness(genotype, state, config));
+    }
+    fn on_select_event<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        event: SelectEvent,
+        genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        self.iter_mut()
+            .for_each(|r| r.on_select_event(event.clone(), genotype, state, config));
+    }
+    fn on_extension_event<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        event: ExtensionEvent,
+        genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        self.iter_mut()
+            .for_each(|r| r.on_extension_event(event.clone(), genotype, state, config));
+    }
+    fn on_crossover_event<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        event: CrossoverEvent,
+        genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        self.iter_mut()
+            .for_each(|r| r.on_crossover_event(event.clone(), genotype, state, config));
+    }
+    fn on_mutate_event<S: StrategyState<Self::Genotype>, C: StrategyConfig>(
+        &mut self,
+        event: MutateEvent,
+        genotype: &Self::Genotype,
+        state: &S,
+        config: &C,
+    ) {
+        self.iter_mut()
+            .for_each(|r| r.on_mutate_event(event.clone(), genotype, state, config));
+    }
+}
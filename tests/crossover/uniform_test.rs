@@ -3,7 +3,9 @@ use crate::support::*;
 use genetic_algorithm::crossover::{Crossover, CrossoverUniform};
 use genetic_algorithm::genotype::{BinaryGenotype, Genotype};
 use genetic_algorithm::population::Population;
-use genetic_algorithm::strategy::evolve::{EvolveConfig, EvolveState};
+use genetic_algorithm::strategy::evolve::{
+    EvolveConfig, EvolveState, EvolveVariant, SteadyStateReplacement,
+};
 use genetic_algorithm::strategy::StrategyReporterNoop;
 
 #[test]
@@ -49,3 +51,37 @@ fn standard() {
         ]
     )
 }
+
+#[test]
+fn steady_state() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(10)
+        .build()
+        .unwrap();
+
+    let population: Population<bool> =
+        build::population_with_age(vec![(vec![true; 10], 0), (vec![false; 10], 0)]);
+
+    let mut state = EvolveState::new(&genotype);
+    state.population = population;
+    let config = EvolveConfig {
+        variant: EvolveVariant::SteadyState(SteadyStateReplacement::Worst),
+        target_population_size: 4,
+        ..Default::default()
+    };
+    let mut reporter = StrategyReporterNoop::new();
+    let mut rng = SmallRng::seed_from_u64(0);
+    state.population.increment_age();
+    // the selection_rate does not apply, the parent pair always crosses over
+    CrossoverUniform::new(0.5, 1.0).call(&genotype, &mut state, &config, &mut reporter, &mut rng);
+
+    let offspring: Vec<_> = inspect::population_with_age(&state.population)
+        .into_iter()
+        .filter(|(_, age)| *age == 0)
+        .map(|(genes, _)| genes)
+        .collect();
+    assert_eq!(offspring.len(), 2);
+    assert!(offspring
+        .iter()
+        .all(|genes| *genes != vec![true; 10] && *genes != vec![false; 10]));
+}
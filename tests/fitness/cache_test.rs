@@ -1,6 +1,8 @@
 #[cfg(test)]
 // use crate::support::*;
-use genetic_algorithm::fitness::cache::Cache;
+use genetic_algorithm::chromosome::Chromosome;
+use genetic_algorithm::fitness::cache::{Cache, CacheBackendSharded};
+use rayon::prelude::*;
 
 #[test]
 fn standard() {
@@ -28,3 +30,166 @@ fn zero_cache_size() {
     assert!(cache.is_err());
     assert_eq!(cache.err(), Some("cache_size must be greater than 0"));
 }
+
+#[test]
+fn unbounded() {
+    let cache = Cache::new_unbounded();
+    (0..1000).for_each(|i| cache.write(i, i as isize * 10));
+
+    assert_eq!(cache.len(), 1000);
+    assert_eq!(cache.read(0), Some(0));
+    assert_eq!(cache.read(999), Some(9990));
+    assert_eq!(cache.read(1000), None);
+    assert_eq!(cache.hit_miss_stats(), (2, 1, 2.0));
+}
+
+#[test]
+fn lru_evicts() {
+    let cache = Cache::try_new(2).unwrap();
+    cache.write(1, 10);
+    cache.write(2, 20);
+    cache.write(3, 30);
+
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.read(1), None);
+    assert_eq!(cache.read(3), Some(30));
+}
+
+#[test]
+fn genes_in_key() {
+    let cache = Cache::new_unbounded().with_genes_in_key(true);
+
+    // forced hash collision, distinguished by the genes
+    let mut chromosome_a = Chromosome::new(vec![true, false]);
+    let mut chromosome_b = Chromosome::new(vec![false, true]);
+    chromosome_a.set_genes_hash(Some(42));
+    chromosome_b.set_genes_hash(Some(42));
+
+    let key_a = cache.key_for(&chromosome_a).unwrap();
+    let key_b = cache.key_for(&chromosome_b).unwrap();
+    assert_ne!(key_a, key_b);
+
+    cache.write_key(key_a.clone(), 1);
+    assert_eq!(cache.read_key(&key_a), Some(1));
+    assert_eq!(cache.read_key(&key_b), None);
+
+    // without genes in the key, the collision returns the wrong value
+    let cache = Cache::new_unbounded();
+    let key_a = cache.key_for(&chromosome_a).unwrap();
+    let key_b = cache.key_for(&chromosome_b).unwrap();
+    assert_eq!(key_a, key_b);
+
+    // without genes_hash on chromosome, there is no key
+    assert_eq!(cache.key_for(&Chromosome::new(vec![true])), None);
+}
+
+#[test]
+fn file_warm_and_flush() {
+    let path = std::env::temp_dir().join(format!(
+        "genetic_algorithm_cache_test_{}.log",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+
+    let chromosome = {
+        let mut chromosome = Chromosome::new(vec![0.5_f32, 1.5]);
+        chromosome.set_genes_hash(Some(chromosome.calculate_hash()));
+        chromosome
+    };
+
+    {
+        let cache = Cache::try_new_file(&path).unwrap().with_genes_in_key(true);
+        assert!(cache.is_empty());
+        cache.write(1, 10);
+        cache.write(2, 20);
+        cache.write(1, -11);
+        cache.write_key(cache.key_for(&chromosome).unwrap(), 99);
+        cache.flush().unwrap();
+    }
+
+    // warm start, last record wins
+    let cache = Cache::try_new_file(&path).unwrap().with_genes_in_key(true);
+    assert_eq!(cache.len(), 3);
+    assert_eq!(cache.read(1), Some(-11));
+    assert_eq!(cache.read(2), Some(20));
+    assert_eq!(
+        cache.read_key(&cache.key_for(&chromosome).unwrap()),
+        Some(99)
+    );
+
+    // flushed on drop
+    cache.write(3, 30);
+    drop(cache);
+
+    // remove truncated trailing record, keep appending after the last valid one
+    let mut bytes = std::fs::read(&path).unwrap();
+    bytes.extend_from_slice(&[1, 2, 3]);
+    std::fs::write(&path, bytes).unwrap();
+
+    let cache = Cache::try_new_file(&path).unwrap();
+    assert_eq!(cache.len(), 4);
+    assert_eq!(cache.read(3), Some(30));
+    cache.write(4, 40);
+    drop(cache);
+
+    let cache = Cache::try_new_file(&path).unwrap();
+    assert_eq!(cache.len(), 5);
+    assert_eq!(cache.read(4), Some(40));
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn lru_promotes_in_batches() {
+    let cache = Cache::try_new(2).unwrap();
+    cache.write(1, 10);
+    cache.write(2, 20);
+
+    // hit is buffered and promoted on the next write, so 2 is evicted instead of 1
+    assert_eq!(cache.read(1), Some(10));
+    cache.write(3, 30);
+
+    assert_eq!(cache.read(1), Some(10));
+    assert_eq!(cache.read(2), None);
+    assert_eq!(cache.read(3), Some(30));
+}
+
+#[test]
+fn sharded() {
+    let cache = Cache::try_new_sharded(1000, 8).unwrap();
+    (0..500).for_each(|i| cache.write(i, i as isize));
+
+    assert_eq!(cache.len(), 500);
+    assert!((0..500).all(|i| cache.read(i) == Some(i as isize)));
+    assert_eq!(cache.hit_miss_stats(), (500, 0, 0.0));
+
+    // number_of_shards is capped for small caches
+    let backend = CacheBackendSharded::try_new_lru(40, 8).unwrap();
+    assert_eq!(backend.shards.len(), 2);
+    assert_eq!(backend.shards[0].cache_size, 20);
+
+    assert_eq!(
+        Cache::try_new_sharded(0, 8).err(),
+        Some("cache_size must be greater than 0")
+    );
+    assert_eq!(
+        Cache::try_new_sharded(100, 0).err(),
+        Some("number_of_shards must be greater than 0")
+    );
+}
+
+#[test]
+fn sharded_par_read_write() {
+    let cache = Cache::try_new_sharded(100_000, 16).unwrap();
+    (0..10_000_u64).into_par_iter().for_each(|i| {
+        if cache.read(i).is_none() {
+            cache.write(i, i as isize);
+        }
+    });
+    (0..10_000_u64).into_par_iter().for_each(|i| {
+        assert_eq!(cache.read(i), Some(i as isize));
+    });
+
+    assert_eq!(cache.len(), 10_000);
+    assert_eq!(cache.hit_miss_stats(), (10_000, 10_000, 1.0));
+}
@@ -2,9 +2,12 @@
 use crate::support::*;
 use genetic_algorithm::chromosome::Chromosome;
 use genetic_algorithm::fitness::placeholders::{
-    CountTrue, CountTrueWithSleep, Countdown, CountdownNoisy, SumGenes, Zero,
+    Ackley, Benchmark, CountTrue, CountTrueWithSleep, Countdown, CountdownNoisy, Griewank,
+    LeadingOnes, NkLandscape, OneMax, QuadraticAssignment, Rastrigin, Rosenbrock, Schwefel, Sphere,
+    SumGenes, Trap, TravelingSalesman, Zero,
 };
-use genetic_algorithm::fitness::Fitness;
+use genetic_algorithm::fitness::{Fitness, FitnessOrdering};
+use genetic_algorithm::strategy::permutate::prelude::*;
 
 #[test]
 fn binary_genotype() {
@@ -223,3 +226,348 @@ fn range_genotype_f64() {
         Some(7199)
     );
 }
+
+const BURMA14: &str = "NAME: burma14
+TYPE: TSP
+COMMENT: 14-Staedte in Burma (Zaw Win)
+DIMENSION: 14
+EDGE_WEIGHT_TYPE: GEO
+EDGE_WEIGHT_FORMAT: FUNCTION
+DISPLAY_DATA_TYPE: COORD_DISPLAY
+NODE_COORD_SECTION
+   1  16.47       96.10
+   2  16.47       94.44
+   3  20.09       92.54
+   4  22.39       93.37
+   5  25.23       97.24
+   6  22.00       96.05
+   7  20.47       97.02
+   8  17.20       96.29
+   9  16.30       97.38
+  10  14.05       98.12
+  11  16.53       97.38
+  12  21.52       95.59
+  13  19.41       97.13
+  14  20.09       94.55
+EOF
+";
+
+const BURMA14_OPT_TOUR: &str = "NAME : burma14.opt.tour
+TYPE : TOUR
+DIMENSION : 14
+TOUR_SECTION
+1
+2
+14
+3
+4
+5
+6
+12
+7
+13
+8
+11
+9
+10
+-1
+EOF
+";
+
+#[test]
+fn continuous_benchmarks() {
+    let genotype = RangeGenotype::builder()
+        .with_genes_size(3)
+        .with_allele_range(-500.0_f64..=500.0_f64)
+        .build()
+        .unwrap();
+
+    let chromosome: Chromosome<f64> = build::chromosome(vec![0.0, 0.0, 0.0]);
+    assert_eq!(
+        Sphere::new().calculate_for_chromosome(&chromosome, &genotype),
+        Some(0)
+    );
+    assert_eq!(
+        Rastrigin::new().calculate_for_chromosome(&chromosome, &genotype),
+        Some(0)
+    );
+    assert_eq!(
+        Ackley::new_with_precision(1e-3).calculate_for_chromosome(&chromosome, &genotype),
+        Some(0)
+    );
+    assert_eq!(
+        Griewank::new_with_precision(1e-3).calculate_for_chromosome(&chromosome, &genotype),
+        Some(0)
+    );
+    assert_eq!(
+        Rosenbrock::new().calculate_for_chromosome(&chromosome, &genotype),
+        Some(2)
+    );
+
+    let chromosome: Chromosome<f64> = build::chromosome(vec![1.0, 1.0, 1.0]);
+    assert_eq!(
+        Rosenbrock::new_with_precision(1e-3).calculate_for_chromosome(&chromosome, &genotype),
+        Some(0)
+    );
+    assert_eq!(
+        Sphere::new_with_precision(1e-3).calculate_for_chromosome(&chromosome, &genotype),
+        Some(3000)
+    );
+    assert_eq!(
+        Rastrigin::new_with_precision(1e-3).calculate_for_chromosome(&chromosome, &genotype),
+        Some(3000)
+    );
+
+    let chromosome: Chromosome<f64> = build::chromosome(vec![420.9687, 420.9687, 420.9687]);
+    assert_eq!(
+        Schwefel::new_with_precision(1e-3).calculate_for_chromosome(&chromosome, &genotype),
+        Some(0)
+    );
+
+    let fitness = Sphere::new();
+    assert!(matches!(
+        fitness.fitness_ordering(),
+        FitnessOrdering::Minimize
+    ));
+    assert_eq!(fitness.optimal_fitness_score(&genotype), Some(0));
+}
+
+#[test]
+fn binary_benchmarks() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(6)
+        .build()
+        .unwrap();
+
+    let chromosome: Chromosome<bool> =
+        build::chromosome(vec![true, true, false, true, false, false]);
+    assert_eq!(
+        OneMax.calculate_for_chromosome(&chromosome, &genotype),
+        Some(3)
+    );
+    assert_eq!(
+        LeadingOnes.calculate_for_chromosome(&chromosome, &genotype),
+        Some(2)
+    );
+    // blocks of 3: [true, true, false] => 0, [true, false, false] => 1
+    assert_eq!(
+        Trap::new(3).calculate_for_chromosome(&chromosome, &genotype),
+        Some(1)
+    );
+
+    let chromosome: Chromosome<bool> = build::chromosome(vec![false; 6]);
+    assert_eq!(
+        Trap::new(3).calculate_for_chromosome(&chromosome, &genotype),
+        Some(4)
+    );
+    let chromosome: Chromosome<bool> = build::chromosome(vec![true; 6]);
+    assert_eq!(
+        Trap::new(3).calculate_for_chromosome(&chromosome, &genotype),
+        Some(6)
+    );
+    assert_eq!(Trap::new(3).optimal_fitness_score(&genotype), Some(6));
+    assert_eq!(OneMax.optimal_fitness_score(&genotype), Some(6));
+    assert_eq!(LeadingOnes.optimal_fitness_score(&genotype), Some(6));
+
+    let mut fitness = NkLandscape::new(6, 2, 0);
+    assert_eq!(fitness.contributions.len(), 6);
+    assert_eq!(fitness.contributions[0].len(), 8);
+    let optimal_fitness_score = fitness.optimal_fitness_score(&genotype).unwrap();
+    let permutate = Permutate::builder()
+        .with_genotype(genotype.clone())
+        .with_fitness(fitness.clone())
+        .call()
+        .unwrap();
+    assert_eq!(permutate.best_fitness_score(), Some(optimal_fitness_score));
+    assert!(
+        fitness
+            .calculate_for_chromosome(&chromosome, &genotype)
+            .unwrap()
+            <= optimal_fitness_score
+    );
+    assert_eq!(
+        NkLandscape::new(30, 2, 0).optimal_fitness_score(&genotype),
+        None
+    );
+}
+
+#[test]
+fn traveling_salesman_tsplib_geo() {
+    let fitness = TravelingSalesman::from_tsplib(BURMA14)
+        .unwrap()
+        .with_optimal_tour_length(3323);
+    assert_eq!(fitness.name, "burma14");
+    assert_eq!(fitness.dimension(), 14);
+    assert_eq!(fitness.distances[0][1], 153);
+
+    let tour = TravelingSalesman::parse_tsplib_tour(BURMA14_OPT_TOUR).unwrap();
+    assert_eq!(tour.len(), 14);
+    assert_eq!(fitness.tour_length(&tour), 3323);
+
+    let genotype = UniqueGenotype::builder()
+        .with_allele_list((0..14).collect())
+        .build()
+        .unwrap();
+    assert!(matches!(
+        fitness.fitness_ordering(),
+        FitnessOrdering::Minimize
+    ));
+    assert_eq!(fitness.optimal_fitness_score(&genotype), Some(3323));
+}
+
+#[test]
+fn traveling_salesman_tsplib_explicit() {
+    let full_matrix = "NAME: square
+TYPE: TSP
+DIMENSION: 4
+EDGE_WEIGHT_TYPE: EXPLICIT
+EDGE_WEIGHT_FORMAT: FULL_MATRIX
+EDGE_WEIGHT_SECTION
+0 1 2 1
+1 0 1 2
+2 1 0 1
+1 2 1 0
+EOF";
+    let upper_row = "NAME: square
+DIMENSION: 4
+EDGE_WEIGHT_TYPE: EXPLICIT
+EDGE_WEIGHT_FORMAT: UPPER_ROW
+EDGE_WEIGHT_SECTION
+1 2 1
+1 2
+1
+EOF";
+    let lower_diag_row = "NAME: square
+DIMENSION: 4
+EDGE_WEIGHT_TYPE: EXPLICIT
+EDGE_WEIGHT_FORMAT: LOWER_DIAG_ROW
+EDGE_WEIGHT_SECTION
+0 1 0 2 1 0 1 2 1 0
+EOF";
+    let euc_2d = "NAME: square
+DIMENSION: 4
+EDGE_WEIGHT_TYPE: EUC_2D
+NODE_COORD_SECTION
+1 0 0
+2 0 1
+3 1 1
+4 1 0
+EOF";
+
+    let expected = TravelingSalesman::from_tsplib(full_matrix)
+        .unwrap()
+        .distances;
+    assert_eq!(
+        TravelingSalesman::from_tsplib(upper_row).unwrap().distances,
+        expected
+    );
+    assert_eq!(
+        TravelingSalesman::from_tsplib(lower_diag_row)
+            .unwrap()
+            .distances,
+        expected
+    );
+    let fitness = TravelingSalesman::from_tsplib(euc_2d).unwrap();
+    assert_eq!(fitness.tour_length(&[0, 1, 2, 3]), 4);
+    assert_eq!(fitness.tour_length(&[0, 2, 1, 3]), 4);
+
+    assert_eq!(
+        TravelingSalesman::from_tsplib("NAME: missing\nEDGE_WEIGHT_TYPE: EUC_2D").err(),
+        Some("TSPLIB DIMENSION is missing")
+    );
+    assert_eq!(
+        TravelingSalesman::from_tsplib("DIMENSION: 4\nEDGE_WEIGHT_TYPE: XRAY1").err(),
+        Some("TSPLIB NODE_COORD_SECTION is missing")
+    );
+    assert_eq!(
+        TravelingSalesman::from_tsplib("DIMENSION: 0\nEDGE_WEIGHT_TYPE: EUC_2D").err(),
+        Some("TSPLIB DIMENSION must be positive")
+    );
+    assert_eq!(
+        TravelingSalesman::from_tsplib(&format!(
+            "DIMENSION: {}\nEDGE_WEIGHT_TYPE: EUC_2D\nNODE_COORD_SECTION\n1 0 0",
+            usize::MAX
+        ))
+        .err(),
+        Some("TSPLIB NODE_COORD_SECTION has less nodes than DIMENSION")
+    );
+    assert_eq!(
+        TravelingSalesman::from_tsplib(&format!(
+            "DIMENSION: {}\nEDGE_WEIGHT_TYPE: EXPLICIT\nEDGE_WEIGHT_FORMAT: UPPER_ROW\nEDGE_WEIGHT_SECTION\n1 2 3",
+            usize::MAX
+        ))
+        .err(),
+        Some("TSPLIB EDGE_WEIGHT_SECTION has less weights than EDGE_WEIGHT_FORMAT")
+    );
+}
+
+#[test]
+fn quadratic_assignment_qaplib() {
+    let instance = "4
+
+0 3 0 2
+3 0 0 1
+0 0 0 4
+2 1 4 0
+
+0 22 53 53
+22 0 40 62
+53 40 0 55
+53 62 55 0
+";
+    let fitness = QuadraticAssignment::from_qaplib(instance).unwrap();
+    assert_eq!(fitness.dimension(), 4);
+
+    let genotype = UniqueGenotype::builder()
+        .with_allele_list((0..4).collect())
+        .build()
+        .unwrap();
+    let permutate = Permutate::builder()
+        .with_genotype(genotype)
+        .with_fitness(fitness.clone())
+        .with_fitness_ordering(FitnessOrdering::Minimize)
+        .call()
+        .unwrap();
+    let optimal_cost = permutate.best_fitness_score().unwrap();
+    let optimal_assignment = permutate.best_genes().unwrap();
+
+    // QAPLIB solutions are 1-based
+    let solution = format!(
+        "4 {}\n{}",
+        optimal_cost,
+        optimal_assignment
+            .iter()
+            .map(|location| (location + 1).to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    );
+    let (cost, assignment) = QuadraticAssignment::parse_qaplib_solution(&solution).unwrap();
+    assert_eq!(cost, optimal_cost);
+    assert_eq!(assignment, optimal_assignment);
+    assert_eq!(fitness.cost(&assignment), optimal_cost);
+
+    assert_eq!(
+        QuadraticAssignment::from_qaplib("4 0 1").err(),
+        Some("QAPLIB has less values than two dimension x dimension matrices")
+    );
+    assert_eq!(
+        QuadraticAssignment::from_qaplib("0").err(),
+        Some("QAPLIB dimension must be positive")
+    );
+    assert_eq!(
+        QuadraticAssignment::from_qaplib("-4 0 1").err(),
+        Some("QAPLIB dimension must be positive")
+    );
+    assert_eq!(
+        QuadraticAssignment::from_qaplib(&format!("{} 0 1", i64::MAX)).err(),
+        Some("QAPLIB has less values than two dimension x dimension matrices")
+    );
+    assert_eq!(
+        QuadraticAssignment::parse_qaplib_solution("-1 10 1").err(),
+        Some("QAPLIB solution dimension must be positive")
+    );
+    assert_eq!(
+        QuadraticAssignment::parse_qaplib_solution(&format!("{} 10 1", i64::MAX)).err(),
+        Some("QAPLIB solution has less locations than dimension")
+    );
+}
@@ -1,8 +1,8 @@
 #[cfg(test)]
 use crate::support::*;
 use genetic_algorithm::genotype::{
-    BinaryGenotype, Genotype, HillClimbGenotype, PermutateGenotype, SupportsGeneCrossover,
-    SupportsPointCrossover,
+    BinaryGenotype, EstimateGenotype, Genotype, HillClimbGenotype, PermutateGenotype,
+    SupportsGeneCrossover, SupportsPointCrossover,
 };
 
 #[test]
@@ -560,3 +560,20 @@ fn calculate_genes_hash() {
     // Different genes should have different hash
     assert_ne!(hash_1, hash_3);
 }
+
+#[test]
+fn estimate_allele_indices() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(3)
+        .build()
+        .unwrap();
+    assert!(genotype.allows_estimation());
+    assert_eq!(genotype.allele_sizes(), vec![2, 2, 2]);
+    assert_eq!(genotype.allele_index(0, &false), Some(0));
+    assert_eq!(genotype.allele_index(0, &true), Some(1));
+
+    let mut chromosome = build::chromosome(vec![true, true, false]);
+    genotype.set_gene_by_allele_index(&mut chromosome, 0, 0);
+    genotype.set_gene_by_allele_index(&mut chromosome, 2, 1);
+    assert_eq!(inspect::chromosome(&chromosome), vec![false, true, true]);
+}
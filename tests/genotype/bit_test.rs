@@ -0,0 +1,231 @@
+#[cfg(test)]
+use crate::support::*;
+use genetic_algorithm::genotype::{
+    BinaryGenotype, BitGenotype, Genotype, HillClimbGenotype, PermutateGenotype,
+    SupportsGeneCrossover, SupportsPointCrossover, TryFromGenotypeBuilderError,
+};
+use genetic_algorithm::strategy::evolve::prelude::*;
+
+#[derive(Clone, Debug)]
+struct CountOnes;
+impl Fitness for CountOnes {
+    type Genotype = BitGenotype;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &FitnessChromosome<Self>,
+        genotype: &FitnessGenotype<Self>,
+    ) -> Option<FitnessValue> {
+        Some(genotype.count_ones(&chromosome.genes) as FitnessValue)
+    }
+}
+
+#[test]
+fn build_error() {
+    assert_eq!(
+        BitGenotype::builder().build().unwrap_err(),
+        TryFromGenotypeBuilderError("BitGenotype requires a genes_size > 0")
+    );
+    assert_eq!(
+        BitGenotype::builder()
+            .with_genes_size(70)
+            .with_seed_genes_list(vec![vec![u64::MAX]])
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError(
+            "BitGenotype seed genes must have words_size words, with the unused bits of the last word zeroed"
+        )
+    );
+    assert_eq!(
+        BitGenotype::builder()
+            .with_genes_size(70)
+            .with_seed_genes_list(vec![vec![u64::MAX, 1 << 6]])
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError(
+            "BitGenotype seed genes must have words_size words, with the unused bits of the last word zeroed"
+        )
+    );
+    assert!(BitGenotype::builder()
+        .with_genes_size(70)
+        .with_seed_genes_list(vec![vec![u64::MAX, (1 << 6) - 1]])
+        .build()
+        .is_ok());
+}
+
+#[test]
+fn set_seed_genes_list_masks_invalid_seeds() {
+    let mut genotype = BitGenotype::builder().with_genes_size(70).build().unwrap();
+    genotype.set_seed_genes_list(vec![vec![u64::MAX], vec![u64::MAX, u64::MAX, u64::MAX]]);
+    assert_eq!(
+        genotype.seed_genes_list(),
+        &vec![vec![u64::MAX, 0], vec![u64::MAX, (1 << 6) - 1]]
+    );
+}
+
+#[test]
+fn helpers() {
+    let genotype = BitGenotype::builder().with_genes_size(130).build().unwrap();
+    assert_eq!(genotype.words_size, 3);
+    assert_eq!(genotype.genes_capacity(), 3);
+
+    let mut genes = genotype.genes_from_bools(&[true, false, true]);
+    assert_eq!(genes, vec![0b101, 0, 0]);
+    genotype.set(&mut genes, 64, true);
+    genotype.set(&mut genes, 129, true);
+    genotype.set(&mut genes, 0, false);
+    genotype.flip(&mut genes, 1);
+    assert_eq!(genes, vec![0b110, 1, 0b10]);
+    assert!(genotype.get(&genes, 129));
+    assert!(!genotype.get(&genes, 128));
+    assert_eq!(genotype.count_ones(&genes), 4);
+    assert_eq!(
+        genotype.ones(&genes).collect::<Vec<_>>(),
+        vec![1, 2, 64, 129]
+    );
+    assert_eq!(genotype.count_ones_masked(&genes, &[0b10, u64::MAX, 0]), 2);
+
+    let bools = genotype.genes_to_bools(&genes);
+    assert_eq!(bools.len(), 130);
+    assert_eq!(genotype.genes_from_bools(&bools), genes);
+}
+
+#[test]
+fn random_genes_factory() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = BitGenotype::builder().with_genes_size(100).build().unwrap();
+
+    for _ in 0..10 {
+        let genes = genotype.random_genes_factory(&mut rng);
+        assert_eq!(genes.len(), 2);
+        // unused bits of the last word stay zero
+        assert_eq!(genes[1] >> 36, 0);
+        assert!(genotype.count_ones(&genes) > 20);
+    }
+}
+
+#[test]
+fn mutate_chromosome_genes() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = BitGenotype::builder().with_genes_size(100).build().unwrap();
+
+    let mut chromosome = build::chromosome(vec![0u64, 0u64]);
+    genotype.mutate_chromosome_genes(10, false, &mut chromosome, &mut rng);
+    assert_eq!(genotype.count_ones(&chromosome.genes), 10);
+    assert_eq!(chromosome.genes[1] >> 36, 0);
+
+    genotype.mutate_chromosome_genes(100, false, &mut chromosome, &mut rng);
+    assert_eq!(genotype.count_ones(&chromosome.genes), 90);
+}
+
+#[test]
+fn crossover_chromosome_genes() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = BitGenotype::builder().with_genes_size(100).build().unwrap();
+
+    let mut father = build::chromosome(genotype.genes_from_bools(&[true; 100]));
+    let mut mother = build::chromosome(genotype.genes_from_bools(&[false; 100]));
+    genotype.crossover_chromosome_genes(30, false, &mut father, &mut mother, &mut rng);
+    assert_eq!(genotype.count_ones(&father.genes), 70);
+    assert_eq!(genotype.count_ones(&mother.genes), 30);
+    // complementary
+    assert!(father
+        .genes
+        .iter()
+        .zip(mother.genes.iter())
+        .all(|(f, m)| f ^ m == f | m));
+}
+
+#[test]
+fn crossover_chromosome_points() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = BitGenotype::builder().with_genes_size(150).build().unwrap();
+
+    let mut father = build::chromosome(genotype.genes_from_bools(&[true; 150]));
+    let mut mother = build::chromosome(genotype.genes_from_bools(&[false; 150]));
+    genotype.crossover_chromosome_points(2, false, &mut father, &mut mother, &mut rng);
+    // a single contiguous range is swapped
+    let father_bools = genotype.genes_to_bools(&father.genes);
+    let mother_bools = genotype.genes_to_bools(&mother.genes);
+    assert!(father_bools
+        .iter()
+        .zip(mother_bools.iter())
+        .all(|(f, m)| f != m));
+    assert!(father_bools.iter().any(|b| !b));
+    assert!(
+        father_bools
+            .windows(2)
+            .filter(|pair| pair[0] != pair[1])
+            .count()
+            <= 2
+    );
+
+    // single point swaps the tail
+    let mut father = build::chromosome(genotype.genes_from_bools(&[true; 150]));
+    let mut mother = build::chromosome(genotype.genes_from_bools(&[false; 150]));
+    genotype.crossover_chromosome_points(1, false, &mut father, &mut mother, &mut rng);
+    let father_bools = genotype.genes_to_bools(&father.genes);
+    let point = father_bools.iter().position(|b| !b).unwrap();
+    assert!(father_bools[point..].iter().all(|b| !b));
+    assert_eq!(genotype.count_ones(&mother.genes), 150 - point);
+    assert_eq!(mother.genes[2] >> 22, 0);
+}
+
+#[test]
+fn neighbouring_population() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = BitGenotype::builder().with_genes_size(3).build().unwrap();
+
+    assert_eq!(genotype.neighbouring_population_size(), BigUint::from(3u32));
+    let chromosome = build::chromosome(vec![0b101u64]);
+    let mut population = Population::new(vec![], true);
+    genotype.fill_neighbouring_population(&chromosome, &mut population, &mut rng);
+    assert_eq!(
+        inspect::population(&population),
+        vec![vec![0b100], vec![0b111], vec![0b001]]
+    );
+}
+
+#[test]
+fn chromosome_permutations() {
+    let genotype = BitGenotype::builder().with_genes_size(2).build().unwrap();
+    let binary_genotype = BinaryGenotype::builder()
+        .with_genes_size(2)
+        .build()
+        .unwrap();
+
+    assert_eq!(genotype.chromosome_permutations_size(), BigUint::from(4u32));
+    // same order as BinaryGenotype
+    assert_eq!(
+        genotype
+            .chromosome_permutations_into_iter(None)
+            .map(|chromosome| genotype.genes_to_bools(&chromosome.genes))
+            .collect::<Vec<_>>(),
+        binary_genotype
+            .chromosome_permutations_into_iter(None)
+            .map(|chromosome| chromosome.genes)
+            .collect::<Vec<_>>(),
+    );
+}
+
+#[test]
+fn evolve() {
+    let genotype = BitGenotype::builder().with_genes_size(200).build().unwrap();
+
+    let evolve = Evolve::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(100)
+        .with_target_fitness_score(200)
+        .with_max_stale_generations(100)
+        .with_fitness(CountOnes)
+        .with_select(SelectTournament::new(0.5, 0.02, 4))
+        .with_crossover(CrossoverUniform::new(0.7, 0.8))
+        .with_mutate(MutateSingleGene::new(0.2))
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+    assert_eq!(evolve.best_fitness_score(), Some(200));
+    assert_eq!(
+        evolve.best_genes().unwrap(),
+        vec![u64::MAX, u64::MAX, u64::MAX, 0xff]
+    );
+}
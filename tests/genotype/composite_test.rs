@@ -0,0 +1,232 @@
+#[cfg(test)]
+use crate::support::*;
+use genetic_algorithm::genotype::{
+    CompositeAllele, CompositeGenotype, Genotype, HillClimbGenotype, PermutateGenotype,
+    SupportsGeneCrossover, SupportsPointCrossover, TryFromGenotypeBuilderError, UniqueGenotype,
+};
+use genetic_algorithm::strategy::evolve::prelude::*;
+use genetic_algorithm::strategy::hill_climb::prelude::{HillClimb, HillClimbVariant};
+use genetic_algorithm::strategy::permutate::prelude::Permutate;
+use CompositeAllele::{First, Second};
+
+type UniqueBinary = CompositeGenotype<UniqueGenotype<u8>, BinaryGenotype>;
+
+fn build_genotype() -> UniqueBinary {
+    CompositeGenotype::new(
+        UniqueGenotype::builder()
+            .with_allele_list(vec![0, 1, 2])
+            .build()
+            .unwrap(),
+        BinaryGenotype::builder()
+            .with_genes_size(2)
+            .build()
+            .unwrap(),
+    )
+}
+
+fn is_valid(genotype: &UniqueBinary, genes: &[CompositeAllele<u8, bool>]) -> bool {
+    let (mut order, mask) = genotype.split_genes(genes);
+    order.sort();
+    genes.len() == 5 && order == vec![0, 1, 2] && mask.len() == 2
+}
+
+#[derive(Clone, Debug)]
+struct OrderAndMask;
+impl Fitness for OrderAndMask {
+    type Genotype = UniqueBinary;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &FitnessChromosome<Self>,
+        genotype: &FitnessGenotype<Self>,
+    ) -> Option<FitnessValue> {
+        let (order, mask) = genotype.split_genes(&chromosome.genes);
+        let ordered = order.windows(2).filter(|pair| pair[0] < pair[1]).count();
+        let masked = mask.iter().filter(|gene| **gene).count();
+        Some((ordered + masked) as FitnessValue)
+    }
+}
+
+#[test]
+fn build_error() {
+    assert_eq!(
+        UniqueBinary::builder().build().unwrap_err(),
+        TryFromGenotypeBuilderError(
+            "CompositeGenotype is constructed from its segments with CompositeGenotype::new(first, second)"
+        )
+    );
+}
+
+#[test]
+fn split_and_join_genes() {
+    let genotype = build_genotype();
+    assert_eq!(genotype.genes_size(), 5);
+
+    let genes = genotype.join_genes(&[2, 0, 1], &[true, false]);
+    assert_eq!(
+        genes,
+        vec![First(2), First(0), First(1), Second(true), Second(false)]
+    );
+    assert_eq!(
+        genotype.split_genes(&genes),
+        (vec![2, 0, 1], vec![true, false])
+    );
+}
+
+#[test]
+fn random_genes_factory() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = build_genotype();
+    for _ in 0..10 {
+        let genes = genotype.random_genes_factory(&mut rng);
+        assert!(is_valid(&genotype, &genes));
+    }
+
+    let genotype =
+        build_genotype().with_seed_genes_list(vec![genotype.join_genes(&[2, 1, 0], &[true, true])]);
+    assert_eq!(
+        genotype.random_genes_factory(&mut rng),
+        vec![First(2), First(1), First(0), Second(true), Second(true)]
+    );
+}
+
+#[test]
+fn mutate_chromosome_genes() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = build_genotype();
+
+    let mut chromosome = build::chromosome(genotype.join_genes(&[0, 1, 2], &[false, false]));
+    let genes_hash = chromosome.genes_hash;
+    genotype.mutate_chromosome_genes(1, true, &mut chromosome, &mut rng);
+    assert!(is_valid(&genotype, &chromosome.genes));
+    assert_ne!(chromosome.genes_hash, genes_hash);
+
+    for _ in 0..100 {
+        genotype.mutate_chromosome_genes(3, false, &mut chromosome, &mut rng);
+        assert!(is_valid(&genotype, &chromosome.genes));
+    }
+}
+
+#[test]
+fn crossover_chromosome_genes_and_points() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = build_genotype();
+
+    let mut father = build::chromosome(genotype.join_genes(&[0, 1, 2], &[false, false]));
+    let mut mother = build::chromosome(genotype.join_genes(&[2, 1, 0], &[true, true]));
+    for _ in 0..100 {
+        genotype.crossover_chromosome_genes(2, false, &mut father, &mut mother, &mut rng);
+        assert!(is_valid(&genotype, &father.genes));
+        assert!(is_valid(&genotype, &mother.genes));
+        genotype.crossover_chromosome_points(1, true, &mut father, &mut mother, &mut rng);
+        assert!(is_valid(&genotype, &father.genes));
+        assert!(is_valid(&genotype, &mother.genes));
+    }
+    // the unique segments are swapped as a whole, the binary genes are swapped per gene
+    let (father_order, father_mask) = genotype.split_genes(&father.genes);
+    let (mother_order, mother_mask) = genotype.split_genes(&mother.genes);
+    let mut orders = vec![father_order, mother_order];
+    orders.sort();
+    assert_eq!(orders, vec![vec![0, 1, 2], vec![2, 1, 0]]);
+    assert_eq!(
+        father_mask
+            .iter()
+            .zip(mother_mask.iter())
+            .filter(|(a, b)| a != b)
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn neighbouring_population() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = build_genotype();
+
+    assert_eq!(genotype.neighbouring_population_size(), BigUint::from(5u32));
+    let chromosome = build::chromosome(genotype.join_genes(&[0, 1, 2], &[false, false]));
+    let mut population = Population::new(vec![], true);
+    genotype.fill_neighbouring_population(&chromosome, &mut population, &mut rng);
+    assert_eq!(
+        population
+            .chromosomes
+            .iter()
+            .map(|chromosome| genotype.split_genes(&chromosome.genes))
+            .collect::<Vec<_>>(),
+        vec![
+            (vec![1, 0, 2], vec![false, false]),
+            (vec![2, 1, 0], vec![false, false]),
+            (vec![0, 2, 1], vec![false, false]),
+            (vec![0, 1, 2], vec![true, false]),
+            (vec![0, 1, 2], vec![false, true]),
+        ]
+    );
+}
+
+#[test]
+fn chromosome_permutations() {
+    let genotype = build_genotype();
+
+    assert!(genotype.allows_permutation());
+    assert_eq!(
+        genotype.chromosome_permutations_size(),
+        BigUint::from(24u32)
+    );
+    let permutations: Vec<_> = genotype.chromosome_permutations_into_iter(None).collect();
+    assert_eq!(permutations.len(), 24);
+    assert!(permutations
+        .iter()
+        .all(|chromosome| is_valid(&genotype, &chromosome.genes)));
+    assert_eq!(
+        permutations[..5]
+            .iter()
+            .map(|chromosome| genotype.split_genes(&chromosome.genes))
+            .collect::<Vec<_>>(),
+        vec![
+            (vec![0, 1, 2], vec![true, true]),
+            (vec![0, 1, 2], vec![true, false]),
+            (vec![0, 1, 2], vec![false, true]),
+            (vec![0, 1, 2], vec![false, false]),
+            (vec![0, 2, 1], vec![true, true]),
+        ]
+    );
+}
+
+#[test]
+fn strategies() {
+    let genotype = build_genotype();
+
+    let evolve = Evolve::builder()
+        .with_genotype(genotype.clone())
+        .with_target_population_size(20)
+        .with_target_fitness_score(4)
+        .with_max_stale_generations(100)
+        .with_fitness(OrderAndMask)
+        .with_select(SelectElite::new(0.5, 0.02))
+        .with_crossover(CrossoverUniform::new(0.7, 0.8))
+        .with_mutate(MutateSingleGene::new(0.2))
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+    assert_eq!(evolve.best_fitness_score(), Some(4));
+    assert_eq!(
+        genotype.split_genes(&evolve.best_genes().unwrap()),
+        (vec![0, 1, 2], vec![true, true])
+    );
+
+    let hill_climb = HillClimb::builder()
+        .with_genotype(genotype.clone())
+        .with_variant(HillClimbVariant::SteepestAscent)
+        .with_max_stale_generations(10)
+        .with_fitness(OrderAndMask)
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+    assert_eq!(hill_climb.best_fitness_score(), Some(4));
+
+    let permutate = Permutate::builder()
+        .with_genotype(genotype.clone())
+        .with_fitness(OrderAndMask)
+        .call()
+        .unwrap();
+    assert_eq!(permutate.best_fitness_score(), Some(4));
+}
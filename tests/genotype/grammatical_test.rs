@@ -0,0 +1,285 @@
+#[cfg(test)]
+use crate::support::*;
+use genetic_algorithm::genotype::{
+    Genotype, Grammar, GrammarDerivationNode, GrammarMappingError, GrammarSymbol,
+    GrammaticalGenotype, RangeGenotype, TryFromGenotypeBuilderError, TryFromGrammarError,
+};
+use genetic_algorithm::strategy::evolve::prelude::*;
+
+const EXPRESSION_BNF: &str = r#"
+    # arithmetic over a single variable
+    <expr> ::= <expr> <op> <expr>
+             | "(" <expr> ")"
+             | <var>
+    <op>   ::= + | - | " * "
+    <var>  ::= x | 1.0
+"#;
+
+#[derive(Clone, Debug)]
+struct SumOfDigits;
+impl Fitness for SumOfDigits {
+    type Genotype = GrammaticalGenotype<u8>;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &FitnessChromosome<Self>,
+        genotype: &FitnessGenotype<Self>,
+    ) -> Option<FitnessValue> {
+        let phenotype = genotype.phenotype(&chromosome.genes)?;
+        let sum = phenotype
+            .split('+')
+            .map(|digit| digit.parse::<isize>().unwrap())
+            .sum::<isize>();
+        Some(-(sum - 23).abs())
+    }
+}
+
+fn u8_codons(genes_size: usize) -> RangeGenotype<u8> {
+    RangeGenotype::builder()
+        .with_genes_size(genes_size)
+        .with_allele_range(0..=u8::MAX)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn from_bnf() {
+    let grammar = Grammar::from_bnf(EXPRESSION_BNF).unwrap();
+    assert_eq!(
+        grammar
+            .rules
+            .iter()
+            .map(|rule| rule.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["expr", "op", "var"]
+    );
+    assert_eq!(
+        grammar.rules[0].productions,
+        vec![
+            vec![
+                GrammarSymbol::NonTerminal(0),
+                GrammarSymbol::NonTerminal(1),
+                GrammarSymbol::NonTerminal(0),
+            ],
+            vec![
+                GrammarSymbol::Terminal("(".to_string()),
+                GrammarSymbol::NonTerminal(0),
+                GrammarSymbol::Terminal(")".to_string()),
+            ],
+            vec![GrammarSymbol::NonTerminal(2)],
+        ]
+    );
+    assert_eq!(
+        grammar.rules[1].productions,
+        vec![
+            vec![GrammarSymbol::Terminal("+".to_string())],
+            vec![GrammarSymbol::Terminal("-".to_string())],
+            vec![GrammarSymbol::Terminal(" * ".to_string())],
+        ]
+    );
+    assert_eq!(grammar.max_wraps, 2);
+    assert_eq!(grammar.max_depth, 64);
+
+    let grammar = Grammar::from_bnf("<list> ::= a <list> | ''").unwrap();
+    assert_eq!(
+        grammar.rules[0].productions[1],
+        vec![GrammarSymbol::Terminal("".to_string())]
+    );
+}
+
+#[test]
+fn from_bnf_errors() {
+    assert_eq!(
+        Grammar::from_bnf("# only comments").unwrap_err(),
+        TryFromGrammarError("Grammar requires at least one rule")
+    );
+    assert_eq!(
+        Grammar::from_bnf("<a> ::= <b> | x").unwrap_err(),
+        TryFromGrammarError("Grammar refers to an undefined non-terminal")
+    );
+    assert_eq!(
+        Grammar::from_bnf("<a> ::= x\n<a> ::= y").unwrap_err(),
+        TryFromGrammarError("Grammar rules must be defined only once (use | for alternatives)")
+    );
+    assert_eq!(
+        Grammar::from_bnf("<a> ::= x | | y").unwrap_err(),
+        TryFromGrammarError(
+            "Grammar productions must not be empty (use \"\" for an empty production)"
+        )
+    );
+    assert_eq!(
+        Grammar::from_bnf("a ::= x").unwrap_err(),
+        TryFromGrammarError("Grammar rule names must have the form <name>")
+    );
+    assert_eq!(
+        Grammar::from_bnf("| x").unwrap_err(),
+        TryFromGrammarError("Grammar continuation line (starting with |) without a preceding rule")
+    );
+}
+
+#[test]
+fn map() {
+    let grammar = Grammar::from_bnf(EXPRESSION_BNF).unwrap();
+
+    // <expr> <op> <expr> (0), (<expr>) (4), <var> (2), x (0), * (5), <var> (8), 1.0 (3)
+    let derivation = grammar.map(&[0u8, 4, 2, 0, 5, 8, 3]).unwrap();
+    assert_eq!(derivation.phenotype(), "(x) * 1.0");
+    assert_eq!(derivation.codons_used, 7);
+    assert_eq!(derivation.wraps, 0);
+
+    // the derivation tree keeps the chosen productions
+    match derivation.root {
+        GrammarDerivationNode::NonTerminal {
+            rule_index,
+            production_index,
+            ref children,
+        } => {
+            assert_eq!((rule_index, production_index), (0, 0));
+            assert_eq!(children.len(), 3);
+        }
+        _ => panic!("root must be a non-terminal"),
+    }
+}
+
+#[test]
+fn map_wraps() {
+    let grammar = Grammar::from_bnf(EXPRESSION_BNF).unwrap();
+
+    // <expr> <op> <expr> (0), <var> (2), 1.0 (1), wrap: + (0), <var> (2), 1.0 (1)
+    let derivation = grammar.map(&[0u8, 2, 1]).unwrap();
+    assert_eq!(derivation.phenotype(), "1.0+1.0");
+    assert_eq!(derivation.codons_used, 6);
+    assert_eq!(derivation.wraps, 1);
+
+    assert_eq!(
+        grammar.clone().with_max_wraps(0).map(&[0u8, 2, 1]),
+        Err(GrammarMappingError::MaxWrapsExceeded)
+    );
+    // always choosing <expr> <op> <expr> never completes
+    assert_eq!(
+        grammar.map(&[0u8, 3]),
+        Err(GrammarMappingError::MaxWrapsExceeded)
+    );
+    assert_eq!(grammar.map::<u8>(&[]), Err(GrammarMappingError::NoCodons));
+}
+
+#[test]
+fn map_max_depth() {
+    let grammar = Grammar::from_bnf(EXPRESSION_BNF).unwrap().with_max_depth(3);
+
+    // ((((x)))) needs a depth of 5
+    assert_eq!(
+        grammar.map(&[1u8, 1, 1, 1, 2, 0]),
+        Err(GrammarMappingError::MaxDepthExceeded)
+    );
+    assert_eq!(grammar.map(&[1u8, 1, 2, 0]).unwrap().phenotype(), "((x))");
+}
+
+#[test]
+fn build_errors() {
+    assert_eq!(
+        GrammaticalGenotype::<u8>::builder()
+            .with_genes_size(10)
+            .with_allele_range(0..=u8::MAX)
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError("GrammaticalGenotype requires a grammar")
+    );
+    assert_eq!(
+        GrammaticalGenotype::<u8>::builder()
+            .with_grammar(Grammar::from_bnf(EXPRESSION_BNF).unwrap())
+            .with_allele_range(0..=u8::MAX)
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError("GrammaticalGenotype requires a genes_size > 0")
+    );
+    assert_eq!(
+        GrammaticalGenotype::<u8>::builder()
+            .with_grammar(Grammar::from_bnf(EXPRESSION_BNF).unwrap())
+            .with_genes_size(10)
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError("GrammaticalGenotype requires a allele_range")
+    );
+}
+
+#[test]
+fn build() {
+    let genotype = GrammaticalGenotype::<u8>::builder()
+        .with_grammar(Grammar::from_bnf(EXPRESSION_BNF).unwrap().with_max_wraps(1))
+        .with_genes_size(8)
+        .with_allele_range(0..=u8::MAX)
+        .with_genes_hashing(false)
+        .build()
+        .unwrap();
+    assert_eq!(genotype.genes_size(), 8);
+    assert_eq!(genotype.grammar.max_wraps, 1);
+    assert_eq!(genotype.codons.allele_range, 0..=u8::MAX);
+    assert!(!genotype.genes_hashing());
+    assert_eq!(
+        genotype.phenotype(&[0, 2, 0, 1, 2, 1]),
+        Some("x-1.0".to_string())
+    );
+}
+
+#[test]
+fn phenotype() {
+    let genotype =
+        GrammaticalGenotype::new(Grammar::from_bnf(EXPRESSION_BNF).unwrap(), u8_codons(8));
+    assert_eq!(genotype.genes_size(), 8);
+    assert_eq!(
+        genotype.phenotype(&[0, 2, 0, 1, 2, 1]),
+        Some("x-1.0".to_string())
+    );
+    assert_eq!(genotype.phenotype(&[0, 3]), None);
+}
+
+#[test]
+fn mutate_chromosome_genes() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype =
+        GrammaticalGenotype::new(Grammar::from_bnf(EXPRESSION_BNF).unwrap(), u8_codons(8));
+    let mut chromosome = build::chromosome(vec![0u8; 8]);
+
+    genotype.mutate_chromosome_genes(3, false, &mut chromosome, &mut rng);
+    assert_eq!(
+        chromosome.genes.iter().filter(|codon| **codon != 0).count(),
+        3
+    );
+}
+
+#[test]
+fn evolve() {
+    let genotype = GrammaticalGenotype::new(
+        Grammar::from_bnf(
+            r#"
+            <sum>   ::= <sum> + <digit> | <digit>
+            <digit> ::= 1 | 2 | 3 | 4 | 5
+            "#,
+        )
+        .unwrap(),
+        u8_codons(20),
+    );
+    let evolve = Evolve::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(100)
+        .with_max_stale_generations(100)
+        .with_target_fitness_score(0)
+        .with_fitness(SumOfDigits)
+        .with_select(SelectTournament::new(0.5, 0.02, 4))
+        .with_crossover(CrossoverSinglePoint::new(0.7, 0.8))
+        .with_mutate(MutateSingleGene::new(0.2))
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    assert_eq!(evolve.best_fitness_score(), Some(0));
+    let best_genes = evolve.best_genes().unwrap();
+    let phenotype = evolve.genotype.phenotype(&best_genes).unwrap();
+    assert_eq!(
+        phenotype
+            .split('+')
+            .map(|digit| digit.parse::<isize>().unwrap())
+            .sum::<isize>(),
+        23
+    );
+}
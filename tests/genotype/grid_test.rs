@@ -0,0 +1,269 @@
+#[cfg(test)]
+use crate::support::*;
+use genetic_algorithm::genotype::{
+    BinaryGenotype, Genotype, GridGenotype, HillClimbGenotype, ListGenotype, PermutateGenotype,
+    SupportsGeneCrossover, SupportsPointCrossover, TryFromGenotypeBuilderError,
+};
+use genetic_algorithm::strategy::evolve::prelude::*;
+
+#[derive(Clone, Debug)]
+struct SameGroupAdjacency;
+impl Fitness for SameGroupAdjacency {
+    type Genotype = GridGenotype<ListGenotype<usize>>;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &FitnessChromosome<Self>,
+        genotype: &FitnessGenotype<Self>,
+    ) -> Option<FitnessValue> {
+        let genes = &chromosome.genes;
+        let score = (0..genotype.genes_size())
+            .map(|index| {
+                genotype
+                    .adjacent_indices(index)
+                    .into_iter()
+                    .filter(|adjacent_index| genes[*adjacent_index] == genes[index])
+                    .count()
+            })
+            .sum::<usize>();
+        Some(score as FitnessValue)
+    }
+}
+
+fn list_grid(rows: usize, columns: usize, allele_list: Vec<usize>) -> GridGenotype<ListGenotype> {
+    GridGenotype::new(
+        rows,
+        columns,
+        ListGenotype::builder()
+            .with_genes_size(rows * columns)
+            .with_allele_list(allele_list)
+            .build()
+            .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn build_error() {
+    assert_eq!(
+        GridGenotype::<BinaryGenotype>::builder()
+            .with_genes_size(4)
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError(
+            "GridGenotype is constructed from its cells genotype with GridGenotype::new(rows, columns, cells)"
+        )
+    );
+}
+
+#[test]
+fn new_errors() {
+    assert_eq!(
+        GridGenotype::new(
+            2,
+            3,
+            BinaryGenotype::builder()
+                .with_genes_size(5)
+                .build()
+                .unwrap(),
+        )
+        .unwrap_err(),
+        TryFromGenotypeBuilderError(
+            "GridGenotype requires a cells genotype with genes_size of rows * columns"
+        )
+    );
+    assert_eq!(
+        GridGenotype::new(
+            0,
+            3,
+            BinaryGenotype::builder()
+                .with_genes_size(3)
+                .build()
+                .unwrap(),
+        )
+        .unwrap_err(),
+        TryFromGenotypeBuilderError("GridGenotype requires rows > 0 and columns > 0")
+    );
+}
+
+#[test]
+fn helpers() {
+    let genotype = list_grid(3, 4, (0..12).collect());
+    let mut genes: Vec<usize> = (0..12).collect();
+
+    assert_eq!(genotype.genes_size(), 12);
+    assert_eq!(genotype.index(2, 1), 9);
+    assert_eq!(genotype.position(9), (2, 1));
+    assert_eq!(genotype.get(&genes, 1, 3), 7);
+    assert_eq!(genotype.row(&genes, 1), &[4, 5, 6, 7]);
+    assert_eq!(
+        genotype.column(&genes, 2).collect::<Vec<_>>(),
+        vec![2, 6, 10]
+    );
+    assert_eq!(genotype.adjacent_indices(0), vec![1, 4]);
+    assert_eq!(genotype.adjacent_indices(5), vec![1, 4, 6, 9]);
+    assert_eq!(genotype.adjacent_indices(11), vec![7, 10]);
+
+    genotype.set(&mut genes, 0, 0, 99);
+    assert_eq!(genes[0], 99);
+    genotype.swap_rows(&mut genes, 2, 0);
+    assert_eq!(genes, vec![8, 9, 10, 11, 4, 5, 6, 7, 99, 1, 2, 3]);
+    genotype.swap_columns(&mut genes, 0, 3);
+    assert_eq!(genes, vec![11, 9, 10, 8, 7, 5, 6, 4, 3, 1, 2, 99]);
+}
+
+#[test]
+fn mutate_chromosome_line_swaps() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = list_grid(3, 4, (0..12).collect()).with_swap_probability(1.0);
+    let original: Vec<usize> = (0..12).collect();
+    let mut chromosome = build::chromosome(original.clone());
+
+    for _ in 0..20 {
+        genotype.mutate_chromosome_genes(2, true, &mut chromosome, &mut rng);
+        // rows and columns only move as a whole
+        for row in 0..3 {
+            let mut values = genotype.row(&chromosome.genes, row).to_vec();
+            values.sort();
+            assert!((0..3).any(|original_row| {
+                let mut original_values = genotype.row(&original, original_row).to_vec();
+                original_values.sort();
+                values == original_values
+            }));
+        }
+        for column in 0..4 {
+            let mut values = genotype
+                .column(&chromosome.genes, column)
+                .collect::<Vec<_>>();
+            values.sort();
+            assert!((0..4).any(|original_column| {
+                let mut original_values = genotype
+                    .column(&original, original_column)
+                    .collect::<Vec<_>>();
+                original_values.sort();
+                values == original_values
+            }));
+        }
+    }
+    assert_ne!(chromosome.genes, original);
+}
+
+#[test]
+fn mutate_chromosome_cells() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = list_grid(3, 4, vec![0, 1]).with_swap_probability(0.0);
+    let mut chromosome = build::chromosome(vec![0; 12]);
+
+    genotype.mutate_chromosome_genes(1, true, &mut chromosome, &mut rng);
+    assert!(chromosome.genes.iter().filter(|value| **value == 1).count() <= 1);
+}
+
+#[test]
+fn crossover_chromosome_points_block() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = list_grid(5, 6, vec![0, 1]);
+
+    for _ in 0..20 {
+        let mut father = build::chromosome(vec![0; 30]);
+        let mut mother = build::chromosome(vec![1; 30]);
+        genotype.crossover_chromosome_points(1, true, &mut father, &mut mother, &mut rng);
+
+        // the swapped cells form a single rectangular block
+        let swapped: Vec<(usize, usize)> = (0..30)
+            .filter(|index| father.genes[*index] == 1)
+            .map(|index| genotype.position(index))
+            .collect();
+        let row_span = swapped.iter().map(|(row, _)| row).max().unwrap()
+            - swapped.iter().map(|(row, _)| row).min().unwrap()
+            + 1;
+        let column_span = swapped.iter().map(|(_, column)| column).max().unwrap()
+            - swapped.iter().map(|(_, column)| column).min().unwrap()
+            + 1;
+        assert_eq!(swapped.len(), row_span * column_span);
+        assert!(mother
+            .genes
+            .iter()
+            .zip(father.genes.iter())
+            .all(|(m, f)| m != f));
+    }
+}
+
+#[test]
+fn crossover_chromosome_genes() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = list_grid(2, 3, vec![0, 1]);
+    let mut father = build::chromosome(vec![0; 6]);
+    let mut mother = build::chromosome(vec![1; 6]);
+
+    genotype.crossover_chromosome_genes(2, false, &mut father, &mut mother, &mut rng);
+    assert_eq!(father.genes.iter().sum::<usize>(), 2);
+    assert_eq!(mother.genes.iter().sum::<usize>(), 4);
+}
+
+#[test]
+fn neighbouring_population() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = GridGenotype::new(
+        2,
+        2,
+        BinaryGenotype::builder()
+            .with_genes_size(4)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(genotype.neighbouring_population_size(), BigUint::from(8u32));
+
+    let chromosome = build::chromosome(vec![true, false, true, true]);
+    let mut population = Population::new(vec![], true);
+    genotype.fill_neighbouring_population(&chromosome, &mut population, &mut rng);
+    assert_eq!(
+        inspect::population(&population),
+        vec![
+            // value neighbours
+            vec![false, false, true, true],
+            vec![true, true, true, true],
+            vec![true, false, false, true],
+            vec![true, false, true, false],
+            // adjacent swaps, skipping the equal values
+            vec![false, true, true, true],
+            vec![true, true, true, false],
+        ]
+    );
+}
+
+#[test]
+fn chromosome_permutations() {
+    let genotype = GridGenotype::new(
+        2,
+        2,
+        BinaryGenotype::builder()
+            .with_genes_size(4)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        genotype.chromosome_permutations_size(),
+        BigUint::from(16u32)
+    );
+    assert_eq!(genotype.chromosome_permutations_into_iter(None).count(), 16);
+}
+
+#[test]
+fn evolve_block_crossover() {
+    let genotype = list_grid(4, 6, vec![0, 1, 2]);
+    let evolve = Evolve::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(100)
+        .with_max_stale_generations(100)
+        .with_fitness(SameGroupAdjacency)
+        .with_select(SelectTournament::new(0.5, 0.02, 4))
+        .with_crossover(CrossoverMultiPoint::new(0.7, 0.8, 2, true))
+        .with_mutate(MutateSingleGene::new(0.2))
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    // a fully homogeneous grid has 2 * (4 * 5 + 3 * 6) = 76 same group adjacencies
+    assert!(evolve.best_fitness_score().unwrap() >= 60);
+}
@@ -1,8 +1,8 @@
 #[cfg(test)]
 use crate::support::*;
 use genetic_algorithm::genotype::{
-    Genotype, HillClimbGenotype, ListGenotype, PermutateGenotype, SupportsGeneCrossover,
-    SupportsPointCrossover,
+    GeneConstraint, Genotype, HillClimbGenotype, ListGenotype, PermutateGenotype,
+    SupportsGeneCrossover, SupportsPointCrossover, TryFromGenotypeBuilderError,
 };
 
 #[test]
@@ -245,3 +245,117 @@ fn integer_calculate_genes_hash() {
     // the sign on does not matter (-0 == 0)
     assert_eq!(hash_1, hash_3);
 }
+
+#[test]
+fn allele_list_weights_build_errors() {
+    assert_eq!(
+        ListGenotype::builder()
+            .with_genes_size(5)
+            .with_allele_list(vec![1, 2, 3])
+            .with_allele_list_weights(vec![1.0, 2.0])
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError(
+            "ListGenotype allele_list_weights length must match allele_list length"
+        )
+    );
+    assert_eq!(
+        ListGenotype::builder()
+            .with_genes_size(5)
+            .with_allele_list(vec![1, 2, 3])
+            .with_allele_list_weights(vec![0.0, 0.0, 0.0])
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError(
+            "ListGenotype allele_list_weights must be non-negative with a positive sum"
+        )
+    );
+}
+
+#[test]
+fn allele_list_weights_random_genes_factory_and_mutate() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = ListGenotype::builder()
+        .with_genes_size(1000)
+        .with_allele_list(vec![1, 2, 3])
+        .with_allele_list_weights(vec![0.0, 9.0, 1.0])
+        .build()
+        .unwrap();
+
+    let mut chromosome = Chromosome::new(genotype.random_genes_factory(&mut rng));
+    let count = |chromosome: &Chromosome<usize>, value: usize| {
+        chromosome
+            .genes
+            .iter()
+            .filter(|gene| **gene == value)
+            .count()
+    };
+    assert_eq!(count(&chromosome, 1), 0);
+    assert!(count(&chromosome, 2) > 850);
+    assert!(count(&chromosome, 3) > 50);
+
+    chromosome.genes = vec![1; 1000];
+    genotype.mutate_chromosome_genes(1000, false, &mut chromosome, &mut rng);
+    assert_eq!(count(&chromosome, 1), 0);
+    assert!(count(&chromosome, 2) > 850);
+}
+
+#[test]
+fn ordinal_mutation() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = ListGenotype::builder()
+        .with_genes_size(5)
+        .with_allele_list(vec![10, 20, 30, 40])
+        .with_ordinal_mutation(true)
+        .build()
+        .unwrap();
+
+    let mut chromosome: Chromosome<usize> = build::chromosome(vec![10, 20, 30, 40, 40]);
+    for _ in 0..100 {
+        let genes = chromosome.genes.clone();
+        genotype.mutate_chromosome_genes(1, true, &mut chromosome, &mut rng);
+        let changed: Vec<_> = genes
+            .iter()
+            .zip(chromosome.genes.iter())
+            .filter(|(before, after)| before != after)
+            .collect();
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].0.abs_diff(*changed[0].1), 10);
+    }
+
+    assert_eq!(
+        genotype.neighbouring_population_size(),
+        BigUint::from(10u32)
+    );
+    let chromosome = build::chromosome(vec![10, 20, 30, 40, 40]);
+    let mut population = Population::new(vec![], true);
+    genotype.fill_neighbouring_population(&chromosome, &mut population, &mut rng);
+    assert_eq!(
+        inspect::population(&population),
+        vec![
+            vec![20, 20, 30, 40, 40],
+            vec![10, 10, 30, 40, 40],
+            vec![10, 30, 30, 40, 40],
+            vec![10, 20, 20, 40, 40],
+            vec![10, 20, 40, 40, 40],
+            vec![10, 20, 30, 30, 40],
+            vec![10, 20, 30, 40, 30],
+        ]
+    );
+}
+
+#[test]
+fn gene_constraints_build_error() {
+    assert_eq!(
+        ListGenotype::builder()
+            .with_genes_size(2)
+            .with_allele_list(vec![0, 1, 2])
+            .with_gene_constraints(vec![GeneConstraint::ForbiddenPair {
+                gene_indices: (0, 1),
+                values: (1, 1),
+            }])
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError("ListGenotype does not support gene_constraints")
+    );
+}
@@ -1,7 +1,15 @@
 pub mod binary_test;
+pub mod bit_test;
+pub mod composite_test;
+pub mod grammatical_test;
+pub mod grid_test;
 pub mod list_test;
 pub mod multi_list_test;
 pub mod multi_range_test;
 pub mod multi_unique_test;
+pub mod partial_permutation_test;
 pub mod range_test;
+pub mod subset_test;
+pub mod tree_test;
 pub mod unique_test;
+pub mod variable_list_test;
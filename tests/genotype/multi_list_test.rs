@@ -1,8 +1,8 @@
 #[cfg(test)]
 use crate::support::*;
 use genetic_algorithm::genotype::{
-    Genotype, HillClimbGenotype, MultiListGenotype, PermutateGenotype, SupportsGeneCrossover,
-    SupportsPointCrossover,
+    EstimateGenotype, GeneConstraint, Genotype, HillClimbGenotype, MultiListGenotype,
+    PermutateGenotype, SupportsGeneCrossover, SupportsPointCrossover, TryFromGenotypeBuilderError,
 };
 
 #[test]
@@ -261,3 +261,193 @@ fn chromosome_permutations_genes_size_huge() {
         vec![vec![0; 10]]
     )
 }
+
+#[test]
+fn estimate_allele_indices() {
+    let genotype = MultiListGenotype::builder()
+        .with_allele_lists(vec![vec![5, 6], vec![7, 8, 9], vec![10]])
+        .build()
+        .unwrap();
+    assert!(genotype.allows_estimation());
+    assert_eq!(genotype.allele_sizes(), vec![2, 3, 1]);
+    assert_eq!(genotype.allele_index(1, &9), Some(2));
+    assert_eq!(genotype.allele_index(1, &5), None);
+
+    let mut chromosome = build::chromosome(vec![5, 7, 10]);
+    genotype.set_gene_by_allele_index(&mut chromosome, 0, 1);
+    genotype.set_gene_by_allele_index(&mut chromosome, 1, 2);
+    assert_eq!(inspect::chromosome(&chromosome), vec![6, 9, 10]);
+}
+
+#[test]
+fn allele_lists_weights_and_ordinal_mutations_build_errors() {
+    assert_eq!(
+        MultiListGenotype::builder()
+            .with_allele_lists(vec![vec![0, 1], vec![0, 1, 2]])
+            .with_allele_lists_weights(vec![vec![1.0, 1.0], vec![1.0, 1.0]])
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError(
+            "MultiListGenotype allele_lists_weights lengths must match allele_lists lengths"
+        )
+    );
+    assert_eq!(
+        MultiListGenotype::builder()
+            .with_allele_lists(vec![vec![0, 1], vec![0, 1, 2]])
+            .with_allele_lists_weights(vec![vec![1.0, 1.0], vec![1.0, -1.0, 1.0]])
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError(
+            "MultiListGenotype allele_lists_weights must be non-negative with a positive sum per gene"
+        )
+    );
+    assert_eq!(
+        MultiListGenotype::builder()
+            .with_allele_lists(vec![vec![0, 1], vec![0, 1, 2]])
+            .with_ordinal_mutations(vec![true])
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError(
+            "MultiListGenotype ordinal_mutations length must match allele_lists length"
+        )
+    );
+}
+
+#[test]
+fn allele_lists_weights_and_ordinal_mutations() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = MultiListGenotype::builder()
+        .with_allele_lists(vec![vec![0, 1, 2], vec![0, 1, 2, 3, 4]])
+        .with_allele_lists_weights(vec![vec![0.0, 1.0, 0.0], vec![1.0; 5]])
+        .with_ordinal_mutations(vec![false, true])
+        .build()
+        .unwrap();
+
+    for _ in 0..100 {
+        let chromosome = Chromosome::new(genotype.random_genes_factory(&mut rng));
+        assert_eq!(chromosome.genes[0], 1);
+    }
+
+    let mut chromosome: Chromosome<usize> = build::chromosome(vec![1, 0]);
+    for _ in 0..100 {
+        let genes = chromosome.genes.clone();
+        genotype.mutate_chromosome_genes(1, true, &mut chromosome, &mut rng);
+        assert_eq!(chromosome.genes[0], 1);
+        assert!(genes[1].abs_diff(chromosome.genes[1]) <= 1);
+    }
+
+    assert_eq!(genotype.neighbouring_population_size(), BigUint::from(4u32));
+    let chromosome = build::chromosome(vec![0, 4]);
+    let mut population = Population::new(vec![], true);
+    genotype.fill_neighbouring_population(&chromosome, &mut population, &mut rng);
+    assert_eq!(
+        inspect::population(&population),
+        vec![vec![1, 4], vec![2, 4], vec![0, 3]]
+    );
+}
+
+#[test]
+fn gene_constraints_build_errors() {
+    assert_eq!(
+        MultiListGenotype::builder()
+            .with_allele_lists(vec![vec![0, 1], vec![0, 1, 2]])
+            .with_gene_constraints(vec![GeneConstraint::sum_to(vec![0, 1], 1.0)])
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError("MultiListGenotype does not support Linear gene_constraints")
+    );
+    assert_eq!(
+        MultiListGenotype::builder()
+            .with_allele_lists(vec![vec![0, 1], vec![0, 1, 2]])
+            .with_gene_constraints(vec![GeneConstraint::ForbiddenPair {
+                gene_indices: (0, 2),
+                values: (1, 1),
+            }])
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError(
+            "MultiListGenotype gene_constraints must refer to distinct gene indices within genes_size"
+        )
+    );
+    assert_eq!(
+        MultiListGenotype::builder()
+            .with_allele_lists(vec![vec![0], vec![0]])
+            .with_gene_constraints(vec![GeneConstraint::ForbiddenPair {
+                gene_indices: (0, 1),
+                values: (0, 0),
+            }])
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError(
+            "MultiListGenotype could not generate random genes satisfying the gene_constraints"
+        )
+    );
+}
+
+#[test]
+fn gene_constraints() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = MultiListGenotype::builder()
+        .with_allele_lists(vec![vec![0, 1], vec![0, 1, 2, 3], vec![0, 1, 2]])
+        .with_gene_constraints(vec![
+            GeneConstraint::ActiveIf {
+                gene_index: 2,
+                condition_gene_index: 0,
+                condition_values: vec![1],
+                inactive_value: 0,
+            },
+            GeneConstraint::ForbiddenPair {
+                gene_indices: (0, 1),
+                values: (1, 3),
+            },
+        ])
+        .build()
+        .unwrap();
+
+    let mut father = Chromosome::new(genotype.random_genes_factory(&mut rng));
+    let mut mother = Chromosome::new(genotype.random_genes_factory(&mut rng));
+    for _ in 0..100 {
+        genotype.mutate_chromosome_genes(2, true, &mut father, &mut rng);
+        genotype.mutate_chromosome_genes(2, false, &mut mother, &mut rng);
+        assert!(genotype.is_feasible(&father.genes));
+        assert!(genotype.is_feasible(&mother.genes));
+        genotype.crossover_chromosome_genes(2, true, &mut father, &mut mother, &mut rng);
+        assert!(genotype.is_feasible(&father.genes));
+        assert!(genotype.is_feasible(&mother.genes));
+        genotype.crossover_chromosome_points(1, true, &mut father, &mut mother, &mut rng);
+        assert!(genotype.is_feasible(&father.genes));
+        assert!(genotype.is_feasible(&mother.genes));
+    }
+
+    // the change of the condition gene deactivates gene 2
+    let chromosome = build::chromosome(vec![1, 0, 2]);
+    let mut population = Population::new(vec![], true);
+    genotype.fill_neighbouring_population(&chromosome, &mut population, &mut rng);
+    assert_eq!(
+        inspect::population(&population),
+        vec![
+            vec![0, 0, 0],
+            vec![1, 1, 2],
+            vec![1, 2, 2],
+            vec![1, 0, 0],
+            vec![1, 0, 1],
+        ]
+    );
+
+    // inactive gene 2 has no neighbours
+    let chromosome = build::chromosome(vec![0, 3, 0]);
+    let mut population = Population::new(vec![], true);
+    genotype.fill_neighbouring_population(&chromosome, &mut population, &mut rng);
+    assert_eq!(
+        inspect::population(&population),
+        vec![vec![0, 0, 0], vec![0, 1, 0], vec![0, 2, 0]]
+    );
+
+    // (0, _, 0) 4x + (1, not 3, _) 9x
+    let permutations = genotype
+        .chromosome_permutations_into_iter(None)
+        .map(|c| c.genes)
+        .collect::<Vec<_>>();
+    assert_eq!(permutations.len(), 13);
+    assert!(permutations.iter().all(|genes| genotype.is_feasible(genes)));
+}
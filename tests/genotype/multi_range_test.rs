@@ -1,8 +1,9 @@
 #[cfg(test)]
 use crate::support::*;
 use genetic_algorithm::genotype::{
-    Genotype, HillClimbGenotype, MultiRangeGenotype, MutationType, PermutateGenotype,
-    SupportsGeneCrossover, SupportsPointCrossover,
+    AlleleScale, GeneConstraint, Genotype, HillClimbGenotype, LinearBound, MultiRangeGenotype,
+    MutationType, ParticleSwarmGenotype, PermutateGenotype, SupportsGeneCrossover,
+    SupportsPointCrossover, TryFromGenotypeBuilderError,
 };
 
 #[test]
@@ -1053,3 +1054,243 @@ fn integer_neighbouring_population_3_discrete() {
         ]
     );
 }
+
+#[test]
+fn particle_swarm_positions() {
+    let genotype = MultiRangeGenotype::builder()
+        .with_allele_ranges(vec![0.0..=1.0, -5.0..=5.0, 10.0..=20.0])
+        .with_mutation_types(vec![
+            MutationType::Random,
+            MutationType::Discrete,
+            MutationType::Range(1.0),
+        ])
+        .build()
+        .unwrap();
+    assert!(genotype.allows_particle_swarm());
+    assert_eq!(
+        genotype.position_bounds(),
+        vec![(0.0, 1.0), (-5.0, 5.0), (10.0, 20.0)]
+    );
+
+    let mut chromosome = build::chromosome(vec![0.5_f32, 1.0, 15.5]);
+    assert_eq!(
+        genotype.genes_to_position(&chromosome.genes),
+        vec![0.5, 1.0, 15.5]
+    );
+    genotype.set_genes_from_position(&mut chromosome, &[0.25, -2.7, 12.5]);
+    assert_eq!(inspect::chromosome(&chromosome), vec![0.25, -3.0, 12.5]);
+}
+
+#[test]
+fn allele_scales_build_errors() {
+    assert_eq!(
+        MultiRangeGenotype::builder()
+            .with_allele_ranges(vec![1..=1024, 1..=10])
+            .with_allele_scales(vec![AlleleScale::Log])
+            .build()
+            .unwrap_err()
+            .0,
+        "MultiRangeGenotype allele_scales length must match allele_ranges length"
+    );
+    assert_eq!(
+        MultiRangeGenotype::builder()
+            .with_allele_ranges(vec![1..=1024, 0..=10])
+            .with_allele_scales(vec![AlleleScale::Log, AlleleScale::Log])
+            .build()
+            .unwrap_err()
+            .0,
+        "MultiRangeGenotype allele_scales must be finite and increasing over the allele_ranges (Log requires a positive allele_range)"
+    );
+    assert_eq!(
+        MultiRangeGenotype::builder()
+            .with_allele_ranges(vec![1.0..=1024.0, 0.5..=10.0])
+            .with_allele_scales(vec![AlleleScale::Log, AlleleScale::Log])
+            .with_mutation_types(vec![MutationType::Discrete, MutationType::Discrete])
+            .build()
+            .unwrap_err()
+            .0,
+        "MultiRangeGenotype allele_scales must be finite and increasing over the allele_ranges (Log requires a positive allele_range)"
+    );
+}
+
+#[test]
+fn allele_scales_custom_integer() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = MultiRangeGenotype::builder()
+        .with_allele_ranges(vec![1..=1024, 0..=10, 1..=100])
+        .with_allele_scales(vec![
+            AlleleScale::Custom {
+                to_scale: f64::log2,
+                from_scale: f64::exp2,
+            },
+            AlleleScale::Linear,
+            AlleleScale::Log,
+        ])
+        .with_mutation_types(vec![
+            MutationType::Step(1),
+            MutationType::Step(2),
+            MutationType::Discrete,
+        ])
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        genotype.chromosome_permutations_size(),
+        BigUint::from(11u32 * 6u32 * 100u32)
+    );
+    assert_eq!(
+        genotype.permutable_gene_values_step(0, 1),
+        vec![1, 2, 4, 8, 16, 32, 64, 128, 256, 512, 1024]
+    );
+    assert_eq!(
+        genotype.permutable_gene_values_step(1, 2),
+        vec![0, 2, 4, 6, 8, 10]
+    );
+
+    let mut chromosome = build::chromosome(vec![64, 4, 50]);
+    for _ in 0..20 {
+        let value = chromosome.genes[0];
+        genotype.mutate_chromosome_genes(1, true, &mut chromosome, &mut rng);
+        assert!([value, value * 2, value / 2].contains(&chromosome.genes[0]));
+        assert!((1..=100).contains(&chromosome.genes[2]));
+    }
+
+    let chromosome = build::chromosome(vec![64, 4, 50]);
+    let mut population = Population::new(vec![], true);
+    genotype.fill_neighbouring_population(&chromosome, &mut population, &mut rng);
+    assert_eq!(
+        inspect::population(&population)[0..4],
+        vec![
+            vec![32, 4, 50],
+            vec![128, 4, 50],
+            vec![64, 2, 50],
+            vec![64, 6, 50],
+        ]
+    );
+    // discrete neighbours are all integer values regardless of scale
+    assert_eq!(population.size(), 4 + 99);
+
+    // discrete sampling is uniform per decade
+    let low_values = (0..1000)
+        .map(|_| genotype.random_genes_factory(&mut rng)[2])
+        .filter(|value| *value < 10)
+        .count();
+    assert!((450..550).contains(&low_values), "{}", low_values);
+}
+
+#[test]
+fn gene_constraints_build_error() {
+    assert_eq!(
+        MultiRangeGenotype::builder()
+            .with_allele_ranges(vec![0.0..=1.0, 0.0..=1.0])
+            .with_gene_constraints(vec![GeneConstraint::sum_to(vec![0, 2], 1.0)])
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError(
+            "MultiRangeGenotype gene_constraints must refer to distinct gene indices within genes_size (and have finite linear values)"
+        )
+    );
+    assert_eq!(
+        MultiRangeGenotype::builder()
+            .with_allele_ranges(vec![0.0..=1.0, 0.0..=1.0])
+            .with_gene_constraints(vec![GeneConstraint::sum_to(vec![0, 1], 5.0)])
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError(
+            "MultiRangeGenotype could not generate random genes satisfying the gene_constraints"
+        )
+    );
+}
+
+#[test]
+fn gene_constraints_linear() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = MultiRangeGenotype::<f64>::builder()
+        .with_allele_ranges(vec![0.0..=1.0, 0.0..=1.0, 0.0..=1.0, 0.0..=1.0])
+        .with_mutation_types(vec![
+            MutationType::Discrete,
+            MutationType::Range(0.1),
+            MutationType::Range(0.1),
+            MutationType::Range(0.1),
+        ])
+        .with_gene_constraints(vec![
+            GeneConstraint::ActiveIf {
+                gene_index: 3,
+                condition_gene_index: 0,
+                condition_values: vec![1.0],
+                inactive_value: 0.0,
+            },
+            GeneConstraint::sum_to(vec![1, 2, 3], 1.0),
+            GeneConstraint::linear(vec![(1, 1.0)], LinearBound::AtMost(0.5)),
+        ])
+        .build()
+        .unwrap();
+
+    let mut father = Chromosome::new(genotype.random_genes_factory(&mut rng));
+    let mut mother = Chromosome::new(genotype.random_genes_factory(&mut rng));
+    for _ in 0..100 {
+        genotype.mutate_chromosome_genes(2, true, &mut father, &mut rng);
+        genotype.mutate_chromosome_genes(2, false, &mut mother, &mut rng);
+        assert!(genotype.is_feasible(&father.genes));
+        assert!(genotype.is_feasible(&mother.genes));
+        genotype.crossover_chromosome_genes(2, true, &mut father, &mut mother, &mut rng);
+        assert!(genotype.is_feasible(&father.genes));
+        assert!(genotype.is_feasible(&mother.genes));
+        genotype.crossover_chromosome_points(1, true, &mut father, &mut mother, &mut rng);
+        assert!(genotype.is_feasible(&father.genes));
+        assert!(genotype.is_feasible(&mother.genes));
+    }
+    let genes = &father.genes;
+    assert!((genes[1] + genes[2] + genes[3] - 1.0).abs() <= 1e-5);
+    assert!(genes[1] <= 0.5 + 1e-5);
+
+    let mut population = Population::new(vec![], true);
+    genotype.fill_neighbouring_population(&father, &mut population, &mut rng);
+    assert!(population
+        .chromosomes
+        .iter()
+        .all(|chromosome| genotype.is_feasible(&chromosome.genes)));
+}
+
+#[test]
+fn gene_constraints_rarely_satisfiable() {
+    // the discrete genes only rarely sum exactly to 20 after repair, random initialization then
+    // falls back to the feasible genes found by the builder (instead of panicking)
+    let mut rng = SmallRng::seed_from_u64(1);
+    let genotype = MultiRangeGenotype::<i32>::builder()
+        .with_allele_ranges(vec![0..=300, 0..=300])
+        .with_mutation_types(vec![MutationType::Discrete; 2])
+        .with_gene_constraints(vec![GeneConstraint::sum_to(vec![0, 1], 20.0)])
+        .build()
+        .unwrap();
+
+    for _ in 0..50 {
+        let genes = genotype.random_genes_factory(&mut rng);
+        assert!(genotype.is_feasible(&genes));
+        assert_eq!(genes[0] + genes[1], 20);
+    }
+}
+
+#[test]
+fn gene_constraints_permutation() {
+    let genotype = MultiRangeGenotype::<f64>::builder()
+        .with_allele_ranges(vec![0.0..=1.0, 0.0..=1.0, 0.0..=1.0])
+        .with_mutation_types(vec![MutationType::Step(0.25); 3])
+        .with_gene_constraints(vec![GeneConstraint::sum_to(vec![0, 1, 2], 1.0)])
+        .build()
+        .unwrap();
+
+    // upper bound
+    assert_eq!(
+        genotype.chromosome_permutations_size(),
+        BigUint::from(125u32)
+    );
+    // distributing 4 quarters over 3 genes
+    let permutations = genotype
+        .chromosome_permutations_into_iter(None)
+        .collect::<Vec<_>>();
+    assert_eq!(permutations.len(), 15);
+    assert!(permutations
+        .iter()
+        .all(|chromosome| genotype.is_feasible(&chromosome.genes)));
+}
@@ -0,0 +1,233 @@
+#[cfg(test)]
+use crate::support::*;
+use genetic_algorithm::genotype::{
+    Genotype, HillClimbGenotype, PartialPermutationGenotype, PermutateGenotype,
+    TryFromGenotypeBuilderError,
+};
+use genetic_algorithm::strategy::evolve::prelude::*;
+use genetic_algorithm::strategy::hill_climb::prelude::{HillClimb, HillClimbVariant};
+use genetic_algorithm::strategy::permutate::prelude::Permutate;
+use itertools::Itertools;
+
+fn is_valid(genes: &[usize], genes_size: usize, allele_list_size: usize) -> bool {
+    genes.len() == genes_size
+        && genes.iter().all(|gene| *gene < allele_list_size)
+        && genes.iter().all_unique()
+}
+
+#[derive(Clone, Debug)]
+struct WeightedPositions;
+impl Fitness for WeightedPositions {
+    type Genotype = PartialPermutationGenotype;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &FitnessChromosome<Self>,
+        _genotype: &FitnessGenotype<Self>,
+    ) -> Option<FitnessValue> {
+        Some(
+            chromosome
+                .genes
+                .iter()
+                .enumerate()
+                .map(|(index, gene)| (index + 1) * gene)
+                .sum::<usize>() as FitnessValue,
+        )
+    }
+}
+
+#[test]
+fn build_errors() {
+    assert_eq!(
+        PartialPermutationGenotype::<usize>::builder()
+            .with_allele_list((0..5).collect())
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError("PartialPermutationGenotype requires a genes_size > 0")
+    );
+    assert_eq!(
+        PartialPermutationGenotype::<usize>::builder()
+            .with_allele_list((0..5).collect())
+            .with_genes_size(6)
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError(
+            "PartialPermutationGenotype requires a genes_size <= allele_list length"
+        )
+    );
+}
+
+#[test]
+fn random_genes_factory() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = PartialPermutationGenotype::builder()
+        .with_allele_list((0..10).collect())
+        .with_genes_size(4)
+        .build()
+        .unwrap();
+
+    for _ in 0..100 {
+        assert!(is_valid(&genotype.random_genes_factory(&mut rng), 4, 10));
+    }
+
+    let genotype = PartialPermutationGenotype::builder()
+        .with_allele_list((0..10).collect())
+        .with_genes_size(4)
+        .with_seed_genes_list(vec![vec![9, 8, 7, 6]])
+        .build()
+        .unwrap();
+    assert_eq!(genotype.random_genes_factory(&mut rng), vec![9, 8, 7, 6]);
+}
+
+#[test]
+fn mutate_chromosome_genes() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = PartialPermutationGenotype::builder()
+        .with_allele_list((0..10).collect())
+        .with_genes_size(4)
+        .build()
+        .unwrap();
+
+    let mut chromosome = build::chromosome(vec![0, 1, 2, 3]);
+    let genes_hash = chromosome.genes_hash;
+    genotype.mutate_chromosome_genes(1, false, &mut chromosome, &mut rng);
+    assert!(is_valid(&chromosome.genes, 4, 10));
+    assert_ne!(chromosome.genes_hash, genes_hash);
+
+    let mut swapped = false;
+    let mut exchanged = false;
+    for _ in 0..100 {
+        let genes = chromosome.genes.clone();
+        genotype.mutate_chromosome_genes(1, true, &mut chromosome, &mut rng);
+        assert!(is_valid(&chromosome.genes, 4, 10));
+        let mut before = genes.clone();
+        let mut after = chromosome.genes.clone();
+        before.sort();
+        after.sort();
+        if before == after && genes != chromosome.genes {
+            swapped = true;
+        } else if before != after {
+            exchanged = true;
+        }
+        genotype.mutate_chromosome_genes(3, false, &mut chromosome, &mut rng);
+        assert!(is_valid(&chromosome.genes, 4, 10));
+    }
+    assert!(swapped && exchanged);
+
+    // k == n, only swaps
+    let genotype = PartialPermutationGenotype::builder()
+        .with_allele_list((0..4).collect())
+        .with_genes_size(4)
+        .build()
+        .unwrap();
+    for _ in 0..100 {
+        genotype.mutate_chromosome_genes(2, true, &mut chromosome, &mut rng);
+        let mut genes = chromosome.genes.clone();
+        genes.sort();
+        assert_eq!(genes, vec![0, 1, 2, 3]);
+    }
+}
+
+#[test]
+fn neighbouring_population() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = PartialPermutationGenotype::builder()
+        .with_allele_list(vec![10, 20, 30, 40])
+        .with_genes_size(2)
+        .build()
+        .unwrap();
+
+    assert_eq!(genotype.neighbouring_population_size(), BigUint::from(5u32));
+    let chromosome = build::chromosome(vec![30, 10]);
+    let mut population = Population::new(vec![], true);
+    genotype.fill_neighbouring_population(&chromosome, &mut population, &mut rng);
+    assert_eq!(
+        inspect::population(&population),
+        vec![
+            vec![10, 30],
+            vec![20, 10],
+            vec![40, 10],
+            vec![30, 20],
+            vec![30, 40],
+        ]
+    );
+}
+
+#[test]
+fn chromosome_permutations() {
+    let genotype = PartialPermutationGenotype::builder()
+        .with_allele_list(vec![10, 20, 30])
+        .with_genes_size(2)
+        .build()
+        .unwrap();
+
+    assert_eq!(genotype.chromosome_permutations_size(), BigUint::from(6u32));
+    assert_eq!(
+        inspect::chromosomes(
+            genotype
+                .chromosome_permutations_into_iter(None)
+                .collect::<Vec<_>>()
+                .as_slice()
+        ),
+        vec![
+            vec![10, 20],
+            vec![10, 30],
+            vec![20, 10],
+            vec![20, 30],
+            vec![30, 10],
+            vec![30, 20],
+        ]
+    );
+
+    let genotype = PartialPermutationGenotype::builder()
+        .with_allele_list((0..100).collect())
+        .with_genes_size(5)
+        .build()
+        .unwrap();
+    assert_eq!(
+        genotype.chromosome_permutations_size(),
+        BigUint::from(9034502400u64)
+    );
+}
+
+#[test]
+fn strategies() {
+    let genotype = PartialPermutationGenotype::builder()
+        .with_allele_list((0..8).collect())
+        .with_genes_size(3)
+        .build()
+        .unwrap();
+    // best is [5, 6, 7]: 5 + 2 * 6 + 3 * 7
+    let best_fitness_score = 38;
+
+    let evolve = Evolve::builder()
+        .with_genotype(genotype.clone())
+        .with_target_population_size(50)
+        .with_target_fitness_score(best_fitness_score)
+        .with_max_stale_generations(100)
+        .with_fitness(WeightedPositions)
+        .with_select(SelectTournament::new(0.5, 0.02, 4))
+        .with_crossover(CrossoverClone::new(0.7))
+        .with_mutate(MutateSingleGene::new(0.5))
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+    assert_eq!(evolve.best_fitness_score(), Some(best_fitness_score));
+    assert_eq!(evolve.best_genes().unwrap(), vec![5, 6, 7]);
+
+    let hill_climb = HillClimb::builder()
+        .with_genotype(genotype.clone())
+        .with_variant(HillClimbVariant::SteepestAscent)
+        .with_max_stale_generations(10)
+        .with_fitness(WeightedPositions)
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+    assert_eq!(hill_climb.best_fitness_score(), Some(best_fitness_score));
+
+    let permutate = Permutate::builder()
+        .with_genotype(genotype)
+        .with_fitness(WeightedPositions)
+        .call()
+        .unwrap();
+    assert_eq!(permutate.best_fitness_score(), Some(best_fitness_score));
+}
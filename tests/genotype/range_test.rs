@@ -1,8 +1,8 @@
 #[cfg(test)]
 use crate::support::*;
 use genetic_algorithm::genotype::{
-    Genotype, HillClimbGenotype, MutationType, PermutateGenotype, RangeGenotype,
-    SupportsGeneCrossover, SupportsPointCrossover,
+    AlleleScale, GeneConstraint, Genotype, HillClimbGenotype, MutationType, ParticleSwarmGenotype,
+    PermutateGenotype, RangeGenotype, SupportsGeneCrossover, SupportsPointCrossover,
 };
 
 #[test]
@@ -1232,3 +1232,236 @@ fn integer_calculate_genes_hash() {
     // the sign on does not matter
     assert_eq!(hash_1, hash_3);
 }
+
+#[test]
+fn particle_swarm_positions() {
+    let genotype = RangeGenotype::builder()
+        .with_genes_size(3)
+        .with_allele_range(0.0..=10.0)
+        .build()
+        .unwrap();
+    assert!(genotype.allows_particle_swarm());
+    assert_eq!(genotype.position_bounds(), vec![(0.0, 10.0); 3]);
+
+    let mut chromosome = build::chromosome(vec![1.5_f32, 2.0, 9.25]);
+    assert_eq!(
+        genotype.genes_to_position(&chromosome.genes),
+        vec![1.5, 2.0, 9.25]
+    );
+    genotype.set_genes_from_position(&mut chromosome, &[0.5, 4.75, 10.0]);
+    assert_eq!(inspect::chromosome(&chromosome), vec![0.5, 4.75, 10.0]);
+}
+
+#[test]
+fn particle_swarm_positions_discrete() {
+    let genotype = RangeGenotype::builder()
+        .with_genes_size(3)
+        .with_allele_range(-2.5..=10.5)
+        .with_mutation_type(MutationType::Discrete)
+        .build()
+        .unwrap();
+    assert_eq!(genotype.position_bounds(), vec![(-3.0, 10.0); 3]);
+
+    let mut chromosome = build::chromosome(vec![0.0_f32, 0.0, 0.0]);
+    genotype.set_genes_from_position(&mut chromosome, &[0.4, 4.6, -2.5]);
+    assert_eq!(inspect::chromosome(&chromosome), vec![0.0, 5.0, -3.0]);
+}
+
+#[test]
+fn particle_swarm_positions_integer() {
+    let genotype = RangeGenotype::<i32>::builder()
+        .with_genes_size(2)
+        .with_allele_range(0..=10)
+        .build()
+        .unwrap();
+    assert_eq!(genotype.position_bounds(), vec![(0.0, 10.0); 2]);
+
+    let mut chromosome = build::chromosome(vec![0, 0]);
+    genotype.set_genes_from_position(&mut chromosome, &[3.4, 3.6]);
+    assert_eq!(inspect::chromosome(&chromosome), vec![3, 4]);
+}
+
+#[test]
+fn allele_scale_log_build_error() {
+    assert_eq!(
+        RangeGenotype::builder()
+            .with_genes_size(1)
+            .with_allele_range(0.0..=1.0)
+            .with_allele_scale(AlleleScale::Log)
+            .build()
+            .unwrap_err()
+            .0,
+        "RangeGenotype allele_scale must be finite and increasing over the allele_range (Log requires a positive allele_range)"
+    );
+    // discrete sampling floors the start to 0.0
+    assert_eq!(
+        RangeGenotype::builder()
+            .with_genes_size(1)
+            .with_allele_range(0.5..=10.0)
+            .with_allele_scale(AlleleScale::Log)
+            .with_mutation_type(MutationType::Discrete)
+            .build()
+            .unwrap_err()
+            .0,
+        "RangeGenotype allele_scale must be finite and increasing over the allele_range (Log requires a positive allele_range)"
+    );
+    assert!(RangeGenotype::builder()
+        .with_genes_size(1)
+        .with_allele_range(0.5..=10.0)
+        .with_allele_scale(AlleleScale::Log)
+        .build()
+        .is_ok());
+    assert!(RangeGenotype::builder()
+        .with_genes_size(1)
+        .with_allele_range(1.0..=10.0)
+        .with_allele_scale(AlleleScale::Log)
+        .with_mutation_type(MutationType::Discrete)
+        .build()
+        .is_ok());
+}
+
+#[test]
+fn allele_scale_log_random_genes_factory() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = RangeGenotype::builder()
+        .with_genes_size(1000)
+        .with_allele_range(0.001..=1.0)
+        .with_allele_scale(AlleleScale::Log)
+        .build()
+        .unwrap();
+
+    // uniform per decade
+    let genes = genotype.random_genes_factory(&mut rng);
+    assert!(genes.iter().all(|gene| (0.001..=1.0).contains(gene)));
+    let first_decade = genes.iter().filter(|gene| **gene < 0.01).count();
+    let last_decade = genes.iter().filter(|gene| **gene >= 0.1).count();
+    assert!((300..370).contains(&first_decade), "{}", first_decade);
+    assert!((300..370).contains(&last_decade), "{}", last_decade);
+}
+
+#[test]
+fn allele_scale_log_mutate_chromosome_step() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = RangeGenotype::builder()
+        .with_genes_size(1)
+        .with_allele_range(0.001..=1.0)
+        .with_allele_scale(AlleleScale::Log)
+        .with_mutation_type(MutationType::Step(1.0))
+        .build()
+        .unwrap();
+
+    let mut chromosome = build::chromosome(vec![0.01]);
+    for _ in 0..20 {
+        let value = chromosome.genes[0];
+        genotype.mutate_chromosome_genes(1, true, &mut chromosome, &mut rng);
+        let ratio = chromosome.genes[0] / value;
+        assert!(
+            relative_eq!(ratio, 10.0, max_relative = 1e-4)
+                || relative_eq!(ratio, 0.1, max_relative = 1e-4)
+                || relative_eq!(ratio, 1.0, max_relative = 1e-4) // clamped
+        );
+    }
+}
+
+#[test]
+fn allele_scale_log_neighbouring_population_step() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = RangeGenotype::builder()
+        .with_genes_size(2)
+        .with_allele_range(0.001..=1.0)
+        .with_allele_scale(AlleleScale::Log)
+        .with_mutation_type(MutationType::Step(1.0))
+        .build()
+        .unwrap();
+
+    let chromosome = build::chromosome(vec![0.01, 1.0]);
+    let mut population = Population::new(vec![], true);
+    genotype.fill_neighbouring_population(&chromosome, &mut population, &mut rng);
+    assert!(relative_population_eq(
+        inspect::population(&population),
+        vec![vec![0.001, 1.0], vec![0.1, 1.0], vec![0.01, 0.1]],
+        1e-6,
+    ));
+}
+
+#[test]
+fn allele_scale_log_chromosome_permutations_step_scaled() {
+    let mut genotype = RangeGenotype::builder()
+        .with_genes_size(1)
+        .with_allele_range(0.001..=1.0)
+        .with_allele_scale(AlleleScale::Log)
+        .with_mutation_type(MutationType::StepScaled(vec![1.0, 0.5]))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        genotype.chromosome_permutations_size(),
+        BigUint::from(4u32 + 5u32)
+    );
+    assert!(relative_population_eq(
+        inspect::chromosomes(
+            &genotype
+                .chromosome_permutations_into_iter(None)
+                .collect::<Vec<_>>()
+        ),
+        vec![vec![0.001], vec![0.01], vec![0.1], vec![1.0]],
+        1e-6,
+    ));
+
+    genotype.increment_scale_index();
+    let chromosome = build::chromosome(vec![0.01]);
+    assert!(relative_population_eq(
+        inspect::chromosomes(
+            &genotype
+                .chromosome_permutations_into_iter(Some(&chromosome))
+                .collect::<Vec<_>>()
+        ),
+        vec![
+            vec![0.001],
+            vec![0.003_162_277_6],
+            vec![0.01],
+            vec![0.031_622_776],
+            vec![0.1],
+        ],
+        1e-6,
+    ));
+}
+
+#[test]
+fn allele_scale_log_particle_swarm_positions() {
+    let genotype = RangeGenotype::builder()
+        .with_genes_size(2)
+        .with_allele_range(0.001..=1.0)
+        .with_allele_scale(AlleleScale::Log)
+        .build()
+        .unwrap();
+
+    let bounds = genotype.position_bounds();
+    assert!(relative_eq!(bounds[0].0, -3.0, epsilon = 1e-6));
+    assert!(relative_eq!(bounds[0].1, 0.0, epsilon = 1e-6));
+    let position = genotype.genes_to_position(&vec![0.01, 0.1]);
+    assert!(relative_eq!(position[0], -2.0, epsilon = 1e-6));
+    assert!(relative_eq!(position[1], -1.0, epsilon = 1e-6));
+
+    let mut chromosome = build::chromosome(vec![0.5, 0.5]);
+    genotype.set_genes_from_position(&mut chromosome, &[-1.0, -0.5]);
+    assert!(relative_chromosome_eq(
+        inspect::chromosome(&chromosome),
+        vec![0.1, 0.316_227_77],
+        1e-6
+    ));
+}
+
+#[test]
+fn gene_constraints_build_error() {
+    assert_eq!(
+        RangeGenotype::builder()
+            .with_genes_size(2)
+            .with_allele_range(0.0..=1.0)
+            .with_gene_constraints(vec![GeneConstraint::sum_to(vec![0, 1], 1.0)])
+            .build()
+            .unwrap_err()
+            .0,
+        "RangeGenotype does not support gene_constraints"
+    );
+}
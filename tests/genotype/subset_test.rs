@@ -0,0 +1,222 @@
+#[cfg(test)]
+use crate::support::*;
+use genetic_algorithm::genotype::{
+    Genotype, HillClimbGenotype, PermutateGenotype, SubsetGenotype, SupportsGeneCrossover,
+    TryFromGenotypeBuilderError,
+};
+use genetic_algorithm::strategy::evolve::prelude::*;
+use genetic_algorithm::strategy::hill_climb::prelude::{HillClimb, HillClimbVariant};
+use genetic_algorithm::strategy::permutate::prelude::Permutate;
+
+fn is_valid(genes: &[usize], genes_size: usize) -> bool {
+    genes.len() == genes_size && genes.windows(2).all(|pair| pair[0] < pair[1])
+}
+
+#[derive(Clone, Debug)]
+struct SumOfSelected;
+impl Fitness for SumOfSelected {
+    type Genotype = SubsetGenotype;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &FitnessChromosome<Self>,
+        _genotype: &FitnessGenotype<Self>,
+    ) -> Option<FitnessValue> {
+        Some(chromosome.genes.iter().sum::<usize>() as FitnessValue)
+    }
+}
+
+#[test]
+fn build_errors() {
+    assert_eq!(
+        SubsetGenotype::<usize>::builder()
+            .with_allele_list((0..5).collect())
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError("SubsetGenotype requires a genes_size > 0")
+    );
+    assert_eq!(
+        SubsetGenotype::<usize>::builder()
+            .with_allele_list((0..5).collect())
+            .with_genes_size(6)
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError("SubsetGenotype requires a genes_size <= allele_list length")
+    );
+}
+
+#[test]
+fn random_genes_factory() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = SubsetGenotype::builder()
+        .with_allele_list((0..10).collect())
+        .with_genes_size(4)
+        .build()
+        .unwrap();
+
+    assert_eq!(genotype.random_genes_factory(&mut rng), vec![3, 4, 7, 8]);
+    for _ in 0..100 {
+        assert!(is_valid(&genotype.random_genes_factory(&mut rng), 4));
+    }
+}
+
+#[test]
+fn mutate_chromosome_genes() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = SubsetGenotype::builder()
+        .with_allele_list((0..10).collect())
+        .with_genes_size(4)
+        .build()
+        .unwrap();
+
+    let mut chromosome = build::chromosome(vec![0, 1, 2, 3]);
+    let genes_hash = chromosome.genes_hash;
+    genotype.mutate_chromosome_genes(2, false, &mut chromosome, &mut rng);
+    assert!(is_valid(&chromosome.genes, 4));
+    assert_ne!(chromosome.genes_hash, genes_hash);
+    // exactly two values swapped out
+    assert_eq!(chromosome.genes.iter().filter(|gene| **gene < 4).count(), 2);
+
+    for _ in 0..100 {
+        genotype.mutate_chromosome_genes(3, true, &mut chromosome, &mut rng);
+        assert!(is_valid(&chromosome.genes, 4));
+        genotype.mutate_chromosome_genes(10, false, &mut chromosome, &mut rng);
+        assert!(is_valid(&chromosome.genes, 4));
+    }
+
+    // unordered seed genes are matched and reordered
+    let mut chromosome = build::chromosome(vec![9, 3, 7, 1]);
+    genotype.mutate_chromosome_genes(0, false, &mut chromosome, &mut rng);
+    assert_eq!(inspect::chromosome(&chromosome), vec![1, 3, 7, 9]);
+}
+
+#[test]
+fn crossover_chromosome_genes() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = SubsetGenotype::builder()
+        .with_allele_list((0..10).collect())
+        .with_genes_size(4)
+        .build()
+        .unwrap();
+
+    let mut father = build::chromosome(vec![0, 1, 2, 3]);
+    let mut mother = build::chromosome(vec![2, 3, 4, 5]);
+    genotype.crossover_chromosome_genes(1, false, &mut father, &mut mother, &mut rng);
+    assert_eq!(inspect::chromosome(&father), vec![0, 2, 3, 4]);
+    assert_eq!(inspect::chromosome(&mother), vec![1, 2, 3, 5]);
+
+    for _ in 0..100 {
+        genotype.crossover_chromosome_genes(2, true, &mut father, &mut mother, &mut rng);
+        assert!(is_valid(&father.genes, 4));
+        assert!(is_valid(&mother.genes, 4));
+        // the shared values are kept and the union is preserved
+        assert!([2, 3].iter().all(|v| father.genes.contains(v)));
+        assert!([2, 3].iter().all(|v| mother.genes.contains(v)));
+        let mut union = [father.genes.clone(), mother.genes.clone()].concat();
+        union.sort();
+        assert_eq!(union, vec![0, 1, 2, 2, 3, 3, 4, 5]);
+    }
+}
+
+#[test]
+fn neighbouring_population() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = SubsetGenotype::builder()
+        .with_allele_list(vec![10, 20, 30, 40])
+        .with_genes_size(2)
+        .build()
+        .unwrap();
+
+    assert_eq!(genotype.neighbouring_population_size(), BigUint::from(4u32));
+    let chromosome = build::chromosome(vec![10, 30]);
+    let mut population = Population::new(vec![], true);
+    genotype.fill_neighbouring_population(&chromosome, &mut population, &mut rng);
+    assert_eq!(
+        inspect::population(&population),
+        vec![vec![20, 30], vec![30, 40], vec![10, 20], vec![10, 40]]
+    );
+}
+
+#[test]
+fn chromosome_permutations() {
+    let genotype = SubsetGenotype::builder()
+        .with_allele_list(vec![10, 20, 30, 40, 50])
+        .with_genes_size(2)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        genotype.chromosome_permutations_size(),
+        BigUint::from(10u32)
+    );
+    assert_eq!(
+        inspect::chromosomes(
+            genotype
+                .chromosome_permutations_into_iter(None)
+                .collect::<Vec<_>>()
+                .as_slice()
+        ),
+        vec![
+            vec![10, 20],
+            vec![10, 30],
+            vec![10, 40],
+            vec![10, 50],
+            vec![20, 30],
+            vec![20, 40],
+            vec![20, 50],
+            vec![30, 40],
+            vec![30, 50],
+            vec![40, 50],
+        ]
+    );
+
+    let genotype = SubsetGenotype::builder()
+        .with_allele_list((0..100).collect())
+        .with_genes_size(50)
+        .build()
+        .unwrap();
+    assert_eq!(
+        genotype.chromosome_permutations_size(),
+        BigUint::parse_bytes(b"100891344545564193334812497256", 10).unwrap()
+    );
+}
+
+#[test]
+fn strategies() {
+    let genotype = SubsetGenotype::builder()
+        .with_allele_list((0..20).collect())
+        .with_genes_size(3)
+        .build()
+        .unwrap();
+
+    let evolve = Evolve::builder()
+        .with_genotype(genotype.clone())
+        .with_target_population_size(50)
+        .with_target_fitness_score(54)
+        .with_max_stale_generations(100)
+        .with_fitness(SumOfSelected)
+        .with_select(SelectTournament::new(0.5, 0.02, 4))
+        .with_crossover(CrossoverUniform::new(0.7, 0.8))
+        .with_mutate(MutateSingleGene::new(0.2))
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+    assert_eq!(evolve.best_fitness_score(), Some(54));
+    assert_eq!(evolve.best_genes().unwrap(), vec![17, 18, 19]);
+
+    let hill_climb = HillClimb::builder()
+        .with_genotype(genotype.clone())
+        .with_variant(HillClimbVariant::SteepestAscent)
+        .with_max_stale_generations(10)
+        .with_fitness(SumOfSelected)
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+    assert_eq!(hill_climb.best_fitness_score(), Some(54));
+
+    let permutate = Permutate::builder()
+        .with_genotype(genotype)
+        .with_fitness(SumOfSelected)
+        .call()
+        .unwrap();
+    assert_eq!(permutate.best_fitness_score(), Some(54));
+}
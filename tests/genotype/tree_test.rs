@@ -0,0 +1,278 @@
+#[cfg(test)]
+use crate::support::*;
+use genetic_algorithm::genotype::{
+    Genotype, HillClimbGenotype, SupportsPointCrossover, TreeGenotype, TryFromGenotypeBuilderError,
+};
+use genetic_algorithm::strategy::evolve::prelude::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum Node {
+    Add,
+    Sub,
+    Mul,
+    Neg,
+    X,
+    One,
+}
+genetic_algorithm::impl_allele!(Node);
+use Node::*;
+
+fn evaluate(genes: &[Node], index: usize, x: isize) -> (isize, usize) {
+    match genes[index] {
+        X => (x, index + 1),
+        One => (1, index + 1),
+        Neg => {
+            let (a, next) = evaluate(genes, index + 1, x);
+            (-a, next)
+        }
+        Add | Sub | Mul => {
+            let (a, next) = evaluate(genes, index + 1, x);
+            let (b, next) = evaluate(genes, next, x);
+            match genes[index] {
+                Add => (a.saturating_add(b), next),
+                Sub => (a.saturating_sub(b), next),
+                _ => (a.saturating_mul(b), next),
+            }
+        }
+    }
+}
+
+fn build_genotype() -> TreeGenotype<Node> {
+    TreeGenotype::builder()
+        .with_function_set(vec![(Add, 2), (Sub, 2), (Mul, 2), (Neg, 1)])
+        .with_terminal_set(vec![X, One])
+        .with_genes_size(20)
+        .with_max_depth(4)
+        .with_init_depth_range(1..=3)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn build_errors() {
+    assert_eq!(
+        TreeGenotype::<Node>::builder()
+            .with_function_set(vec![(Add, 2)])
+            .with_terminal_set(vec![X])
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError(
+            "TreeGenotype requires a genes_size > 0 (maximum number of nodes)"
+        )
+    );
+    assert_eq!(
+        TreeGenotype::<Node>::builder()
+            .with_function_set(vec![(Add, 2)])
+            .with_genes_size(20)
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError("TreeGenotype requires non-empty terminal_set")
+    );
+    assert_eq!(
+        TreeGenotype::<Node>::builder()
+            .with_function_set(vec![(Add, 0)])
+            .with_terminal_set(vec![X])
+            .with_genes_size(20)
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError("TreeGenotype requires function_set arities > 0")
+    );
+    assert_eq!(
+        TreeGenotype::<Node>::builder()
+            .with_function_set(vec![(Add, 2)])
+            .with_terminal_set(vec![X])
+            .with_genes_size(20)
+            .with_max_depth(3)
+            .with_init_depth_range(2..=4)
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError(
+            "TreeGenotype requires a non-empty init_depth_range within max_depth"
+        )
+    );
+}
+
+#[test]
+fn arity_subtree_end_and_depth() {
+    let genotype = build_genotype();
+    // (x * (x + 1)) - -1
+    let genes = vec![Sub, Mul, X, Add, X, One, Neg, One];
+    assert_eq!(genotype.arity(&Sub), 2);
+    assert_eq!(genotype.arity(&Neg), 1);
+    assert_eq!(genotype.arity(&X), 0);
+    assert_eq!(genotype.subtree_end(&genes, 0), 8);
+    assert_eq!(genotype.subtree_end(&genes, 1), 6);
+    assert_eq!(genotype.subtree_end(&genes, 3), 6);
+    assert_eq!(genotype.subtree_end(&genes, 6), 8);
+    assert_eq!(genotype.depth(&genes), 3);
+    assert_eq!(genotype.depth(&[X]), 0);
+    assert_eq!(evaluate(&genes, 0, 2), (7, 8));
+}
+
+#[test]
+fn random_genes_factory() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = build_genotype();
+
+    let genes_list: Vec<Vec<Node>> = (0..4)
+        .map(|_| genotype.random_genes_factory(&mut rng))
+        .collect();
+    assert_eq!(
+        genes_list,
+        vec![
+            vec![Mul, Neg, X, Add, X, X],
+            vec![X],
+            vec![Sub, Neg, X, Neg, X],
+            vec![Mul, Sub, One, X, X],
+        ]
+    );
+}
+
+#[test]
+fn random_genes_factory_valid_trees() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = build_genotype();
+
+    for _ in 0..100 {
+        let genes = genotype.random_genes_factory(&mut rng);
+        assert_eq!(genotype.subtree_end(&genes, 0), genes.len());
+        assert!(genotype.within_limits(&genes));
+        assert!(genotype.depth(&genes) <= 3);
+    }
+}
+
+#[test]
+fn mutate_chromosome_genes() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = build_genotype();
+
+    let mut chromosome = build::chromosome(vec![Sub, Mul, X, Add, X, One, Neg, One]);
+    genotype.mutate_chromosome_genes(1, true, &mut chromosome, &mut rng);
+    assert_eq!(
+        inspect::chromosome(&chromosome),
+        vec![Sub, Mul, X, Add, X, One, Neg, X] // point
+    );
+    genotype.mutate_chromosome_genes(1, true, &mut chromosome, &mut rng);
+    assert_eq!(
+        inspect::chromosome(&chromosome),
+        vec![Sub, Mul, X, Add, X, X, Neg, X] // point
+    );
+    genotype.mutate_chromosome_genes(1, true, &mut chromosome, &mut rng);
+    assert_eq!(
+        inspect::chromosome(&chromosome),
+        vec![Mul, X, Add, X, X] // hoist
+    );
+}
+
+#[test]
+fn mutate_chromosome_genes_valid_trees() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = build_genotype();
+
+    let mut chromosome = build::chromosome(vec![Sub, Mul, X, Add, X, One, Neg, One]);
+    for _ in 0..100 {
+        genotype.mutate_chromosome_genes(2, true, &mut chromosome, &mut rng);
+        assert_eq!(
+            genotype.subtree_end(&chromosome.genes, 0),
+            chromosome.genes.len()
+        );
+        assert!(genotype.within_limits(&chromosome.genes));
+    }
+}
+
+#[test]
+fn crossover_chromosome_pair_single_point() {
+    let rng = &mut SmallRng::seed_from_u64(0);
+    let genotype = build_genotype();
+
+    let mut father = build::chromosome(vec![Sub, Mul, X, Add, X, One, Neg, One]);
+    let mut mother = build::chromosome(vec![Add, One, Neg, X]);
+    genotype.crossover_chromosome_points(1, true, &mut father, &mut mother, rng);
+    assert_eq!(
+        inspect::chromosome(&father),
+        vec![Sub, Mul, X, Add, X, One, Neg, Neg, X]
+    );
+    assert_eq!(inspect::chromosome(&mother), vec![Add, One, One]);
+}
+
+#[test]
+fn crossover_chromosome_pair_valid_trees() {
+    let rng = &mut SmallRng::seed_from_u64(0);
+    let genotype = build_genotype();
+
+    let mut father = build::chromosome(vec![Sub, Mul, X, Add, X, One, Neg, One]);
+    let mut mother = build::chromosome(vec![Add, One, Neg, X]);
+    for _ in 0..100 {
+        genotype.crossover_chromosome_points(2, true, &mut father, &mut mother, rng);
+        for chromosome in [&father, &mother] {
+            assert_eq!(
+                genotype.subtree_end(&chromosome.genes, 0),
+                chromosome.genes.len()
+            );
+            assert!(genotype.within_limits(&chromosome.genes));
+        }
+    }
+}
+
+#[test]
+fn neighbouring_population() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = build_genotype();
+
+    assert_eq!(
+        genotype.neighbouring_population_size(),
+        BigUint::from(40u32)
+    );
+    let chromosome = build::chromosome(vec![Add, X, Neg, One]);
+    let mut population = Population::new(vec![], true);
+    genotype.fill_neighbouring_population(&chromosome, &mut population, &mut rng);
+    assert_eq!(
+        inspect::population(&population),
+        vec![
+            vec![Sub, X, Neg, One],
+            vec![Mul, X, Neg, One],
+            vec![Add, One, Neg, One],
+            vec![Add, X, Neg, X],
+        ]
+    );
+}
+
+#[derive(Clone, Debug)]
+struct SymbolicRegression;
+impl Fitness for SymbolicRegression {
+    type Genotype = TreeGenotype<Node>;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &FitnessChromosome<Self>,
+        _genotype: &FitnessGenotype<Self>,
+    ) -> Option<FitnessValue> {
+        // x^2 - x + 1
+        let error = (-5..=5)
+            .map(|x| (evaluate(&chromosome.genes, 0, x).0 - (x * x - x + 1)).abs())
+            .sum::<isize>();
+        Some(error)
+    }
+}
+
+#[test]
+fn evolve_symbolic_regression() {
+    let genotype = build_genotype();
+
+    let evolve = Evolve::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(100)
+        .with_max_stale_generations(100)
+        .with_fitness(SymbolicRegression)
+        .with_fitness_ordering(FitnessOrdering::Minimize)
+        .with_target_fitness_score(0)
+        .with_mutate(MutateSingleGene::new(0.2))
+        .with_crossover(CrossoverSinglePoint::new(0.7, 0.8))
+        .with_select(SelectTournament::new(0.5, 0.02, 4))
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    let (best_genes, best_fitness_score) = evolve.best_genes_and_fitness_score().unwrap();
+    assert_eq!(best_fitness_score, 0);
+    assert_eq!(best_genes, vec![Add, Mul, X, X, Sub, One, X]);
+}
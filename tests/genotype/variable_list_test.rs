@@ -0,0 +1,248 @@
+#[cfg(test)]
+use crate::support::*;
+use genetic_algorithm::fitness::placeholders::SumGenes;
+use genetic_algorithm::genotype::{
+    GeneConstraint, Genotype, HillClimbGenotype, PermutateGenotype, SupportsGeneCrossover,
+    SupportsPointCrossover, TryFromGenotypeBuilderError, VariableListGenotype,
+};
+use genetic_algorithm::strategy::evolve::prelude::*;
+
+#[test]
+fn build_errors() {
+    assert_eq!(
+        VariableListGenotype::<usize>::builder()
+            .with_genes_size(5)
+            .with_allele_list(vec![1, 2, 3])
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError(
+            "VariableListGenotype requires with_genes_size_range, not with_genes_size"
+        )
+    );
+    assert_eq!(
+        VariableListGenotype::<usize>::builder()
+            .with_genes_size_range(0..=0)
+            .with_allele_list(vec![1, 2, 3])
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError(
+            "VariableListGenotype requires a non-empty genes_size_range with max > 0"
+        )
+    );
+    assert_eq!(
+        VariableListGenotype::<usize>::builder()
+            .with_genes_size_range(2..=5)
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError("VariableListGenotype requires allele_list")
+    );
+    assert_eq!(
+        VariableListGenotype::builder()
+            .with_genes_size_range(1..=4)
+            .with_allele_list(vec![0, 1, 2])
+            .with_gene_constraints(vec![GeneConstraint::ForbiddenPair {
+                gene_indices: (0, 1),
+                values: (1, 1),
+            }])
+            .build()
+            .unwrap_err(),
+        TryFromGenotypeBuilderError("VariableListGenotype does not support gene_constraints")
+    );
+}
+
+#[test]
+fn random_genes_factory() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = VariableListGenotype::builder()
+        .with_genes_size_range(2..=6)
+        .with_allele_list(vec![5, 2, 3, 4])
+        .build()
+        .unwrap();
+
+    let genes_list: Vec<Vec<usize>> = (0..4)
+        .map(|_| genotype.random_genes_factory(&mut rng))
+        .collect();
+    assert_eq!(
+        genes_list,
+        vec![
+            vec![2, 4, 2, 4],
+            vec![3, 2, 2, 4, 5, 4],
+            vec![5, 4, 5, 5, 2],
+            vec![3, 3, 5, 2, 4],
+        ]
+    );
+}
+
+#[test]
+fn mutate_chromosome_genes() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = VariableListGenotype::builder()
+        .with_genes_size_range(2..=6)
+        .with_allele_list(vec![2, 3, 4])
+        .build()
+        .unwrap();
+
+    let mut chromosome = build::chromosome(vec![1, 1, 1, 1]);
+    genotype.mutate_chromosome_genes(1, true, &mut chromosome, &mut rng);
+    assert_eq!(inspect::chromosome(&chromosome), vec![1, 1, 1, 2]); // substitute
+    genotype.mutate_chromosome_genes(1, true, &mut chromosome, &mut rng);
+    assert_eq!(inspect::chromosome(&chromosome), vec![1, 1, 2]); // delete
+    genotype.mutate_chromosome_genes(1, true, &mut chromosome, &mut rng);
+    assert_eq!(inspect::chromosome(&chromosome), vec![1, 1, 2, 2]); // insert
+    genotype.mutate_chromosome_genes(1, true, &mut chromosome, &mut rng);
+    assert_eq!(inspect::chromosome(&chromosome), vec![4, 1, 2, 2]); // substitute
+}
+
+#[test]
+fn mutate_chromosome_genes_within_bounds() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = VariableListGenotype::builder()
+        .with_genes_size_range(2..=4)
+        .with_allele_list(vec![1, 2, 3, 4])
+        .build()
+        .unwrap();
+
+    let mut chromosome = build::chromosome(vec![1, 1, 1]);
+    for _ in 0..100 {
+        genotype.mutate_chromosome_genes(3, true, &mut chromosome, &mut rng);
+        assert!((2..=4).contains(&chromosome.genes.len()));
+    }
+}
+
+#[test]
+fn crossover_chromosome_pair_single_gene() {
+    let rng = &mut SmallRng::seed_from_u64(0);
+    let genotype = VariableListGenotype::builder()
+        .with_genes_size_range(2..=6)
+        .with_allele_list(vec![5, 2, 3, 4])
+        .build()
+        .unwrap();
+
+    let mut father = build::chromosome(vec![2, 2, 3]);
+    let mut mother = build::chromosome(vec![5, 5, 4, 4, 3]);
+    genotype.crossover_chromosome_genes(3, false, &mut father, &mut mother, rng);
+    assert_eq!(inspect::chromosome(&father), vec![5, 5, 4]);
+    assert_eq!(inspect::chromosome(&mother), vec![2, 2, 3, 4, 3]);
+}
+
+#[test]
+fn crossover_chromosome_pair_single_point() {
+    let rng = &mut SmallRng::seed_from_u64(0);
+    let genotype = VariableListGenotype::builder()
+        .with_genes_size_range(2..=6)
+        .with_allele_list(vec![5, 2, 3, 4])
+        .build()
+        .unwrap();
+
+    let mut father = build::chromosome(vec![2, 2, 3]);
+    let mut mother = build::chromosome(vec![5, 5, 4, 4, 3]);
+    genotype.crossover_chromosome_points(1, true, &mut father, &mut mother, rng);
+    assert_eq!(inspect::chromosome(&father), vec![2, 2, 4, 3]);
+    assert_eq!(inspect::chromosome(&mother), vec![5, 5, 4, 3]);
+}
+
+#[test]
+fn crossover_chromosome_pair_multi_point_within_bounds() {
+    let rng = &mut SmallRng::seed_from_u64(0);
+    let genotype = VariableListGenotype::builder()
+        .with_genes_size_range(2..=6)
+        .with_allele_list(vec![5, 2, 3, 4])
+        .build()
+        .unwrap();
+
+    let mut father = build::chromosome(vec![2, 2]);
+    let mut mother = build::chromosome(vec![5, 5, 4, 4, 3, 3]);
+    for _ in 0..100 {
+        genotype.crossover_chromosome_points(3, true, &mut father, &mut mother, rng);
+        assert!((2..=6).contains(&father.genes.len()));
+        assert!((2..=6).contains(&mother.genes.len()));
+        assert_eq!(father.genes.len() + mother.genes.len(), 8);
+    }
+}
+
+#[test]
+fn neighbouring_population() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let genotype = VariableListGenotype::builder()
+        .with_genes_size_range(1..=3)
+        .with_allele_list(vec![1, 2])
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        genotype.neighbouring_population_size(),
+        BigUint::from(12u32)
+    );
+    let chromosome = build::chromosome(vec![1, 2]);
+    let mut population = Population::new(vec![], true);
+    genotype.fill_neighbouring_population(&chromosome, &mut population, &mut rng);
+    assert_eq!(
+        inspect::population(&population),
+        vec![
+            vec![2, 2],
+            vec![1, 1],
+            vec![1, 1, 2],
+            vec![2, 1, 2],
+            vec![1, 1, 2],
+            vec![1, 2, 2],
+            vec![1, 2, 1],
+            vec![1, 2, 2],
+            vec![2],
+            vec![1],
+        ]
+    );
+}
+
+#[test]
+fn chromosome_permutations() {
+    let genotype = VariableListGenotype::builder()
+        .with_genes_size_range(0..=2)
+        .with_allele_list(vec![0, 1])
+        .build()
+        .unwrap();
+
+    assert_eq!(genotype.chromosome_permutations_size(), BigUint::from(7u32));
+    assert_eq!(
+        inspect::chromosomes(
+            genotype
+                .chromosome_permutations_into_iter(None)
+                .collect::<Vec<_>>()
+                .as_slice()
+        ),
+        vec![
+            vec![],
+            vec![0],
+            vec![1],
+            vec![0, 0],
+            vec![0, 1],
+            vec![1, 0],
+            vec![1, 1],
+        ]
+    );
+}
+
+#[test]
+fn evolve_towards_max_genes_size() {
+    let genotype = VariableListGenotype::<u32>::builder()
+        .with_genes_size_range(2..=10)
+        .with_allele_list((0..5).collect())
+        .build()
+        .unwrap();
+
+    let evolve = Evolve::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(100)
+        .with_max_stale_generations(100)
+        .with_target_fitness_score(40)
+        .with_fitness(SumGenes::new())
+        .with_mutate(MutateSingleGene::new(0.2))
+        .with_crossover(CrossoverSinglePoint::new(0.7, 0.8))
+        .with_select(SelectTournament::new(0.5, 0.02, 4))
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    let (best_genes, best_fitness_score) = evolve.best_genes_and_fitness_score().unwrap();
+    assert_eq!(best_fitness_score, 40);
+    assert_eq!(best_genes, vec![4; 10]);
+}
@@ -1,4 +1,5 @@
 pub mod elite_test;
+pub mod novelty_test;
 pub mod tournament_test;
 
 mod select_test {
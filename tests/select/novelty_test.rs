@@ -0,0 +1,280 @@
+#[cfg(test)]
+use crate::support::*;
+use genetic_algorithm::fitness::placeholders::CountTrue;
+use genetic_algorithm::strategy::evolve::prelude::*;
+
+#[derive(Clone, Debug)]
+struct CountTrueBehaviour;
+impl BehaviourDescriptor for CountTrueBehaviour {
+    type Genotype = BinaryGenotype;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &Chromosome<bool>,
+        _genotype: &Self::Genotype,
+    ) -> Vec<f32> {
+        vec![chromosome.genes.iter().filter(|v| **v).count() as f32]
+    }
+}
+
+/// Deceptive: counting true values leads away from the optimum, which is all false
+#[derive(Clone, Debug)]
+struct DeceptiveCountTrue;
+impl Fitness for DeceptiveCountTrue {
+    type Genotype = BinaryGenotype;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &FitnessChromosome<Self>,
+        _genotype: &FitnessGenotype<Self>,
+    ) -> Option<FitnessValue> {
+        let count = chromosome.genes.iter().filter(|v| **v).count();
+        if count == 0 {
+            Some(2 * chromosome.genes.len() as FitnessValue)
+        } else {
+            Some(count as FitnessValue)
+        }
+    }
+}
+
+fn build_population() -> Population<bool> {
+    build::population(vec![
+        vec![false, false, false],
+        vec![false, false, true],
+        vec![false, true, false],
+        vec![false, true, true],
+        vec![true, false, false],
+        vec![true, false, true],
+        vec![true, true, false],
+        vec![true, true, true],
+    ])
+}
+
+#[test]
+fn sparseness() {
+    let mut select = SelectNovelty::new(CountTrueBehaviour, 0.5, 0.0, 4, 2, 10.0);
+    let behaviours = vec![vec![0.0], vec![1.0], vec![3.0]];
+    assert_eq!(select.sparseness(0, &behaviours), 2.0);
+    assert_eq!(select.sparseness(1, &behaviours), 1.5);
+    assert_eq!(select.sparseness(2, &behaviours), 2.5);
+
+    select.archive.push(vec![3.5]);
+    assert_eq!(select.sparseness(2, &behaviours), 1.25);
+}
+
+#[test]
+fn call_novelty() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(3)
+        .build()
+        .unwrap();
+
+    let mut state = EvolveState::new(&genotype);
+    state.population = build_population();
+    let mut reporter = StrategyReporterNoop::<BinaryGenotype>::new();
+    let mut rng = SmallRng::seed_from_u64(0);
+    CountTrue.call_for_population(&mut state.population, &genotype, None, None);
+    let config = EvolveConfig {
+        fitness_ordering: FitnessOrdering::Maximize,
+        target_population_size: 4,
+        ..Default::default()
+    };
+    let mut select = SelectNovelty::new(CountTrueBehaviour, 0.5, 0.0, 8, 3, 0.9);
+    select.call(&genotype, &mut state, &config, &mut reporter, &mut rng);
+
+    // the rare behaviours (zero or three true values) are the most novel
+    assert_eq!(
+        inspect::population(&state.population),
+        vec![
+            vec![true, true, true],
+            vec![false, false, false],
+            vec![false, true, false],
+            vec![true, true, false],
+        ]
+    );
+    assert_eq!(select.archive, vec![vec![0.0], vec![3.0]]);
+}
+
+#[test]
+fn call_novelty_does_not_archive_surviving_parents() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(3)
+        .build()
+        .unwrap();
+
+    let mut state = EvolveState::new(&genotype);
+    state.population = build_population();
+    let mut reporter = StrategyReporterNoop::<BinaryGenotype>::new();
+    let mut rng = SmallRng::seed_from_u64(0);
+    CountTrue.call_for_population(&mut state.population, &genotype, None, None);
+    let config = EvolveConfig {
+        fitness_ordering: FitnessOrdering::Maximize,
+        target_population_size: 4,
+        ..Default::default()
+    };
+    let mut select = SelectNovelty::new(CountTrueBehaviour, 0.5, 0.0, 8, 3, 0.9);
+    select.call(&genotype, &mut state, &config, &mut reporter, &mut rng);
+    assert_eq!(select.archive, vec![vec![0.0], vec![3.0]]);
+
+    // the survivors are parents in the next generation
+    state.population.increment_age();
+    select.call(&genotype, &mut state, &config, &mut reporter, &mut rng);
+    assert_eq!(select.archive, vec![vec![0.0], vec![3.0]]);
+}
+
+#[test]
+fn call_novelty_archive_max_size() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(3)
+        .build()
+        .unwrap();
+
+    let mut state = EvolveState::new(&genotype);
+    state.population = build_population();
+    let mut reporter = StrategyReporterNoop::<BinaryGenotype>::new();
+    let mut rng = SmallRng::seed_from_u64(0);
+    CountTrue.call_for_population(&mut state.population, &genotype, None, None);
+    let config = EvolveConfig {
+        fitness_ordering: FitnessOrdering::Maximize,
+        target_population_size: 4,
+        ..Default::default()
+    };
+    let mut select =
+        SelectNovelty::new(CountTrueBehaviour, 0.5, 0.0, 8, 3, 0.0).with_archive_max_size(3);
+    select.call(&genotype, &mut state, &config, &mut reporter, &mut rng);
+
+    assert_eq!(select.archive.len(), 3);
+}
+
+#[test]
+fn call_fitness_weight() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(3)
+        .build()
+        .unwrap();
+
+    let mut state = EvolveState::new(&genotype);
+    state.population = build_population();
+    let mut reporter = StrategyReporterNoop::<BinaryGenotype>::new();
+    let mut rng = SmallRng::seed_from_u64(0);
+    CountTrue.call_for_population(&mut state.population, &genotype, None, None);
+    let config = EvolveConfig {
+        fitness_ordering: FitnessOrdering::Maximize,
+        target_population_size: 4,
+        ..Default::default()
+    };
+    let mut select =
+        SelectNovelty::new(CountTrueBehaviour, 0.5, 0.0, 8, 3, 1.0).with_fitness_weight(1.0);
+    select.call(&genotype, &mut state, &config, &mut reporter, &mut rng);
+
+    // pure fitness, like a tournament on fitness
+    assert_eq!(
+        state
+            .population
+            .chromosomes
+            .iter()
+            .map(|c| c.fitness_score().unwrap())
+            .collect::<Vec<_>>(),
+        vec![3, 2, 2, 2]
+    );
+}
+
+#[test]
+fn evolve_deceptive() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(20)
+        .build()
+        .unwrap();
+
+    let evolve = Evolve::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(100)
+        .with_max_stale_generations(1000)
+        .with_fitness(DeceptiveCountTrue)
+        .with_target_fitness_score(40)
+        .with_mutate(MutateSingleGene::new(0.2))
+        .with_crossover(CrossoverUniform::new(0.7, 0.8))
+        .with_select(SelectNovelty::new(
+            CountTrueBehaviour,
+            0.5,
+            0.02,
+            4,
+            15,
+            1.0,
+        ))
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    // best chromosome is reported on the true objective
+    let (best_genes, best_fitness_score) = evolve.best_genes_and_fitness_score().unwrap();
+    assert_eq!(best_fitness_score, 40);
+    assert_eq!(best_genes, vec![false; 20]);
+    assert!(!evolve.plugins.select.archive.is_empty());
+}
+
+#[test]
+fn evolve_clears_archive_per_run() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(20)
+        .build()
+        .unwrap();
+    // nothing is archived during a run, only the stale archive of a previous run is present
+    let mut select = SelectNovelty::new(CountTrueBehaviour, 0.5, 0.02, 4, 15, f32::MAX);
+    select.archive.push(vec![-1.0]);
+
+    let builder = Evolve::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(20)
+        .with_max_generations(5)
+        .with_fitness(DeceptiveCountTrue)
+        .with_mutate(MutateSingleGene::new(0.2))
+        .with_crossover(CrossoverUniform::new(0.7, 0.8))
+        .with_select(select)
+        .with_rng_seed_from_u64(0);
+
+    let (mut evolve, runs) = builder.clone().call_repeatedly(3).unwrap();
+    assert_eq!(runs.len(), 2);
+    assert!(evolve.plugins.select.archive.is_empty());
+    assert!(runs.iter().all(|run| run.plugins.select.archive.is_empty()));
+
+    evolve.plugins.select.archive.push(vec![-1.0]);
+    evolve.call();
+    assert!(evolve.plugins.select.archive.is_empty());
+
+    let (evolve, runs) = builder.call_par_repeatedly(3).unwrap();
+    assert_eq!(runs.len(), 2);
+    assert!(evolve.plugins.select.archive.is_empty());
+    assert!(runs.iter().all(|run| run.plugins.select.archive.is_empty()));
+}
+
+#[test]
+fn evolve_steady_state_invalid() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(20)
+        .build()
+        .unwrap();
+
+    let evolve = Evolve::builder()
+        .with_genotype(genotype)
+        .with_variant(EvolveVariant::SteadyState(SteadyStateReplacement::Worst))
+        .with_target_population_size(100)
+        .with_max_stale_generations(1000)
+        .with_fitness(DeceptiveCountTrue)
+        .with_mutate(MutateSingleGene::new(0.2))
+        .with_crossover(CrossoverUniform::new(0.7, 0.8))
+        .with_select(SelectNovelty::new(
+            CountTrueBehaviour,
+            0.5,
+            0.02,
+            4,
+            15,
+            1.0,
+        ))
+        .build();
+
+    assert_eq!(
+        evolve.err(),
+        Some(TryFromEvolveBuilderError(
+            "Evolve SteadyState variant requires a Select strategy which picks parents one at a time (SelectElite or SelectTournament)"
+        ))
+    );
+}
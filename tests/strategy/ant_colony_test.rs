@@ -0,0 +1,243 @@
+#[cfg(test)]
+use genetic_algorithm::strategy::ant_colony::prelude::*;
+
+// 12 cities on a circle, the shortest tour visits them in circular order (in either direction)
+fn city(index: usize) -> (f32, f32) {
+    let angle = index as f32 * std::f32::consts::TAU / 12.0;
+    (100.0 * angle.cos(), 100.0 * angle.sin())
+}
+fn distance(from: usize, to: usize) -> f32 {
+    let (x1, y1) = city(from);
+    let (x2, y2) = city(to);
+    ((x1 - x2).powi(2) + (y1 - y2).powi(2)).sqrt()
+}
+
+#[derive(Clone, Debug)]
+struct TourLength;
+impl Fitness for TourLength {
+    type Genotype = UniqueGenotype<usize>;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &FitnessChromosome<Self>,
+        _genotype: &FitnessGenotype<Self>,
+    ) -> Option<FitnessValue> {
+        let genes = &chromosome.genes;
+        let length: f32 = (0..genes.len())
+            .map(|i| distance(genes[i], genes[(i + 1) % genes.len()]))
+            .sum();
+        Some(length.round() as FitnessValue)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct InverseDistance;
+impl AntColonyHeuristic for InverseDistance {
+    type Allele = usize;
+    fn desirability(&mut self, from: &usize, to: &usize) -> f32 {
+        1.0 / distance(*from, *to)
+    }
+}
+
+// 12 * 2 * 100 * sin(PI / 12)
+const SHORTEST_TOUR: FitnessValue = 621;
+
+fn is_circular_order(genes: &[usize]) -> bool {
+    let size = genes.len();
+    let forward = (0..size).all(|i| (genes[i] + 1) % size == genes[(i + 1) % size]);
+    let backward = (0..size).all(|i| (genes[(i + 1) % size] + 1) % size == genes[i]);
+    forward || backward
+}
+
+#[test]
+fn build_invalid() {
+    let genotype = UniqueGenotype::builder()
+        .with_allele_list((0..12).collect())
+        .build()
+        .unwrap();
+    let ant_colony = AntColony::builder()
+        .with_genotype(genotype.clone())
+        .with_fitness(TourLength)
+        .with_max_stale_generations(10)
+        .build();
+    assert_eq!(
+        ant_colony.err(),
+        Some(TryFromAntColonyBuilderError(
+            "AntColony requires a target_population_size > 0"
+        ))
+    );
+
+    let ant_colony = AntColony::builder()
+        .with_genotype(genotype.clone())
+        .with_fitness(TourLength)
+        .with_target_population_size(10)
+        .with_evaporation_rate(0.0)
+        .with_max_stale_generations(10)
+        .build();
+    assert_eq!(
+        ant_colony.err(),
+        Some(TryFromAntColonyBuilderError(
+            "AntColony requires an evaporation_rate > 0.0 and <= 1.0"
+        ))
+    );
+
+    let ant_colony = AntColony::builder()
+        .with_genotype(genotype)
+        .with_fitness(TourLength)
+        .with_target_population_size(10)
+        .build();
+    assert_eq!(
+        ant_colony.err(),
+        Some(TryFromAntColonyBuilderError(
+            "AntColony requires at least a max_stale_generations, max_generations or target_fitness_score ending condition"
+        ))
+    );
+}
+
+#[test]
+fn call_ant_system_with_heuristic() {
+    let genotype = UniqueGenotype::builder()
+        .with_allele_list((0..12).collect())
+        .build()
+        .unwrap();
+    let ant_colony = AntColony::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(20)
+        .with_heuristic(InverseDistance)
+        .with_fitness(TourLength)
+        .with_fitness_ordering(FitnessOrdering::Minimize)
+        .with_target_fitness_score(SHORTEST_TOUR)
+        .with_max_stale_generations(50)
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    assert_eq!(ant_colony.best_fitness_score(), Some(SHORTEST_TOUR));
+    assert!(is_circular_order(&ant_colony.best_genes().unwrap()));
+    assert_eq!(ant_colony.state.pheromones.len(), 13);
+    assert!(ant_colony
+        .state
+        .pheromones
+        .iter()
+        .all(|row| row.len() == 12));
+}
+
+#[test]
+fn call_max_min_without_heuristic() {
+    let genotype = UniqueGenotype::builder()
+        .with_allele_list((0..12).collect())
+        .build()
+        .unwrap();
+    let ant_colony = AntColony::builder()
+        .with_genotype(genotype)
+        .with_variant(AntColonyVariant::MaxMin)
+        .with_target_population_size(20)
+        .with_evaporation_rate(0.2)
+        .with_fitness(TourLength)
+        .with_fitness_ordering(FitnessOrdering::Minimize)
+        .with_target_fitness_score(SHORTEST_TOUR)
+        .with_max_stale_generations(200)
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    assert_eq!(ant_colony.best_fitness_score(), Some(SHORTEST_TOUR));
+    let max_pheromone = ant_colony.config.max_pheromone();
+    let min_pheromone = max_pheromone / 24.0;
+    assert!(ant_colony
+        .state
+        .pheromones
+        .iter()
+        .flatten()
+        .all(|p| *p >= min_pheromone - 1e-5 && *p <= max_pheromone + 1e-5));
+}
+
+#[test]
+fn call_par_fitness_max_generations() {
+    let genotype = UniqueGenotype::builder()
+        .with_allele_list((0..12).collect())
+        .build()
+        .unwrap();
+    let ant_colony = AntColony::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(10)
+        .with_heuristic(InverseDistance)
+        .with_fitness(TourLength)
+        .with_fitness_ordering(FitnessOrdering::Minimize)
+        .with_par_fitness(true)
+        .with_max_generations(5)
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    assert_eq!(ant_colony.state.current_generation, 5);
+    assert!(ant_colony.best_fitness_score().is_some());
+    assert_eq!(ant_colony.state.strongest_path().len(), 12);
+}
+
+#[test]
+fn call_with_seed_genes() {
+    let genotype = UniqueGenotype::builder()
+        .with_allele_list((0..12).collect())
+        .with_seed_genes_list(vec![(0..12).collect()])
+        .build()
+        .unwrap();
+    let ant_colony = AntColony::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(10)
+        .with_fitness(TourLength)
+        .with_fitness_ordering(FitnessOrdering::Minimize)
+        .with_max_generations(1)
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    assert_eq!(ant_colony.best_fitness_score(), Some(SHORTEST_TOUR));
+    assert_eq!(ant_colony.best_generation(), 0);
+}
+
+#[test]
+fn call_repeatedly() {
+    let genotype = UniqueGenotype::builder()
+        .with_allele_list((0..12).collect())
+        .build()
+        .unwrap();
+    let (best_run, _other_runs) = AntColony::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(10)
+        .with_heuristic(InverseDistance)
+        .with_fitness(TourLength)
+        .with_fitness_ordering(FitnessOrdering::Minimize)
+        .with_target_fitness_score(SHORTEST_TOUR)
+        .with_max_stale_generations(20)
+        .with_rng_seed_from_u64(0)
+        .call_par_repeatedly(3)
+        .unwrap();
+
+    assert_eq!(best_run.best_fitness_score(), Some(SHORTEST_TOUR));
+}
+
+#[test]
+fn call_with_reporter() {
+    let genotype = UniqueGenotype::builder()
+        .with_allele_list((0..12).collect())
+        .build()
+        .unwrap();
+    let mut ant_colony = AntColony::builder()
+        .with_genotype(genotype)
+        .with_variant(AntColonyVariant::MaxMin)
+        .with_target_population_size(10)
+        .with_fitness(TourLength)
+        .with_fitness_ordering(FitnessOrdering::Minimize)
+        .with_max_generations(3)
+        .with_reporter(AntColonyReporterSimple::new_with_buffer(100))
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    let mut buffer: Vec<u8> = vec![];
+    ant_colony.flush_reporter(&mut buffer);
+    let output = String::from_utf8(buffer).unwrap();
+    assert!(output.starts_with("enter - ant_colony/max_min, iteration: 0"));
+    assert!(output.contains("pheromone_matrix_size: 156"));
+    assert!(output.contains("exit - ant_colony/max_min, iteration: 0"));
+}
@@ -1,5 +1,5 @@
 #[cfg(test)]
-use genetic_algorithm::fitness::placeholders::CountTrue;
+use genetic_algorithm::fitness::placeholders::{CountTrue, SumGenes};
 use genetic_algorithm::strategy::prelude::*;
 
 #[test]
@@ -192,3 +192,129 @@ fn call_repeatedly_hill_climb_steepest_ascent() {
         other_first_lines
     );
 }
+
+#[test]
+fn call_repeatedly_estimate_compact_ga() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(5)
+        .build()
+        .unwrap();
+
+    let (mut strategy, others) = StrategyBuilder::new()
+        .with_genotype(genotype)
+        .with_variant(StrategyVariant::Estimate(EstimateVariant::CompactGa))
+        .with_reporter(StrategyReporterSimple::new_with_buffer(100))
+        .with_target_population_size(10)
+        .with_target_fitness_score(5)
+        .with_max_stale_generations(100)
+        .with_fitness(CountTrue)
+        .with_mutate(MutateSingleGene::new(0.1))
+        .with_crossover(CrossoverSingleGene::new(0.7, 0.8))
+        .with_select(SelectTournament::new(0.5, 0.02, 4))
+        .with_rng_seed_from_u64(0)
+        .call_repeatedly(3)
+        .unwrap();
+
+    let (best_genes, best_fitness_score) = strategy.best_genes_and_fitness_score().unwrap();
+    assert_eq!(best_genes, vec![true; 5]);
+    assert_eq!(best_fitness_score, 5);
+    // short circuits on target_fitness_score
+    assert!(others.is_empty());
+
+    let mut buffer: Vec<u8> = vec![];
+    strategy.flush_reporter(&mut buffer);
+    assert_eq!(
+        Some("enter - estimate/compact_ga, iteration: 0"),
+        String::from_utf8(buffer).unwrap().lines().next()
+    );
+}
+
+#[test]
+fn build_invalid_estimate_range() {
+    let genotype = RangeGenotype::builder()
+        .with_genes_size(5)
+        .with_allele_range(0.0..=1.0)
+        .build()
+        .unwrap();
+
+    let strategy = StrategyBuilder::new()
+        .with_genotype(genotype)
+        .with_variant(StrategyVariant::Estimate(EstimateVariant::Umda))
+        .with_target_population_size(10)
+        .with_max_stale_generations(100)
+        .with_fitness(SumGenes::new())
+        .with_mutate(MutateSingleGene::new(0.1))
+        .with_crossover(CrossoverSingleGene::new(0.7, 0.8))
+        .with_select(SelectTournament::new(0.5, 0.02, 4))
+        .build();
+
+    assert_eq!(
+        strategy.err(),
+        Some(TryFromStrategyBuilderError(
+            "Estimate requires a genotype with a finite allele list per gene (BinaryGenotype, ListGenotype or MultiListGenotype without gene_constraints)"
+        ))
+    );
+}
+
+#[test]
+fn call_repeatedly_particle_swarm_ring() {
+    let genotype = RangeGenotype::builder()
+        .with_genes_size(5)
+        .with_allele_range(0.0..=1.0)
+        .build()
+        .unwrap();
+
+    let (mut strategy, others) = StrategyBuilder::new()
+        .with_genotype(genotype)
+        .with_variant(StrategyVariant::ParticleSwarm(ParticleSwarmVariant::Ring))
+        .with_reporter(StrategyReporterSimple::new_with_buffer(100))
+        .with_target_population_size(10)
+        .with_target_fitness_score(5000)
+        .with_max_stale_generations(100)
+        .with_fitness(SumGenes::new_with_precision(1e-3))
+        .with_mutate(MutateSingleGene::new(0.1))
+        .with_crossover(CrossoverSingleGene::new(0.7, 0.8))
+        .with_select(SelectTournament::new(0.5, 0.02, 4))
+        .with_rng_seed_from_u64(0)
+        .call_repeatedly(3)
+        .unwrap();
+
+    let (best_genes, best_fitness_score) = strategy.best_genes_and_fitness_score().unwrap();
+    assert_eq!(best_genes, vec![1.0; 5]);
+    assert_eq!(best_fitness_score, 5000);
+    // short circuits on target_fitness_score
+    assert!(others.is_empty());
+
+    let mut buffer: Vec<u8> = vec![];
+    strategy.flush_reporter(&mut buffer);
+    assert_eq!(
+        Some("enter - particle_swarm/ring, iteration: 0"),
+        String::from_utf8(buffer).unwrap().lines().next()
+    );
+}
+
+#[test]
+fn build_invalid_particle_swarm_binary() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(5)
+        .build()
+        .unwrap();
+
+    let strategy = StrategyBuilder::new()
+        .with_genotype(genotype)
+        .with_variant(StrategyVariant::ParticleSwarm(ParticleSwarmVariant::Global))
+        .with_target_population_size(10)
+        .with_max_stale_generations(100)
+        .with_fitness(CountTrue)
+        .with_mutate(MutateSingleGene::new(0.1))
+        .with_crossover(CrossoverSingleGene::new(0.7, 0.8))
+        .with_select(SelectTournament::new(0.5, 0.02, 4))
+        .build();
+
+    assert_eq!(
+        strategy.err(),
+        Some(TryFromStrategyBuilderError(
+            "ParticleSwarm requires a genotype with a numeric allele range per gene (RangeGenotype or MultiRangeGenotype without gene_constraints)"
+        ))
+    );
+}
@@ -0,0 +1,297 @@
+#[cfg(test)]
+use genetic_algorithm::fitness::placeholders::{CountTrue, SumGenes};
+use genetic_algorithm::strategy::estimate::prelude::*;
+
+#[test]
+fn build_invalid() {
+    let genotype = RangeGenotype::builder()
+        .with_genes_size(10)
+        .with_allele_range(0.0..=1.0)
+        .build()
+        .unwrap();
+    let estimate = Estimate::builder()
+        .with_genotype(genotype)
+        .with_fitness(SumGenes::new_with_precision(1e-3))
+        .with_target_population_size(100)
+        .with_max_stale_generations(10)
+        .build();
+    assert_eq!(
+        estimate.err(),
+        Some(TryFromEstimateBuilderError(
+            "Estimate requires a genotype with a finite allele list per gene (BinaryGenotype, ListGenotype or MultiListGenotype without gene_constraints)"
+        ))
+    );
+
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(10)
+        .build()
+        .unwrap();
+    let estimate = Estimate::builder()
+        .with_genotype(genotype.clone())
+        .with_fitness(CountTrue)
+        .with_max_stale_generations(10)
+        .build();
+    assert_eq!(
+        estimate.err(),
+        Some(TryFromEstimateBuilderError(
+            "Estimate requires a target_population_size > 0"
+        ))
+    );
+
+    let estimate = Estimate::builder()
+        .with_genotype(genotype.clone())
+        .with_fitness(CountTrue)
+        .with_target_population_size(100)
+        .with_learning_rate(0.0)
+        .with_max_stale_generations(10)
+        .build();
+    assert_eq!(
+        estimate.err(),
+        Some(TryFromEstimateBuilderError(
+            "Estimate requires a learning_rate > 0.0 and <= 1.0"
+        ))
+    );
+
+    let estimate = Estimate::builder()
+        .with_genotype(genotype)
+        .with_fitness(CountTrue)
+        .with_target_population_size(100)
+        .build();
+    assert_eq!(
+        estimate.err(),
+        Some(TryFromEstimateBuilderError(
+            "Estimate requires at least a max_stale_generations, max_generations or target_fitness_score ending condition"
+        ))
+    );
+
+    // the probability model can't enforce gene_constraints
+    let genotype = MultiListGenotype::builder()
+        .with_allele_lists(vec![vec![0_u8, 1], vec![0, 1, 2]])
+        .with_gene_constraints(vec![GeneConstraint::ForbiddenPair {
+            gene_indices: (0, 1),
+            values: (1, 2),
+        }])
+        .build()
+        .unwrap();
+    let estimate = Estimate::builder()
+        .with_genotype(genotype)
+        .with_fitness(SumGenes::new())
+        .with_target_population_size(100)
+        .with_max_stale_generations(10)
+        .build();
+    assert_eq!(
+        estimate.err(),
+        Some(TryFromEstimateBuilderError(
+            "Estimate requires a genotype with a finite allele list per gene (BinaryGenotype, ListGenotype or MultiListGenotype without gene_constraints)"
+        ))
+    );
+}
+
+#[test]
+fn call_binary_umda_target_fitness_score() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(50)
+        .build()
+        .unwrap();
+    let estimate = Estimate::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(50)
+        .with_fitness(CountTrue)
+        .with_target_fitness_score(50)
+        .with_max_stale_generations(20)
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    assert_eq!(estimate.best_fitness_score(), Some(50));
+    assert_eq!(estimate.best_genes(), Some(vec![true; 50]));
+    assert_eq!(estimate.state.probabilities.len(), 50);
+    assert!(estimate
+        .state
+        .probabilities
+        .iter()
+        .all(|p| (p.iter().sum::<f32>() - 1.0).abs() < 1e-5));
+    assert_eq!(estimate.most_likely_genes(), Some(vec![true; 50]));
+}
+
+#[test]
+fn call_binary_umda_keeps_lost_alleles_sampleable() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(10)
+        .build()
+        .unwrap();
+    let estimate = Estimate::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(50)
+        .with_fitness(CountTrue)
+        .with_max_stale_generations(20)
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    assert_eq!(estimate.best_genes(), Some(vec![true; 10]));
+    // the selection has lost the false allele, but the floor of 1/(10 * 2) keeps it sampleable
+    assert!(estimate
+        .state
+        .probabilities
+        .iter()
+        .all(|p| p[0] >= 0.05 - 1e-5 && p[0] < 0.1));
+    assert!(estimate
+        .state
+        .probabilities
+        .iter()
+        .all(|p| (p.iter().sum::<f32>() - 1.0).abs() < 1e-5));
+}
+
+#[test]
+fn call_binary_pbil_minimize() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(20)
+        .build()
+        .unwrap();
+    let estimate = Estimate::builder()
+        .with_genotype(genotype)
+        .with_variant(EstimateVariant::Pbil)
+        .with_target_population_size(20)
+        .with_selection_rate(0.2)
+        .with_learning_rate(0.2)
+        .with_fitness(CountTrue)
+        .with_fitness_ordering(FitnessOrdering::Minimize)
+        .with_max_stale_generations(20)
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    assert_eq!(estimate.best_fitness_score(), Some(0));
+    assert_eq!(estimate.best_genes(), Some(vec![false; 20]));
+    assert!(estimate.state.model_convergence() > 0.9);
+    assert_eq!(estimate.state.most_likely_allele_indices(), vec![0; 20]);
+}
+
+#[test]
+fn call_binary_compact_ga() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(20)
+        .build()
+        .unwrap();
+    let estimate = Estimate::builder()
+        .with_genotype(genotype)
+        .with_variant(EstimateVariant::CompactGa)
+        .with_target_population_size(20)
+        .with_fitness(CountTrue)
+        .with_target_fitness_score(20)
+        .with_max_stale_generations(1000)
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    assert_eq!(estimate.best_fitness_score(), Some(20));
+    assert_eq!(estimate.config.sample_size(), 2);
+    // probabilities move in steps of 1/20
+    assert!(estimate
+        .state
+        .probabilities
+        .iter()
+        .all(|p| (p[1] * 20.0 - (p[1] * 20.0).round()).abs() < 1e-3));
+}
+
+#[test]
+fn call_list_umda() {
+    let genotype = ListGenotype::builder()
+        .with_genes_size(10)
+        .with_allele_list(vec![0_u8, 1, 2, 3])
+        .build()
+        .unwrap();
+    let estimate = Estimate::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(50)
+        .with_fitness(SumGenes::new())
+        .with_target_fitness_score(30)
+        .with_max_stale_generations(20)
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    assert_eq!(estimate.best_fitness_score(), Some(30));
+    assert_eq!(estimate.best_genes(), Some(vec![3; 10]));
+    assert!(estimate.state.probabilities.iter().all(|p| p.len() == 4));
+}
+
+#[test]
+fn call_multi_list_pbil() {
+    let genotype = MultiListGenotype::builder()
+        .with_allele_lists(vec![
+            vec![0_u8, 1],
+            vec![0, 1, 2],
+            vec![0, 1, 2, 3],
+            vec![0, 1, 2, 3, 4],
+        ])
+        .build()
+        .unwrap();
+    let estimate = Estimate::builder()
+        .with_genotype(genotype)
+        .with_variant(EstimateVariant::Pbil)
+        .with_target_population_size(20)
+        .with_fitness(SumGenes::new())
+        .with_fitness_ordering(FitnessOrdering::Minimize)
+        .with_max_stale_generations(20)
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    assert_eq!(estimate.best_fitness_score(), Some(0));
+    assert_eq!(estimate.best_genes(), Some(vec![0, 0, 0, 0]));
+    assert_eq!(
+        estimate
+            .state
+            .probabilities
+            .iter()
+            .map(|p| p.len())
+            .collect::<Vec<_>>(),
+        vec![2, 3, 4, 5]
+    );
+    assert_eq!(estimate.most_likely_genes(), Some(vec![0, 0, 0, 0]));
+}
+
+#[test]
+fn call_binary_with_reporter() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(10)
+        .build()
+        .unwrap();
+    let mut estimate = Estimate::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(20)
+        .with_fitness(CountTrue)
+        .with_target_fitness_score(10)
+        .with_max_stale_generations(20)
+        .with_reporter(EstimateReporterSimple::new_with_buffer(100))
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    assert_eq!(estimate.best_fitness_score(), Some(10));
+    let mut buffer: Vec<u8> = vec![];
+    estimate.flush_reporter(&mut buffer);
+    let output = String::from_utf8(buffer).unwrap();
+    assert!(output.starts_with("enter - estimate/umda, iteration: 0"));
+    assert!(output.contains("probability_model_size: 20"));
+}
+
+#[test]
+fn most_likely_genes_before_call() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(10)
+        .build()
+        .unwrap();
+    let estimate = Estimate::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(20)
+        .with_fitness(CountTrue)
+        .with_max_generations(10)
+        .build()
+        .unwrap();
+
+    assert_eq!(estimate.most_likely_genes(), None);
+    assert!(estimate.state.probabilities.is_empty());
+}
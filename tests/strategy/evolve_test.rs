@@ -2,6 +2,8 @@
 use crate::support::*;
 use genetic_algorithm::fitness::placeholders::{CountTrue, SumGenes};
 use genetic_algorithm::strategy::evolve::prelude::*;
+use itertools::Itertools;
+use rand::Rng;
 
 #[test]
 fn build_invalid_missing_ending_condition() {
@@ -57,6 +59,38 @@ fn call_binary_max_stale_generations_maximize() {
     );
 }
 
+#[test]
+fn call_binary_shared_fitness_cache() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(10)
+        .with_genes_hashing(true)
+        .build()
+        .unwrap();
+    let fitness_cache = FitnessCache::new_unbounded().with_genes_in_key(true);
+    let evolve_builder = Evolve::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(100)
+        .with_max_stale_generations(20)
+        .with_mutate(MutateSingleGene::new(0.1))
+        .with_fitness(CountTrue)
+        .with_shared_fitness_cache(fitness_cache.clone())
+        .with_crossover(CrossoverSingleGene::new(0.7, 0.8))
+        .with_select(SelectTournament::new(0.5, 0.02, 4))
+        .with_rng_seed_from_u64(0);
+
+    let evolve = evolve_builder.clone().call().unwrap();
+    assert_eq!(evolve.best_fitness_score(), Some(10));
+    let cache_size = fitness_cache.len();
+    assert!(cache_size > 0);
+    let (_, cache_misses, _) = fitness_cache.hit_miss_stats();
+
+    // same seed, all values are cached by the first run
+    let evolve = evolve_builder.call().unwrap();
+    assert_eq!(evolve.best_fitness_score(), Some(10));
+    assert_eq!(fitness_cache.len(), cache_size);
+    assert_eq!(fitness_cache.hit_miss_stats().1, cache_misses);
+}
+
 #[test]
 fn call_binary_max_stale_generations_minimize() {
     let genotype = BinaryGenotype::builder()
@@ -509,3 +543,285 @@ fn population_factory_binary() {
         ]
     )
 }
+
+#[test]
+fn tune_search_space_candidates() {
+    let search_space = EvolveTuneSearchSpace::new()
+        .with_target_population_sizes(vec![10, 20, 30])
+        .with_mutation_probabilities(vec![0.1, 0.2]);
+    let candidates = search_space.candidates();
+    assert_eq!(candidates.len(), 6);
+    assert_eq!(candidates[0].target_population_size, 10);
+    assert_eq!(candidates[0].mutation_probability, 0.1);
+    assert_eq!(candidates[5].target_population_size, 30);
+    assert_eq!(candidates[5].mutation_probability, 0.2);
+}
+
+#[test]
+fn tune_successive_halving() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(20)
+        .build()
+        .unwrap();
+    // mutation_probability 0.0 without crossover never reaches the target
+    let tuner = EvolveTuner::new(vec![0.0, 0.5, 0.0, 0.0, 0.0])
+        .with_repeats_per_round(2)
+        .with_rng_seed_from_u64(0);
+    let call = |par_runs: bool| {
+        tuner
+            .clone()
+            .with_par_runs(par_runs)
+            .call(|mutation_probability: &f32| {
+                Evolve::builder()
+                    .with_genotype(genotype.clone())
+                    .with_target_population_size(20)
+                    .with_target_fitness_score(20)
+                    .with_max_generations(200)
+                    .with_fitness(CountTrue)
+                    .with_select(SelectElite::new(0.5, 0.02))
+                    .with_crossover(CrossoverClone::new(0.5))
+                    .with_mutate(MutateSingleGene::new(*mutation_probability))
+            })
+            .unwrap()
+    };
+
+    let results = call(true);
+    assert_eq!(results.len(), 5);
+    // 5 -> 3 -> 2 -> 1 candidates
+    assert_eq!(
+        results.iter().map(|r| r.rounds).collect::<Vec<_>>(),
+        vec![4, 3, 2, 1, 1]
+    );
+    assert_eq!(results[0].parameters, 0.5);
+    assert_eq!(results[0].runs.len(), 8);
+    assert_eq!(results[0].target_rate(), 1.0);
+    assert_eq!(results[0].best_fitness_score_mean(), Some(20.0));
+    assert_eq!(results[0].best_fitness_score_variance(), Some(0.0));
+    assert!(results[0].time_to_target_mean().is_some());
+    assert!(results[0].generations_to_target_mean().is_some());
+    assert_eq!(results[4].runs.len(), 2);
+    assert_eq!(results[4].target_rate(), 0.0);
+    assert_eq!(results[4].time_to_target_mean(), None);
+
+    let rng_seeds = |results: &[EvolveTuneResult<f32>]| {
+        results[0]
+            .runs
+            .iter()
+            .map(|run| (run.rng_seed, run.best_fitness_score, run.generations))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(rng_seeds(&call(false)), rng_seeds(&results));
+}
+
+#[test]
+fn tune_max_rounds_and_invalid_builder() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(10)
+        .build()
+        .unwrap();
+    let factory = |max_stale_generations: &Option<usize>| {
+        let builder = Evolve::builder()
+            .with_genotype(genotype.clone())
+            .with_target_population_size(20)
+            .with_fitness(CountTrue)
+            .with_select(SelectTournament::new(0.5, 0.02, 4))
+            .with_crossover(CrossoverUniform::new(0.7, 0.8))
+            .with_mutate(MutateSingleGene::new(0.2));
+        match max_stale_generations {
+            Some(max_stale_generations) => {
+                builder.with_max_stale_generations(*max_stale_generations)
+            }
+            None => builder,
+        }
+    };
+
+    let results = EvolveTuner::new(vec![Some(5), Some(10), Some(20), Some(50)])
+        .with_max_rounds(1)
+        .with_rng_seed_from_u64(0)
+        .call(factory)
+        .unwrap();
+    assert!(results.iter().all(|r| r.rounds == 1 && r.runs.len() == 3));
+
+    let result = EvolveTuner::new(vec![Some(5), None])
+        .with_rng_seed_from_u64(0)
+        .call(factory);
+    assert_eq!(
+        result.err(),
+        Some(TryFromEvolveBuilderError(
+            "Evolve requires at least a max_stale_generations, max_generations or target_fitness_score ending condition"
+        ))
+    );
+}
+
+#[test]
+fn call_binary_steady_state_replacements() {
+    for replacement in [
+        SteadyStateReplacement::Worst,
+        SteadyStateReplacement::Oldest,
+        SteadyStateReplacement::Random,
+        SteadyStateReplacement::ParentIfBetter,
+    ] {
+        let genotype = BinaryGenotype::builder()
+            .with_genes_size(20)
+            .build()
+            .unwrap();
+        let evolve = Evolve::builder()
+            .with_genotype(genotype)
+            .with_variant(EvolveVariant::SteadyState(replacement))
+            .with_target_population_size(50)
+            .with_max_stale_generations(100)
+            .with_target_fitness_score(20)
+            .with_mutate(MutateSingleGene::new(0.5))
+            .with_fitness(CountTrue)
+            .with_crossover(CrossoverUniform::new(1.0, 0.8))
+            .with_select(SelectTournament::new(0.5, 0.02, 4))
+            .with_rng_seed_from_u64(0)
+            .call()
+            .unwrap();
+
+        assert_eq!(
+            evolve.best_fitness_score(),
+            Some(20),
+            "replacement: {:?}",
+            replacement
+        );
+    }
+}
+
+// records the fitness score and age of the population chromosomes after each generation
+#[derive(Clone, Debug, Default)]
+struct PopulationRecorder {
+    pub generations: Vec<Vec<(Option<FitnessValue>, usize)>>,
+}
+impl Extension for PopulationRecorder {
+    type Genotype = BinaryGenotype;
+    fn after_generation_complete<R: Rng, SR: StrategyReporter<Genotype = Self::Genotype>>(
+        &mut self,
+        _genotype: &mut Self::Genotype,
+        state: &mut EvolveState<Self::Genotype>,
+        _config: &EvolveConfig,
+        _reporter: &mut SR,
+        _rng: &mut R,
+    ) {
+        self.generations.push(
+            state
+                .population
+                .chromosomes
+                .iter()
+                .map(|chromosome| (chromosome.fitness_score(), chromosome.age()))
+                .collect(),
+        );
+    }
+}
+
+#[test]
+fn call_binary_steady_state_replacement_policies() {
+    let call = |replacement: SteadyStateReplacement, elitism_rate: f32| {
+        let genotype = BinaryGenotype::builder()
+            .with_genes_size(20)
+            .build()
+            .unwrap();
+        let evolve = Evolve::builder()
+            .with_genotype(genotype)
+            .with_variant(EvolveVariant::SteadyState(replacement))
+            .with_target_population_size(20)
+            .with_max_generations(10)
+            .with_mutate(MutateSingleGene::new(0.5))
+            .with_fitness(CountTrue)
+            .with_crossover(CrossoverUniform::new(1.0, 0.8))
+            .with_select(SelectTournament::new(0.5, elitism_rate, 4))
+            .with_extension(PopulationRecorder::default())
+            .with_rng_seed_from_u64(0)
+            .call()
+            .unwrap();
+        let generations = evolve.plugins.extension.generations;
+        assert_eq!(generations.len(), 10);
+        assert!(generations.iter().all(|population| population.len() == 20));
+        generations
+    };
+    let best_fitness_score = |population: &Vec<(Option<FitnessValue>, usize)>| {
+        population.iter().filter_map(|(score, _)| *score).max()
+    };
+
+    // the offspring only replace their own parent, and only when not worse
+    let generations = call(SteadyStateReplacement::ParentIfBetter, 0.0);
+    assert_ne!(generations.first(), generations.last());
+    for (previous, current) in generations.iter().tuple_windows() {
+        assert!(previous
+            .iter()
+            .zip(current.iter())
+            .all(|((previous_score, _), (current_score, _))| current_score >= previous_score));
+    }
+
+    // a generation worth of offspring replaces all chromosomes of the previous generations
+    let generations = call(SteadyStateReplacement::Oldest, 0.0);
+    assert!(generations.iter().flatten().all(|(_, age)| *age == 0));
+
+    // except for the protected elite (of 2 chromosomes)
+    let generations = call(SteadyStateReplacement::Oldest, 0.1);
+    assert!(generations.iter().all(|population| population
+        .iter()
+        .filter(|(_, age)| *age > 0)
+        .count()
+        <= 2));
+    assert!(generations
+        .iter()
+        .any(|population| population.iter().any(|(_, age)| *age > 0)));
+
+    // the elite protects the best chromosome from random replacement
+    let generations = call(SteadyStateReplacement::Random, 0.1);
+    for (previous, current) in generations.iter().tuple_windows() {
+        assert!(best_fitness_score(current) >= best_fitness_score(previous));
+    }
+
+    // the worst chromosome is replaced, so the best chromosome survives
+    let generations = call(SteadyStateReplacement::Worst, 0.0);
+    for (previous, current) in generations.iter().tuple_windows() {
+        assert!(best_fitness_score(current) >= best_fitness_score(previous));
+    }
+}
+
+#[derive(Clone, Debug)]
+struct CountTrueEvaluations {
+    pub evaluations: usize,
+}
+impl Fitness for CountTrueEvaluations {
+    type Genotype = BinaryGenotype;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &FitnessChromosome<Self>,
+        _genotype: &FitnessGenotype<Self>,
+    ) -> Option<FitnessValue> {
+        self.evaluations += 1;
+        Some(chromosome.genes.iter().filter(|&value| *value).count() as FitnessValue)
+    }
+}
+
+#[test]
+fn call_binary_steady_state_generations() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(100)
+        .build()
+        .unwrap();
+    let evolve = Evolve::builder()
+        .with_genotype(genotype)
+        .with_variant(EvolveVariant::SteadyState(SteadyStateReplacement::Worst))
+        .with_target_population_size(20)
+        .with_max_generations(5)
+        .with_mutate(MutateSingleGene::new(1.0))
+        .with_fitness(CountTrueEvaluations { evaluations: 0 })
+        .with_crossover(CrossoverUniform::new(1.0, 1.0))
+        .with_select(SelectTournament::new(0.5, 0.02, 4))
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    // a generation is a population size worth of offspring, all mutated so all evaluated
+    assert_eq!(evolve.state.current_generation, 5);
+    assert_eq!(evolve.fitness.evaluations, 20 + 5 * 20);
+    assert!(evolve.best_fitness_score().unwrap() > 50);
+    assert_eq!(
+        format!("{}", evolve.config.variant()),
+        "evolve/steady_state/worst"
+    );
+}
@@ -278,3 +278,80 @@ fn call_binary_steepest_ascent() {
     println!("{:#?}", hill_climb.best_genes());
     assert_eq!(hill_climb.best_fitness_score(), Some(0));
 }
+
+#[test]
+fn build_invalid_stochastic_tabu_evaluated() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(10)
+        .build()
+        .unwrap();
+
+    let hill_climb = HillClimb::builder()
+        .with_genotype(genotype)
+        .with_variant(HillClimbVariant::Stochastic)
+        .with_max_stale_generations(10)
+        .with_fitness(CountTrue)
+        .with_tabu_evaluated(true)
+        .build();
+
+    assert!(hill_climb.is_err());
+    assert_eq!(
+        hill_climb.err(),
+        Some(TryFromHillClimbBuilderError(
+            "HillClimb tabu_evaluated is only supported for the SteepestAscent variant"
+        ))
+    );
+}
+
+#[derive(Clone, Debug)]
+struct CountTrueEvaluations {
+    pub evaluations: usize,
+}
+impl Fitness for CountTrueEvaluations {
+    type Genotype = BinaryGenotype;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &FitnessChromosome<Self>,
+        _genotype: &FitnessGenotype<Self>,
+    ) -> Option<FitnessValue> {
+        self.evaluations += 1;
+        Some(chromosome.genes.iter().filter(|&value| *value).count() as FitnessValue)
+    }
+}
+
+#[test]
+fn call_binary_steepest_ascent_tabu_evaluated() {
+    let run = |tabu_evaluated: bool| {
+        let genotype = BinaryGenotype::builder()
+            .with_genes_size(100)
+            .with_genes_hashing(false)
+            .build()
+            .unwrap();
+        let mut hill_climb = HillClimb::builder()
+            .with_genotype(genotype)
+            .with_variant(HillClimbVariant::SteepestAscent)
+            .with_fitness_ordering(FitnessOrdering::Minimize)
+            .with_target_fitness_score(0)
+            .with_fitness(CountTrueEvaluations { evaluations: 0 })
+            .with_tabu_evaluated(tabu_evaluated)
+            .with_reporter(StrategyReporterNoop::new())
+            .with_rng_seed_from_u64(0)
+            .build()
+            .unwrap();
+        assert_eq!(hill_climb.genotype.genes_hashing(), tabu_evaluated);
+
+        hill_climb.call();
+        assert_eq!(hill_climb.best_fitness_score(), Some(0));
+        assert!(hill_climb.state.evaluated_genes_hashes.is_empty());
+        (
+            hill_climb.state.current_generation,
+            hill_climb.fitness.evaluations,
+        )
+    };
+
+    let (generations, evaluations) = run(false);
+    let (tabu_generations, tabu_evaluations) = run(true);
+    // same search path, but each generation after the first skips at least the step back
+    assert_eq!(tabu_generations, generations);
+    assert!(tabu_evaluations + generations - 1 <= evaluations);
+}
@@ -0,0 +1,263 @@
+#[cfg(test)]
+use crate::support::*;
+use genetic_algorithm::fitness::placeholders::{CountTrue, SumGenes};
+use genetic_algorithm::strategy::map_elites::prelude::*;
+
+#[derive(Clone, Debug)]
+struct FirstAndLastGene;
+impl BehaviourDescriptor for FirstAndLastGene {
+    type Genotype = RangeGenotype<f32>;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &Chromosome<f32>,
+        _genotype: &Self::Genotype,
+    ) -> Vec<f32> {
+        vec![chromosome.genes[0], *chromosome.genes.last().unwrap()]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct CountTrueInFirstHalf;
+impl BehaviourDescriptor for CountTrueInFirstHalf {
+    type Genotype = BinaryGenotype;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &Chromosome<bool>,
+        _genotype: &Self::Genotype,
+    ) -> Vec<f32> {
+        let half = chromosome.genes.len() / 2;
+        vec![chromosome.genes[..half].iter().filter(|v| **v).count() as f32]
+    }
+}
+
+fn build_range_genotype() -> RangeGenotype<f32> {
+    RangeGenotype::builder()
+        .with_genes_size(10)
+        .with_allele_range(0.0..=1.0)
+        .with_mutation_type(MutationType::Random)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn build_invalid() {
+    let map_elites = MapElites::<_, _, FirstAndLastGene, _>::builder()
+        .with_genotype(build_range_genotype())
+        .with_fitness(SumGenes::new_with_precision(1e-3))
+        .with_grid(MapElitesGrid::new(vec![(0.0..=1.0, 5), (0.0..=1.0, 5)]))
+        .with_max_generations(10)
+        .build();
+    assert_eq!(
+        map_elites.err(),
+        Some(TryFromMapElitesBuilderError(
+            "MapElites requires a BehaviourDescriptor"
+        ))
+    );
+
+    let map_elites = MapElites::builder()
+        .with_genotype(build_range_genotype())
+        .with_fitness(SumGenes::new_with_precision(1e-3))
+        .with_descriptor(FirstAndLastGene)
+        .with_grid(MapElitesGrid::new(vec![(0.0..=1.0, 0)]))
+        .with_max_generations(10)
+        .build();
+    assert_eq!(
+        map_elites.err(),
+        Some(TryFromMapElitesBuilderError(
+            "MapElites requires a grid with at least one dimension and bins > 0"
+        ))
+    );
+
+    let map_elites = MapElites::builder()
+        .with_genotype(build_range_genotype())
+        .with_fitness(SumGenes::new_with_precision(1e-3))
+        .with_descriptor(FirstAndLastGene)
+        .with_grid(MapElitesGrid::new(vec![(0.0..=1.0, 5), (0.0..=1.0, 5)]))
+        .build();
+    assert_eq!(
+        map_elites.err(),
+        Some(TryFromMapElitesBuilderError(
+            "MapElites requires at least a max_stale_generations, max_generations or target_fitness_score ending condition"
+        ))
+    );
+}
+
+#[test]
+fn grid_cell_index() {
+    let grid = MapElitesGrid::new(vec![(0.0..=1.0, 4), (-10.0..=10.0, 2)]);
+    assert_eq!(grid.number_of_cells(), 8);
+    assert_eq!(grid.cell_index(&[0.0, -10.0]), Some(0));
+    assert_eq!(grid.cell_index(&[0.0, 10.0]), Some(1));
+    assert_eq!(grid.cell_index(&[0.3, 5.0]), Some(3));
+    assert_eq!(grid.cell_index(&[1.0, 10.0]), Some(7));
+    // clamped to outer bins
+    assert_eq!(grid.cell_index(&[-5.0, 50.0]), Some(1));
+    assert_eq!(grid.cell_index(&[5.0, -50.0]), Some(6));
+    // invalid descriptors
+    assert_eq!(grid.cell_index(&[0.5]), None);
+    assert_eq!(grid.cell_index(&[f32::NAN, 0.0]), None);
+
+    assert_eq!(grid.cell_coordinates(3), vec![1, 1]);
+    assert_eq!(grid.cell_coordinates(6), vec![3, 0]);
+}
+
+#[test]
+fn archive_insert() {
+    let mut archive: MapElitesArchive<bool> = MapElitesArchive::new(4);
+    let mut chromosome = build::chromosome(vec![true, false]);
+
+    // no fitness score, never inserted
+    let maximize = FitnessOrdering::Maximize;
+    assert_eq!(
+        archive.insert(1, vec![0.0], &chromosome, maximize, true),
+        (false, false)
+    );
+
+    chromosome.set_fitness_score(Some(5));
+    assert_eq!(
+        archive.insert(1, vec![0.0], &chromosome, maximize, true),
+        (true, true)
+    );
+    chromosome.set_fitness_score(Some(3));
+    assert_eq!(
+        archive.insert(1, vec![0.1], &chromosome, maximize, true),
+        (false, false)
+    );
+    chromosome.set_fitness_score(Some(5));
+    assert_eq!(
+        archive.insert(1, vec![0.2], &chromosome, maximize, false),
+        (false, false)
+    );
+    assert_eq!(
+        archive.insert(1, vec![0.3], &chromosome, maximize, true),
+        (true, false)
+    );
+    chromosome.set_fitness_score(Some(7));
+    assert_eq!(
+        archive.insert(3, vec![0.9], &chromosome, maximize, true),
+        (true, true)
+    );
+    // out of grid
+    assert_eq!(
+        archive.insert(4, vec![1.0], &chromosome, maximize, true),
+        (false, false)
+    );
+
+    assert_eq!(archive.size(), 2);
+    assert_eq!(archive.coverage(), 0.5);
+    assert_eq!(archive.qd_score(), 12);
+    assert_eq!(archive.get(1).unwrap().descriptor, vec![0.3]);
+    assert_eq!(
+        archive
+            .elites()
+            .map(|elite| elite.cell_index)
+            .collect::<Vec<_>>(),
+        vec![1, 3]
+    );
+}
+
+#[test]
+fn call_range_max_generations() {
+    let map_elites = MapElites::builder()
+        .with_genotype(build_range_genotype())
+        .with_descriptor(FirstAndLastGene)
+        .with_grid(MapElitesGrid::new(vec![(0.0..=1.0, 5), (0.0..=1.0, 5)]))
+        .with_fitness(SumGenes::new_with_precision(1e-3))
+        .with_batch_size(50)
+        .with_number_of_mutations(2)
+        .with_max_generations(100)
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    assert_eq!(map_elites.state.current_generation, 100);
+    assert_eq!(map_elites.state.archive_size(), Some(25));
+    assert_eq!(map_elites.state.archive_coverage(), Some(1.0));
+    assert!(map_elites.state.archive_qd_score().unwrap() > 25 * 5_000);
+    assert!(map_elites.best_fitness_score().unwrap() > 8_000);
+
+    // the elite descriptors are within their cells
+    let grid = &map_elites.config.grid;
+    map_elites.state.archive.elites().for_each(|elite| {
+        assert_eq!(grid.cell_index(&elite.descriptor), Some(elite.cell_index));
+    });
+    // the best chromosome in the corner cell with high first and last genes
+    let corner = map_elites.state.archive.get(24).unwrap();
+    assert_eq!(
+        corner.chromosome.fitness_score(),
+        map_elites.best_fitness_score()
+    );
+}
+
+#[test]
+fn call_binary_gene_crossover_max_stale_generations() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(10)
+        .build()
+        .unwrap();
+
+    let map_elites = MapElites::builder()
+        .with_genotype(genotype)
+        .with_descriptor(CountTrueInFirstHalf)
+        .with_grid(MapElitesGrid::new(vec![(0.0..=5.0, 6)]))
+        .with_fitness(CountTrue)
+        .with_batch_size(20)
+        .with_gene_crossover(0.5)
+        .with_max_stale_generations(20)
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    assert_eq!(map_elites.state.archive_size(), Some(6));
+    // each cell holds the maximum number of true values given the first half constraint
+    let mut cells = map_elites
+        .state
+        .archive
+        .elites()
+        .map(|elite| (elite.cell_index, elite.chromosome.fitness_score()))
+        .collect::<Vec<_>>();
+    cells.sort();
+    assert_eq!(
+        cells,
+        vec![
+            (0, Some(5)),
+            (1, Some(6)),
+            (2, Some(7)),
+            (3, Some(8)),
+            (4, Some(9)),
+            (5, Some(10)),
+        ]
+    );
+    assert_eq!(map_elites.state.archive_qd_score(), Some(45));
+    assert_eq!(map_elites.best_fitness_score(), Some(10));
+}
+
+#[test]
+fn call_binary_target_fitness_score_with_reporter() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(10)
+        .build()
+        .unwrap();
+
+    let mut map_elites = MapElites::builder()
+        .with_genotype(genotype)
+        .with_descriptor(CountTrueInFirstHalf)
+        .with_grid(MapElitesGrid::new(vec![(0.0..=5.0, 6)]))
+        .with_fitness(CountTrue)
+        .with_batch_size(20)
+        .with_point_crossover(0.5)
+        .with_target_fitness_score(10)
+        .with_reporter(MapElitesReporterSimple::new_with_buffer(1))
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    assert_eq!(map_elites.best_fitness_score(), Some(10));
+    assert_eq!(map_elites.best_genes(), Some(vec![true; 10]));
+
+    let mut buffer: Vec<u8> = vec![];
+    map_elites.flush_reporter(&mut buffer);
+    let output = String::from_utf8(buffer).unwrap();
+    assert!(output.starts_with("enter - map_elites, iteration: 0"));
+    assert!(output.contains("archive_size: Some(6), archive_coverage: Some(1.000)"));
+}
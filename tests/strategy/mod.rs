@@ -1,4 +1,9 @@
+pub mod ant_colony_test;
 pub mod builder_test;
+pub mod estimate_test;
 pub mod evolve_test;
 pub mod hill_climb_test;
+pub mod map_elites_test;
+pub mod particle_swarm_test;
 pub mod permutate_test;
+pub mod reporter_test;
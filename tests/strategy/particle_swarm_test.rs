@@ -0,0 +1,321 @@
+#[cfg(test)]
+use genetic_algorithm::fitness::placeholders::{CountTrue, SumGenes};
+use genetic_algorithm::strategy::particle_swarm::prelude::*;
+
+// minimize the sum of squares, with a precision of 1e-3
+#[derive(Clone, Debug)]
+struct Sphere;
+impl Fitness for Sphere {
+    type Genotype = RangeGenotype<f64>;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &FitnessChromosome<Self>,
+        _genotype: &FitnessGenotype<Self>,
+    ) -> Option<FitnessValue> {
+        let sum_of_squares: f64 = chromosome.genes.iter().map(|gene| gene * gene).sum();
+        Some((sum_of_squares / 1e-3) as FitnessValue)
+    }
+}
+
+// minimize the distance to (3, 7, 0.5), with a precision of 1e-3
+#[derive(Clone, Debug)]
+struct DistanceToTarget;
+impl Fitness for DistanceToTarget {
+    type Genotype = MultiRangeGenotype<f32>;
+    fn calculate_for_chromosome(
+        &mut self,
+        chromosome: &FitnessChromosome<Self>,
+        _genotype: &FitnessGenotype<Self>,
+    ) -> Option<FitnessValue> {
+        let distance: f32 = chromosome
+            .genes
+            .iter()
+            .zip([3.0, 7.0, 0.5])
+            .map(|(gene, target)| (gene - target).abs())
+            .sum();
+        Some((distance / 1e-3) as FitnessValue)
+    }
+}
+
+#[test]
+fn build_invalid() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(10)
+        .build()
+        .unwrap();
+    let particle_swarm = ParticleSwarm::builder()
+        .with_genotype(genotype)
+        .with_fitness(CountTrue)
+        .with_target_population_size(10)
+        .with_max_stale_generations(10)
+        .build();
+    assert_eq!(
+        particle_swarm.err(),
+        Some(TryFromParticleSwarmBuilderError(
+            "ParticleSwarm requires a genotype with a numeric allele range per gene (RangeGenotype or MultiRangeGenotype without gene_constraints)"
+        ))
+    );
+
+    let genotype = RangeGenotype::builder()
+        .with_genes_size(10)
+        .with_allele_range(-5.0..=5.0)
+        .build()
+        .unwrap();
+    let particle_swarm = ParticleSwarm::builder()
+        .with_genotype(genotype.clone())
+        .with_fitness(Sphere)
+        .with_max_stale_generations(10)
+        .build();
+    assert_eq!(
+        particle_swarm.err(),
+        Some(TryFromParticleSwarmBuilderError(
+            "ParticleSwarm requires a target_population_size > 0"
+        ))
+    );
+
+    let particle_swarm = ParticleSwarm::builder()
+        .with_genotype(genotype.clone())
+        .with_fitness(Sphere)
+        .with_target_population_size(10)
+        .with_schedule(ParticleSwarmSchedule::Constriction)
+        .with_max_stale_generations(10)
+        .build();
+    assert_eq!(
+        particle_swarm.err(),
+        Some(TryFromParticleSwarmBuilderError(
+            "ParticleSwarm requires a cognitive_weight + social_weight > 4.0 for the Constriction schedule"
+        ))
+    );
+
+    let particle_swarm = ParticleSwarm::builder()
+        .with_genotype(genotype.clone())
+        .with_fitness(Sphere)
+        .with_target_population_size(10)
+        .with_schedule(ParticleSwarmSchedule::LinearInertia(0.9, 0.4))
+        .with_max_stale_generations(10)
+        .build();
+    assert_eq!(
+        particle_swarm.err(),
+        Some(TryFromParticleSwarmBuilderError(
+            "ParticleSwarm requires a max_generations for the LinearInertia schedule"
+        ))
+    );
+
+    let particle_swarm = ParticleSwarm::builder()
+        .with_genotype(genotype)
+        .with_fitness(Sphere)
+        .with_target_population_size(10)
+        .build();
+    assert_eq!(
+        particle_swarm.err(),
+        Some(TryFromParticleSwarmBuilderError(
+            "ParticleSwarm requires at least a max_stale_generations, max_generations or target_fitness_score ending condition"
+        ))
+    );
+
+    // the particles can't enforce gene_constraints
+    let genotype = MultiRangeGenotype::<f32>::builder()
+        .with_allele_ranges(vec![0.0..=10.0, 0.0..=10.0, 0.0..=1.0])
+        .with_gene_constraints(vec![GeneConstraint::sum_to(vec![0, 1], 10.0)])
+        .build()
+        .unwrap();
+    let particle_swarm = ParticleSwarm::builder()
+        .with_genotype(genotype)
+        .with_fitness(DistanceToTarget)
+        .with_target_population_size(10)
+        .with_max_stale_generations(10)
+        .build();
+    assert_eq!(
+        particle_swarm.err(),
+        Some(TryFromParticleSwarmBuilderError(
+            "ParticleSwarm requires a genotype with a numeric allele range per gene (RangeGenotype or MultiRangeGenotype without gene_constraints)"
+        ))
+    );
+}
+
+#[test]
+fn call_range_global_inertia() {
+    let genotype = RangeGenotype::builder()
+        .with_genes_size(10)
+        .with_allele_range(-5.0..=5.0)
+        .build()
+        .unwrap();
+    let particle_swarm = ParticleSwarm::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(30)
+        .with_fitness(Sphere)
+        .with_fitness_ordering(FitnessOrdering::Minimize)
+        .with_target_fitness_score(0)
+        .with_max_stale_generations(100)
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    assert_eq!(particle_swarm.best_fitness_score(), Some(0));
+    assert!(particle_swarm
+        .best_genes()
+        .unwrap()
+        .iter()
+        .all(|gene| gene.abs() < 0.1));
+    assert_eq!(particle_swarm.state.positions.len(), 30);
+    assert_eq!(particle_swarm.state.velocities.len(), 30);
+    assert_eq!(particle_swarm.state.personal_best_fitness_scores.len(), 30);
+}
+
+#[test]
+fn call_range_ring_constriction() {
+    let genotype = RangeGenotype::builder()
+        .with_genes_size(10)
+        .with_allele_range(-5.0..=5.0)
+        .build()
+        .unwrap();
+    let particle_swarm = ParticleSwarm::builder()
+        .with_genotype(genotype)
+        .with_variant(ParticleSwarmVariant::Ring)
+        .with_schedule(ParticleSwarmSchedule::Constriction)
+        .with_cognitive_weight(2.05)
+        .with_social_weight(2.05)
+        .with_target_population_size(30)
+        .with_fitness(Sphere)
+        .with_fitness_ordering(FitnessOrdering::Minimize)
+        .with_target_fitness_score(0)
+        .with_max_stale_generations(100)
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    assert_eq!(particle_swarm.best_fitness_score(), Some(0));
+    assert!((particle_swarm.config.constriction_factor() - 0.7298).abs() < 1e-3);
+}
+
+#[test]
+fn call_range_linear_inertia_max_generations() {
+    let genotype = RangeGenotype::builder()
+        .with_genes_size(10)
+        .with_allele_range(-5.0..=5.0)
+        .build()
+        .unwrap();
+    let particle_swarm = ParticleSwarm::builder()
+        .with_genotype(genotype)
+        .with_schedule(ParticleSwarmSchedule::LinearInertia(0.9, 0.4))
+        .with_target_population_size(20)
+        .with_fitness(Sphere)
+        .with_fitness_ordering(FitnessOrdering::Minimize)
+        .with_par_fitness(true)
+        .with_max_generations(50)
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    assert_eq!(particle_swarm.state.current_generation, 50);
+    assert!(particle_swarm.best_fitness_score().unwrap() < 1000);
+    // positions are clamped to the allele range
+    assert!(particle_swarm
+        .state
+        .positions
+        .iter()
+        .flatten()
+        .all(|position| (-5.0..=5.0).contains(position)));
+}
+
+#[test]
+fn call_range_bounds_maximize() {
+    let genotype = RangeGenotype::builder()
+        .with_genes_size(10)
+        .with_allele_range(0.0..=1.0)
+        .build()
+        .unwrap();
+    let particle_swarm = ParticleSwarm::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(10)
+        .with_fitness(SumGenes::new_with_precision(1e-3))
+        .with_max_stale_generations(20)
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    // particles are clamped to the upper bound
+    assert!(particle_swarm.best_fitness_score().unwrap() >= 9_990);
+    assert!(particle_swarm
+        .best_genes()
+        .unwrap()
+        .iter()
+        .all(|gene| *gene > 0.99 && *gene <= 1.0));
+}
+
+#[test]
+fn call_range_discrete() {
+    let genotype = RangeGenotype::<f32>::builder()
+        .with_genes_size(5)
+        .with_allele_range(-10.0..=10.0)
+        .with_mutation_type(MutationType::Discrete)
+        .build()
+        .unwrap();
+    let particle_swarm = ParticleSwarm::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(10)
+        .with_fitness(SumGenes::new())
+        .with_fitness_ordering(FitnessOrdering::Minimize)
+        .with_target_fitness_score(-50)
+        .with_max_stale_generations(100)
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    assert_eq!(particle_swarm.best_fitness_score(), Some(-50));
+    assert_eq!(particle_swarm.best_genes(), Some(vec![-10.0; 5]));
+}
+
+#[test]
+fn call_multi_range() {
+    let genotype = MultiRangeGenotype::builder()
+        .with_allele_ranges(vec![0.0..=10.0, 0.0..=10.0, 0.0..=1.0])
+        .with_mutation_types(vec![
+            MutationType::Discrete,
+            MutationType::Discrete,
+            MutationType::Random,
+        ])
+        .build()
+        .unwrap();
+    let particle_swarm = ParticleSwarm::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(20)
+        .with_fitness(DistanceToTarget)
+        .with_fitness_ordering(FitnessOrdering::Minimize)
+        .with_target_fitness_score(10)
+        .with_max_stale_generations(100)
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    let best_genes = particle_swarm.best_genes().unwrap();
+    assert_eq!(best_genes[0], 3.0);
+    assert_eq!(best_genes[1], 7.0);
+    assert!((best_genes[2] - 0.5).abs() < 0.01);
+}
+
+#[test]
+fn call_with_reporter() {
+    let genotype = RangeGenotype::builder()
+        .with_genes_size(10)
+        .with_allele_range(-5.0..=5.0)
+        .build()
+        .unwrap();
+    let mut particle_swarm = ParticleSwarm::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(10)
+        .with_fitness(Sphere)
+        .with_fitness_ordering(FitnessOrdering::Minimize)
+        .with_max_generations(3)
+        .with_reporter(ParticleSwarmReporterSimple::new_with_buffer(100))
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    let mut buffer: Vec<u8> = vec![];
+    particle_swarm.flush_reporter(&mut buffer);
+    let output = String::from_utf8(buffer).unwrap();
+    assert!(output.starts_with("enter - particle_swarm/global, iteration: 0"));
+    assert!(output.contains("position_size: 10"));
+    assert!(output.contains("exit - particle_swarm/global, iteration: 0"));
+}
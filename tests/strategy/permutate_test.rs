@@ -141,6 +141,34 @@ fn call_range_usize_scaled() {
     assert_eq!(permutate.best_genes().unwrap(), vec![100, 100, 100, 100]);
 }
 
+#[test]
+fn call_range_usize_scaled_fitness_cache() {
+    let genotype = RangeGenotype::builder()
+        .with_genes_size(4)
+        .with_allele_range(0..=100)
+        .with_mutation_type(MutationType::StepScaled(vec![10, 1]))
+        .build()
+        .unwrap();
+    let fitness_cache = FitnessCache::new_unbounded();
+
+    let permutate = Permutate::builder()
+        .with_genotype(genotype)
+        .with_fitness(SumGenes::new())
+        .with_shared_fitness_cache(fitness_cache.clone())
+        .with_reporter(StrategyReporterNoop::new())
+        .call()
+        .unwrap();
+
+    println!("{:#?}", permutate.best_genes());
+    assert_eq!(permutate.best_fitness_score(), Some(400));
+    assert_eq!(permutate.best_genes().unwrap(), vec![100, 100, 100, 100]);
+
+    // the second scale grid overlaps with the first scale grid
+    let (cache_hits, cache_misses, _) = fitness_cache.hit_miss_stats();
+    assert!(cache_hits > 0);
+    assert_eq!(cache_misses, fitness_cache.len());
+}
+
 #[test]
 fn call_range_f32_random_invalid() {
     let genotype = RangeGenotype::builder()
@@ -184,3 +212,28 @@ fn call_par_fitness() {
     assert_eq!(permutate.best_fitness_score(), Some(45));
     assert_eq!(permutate.best_genes().unwrap(), vec![9, 9, 9, 9, 9]);
 }
+
+#[test]
+fn call_par_fitness_fitness_cache() {
+    let genotype = RangeGenotype::builder()
+        .with_genes_size(4)
+        .with_allele_range(0..=100)
+        .with_mutation_type(MutationType::StepScaled(vec![10, 1]))
+        .build()
+        .unwrap();
+
+    let permutate = Permutate::builder()
+        .with_genotype(genotype)
+        .with_fitness(SumGenes::new())
+        .with_fitness_cache(100_000)
+        .with_par_fitness(true)
+        .with_reporter(StrategyReporterNoop::new())
+        .call()
+        .unwrap();
+
+    println!("{:#?}", permutate.best_genes());
+    assert_eq!(permutate.best_fitness_score(), Some(400));
+    assert_eq!(permutate.best_genes().unwrap(), vec![100, 100, 100, 100]);
+    let (cache_hits, _, _) = permutate.config.fitness_cache.unwrap().hit_miss_stats();
+    assert!(cache_hits > 0);
+}
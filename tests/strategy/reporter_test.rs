@@ -0,0 +1,335 @@
+#[cfg(test)]
+use crate::support::*;
+use genetic_algorithm::fitness::placeholders::CountTrue;
+use genetic_algorithm::strategy::prelude::*;
+use genetic_algorithm::strategy::reporter::Recorder;
+use genetic_algorithm::strategy::StrategyAction;
+use std::sync::{Mutex, Once};
+
+struct CaptureLogger;
+static CAPTURED_LOGS: Mutex<Vec<(String, log::Level, String)>> = Mutex::new(Vec::new());
+static CAPTURE_LOGGER: CaptureLogger = CaptureLogger;
+static INIT_CAPTURE_LOGGER: Once = Once::new();
+impl log::Log for CaptureLogger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+    fn log(&self, record: &log::Record) {
+        CAPTURED_LOGS.lock().unwrap().push((
+            record.target().to_string(),
+            record.level(),
+            record.args().to_string(),
+        ));
+    }
+    fn flush(&self) {}
+}
+fn captured_logs(target: &str) -> Vec<(log::Level, String)> {
+    CAPTURED_LOGS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(t, _, _)| t == target)
+        .map(|(_, level, message)| (*level, message.clone()))
+        .collect()
+}
+
+#[test]
+fn recorder_evolve() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(10)
+        .build()
+        .unwrap();
+    let evolve = Evolve::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(20)
+        .with_max_generations(10)
+        .with_fitness(CountTrue)
+        .with_select(SelectElite::new(0.5, 0.02))
+        .with_crossover(CrossoverUniform::new(0.7, 0.8))
+        .with_mutate(MutateSingleGene::new(0.2))
+        .with_reporter(EvolveReporterRecorder::new(2))
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    let records = &evolve.reporter.records;
+    assert_eq!(records.len(), 5);
+    assert_eq!(
+        records.iter().map(|r| r.generation).collect::<Vec<_>>(),
+        vec![2, 4, 6, 8, 10]
+    );
+    let last = records.last().unwrap();
+    assert_eq!(last.best_fitness_score, evolve.best_fitness_score());
+    assert!(last.fitness_score_mean.is_some());
+    assert!(last.fitness_score_median.is_some());
+    assert!(last.fitness_score_stddev.is_some());
+    assert_eq!(
+        last.population_size,
+        last.parents_size + last.offspring_size
+    );
+    assert!(last.duration(StrategyAction::Fitness) > std::time::Duration::ZERO);
+}
+
+#[test]
+fn recorder_hill_climb_steepest_ascent() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(10)
+        .build()
+        .unwrap();
+    let hill_climb = HillClimb::builder()
+        .with_genotype(genotype)
+        .with_variant(HillClimbVariant::SteepestAscent)
+        .with_max_generations(3)
+        .with_fitness(CountTrue)
+        .with_reporter(HillClimbReporterRecorder::new(1))
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    let records = &hill_climb.reporter.records;
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].population_size, 10);
+    assert!(records[0].fitness_score_mean.is_some());
+}
+
+#[test]
+fn recorder_permutate() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(3)
+        .build()
+        .unwrap();
+    let permutate = Permutate::builder()
+        .with_genotype(genotype)
+        .with_fitness(CountTrue)
+        .with_reporter(PermutateReporterRecorder::new(4))
+        .call()
+        .unwrap();
+
+    let records = &permutate.reporter.records;
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].population_size, 0);
+    assert_eq!(records[0].fitness_score_mean, None);
+    assert_eq!(records[1].best_fitness_score, Some(3));
+}
+
+#[test]
+fn recorder_exports() {
+    let mut recorder: Recorder<BinaryGenotype> = Recorder::new(1);
+    recorder.records.push(StrategyReporterRecord {
+        iteration: 0,
+        generation: 1,
+        stale_generations: 0,
+        best_generation: 1,
+        best_fitness_score: Some(5),
+        fitness_score_mean: Some(2.5),
+        fitness_score_median: Some(2),
+        fitness_score_stddev: None,
+        population_cardinality: None,
+        population_size: 4,
+        parents_size: 2,
+        offspring_size: 2,
+        scale_index: None,
+        number_of_select_events: 0,
+        number_of_extension_events: 1,
+        number_of_crossover_events: 0,
+        number_of_mutate_events: 0,
+        durations: [std::time::Duration::from_millis(500); 8],
+    });
+
+    let csv = recorder.to_csv();
+    let lines = csv.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(
+        lines[0],
+        "iteration,generation,stale_generations,best_generation,best_fitness_score,fitness_score_mean,fitness_score_median,fitness_score_stddev,population_cardinality,population_size,parents_size,offspring_size,scale_index,number_of_select_events,number_of_extension_events,number_of_crossover_events,number_of_mutate_events,duration_setup_and_cleanup,duration_extension,duration_select,duration_crossover,duration_mutate,duration_fitness,duration_update_best_chromosome,duration_other"
+    );
+    assert_eq!(
+        lines[1],
+        "0,1,0,1,5,2.5,2,,,4,2,2,,0,1,0,0,0.5,0.5,0.5,0.5,0.5,0.5,0.5,0.5"
+    );
+
+    let jsonl = recorder.to_jsonl();
+    assert_eq!(
+        jsonl,
+        "{\"iteration\":0,\"generation\":1,\"stale_generations\":0,\"best_generation\":1,\"best_fitness_score\":5,\"fitness_score_mean\":2.5,\"fitness_score_median\":2,\"fitness_score_stddev\":null,\"population_cardinality\":null,\"population_size\":4,\"parents_size\":2,\"offspring_size\":2,\"scale_index\":null,\"number_of_select_events\":0,\"number_of_extension_events\":1,\"number_of_crossover_events\":0,\"number_of_mutate_events\":0,\"duration_setup_and_cleanup\":0.5,\"duration_extension\":0.5,\"duration_select\":0.5,\"duration_crossover\":0.5,\"duration_mutate\":0.5,\"duration_fitness\":0.5,\"duration_update_best_chromosome\":0.5,\"duration_other\":0.5}\n"
+    );
+}
+
+#[test]
+fn recorder_exports_without_fitness_scores() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(3)
+        .build()
+        .unwrap();
+    let mut state = EvolveState::new(&genotype);
+    state.population = build::population(vec![vec![true, false, true], vec![false, false, true]]);
+    let config = EvolveConfig::new();
+    let mut recorder: Recorder<BinaryGenotype> = Recorder::new(1);
+    recorder.on_generation_complete(&genotype, &state, &config);
+
+    // non-finite statistics are exported as null as well
+    let mut record = recorder.records[0].clone();
+    record.fitness_score_mean = Some(f32::NAN);
+    record.fitness_score_stddev = Some(f32::INFINITY);
+    recorder.records.push(record);
+
+    let jsonl = recorder.to_jsonl();
+    let lines = jsonl.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2);
+    for line in lines {
+        assert!(line.contains("\"best_fitness_score\":null"));
+        assert!(line.contains("\"fitness_score_mean\":null"));
+        assert!(line.contains("\"fitness_score_median\":null"));
+        assert!(line.contains("\"fitness_score_stddev\":null"));
+        assert!(line.contains("\"population_size\":2"));
+        assert!(!line.contains("NaN") && !line.contains("inf"));
+    }
+    assert!(recorder
+        .to_csv()
+        .lines()
+        .skip(1)
+        .all(|line| line.starts_with("0,0,0,0,,,,,")));
+}
+
+#[test]
+fn log_evolve() {
+    INIT_CAPTURE_LOGGER.call_once(|| {
+        log::set_logger(&CAPTURE_LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+    });
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(10)
+        .build()
+        .unwrap();
+    let _evolve = Evolve::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(20)
+        .with_max_generations(10)
+        .with_fitness(CountTrue)
+        .with_select(SelectElite::new(0.5, 0.02))
+        .with_crossover(CrossoverUniform::new(0.7, 0.8))
+        .with_mutate(MutateSingleGene::new(0.2))
+        .with_reporter(EvolveReporterLog::new_with_levels(
+            5,
+            Some("reporter_test::log_evolve"),
+            log::Level::Warn,
+            log::Level::Info,
+            log::Level::Debug,
+            log::Level::Trace,
+        ))
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    let logs = captured_logs("reporter_test::log_evolve");
+    assert_eq!(
+        logs.first(),
+        Some(&(log::Level::Warn, "enter - evolve, iteration: 0".to_string()))
+    );
+    assert!(logs
+        .iter()
+        .any(|(level, message)| *level == log::Level::Info && message.starts_with("new best - ")));
+    assert_eq!(
+        logs.iter()
+            .filter(|(level, message)| *level == log::Level::Debug
+                && message.starts_with("periodic - "))
+            .count(),
+        2
+    );
+    assert!(logs
+        .iter()
+        .any(|(level, message)| *level == log::Level::Warn
+            && message.starts_with("exit - evolve, iteration: 0")));
+    assert!(logs.last().is_some_and(
+        |(level, message)| *level == log::Level::Warn && message.starts_with("  Total: ")
+    ));
+}
+
+#[test]
+fn log_hill_climb_default_target() {
+    INIT_CAPTURE_LOGGER.call_once(|| {
+        log::set_logger(&CAPTURE_LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+    });
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(10)
+        .build()
+        .unwrap();
+    let _hill_climb = HillClimb::builder()
+        .with_genotype(genotype)
+        .with_variant(HillClimbVariant::SteepestAscent)
+        .with_max_generations(3)
+        .with_fitness(CountTrue)
+        .with_reporter(HillClimbReporterLog::new(1))
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    let logs = captured_logs("genetic_algorithm::strategy::hill_climb");
+    assert!(logs
+        .iter()
+        .any(|(level, message)| *level == log::Level::Info
+            && message == "enter - hill_climb/steepest_ascent, iteration: 0"));
+}
+
+#[test]
+fn combined_evolve_with_additional_reporter() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(10)
+        .build()
+        .unwrap();
+    let mut evolve = Evolve::builder()
+        .with_genotype(genotype)
+        .with_target_population_size(20)
+        .with_max_generations(10)
+        .with_fitness(CountTrue)
+        .with_select(SelectElite::new(0.5, 0.02))
+        .with_crossover(CrossoverUniform::new(0.7, 0.8))
+        .with_mutate(MutateSingleGene::new(0.2))
+        .with_reporter(EvolveReporterDuration::new_with_buffer())
+        .with_additional_reporter(EvolveReporterRecorder::new(5))
+        .with_additional_reporter(EvolveReporterSimple::new_with_buffer(5))
+        .with_rng_seed_from_u64(0)
+        .call()
+        .unwrap();
+
+    let ((_duration, recorder), _simple) = &evolve.reporter;
+    assert_eq!(recorder.records.len(), 2);
+
+    let mut output: Vec<u8> = vec![];
+    evolve.flush_reporter(&mut output);
+    let output = String::from_utf8(output).unwrap();
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "enter - evolve, iteration: 0");
+    assert!(lines.contains(&"exit - evolve, iteration: 0"));
+    assert!(lines
+        .iter()
+        .any(|line| line.starts_with("periodic - current_generation: 5,")));
+    assert_eq!(
+        lines
+            .iter()
+            .filter(|line| line.starts_with("enter - evolve"))
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn combined_vec_permutate() {
+    let genotype = BinaryGenotype::builder()
+        .with_genes_size(3)
+        .build()
+        .unwrap();
+    let permutate = Permutate::builder()
+        .with_genotype(genotype)
+        .with_fitness(CountTrue)
+        .with_reporter(vec![
+            PermutateReporterRecorder::new(2),
+            PermutateReporterRecorder::new(4),
+        ])
+        .call()
+        .unwrap();
+
+    assert_eq!(permutate.reporter[0].records.len(), 4);
+    assert_eq!(permutate.reporter[1].records.len(), 2);
+}